@@ -0,0 +1,195 @@
+//! Headless training sessions, straight from the terminal:
+//!
+//! ```text
+//! cargo run -p evobird-cli -- --generations 20 --out stats.csv --save champion.json
+//! ```
+
+use lib_simulation as sim;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+usage: evobird-cli [options]
+
+  --generations <n>    number of generations to train (default: 10)
+  --animals <n>        overrides the config's n_animals
+  --foods <n>          overrides the config's n_foods
+  --seed <n>           seed of the simulation (default: 0)
+  --config <json>      path to a JSON-encoded SimulationConfig
+  --out <csv>          where to write per-generation statistics
+  --save <json>        where to write the best bird ever seen";
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Args {
+    pub generations: usize,
+    pub animals: Option<usize>,
+    pub foods: Option<usize>,
+    pub seed: u64,
+    pub config: Option<PathBuf>,
+    pub out: Option<PathBuf>,
+    pub save: Option<PathBuf>,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut parsed = Self {
+            generations: 10,
+            ..Default::default()
+        };
+
+        let mut args = args.into_iter();
+
+        while let Some(flag) = args.next() {
+            let value = match flag.as_str() {
+                "--generations" | "--animals" | "--foods" | "--seed" | "--config" | "--out"
+                | "--save" => args.next().ok_or_else(|| CliError::MissingValue(flag.clone()))?,
+                _ => return Err(CliError::UnknownFlag(flag)),
+            };
+
+            match flag.as_str() {
+                "--generations" => parsed.generations = number(&flag, &value)?,
+                "--animals" => parsed.animals = Some(number(&flag, &value)?),
+                "--foods" => parsed.foods = Some(number(&flag, &value)?),
+                "--seed" => parsed.seed = number(&flag, &value)?,
+                "--config" => parsed.config = Some(value.into()),
+                "--out" => parsed.out = Some(value.into()),
+                _ => parsed.save = Some(value.into()),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Loads the config pointed at by `--config` (or the default one),
+    /// applies overrides on top of it and validates the result.
+    pub fn config(&self) -> Result<sim::SimulationConfig, CliError> {
+        let mut config = match &self.config {
+            Some(path) => sim::SimulationConfig::from_json(&fs::read(path)?)?,
+            None => Default::default(),
+        };
+
+        if let Some(animals) = self.animals {
+            config.n_animals = animals;
+        }
+
+        if let Some(foods) = self.foods {
+            config.n_foods = foods;
+        }
+
+        config.try_validate()?;
+
+        Ok(config)
+    }
+}
+
+fn number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, CliError> {
+    value.parse().map_err(|_| CliError::InvalidValue {
+        flag: flag.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// Trains a simulation as described by `args`, printing one line per
+/// generation into `stdout`.
+pub fn run(args: &Args, stdout: &mut dyn Write) -> Result<(), CliError> {
+    let mut rng = ChaCha8Rng::seed_from_u64(args.seed);
+    let mut simulation = sim::Simulation::from_config(args.config()?, &mut rng);
+    let mut recorder = sim::StatsRecorder::new();
+
+    for summary in simulation.train_many(&mut rng, args.generations) {
+        writeln!(stdout, "generation {:>4} | {}", summary.generation, summary.stats)?;
+        recorder.record(&summary);
+    }
+
+    if let Some(path) = &args.out {
+        fs::write(path, recorder.to_csv())?;
+    }
+
+    if let Some(path) = &args.save {
+        let champion = simulation
+            .hall_of_fame()
+            .champions()
+            .first()
+            .ok_or(CliError::NoChampion)?;
+
+        fs::write(path, serde_json::to_vec_pretty(champion).expect("champion should always serialize"))?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum CliError {
+    UnknownFlag(String),
+    MissingValue(String),
+    InvalidValue { flag: String, value: String },
+    Config(sim::ConfigError),
+    Io(io::Error),
+
+    /// `--save` was given, but the hall of fame is empty (e.g. because
+    /// `--generations 0` or `hall_of_fame_size: 0`).
+    NoChampion,
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFlag(flag) => write!(f, "unknown flag: {}", flag),
+            Self::MissingValue(flag) => write!(f, "{} requires a value", flag),
+            Self::InvalidValue { flag, value } => write!(f, "invalid value for {}: {}", flag, value),
+            Self::Config(err) => write!(f, "{}", err),
+            Self::Io(err) => write!(f, "i/o error: {}", err),
+            Self::NoChampion => write!(f, "there's no champion to save"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<sim::ConfigError> for CliError {
+    fn from(err: sim::ConfigError) -> Self {
+        Self::Config(err)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, CliError> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_flags() {
+        let args = parse(&["--generations", "3", "--animals", "5", "--seed", "42", "--out", "stats.csv"]).unwrap();
+
+        assert_eq!(
+            args,
+            Args {
+                generations: 3,
+                animals: Some(5),
+                seed: 42,
+                out: Some("stats.csv".into()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_bad_flags() {
+        assert!(matches!(parse(&["--frobnicate"]), Err(CliError::UnknownFlag(_))));
+        assert!(matches!(parse(&["--seed"]), Err(CliError::MissingValue(_))));
+        assert!(matches!(parse(&["--foods", "many"]), Err(CliError::InvalidValue { .. })));
+    }
+}
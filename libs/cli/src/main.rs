@@ -0,0 +1,28 @@
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    if std::env::args().any(|arg| arg == "--help") {
+        println!("{}", evobird_cli::USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let args = match evobird_cli::Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, evobird_cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match evobird_cli::run(&args, &mut std::io::stdout()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err @ evobird_cli::CliError::Config(_)) => {
+            eprintln!("{}\n\n{}", err, evobird_cli::USAGE);
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
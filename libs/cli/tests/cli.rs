@@ -0,0 +1,71 @@
+use evobird_cli::{run, Args, CliError};
+use lib_simulation::{Champion, SimulationConfig};
+use std::fs;
+
+#[test]
+fn trains_and_writes_outputs() {
+    let dir = std::env::temp_dir().join(format!("evobird-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let config = SimulationConfig {
+        n_animals: 10,
+        n_foods: 20,
+        generation_length: 100,
+        ..Default::default()
+    };
+
+    fs::write(dir.join("config.json"), config.to_json()).unwrap();
+
+    let args = Args::parse(
+        [
+            "--generations",
+            "3",
+            "--config",
+            dir.join("config.json").to_str().unwrap(),
+            "--out",
+            dir.join("stats.csv").to_str().unwrap(),
+            "--save",
+            dir.join("champion.json").to_str().unwrap(),
+        ]
+        .map(String::from),
+    )
+    .unwrap();
+
+    let mut stdout = Vec::new();
+    run(&args, &mut stdout).unwrap();
+
+    let stdout = String::from_utf8(stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 3);
+
+    let csv = fs::read_to_string(dir.join("stats.csv")).unwrap();
+    let rows: Vec<_> = csv.lines().skip(1).collect();
+
+    assert_eq!(rows.len(), 3);
+
+    for row in rows {
+        let columns: Vec<_> = row.split(',').collect();
+
+        for column in &columns[1..4] {
+            column.parse::<f32>().unwrap();
+        }
+    }
+
+    let champion: Champion =
+        serde_json::from_slice(&fs::read(dir.join("champion.json")).unwrap()).unwrap();
+
+    assert_eq!(champion.genes.len(), champion.genome.chromosome_len);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn rejects_invalid_configs() {
+    let args = Args::parse(["--animals", "0"].map(String::from)).unwrap();
+    let mut stdout = Vec::new();
+
+    let err = run(&args, &mut stdout).unwrap_err();
+
+    assert!(matches!(err, CliError::Config(_)));
+    assert!(err.to_string().contains("at least one animal"), "{}", err);
+    assert!(stdout.is_empty());
+}
@@ -0,0 +1,85 @@
+//! A `no_std` consumer of `lib-genetic-algorithm`, evolving a population
+//! of "robots" whose fitness is simply the sum of their genes.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use lib_genetic_algorithm as ga;
+use rand_core::RngCore;
+
+pub struct Robot {
+    chromosome: ga::Chromosome,
+}
+
+impl ga::Individual for Robot {
+    fn fitness(&self) -> ga::Float {
+        self.chromosome.iter().sum::<ga::Float>().max(0.0)
+    }
+
+    fn chromosome(&self) -> &ga::Chromosome {
+        &self.chromosome
+    }
+
+    fn create(chromosome: ga::Chromosome) -> Self {
+        Self { chromosome }
+    }
+}
+
+/// Xorshift - embedded targets usually don't have anything fancier.
+pub struct XorShift(pub u64);
+
+impl RngCore for XorShift {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Evolves `robots` robots (of `genes` genes each, all starting at 1.0)
+/// for `generations` generations; returns the final population's stats.
+pub fn evolve(seed: u64, robots: usize, genes: usize, generations: usize) -> ga::Statistics {
+    let ga = ga::GeneticAlgorithm::new(
+        ga::RouletteWheelSelection,
+        ga::UniformCrossover,
+        ga::GaussianMutation::new(0.5, 0.5),
+    );
+
+    let mut rng = XorShift(seed.max(1));
+
+    let mut population: Vec<Robot> = (0..robots)
+        .map(|_| ga::Individual::create((0..genes).map(|_| 1.0).collect()))
+        .collect();
+
+    let mut stats = None;
+
+    for _ in 0..generations {
+        let (evolved, generation_stats) = ga
+            .evolve(&mut rng, &population)
+            .expect("robots have the same number of genes");
+
+        population = evolved;
+        stats = Some(generation_stats);
+    }
+
+    stats.expect("got zero generations")
+}
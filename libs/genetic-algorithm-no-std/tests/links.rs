@@ -0,0 +1,7 @@
+#[test]
+fn evolves_without_std() {
+    let stats = lib_genetic_algorithm_no_std::evolve(42, 10, 4, 5);
+
+    assert!(stats.is_finite());
+    assert!(stats.max_fitness >= stats.min_fitness);
+}
@@ -1,10 +1,52 @@
-use rand::{seq::SliceRandom, Rng, RngCore};
-use std::ops::Index;
+//! Without the (default) `std` feature, this crate is `no_std` and needs
+//! just `alloc` - e.g. to evolve robots on an embedded target.
 
-pub struct GeneticAlgorithm<S,C,M>{
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Index, Range, RangeInclusive};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{seq::IteratorRandom, seq::SliceRandom, Rng, RngCore};
+
+pub mod tuning;
+
+/// Type of genes and fitness - `f32`, unless the `precision-f64` feature
+/// is enabled (e.g. for numerical studies, where rounding differences
+/// between platforms throw evolution off its trajectory).
+#[cfg(not(feature = "precision-f64"))]
+pub type Float = f32;
+
+#[cfg(feature = "precision-f64")]
+pub type Float = f64;
+
+pub struct GeneticAlgorithm<S,C,M,R = NoRepair>{
     selection_method:S,
     crossover_method:C,
     mutation_method:M,
+    repair_method:R,
+
+    /// How many children each selected pair of parents gets.
+    brood_size: usize,
+
+    compatibility: CompatibilityPolicy,
+
+    /// How many times parent_b gets re-selected when it's incompatible
+    /// with parent_a.
+    compatibility_retries: usize,
+
+    reproduction: ReproductionMode,
+
+    /// Whether an individual can get selected as both parents.
+    self_mating: bool,
+
+    operator_order: OperatorOrder,
+
+    /// Whether each crossover fills two slots, with complementary children.
+    paired_offspring: bool,
 }
 
 impl<S,C,M> GeneticAlgorithm<S,C,M>
@@ -17,463 +59,8665 @@ impl<S,C,M> GeneticAlgorithm<S,C,M>
             crossover_method: C,
             mutation_method: M,
         ) -> Self {
-            Self { selection_method, crossover_method, mutation_method }
+            Self {
+                selection_method,
+                crossover_method,
+                mutation_method,
+                repair_method: NoRepair,
+                brood_size: 1,
+                compatibility: CompatibilityPolicy::Any,
+                compatibility_retries: 0,
+                reproduction: ReproductionMode::Sexual,
+                self_mating: false,
+                operator_order: OperatorOrder::CrossoverFirst,
+                paired_offspring: false,
+            }
+        }
+}
+
+impl<S,C,M,R> GeneticAlgorithm<S,C,M,R>
+    where S:SelectionMethod,
+          C:CrossoverMethod,
+          M:MutationMethod,
+          R:RepairMethod,
+    {
+        /// Repairs each child right after its crossover - see: `RepairMethod`.
+        ///
+        /// Defaults to `NoRepair`.
+        pub fn with_repair<R2>(self, repair_method: R2) -> GeneticAlgorithm<S, C, M, R2>
+        where
+            R2: RepairMethod,
+        {
+            GeneticAlgorithm {
+                selection_method: self.selection_method,
+                crossover_method: self.crossover_method,
+                mutation_method: self.mutation_method,
+                repair_method,
+                brood_size: self.brood_size,
+                compatibility: self.compatibility,
+                compatibility_retries: self.compatibility_retries,
+                reproduction: self.reproduction,
+                self_mating: self.self_mating,
+                operator_order: self.operator_order,
+                paired_offspring: self.paired_offspring,
+            }
+        }
+
+        /// Switches between mutating children and mutating their parents
+        /// (before the crossover) - see: `OperatorOrder`.
+        ///
+        /// Defaults to `OperatorOrder::CrossoverFirst`.
+        pub fn with_operator_order(mut self, order: OperatorOrder) -> Self {
+            self.operator_order = order;
+            self
+        }
+
+        /// Makes each selected pair of parents produce `brood_size`
+        /// children (each with its own crossover & mutation) before the
+        /// next pair gets selected; the last brood gets cut short if the
+        /// population size isn't divisible by `brood_size`.
+        ///
+        /// Defaults to 1 - a fresh pair of parents for every child.
+        pub fn with_brood_size(mut self, brood_size: usize) -> Self {
+            assert!(brood_size > 0);
+
+            self.brood_size = brood_size;
+            self
+        }
+
+        /// Makes each crossover produce two complementary children (see:
+        /// `CrossoverMethod::crossover_pair()`) filling two consecutive
+        /// slots - so that neither parent's half of the genes goes to waste
+        /// and it takes half as many selections to fill the population;
+        /// each selected pair of parents then gets `2 * brood_size`
+        /// children.
+        ///
+        /// When the population size is odd, the last child's twin gets
+        /// discarded; children cloned out of a single parent (see:
+        /// `ReproductionMode::Asexual`, `with_compatibility()`) and
+        /// children of more than two parents have no twins and fill their
+        /// slots one by one.
+        ///
+        /// Defaults to false - each crossover produces a single child.
+        pub fn with_paired_offspring(mut self, paired: bool) -> Self {
+            self.paired_offspring = paired;
+            self
+        }
+
+        /// Makes sure only compatible parents get crossed: after selecting
+        /// an incompatible pair, parent_b gets re-selected up to `retries`
+        /// times - and if that doesn't help, the child becomes a (mutated)
+        /// clone of parent_a.
+        ///
+        /// Defaults to `CompatibilityPolicy::Any`.
+        pub fn with_compatibility(mut self, policy: CompatibilityPolicy, retries: usize) -> Self {
+            self.compatibility = policy;
+            self.compatibility_retries = retries;
+            self
+        }
+
+        /// Switches between children having two parents and children
+        /// being (mutated) clones of a single one - see: `ReproductionMode`.
+        ///
+        /// Defaults to `ReproductionMode::Sexual`.
+        pub fn with_reproduction(mut self, mode: ReproductionMode) -> Self {
+            self.reproduction = mode;
+            self
+        }
+
+        /// Allows both parents of a child to be the same individual, as they
+        /// could before `SelectionMethod::select_pair()` came along - then
+        /// parents get selected independently of each other.
+        ///
+        /// Defaults to false - as long as the population has at least two
+        /// individuals, every pair consists of two different ones.
+        pub fn with_self_mating(mut self, self_mating: bool) -> Self {
+            self.self_mating = self_mating;
+            self
+        }
+
+        /// Gives access to the selection method between generations - e.g.
+        /// to anneal `BoltzmannSelection`'s temperature.
+        pub fn selection_method_mut(&mut self) -> &mut S {
+            &mut self.selection_method
+        }
+
+        /// Gives access to the mutation method between generations - e.g.
+        /// to advance `DecayingMutation`'s schedule.
+        pub fn mutation_method_mut(&mut self) -> &mut M {
+            &mut self.mutation_method
+        }
+
+        /// Fails only if the crossover does - e.g. when the population has
+        /// chromosomes of different lengths (see: `CrossoverError`).
+        pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> Result<(Vec<I>, Statistics), CrossoverError>
+        where
+            I: Individual,
+        {
+            let evolution = self.evolve_with(&mut Rngs::Shared(rng), population, population.len(), 0.0, false, None)?;
+            Ok((evolution.population, evolution.stats))
+        }
+
+        /// Like `evolve()`, but additionally records both the mutated and
+        /// the un-mutated (crossover-only) chromosome of a `sample_rate`
+        /// fraction of children, so that the caller can measure how much
+        /// mutation alone affects fitness.
+        ///
+        /// Children are sampled at evenly spaced positions, without any
+        /// random draws - so the evolved population is the same as the
+        /// one `evolve()` would return for the same `rng`.
+        pub fn evolve_sampled<I>(
+            &self,
+            rng: &mut dyn RngCore,
+            population: &[I],
+            sample_rate: f32,
+        ) -> Result<(Vec<I>, Statistics, Vec<MutationSample>), CrossoverError>
+        where
+            I: Individual,
+        {
+            let evolution = self.evolve_traced(rng, population, sample_rate)?;
+            Ok((evolution.population, evolution.stats, evolution.samples))
+        }
+
+        /// Like `evolve_sampled()`, but additionally reports which parents
+        /// each child came from, along with what the crossover & mutation
+        /// did to it (see: `EvolveDiagnostics`).
+        pub fn evolve_traced<I>(
+            &self,
+            rng: &mut dyn RngCore,
+            population: &[I],
+            sample_rate: f32,
+        ) -> Result<Evolution<I>, CrossoverError>
+        where
+            I: Individual,
+        {
+            assert!((0.0..=1.0).contains(&sample_rate));
+            self.evolve_with(&mut Rngs::Shared(rng), population, population.len(), sample_rate, true, None)
+        }
+
+        /// Like `evolve_traced()`, but additionally reports how many
+        /// generations each child's gene has survived unchanged (see:
+        /// `Evolution::gene_ages`), given the ages of `population`'s genes
+        /// - e.g. the ones returned from the previous call.
+        ///
+        /// A gene inherits the age of the parent it came from, plus one;
+        /// it's back at zero when mutation touches it or when crossover
+        /// produces a value neither parent had.
+        pub fn evolve_with_gene_ages<I>(
+            &self,
+            rng: &mut dyn RngCore,
+            population: &[I],
+            sample_rate: f32,
+            gene_ages: &[Vec<u16>],
+        ) -> Result<Evolution<I>, CrossoverError>
+        where
+            I: Individual,
+        {
+            assert!((0.0..=1.0).contains(&sample_rate));
+            assert_eq!(gene_ages.len(), population.len());
+
+            for (individual, ages) in population.iter().zip(gene_ages) {
+                assert_eq!(ages.len(), individual.chromosome().len());
+            }
+
+            self.evolve_with(
+                &mut Rngs::Shared(rng),
+                population,
+                population.len(),
+                sample_rate,
+                true,
+                Some(gene_ages),
+            )
+        }
+
+        /// Like `evolve()`, but draws from two independent streams:
+        ///
+        /// - `rng_sel` is used for selection and crossover - for each
+        ///   child, in order: select parent_a, select parent_b (once per
+        ///   brood, see: `with_brood_size()`; see also: `with_self_mating()`),
+        ///   crossover,
+        /// - `rng_mut` is used for mutation only.
+        ///
+        /// This way changing the mutation method (or its parameters)
+        /// doesn't affect which parents get selected, as long as the
+        /// population's fitness stays the same.
+        pub fn evolve_split<I>(
+            &self,
+            rng_sel: &mut dyn RngCore,
+            rng_mut: &mut dyn RngCore,
+            population: &[I],
+        ) -> Result<(Vec<I>, Statistics), CrossoverError>
+        where
+            I: Individual,
+        {
+            let evolution = self.evolve_with(
+                &mut Rngs::Split { rng_sel, rng_mut },
+                population,
+                population.len(),
+                0.0,
+                false,
+                None,
+            )?;
+            Ok((evolution.population, evolution.stats))
+        }
+
+        /// Like `evolve_traced()` (without sampling), but produces `size`
+        /// children instead of as many as there are in `population` - e.g.
+        /// to grow or shrink the population between generations.
+        pub fn evolve_to<I>(
+            &self,
+            rng: &mut dyn RngCore,
+            population: &[I],
+            size: usize,
+        ) -> Result<Evolution<I>, CrossoverError>
+        where
+            I: Individual,
+        {
+            assert!(size > 0);
+            self.evolve_with(&mut Rngs::Shared(rng), population, size, 0.0, true, None)
+        }
+
+        /// Produces a single child of given parents, going through the
+        /// same crossover & mutation as `evolve()` - just without the
+        /// selection (e.g. when parents are picked by hand).
+        ///
+        /// In `ReproductionMode::Asexual`, the child is a mutated clone of
+        /// `parent_a`; crossovers that need more than two parents (see:
+        /// `CrossoverMethod::parent_count()`) fail with
+        /// `CrossoverError::NotEnoughParents`.
+        pub fn breed(
+            &self,
+            rng: &mut dyn RngCore,
+            parent_a: &Chromosome,
+            parent_b: &Chromosome,
+        ) -> Result<Chromosome, CrossoverError> {
+            let asexual = self.reproduction == ReproductionMode::Asexual;
+
+            let offspring = self.reproduce(&mut Rngs::Shared(rng), &[parent_a, parent_b], asexual, Pairing::Single, Tracking::default())?;
+            Ok(offspring.child)
+        }
+
+        /// Crosses given parents over (unless `asexual`, in which case the
+        /// child is a clone of `parent_a`), repairs the child and mutates
+        /// it - or mutates the parents first, depending on the operator
+        /// order.
+        ///
+        /// `parents` are `parent_a`, `parent_b` and - for crossovers that
+        /// need more of them - the extra ones.
+        ///
+        /// With `Pairing::Second`, the crossover (and, when the parents get
+        /// mutated first, the mutation as well) has already happened, so
+        /// the twin just goes through whatever's left.
+        fn reproduce(
+            &self,
+            rngs: &mut Rngs,
+            parents: &[&Chromosome],
+            asexual: bool,
+            pairing: Pairing,
+            mut tracking: Tracking,
+        ) -> Result<Offspring, CrossoverError> {
+            let (parent_a, parent_b) = (parents[0], parents[1]);
+
+            let offspring = match self.operator_order {
+                OperatorOrder::CrossoverFirst => {
+                    let mut child = match pairing {
+                        Pairing::Single => self.crossover(rngs, parents, asexual)?,
+                        Pairing::First(twin) => {
+                            let (child, sibling) = self.crossover_pair(rngs, parents, asexual)?;
+
+                            *twin = sibling.map(|child| Twin { child, parents: None });
+                            child
+                        }
+                        Pairing::Second(twin) => twin.child,
+                    };
+
+                    let unmutated = tracking.sample.then(|| child.clone());
+                    let crossed = tracking.gene_ages.then(|| child.clone());
+
+                    if let Some(diagnostics) = &mut tracking.diagnostics {
+                        diagnostics.inheritance.push(inheritance(parent_a, parent_b, &child));
+                    }
+
+                    self.mutate(rngs, &mut child, &mut tracking.diagnostics);
+
+                    Offspring {
+                        sample: unmutated.map(|unmutated| MutationSample {
+                            unmutated,
+                            mutated: child.clone(),
+                        }),
+                        crossed,
+                        child,
+                    }
+                }
+
+                OperatorOrder::MutationFirst => {
+                    if let Pairing::Second(Twin { child, parents: Some((mutated_a, mutated_b)) }) = pairing {
+                        if let Some(diagnostics) = &mut tracking.diagnostics {
+                            diagnostics.inheritance.push(inheritance(&mutated_a, &mutated_b, &child));
+                        }
+
+                        return Ok(Offspring {
+                            sample: None,
+                            crossed: tracking.gene_ages.then(|| child.clone()),
+                            child,
+                        });
+                    }
+
+                    // Cloning needs just parent_a, so just parent_a gets mutated
+                    let mutated: Vec<_> = parents
+                        .iter()
+                        .take(if asexual { 1 } else { parents.len() })
+                        .map(|parent| {
+                            let mut mutated = (*parent).clone();
+                            self.mutate(rngs, &mut mutated, &mut tracking.diagnostics);
+                            mutated
+                        })
+                        .collect();
+
+                    let mutated_parents: Vec<_> = if asexual {
+                        Vec::from([&mutated[0], &mutated[0]])
+                    } else {
+                        mutated.iter().collect()
+                    };
+
+                    let (mutated_a, mutated_b) = (mutated_parents[0], mutated_parents[1]);
+
+                    let child = match pairing {
+                        Pairing::First(twin) => {
+                            let (child, sibling) = self.crossover_pair(rngs, &mutated_parents, asexual)?;
+
+                            *twin = sibling.map(|child| Twin {
+                                child,
+                                parents: Some((mutated_a.clone(), mutated_b.clone())),
+                            });
+
+                            child
+                        }
+                        _ => self.crossover(rngs, &mutated_parents, asexual)?,
+                    };
+
+                    if let Some(diagnostics) = &mut tracking.diagnostics {
+                        diagnostics.inheritance.push(inheritance(mutated_a, mutated_b, &child));
+                    }
+
+                    Offspring {
+                        sample: tracking.sample.then(|| MutationSample {
+                            unmutated: parent_a.clone(),
+                            mutated: mutated_a.clone(),
+                        }),
+                        crossed: tracking.gene_ages.then(|| child.clone()),
+                        child,
+                    }
+                }
+            };
+
+            Ok(offspring)
+        }
+
+        fn crossover(&self, rngs: &mut Rngs, parents: &[&Chromosome], asexual: bool) -> Result<Chromosome, CrossoverError> {
+            let mut child = match parents {
+                _ if asexual => parents[0].clone(),
+                [parent_a, parent_b] => self.crossover_method.crossover(rngs.selection(), parent_a, parent_b)?,
+                _ => self.crossover_method.crossover_many(rngs.selection(), parents)?,
+            };
+
+            self.repair_method.repair(&mut child);
+            Ok(child)
+        }
+
+        /// Like `crossover()`, but also returns the child's twin - unless
+        /// `asexual` or crossed from more than two parents, in which case
+        /// there's no twin to speak of.
+        fn crossover_pair(
+            &self,
+            rngs: &mut Rngs,
+            parents: &[&Chromosome],
+            asexual: bool,
+        ) -> Result<(Chromosome, Option<Chromosome>), CrossoverError> {
+            let &[parent_a, parent_b] = parents else {
+                return Ok((self.crossover(rngs, parents, asexual)?, None));
+            };
+
+            if asexual {
+                return Ok((self.crossover(rngs, parents, true)?, None));
+            }
+
+            let (mut child, mut twin) = self.crossover_method.crossover_pair(rngs.selection(), parent_a, parent_b)?;
+
+            self.repair_method.repair(&mut child);
+            self.repair_method.repair(&mut twin);
+
+            Ok((child, Some(twin)))
+        }
+
+        fn mutate(&self, rngs: &mut Rngs, chromosome: &mut Chromosome, diagnostics: &mut Option<&mut EvolveDiagnostics>) {
+            if let Some(diagnostics) = diagnostics {
+                let report = self.mutation_method.mutate_reporting(rngs.mutation(), chromosome);
+                diagnostics.genes += chromosome.len();
+                diagnostics.genes_touched += report.genes_touched;
+                diagnostics.total_delta += report.total_delta;
+            } else {
+                self.mutation_method.mutate(rngs.mutation(), chromosome);
+            }
+        }
+
+        /// Selects parent_b for given parent_a, respecting the compatibility
+        /// policy; returns indices of both parents, along with whether the
+        /// child has to be cloned out of parent_a alone.
+        ///
+        /// `idx_b` is the first candidate; the ones it takes to retry get
+        /// selected one by one.
+        fn select_partner<I>(
+            &self,
+            rngs: &mut Rngs,
+            selection: &dyn PreparedSelection,
+            population: &[I],
+            idx_a: usize,
+            mut idx_b: usize,
+            diagnostics: &mut EvolveDiagnostics,
+        ) -> (usize, usize, bool)
+        where
+            I: Individual,
+        {
+            let allows = |idx_b: usize| {
+                self.compatibility.allows(population[idx_a].chromosome(), population[idx_b].chromosome())
+            };
+
+            let mut retries = 0;
+
+            while !allows(idx_b) && retries < self.compatibility_retries {
+                idx_b = if self.self_mating {
+                    selection.select_index(rngs.selection())
+                } else {
+                    select_other(rngs.selection(), population.len(), idx_a, |rng| selection.select_index(rng))
+                };
+
+                retries += 1;
+            }
+
+            diagnostics.compatibility_retries += retries;
+
+            let asexual = !allows(idx_b);
+            (idx_a, if asexual { idx_a } else { idx_b }, asexual)
         }
 
-        pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+        fn evolve_with<I>(
+            &self,
+            rngs: &mut Rngs,
+            population: &[I],
+            size: usize,
+            sample_rate: f32,
+            diagnose: bool,
+            gene_ages: Option<&[Vec<u16>]>,
+        ) -> Result<Evolution<I>, CrossoverError>
         where
             I: Individual,
         {
             assert!(!population.is_empty());
+
+            let evaluated: Vec<_> = population.iter().map(Evaluated::new).collect();
+            let population = evaluated.as_slice();
+
+            let mut samples = Vec::new();
+            let mut diagnostics = EvolveDiagnostics::default();
+            let mut parents = Vec::with_capacity(size);
+            let mut child_ages = Vec::new();
+            let mut stats = StatisticsBuilder::default();
+            let mut brood = None;
+            let mut twin = None;
+
+            // Parents on top of parent_a and parent_b, re-selected for each
+            // brood - see: `CrossoverMethod::parent_count()`
+            let mut extra_parents = Vec::new();
+
+            let brood_size = if self.paired_offspring {
+                2 * self.brood_size
+            } else {
+                self.brood_size
+            };
+
+            for individual in population {
+                stats.observe(individual.fitness());
+            }
+
+            // Each brood needs one parent per sex
+            let picks_per_brood = match self.reproduction {
+                ReproductionMode::Sexual => 2,
+                ReproductionMode::Asexual => 1,
+            };
+
+            let batched = self.selection_method.is_batched();
+            let selection = self.selection_method.prepare(population);
+
+            let mut picks = batched.then(|| {
+                let mut picks = self.selection_method.select_many(
+                    rngs.selection(),
+                    population,
+                    size.div_ceil(brood_size) * picks_per_brood,
+                );
+
+                if self.reproduction == ReproductionMode::Sexual && !self.self_mating {
+                    separate_pairs(&mut picks);
+                }
+
+                picks.into_iter()
+            });
+
+            let mut pick = |rngs: &mut Rngs| match &mut picks {
+                Some(picks) => picks.next().expect("every brood gets its parents selected"),
+                None => selection.select_index(rngs.selection()),
+            };
             
-            let new_population = (0..population.len())
-                .map(|_| {
+            let new_population = (0..size)
+                .map(|idx| {
                     // Selection
-                    let parent_a = self.selection_method.select(rng, population).chromosome();
-                    let parent_b = self.selection_method.select(rng, population).chromosome();
-                    // Crossover
-                    let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
-                    // Mutation
-                    self.mutation_method.mutate(rng, &mut child);
-                    I::create(child)
+                    if idx.is_multiple_of(brood_size) {
+                        twin = None;
+
+                        brood = Some(match self.reproduction {
+                            ReproductionMode::Sexual => {
+                                let (idx_a, idx_b) = if batched || self.self_mating {
+                                    let (idx_a, idx_b) = (pick(rngs), pick(rngs));
+
+                                    if self.self_mating || idx_a != idx_b {
+                                        (idx_a, idx_b)
+                                    } else {
+                                        // Batched picks got separated up front (see:
+                                        // `separate_pairs()`), so this happens only when
+                                        // there was nobody to trade with
+                                        let idx_b = select_other(rngs.selection(), population.len(), idx_a, |rng| {
+                                            selection.select_index(rng)
+                                        });
+
+                                        (idx_a, idx_b)
+                                    }
+                                } else {
+                                    selection.select_pair(rngs.selection())
+                                };
+
+                                self.select_partner(rngs, &*selection, population, idx_a, idx_b, &mut diagnostics)
+                            }
+                            ReproductionMode::Asexual => {
+                                let idx_a = pick(rngs);
+                                (idx_a, idx_a, true)
+                            }
+                        });
+
+                        extra_parents.clear();
+
+                        if let Some((idx_a, idx_b, false)) = brood {
+                            let mut taken = Vec::from([idx_a, idx_b]);
+
+                            for _ in 2..self.crossover_method.parent_count() {
+                                let extra = select_distinct(rngs.selection(), population.len(), &taken, |rng| {
+                                    selection.select_index(rng)
+                                });
+
+                                taken.push(extra);
+                            }
+
+                            extra_parents = taken.split_off(2);
+                        }
+                    }
+
+                    let (idx_a, idx_b, asexual) = brood.expect("every brood starts with a selection");
+                    parents.push((idx_a, idx_b));
+                    let (parent_a, parent_b) = (population[idx_a].chromosome(), population[idx_b].chromosome());
+
+                    if asexual {
+                        diagnostics.asexual += 1;
+                    }
+
+                    // Crossover & mutation
+                    let tracking = Tracking {
+                        sample: is_sampled(idx, sample_rate),
+                        gene_ages: gene_ages.is_some(),
+                        diagnostics: diagnose.then_some(&mut diagnostics),
+                    };
+
+                    let pairing = match twin.take() {
+                        _ if !self.paired_offspring => Pairing::Single,
+                        Some(sibling) => Pairing::Second(sibling),
+                        None => Pairing::First(&mut twin),
+                    };
+
+                    let chromosomes: Vec<_> = [parent_a, parent_b]
+                        .into_iter()
+                        .chain(extra_parents.iter().map(|&idx| population[idx].chromosome()))
+                        .collect();
+
+                    let offspring = self.reproduce(rngs, &chromosomes, asexual, pairing, tracking)?;
+
+                    if let (Some(gene_ages), Some(crossed)) = (gene_ages, &offspring.crossed) {
+                        child_ages.push(age_genes(
+                            (parent_a, &gene_ages[idx_a]),
+                            (parent_b, &gene_ages[idx_b]),
+                            crossed,
+                            &offspring.child,
+                        ));
+                    }
+
+                    samples.extend(offspring.sample);
+
+                    Ok(I::create(offspring.child))
                 })
-                .collect();
+                .collect::<Result<_, _>>()?;
+
+            let fitnesses: Vec<_> = population.iter().map(|individual| individual.fitness).collect();
+            diagnostics.selection_differential = selection_differential(&fitnesses, &parents);
 
-            let stats = Statistics::new(population);
-            (new_population, stats)
+            Ok(Evolution {
+                population: new_population,
+                stats: stats.finish(),
+                samples,
+                parents,
+                diagnostics,
+                gene_ages: child_ages,
+            })
         }
 }
 
-pub trait Individual {
-    fn fitness(&self) -> f32;
-    fn chromosome(&self) -> &Chromosome;
-    fn create(chromosome: Chromosome) -> Self;
+/// Outcome of `evolve_traced()`.
+#[derive(Clone, Debug)]
+pub struct Evolution<I> {
+    pub population: Vec<I>,
+    pub stats: Statistics,
+    pub samples: Vec<MutationSample>,
+
+    /// Indices (into the original population) of each child's parents.
+    pub parents: Vec<(usize, usize)>,
+
+    pub diagnostics: EvolveDiagnostics,
+
+    /// Ages of each child's genes; empty unless the evolution came from
+    /// `evolve_with_gene_ages()`.
+    pub gene_ages: Vec<Vec<u16>>,
 }
 
-pub trait SelectionMethod {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
-    where
-        I: Individual;
+/// What the crossover & mutation did during a single `evolve_traced()`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EvolveDiagnostics {
+    /// Number of genes across all children.
+    pub genes: usize,
+
+    /// Number of genes changed by mutation, across all children.
+    pub genes_touched: usize,
+
+    /// Sum of absolute changes made by mutation, across all children.
+    pub total_delta: Float,
+
+    /// Fraction of each child's genes that came from its first parent
+    /// (see: `Evolution::parents`); genes both parents have in common
+    /// count as inherited from both of them in halves.
+    pub inheritance: Vec<f32>,
+
+    /// Number of times parent_b got re-selected, because it was
+    /// incompatible with parent_a (see: `with_compatibility()`).
+    pub compatibility_retries: usize,
+
+    /// Number of children cloned out of a single parent - either because
+    /// no compatible partner was found, or because of
+    /// `ReproductionMode::Asexual`.
+    pub asexual: usize,
+
+    /// Mean fitness of the selected parents minus the population's mean -
+    /// the `S` of the breeder's equation; see: `selection_differential()`.
+    pub selection_differential: Float,
 }
 
-pub struct RouletteWheelSelection;
-impl SelectionMethod for RouletteWheelSelection {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
-    where
-        I: Individual,
-    {
-        population
-        .choose_weighted(rng, |indiv| indiv.fitness())
-        .expect("got an empty population")
+/// Mean fitness of `parents` (indices into `fitnesses`, two per child -
+/// as in `Evolution::parents`) minus the mean of `fitnesses`; zero when
+/// there are no parents.
+///
+/// Each child counts separately, so parents get weighted by how many
+/// children they've had - and a cloned parent counts twice for its child.
+pub fn selection_differential(fitnesses: &[Float], parents: &[(usize, usize)]) -> Float {
+    if parents.is_empty() {
+        return 0.0;
     }
+
+    let mean = fitnesses.iter().sum::<Float>() / fitnesses.len() as Float;
+
+    let parents_mean = parents
+        .iter()
+        .map(|&(a, b)| fitnesses[a] + fitnesses[b])
+        .sum::<Float>()
+        / (2 * parents.len()) as Float;
+
+    parents_mean - mean
 }
 
+/// How many parents each child has - see:
+/// `GeneticAlgorithm::with_reproduction()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReproductionMode {
+    /// Two parents get selected and crossed over.
+    #[default]
+    Sexual,
 
-#[derive(Clone, Debug)]
-pub struct Chromosome { //Carrying properties of our birds
-    genes: Vec<f32>,
+    /// Just one parent gets selected, and the child is its clone - so
+    /// evolution happens through selection and mutation alone; the crossover
+    /// method doesn't get called at all (see also: `AsexualReproduction`).
+    Asexual,
 }
-impl Chromosome {
-    pub fn len(&self) -> usize {
-        self.genes.len()
-    }
 
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
-        self.genes.iter()
-    }
+/// When mutation happens - see: `GeneticAlgorithm::with_operator_order()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OperatorOrder {
+    /// Parents get crossed over (and the child repaired), then the child
+    /// gets mutated.
+    #[default]
+    CrossoverFirst,
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
-        self.genes.iter_mut()
-    }
+    /// Both parents get mutated (their copies, that is), then crossed over
+    /// and the child gets repaired; mutation samples then show what's
+    /// happened to parent_a, and mutation diagnostics count both parents'
+    /// genes.
+    ///
+    /// Mostly for experiments, e.g. to see how much it matters.
+    MutationFirst,
 }
 
-/*Bunch of useful properties for easy handling of chromosomes : */
+/// Decides which chromosomes can be crossed with each other - see:
+/// `GeneticAlgorithm::with_compatibility()`.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum CompatibilityPolicy {
+    /// Every two chromosomes are compatible.
+    #[default]
+    Any,
 
-// ---------------------------------------------------------------------
-// | this is the type of expression you expect inside the square brackets
-// |
-// | e.g. if you implemented `Index<&str>`, you could write:
-// |   chromosome["yass"]
-// ---------------------------------------------------------------------
-impl Index<usize> for Chromosome {
-    type Output = f32;
+    /// Two chromosomes are compatible when given function says so.
+    Predicate(fn(&Chromosome, &Chromosome) -> bool),
 
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.genes[index]
-    }
+    /// Two chromosomes are compatible when they have the same length and
+    /// the Euclidean distance between their genes doesn't exceed given
+    /// threshold.
+    MaxDistance(Float),
 }
-// ------------------------------------------------------------------------------------------
-// | this is the type of the item an iterator should provide in order to be compatible
-// | with our chromosome
-// |
-// | (sometimes it's called the type an iterator *yields*)
-// |
-// | intuitively, since our chromosome is built of of floating-point numbers, we
-// | expect floating-point numbers in here as well
-// -------------- ---------------------------------------------------------------------------
-impl FromIterator<f32> for Chromosome {
-    fn from_iter<T: IntoIterator<Item = f32>>(iter: T) -> Self {
-        Self {
-            genes: iter.into_iter().collect(),
+
+impl CompatibilityPolicy {
+    pub fn allows(&self, a: &Chromosome, b: &Chromosome) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Predicate(predicate) => predicate(a, b),
+            Self::MaxDistance(threshold) => {
+                let distance: Float = a.iter().zip(b.iter()).map(|(a, b)| (a - b) * (a - b)).sum();
+
+                a.len() == b.len() && distance <= threshold * threshold
+            }
         }
     }
 }
-// works in the opposite way - it converts a type into an iterator
-impl IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = std::vec::IntoIter<f32>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.genes.into_iter()
+/// Fraction of `child`'s genes inherited from `parent_a`.
+fn inheritance(parent_a: &Chromosome, parent_b: &Chromosome, child: &Chromosome) -> f32 {
+    if child.is_empty() {
+        return 0.5;
     }
+
+    let from_a: f32 = parent_a
+        .iter()
+        .zip(parent_b.iter())
+        .zip(child.iter())
+        .map(|((a, b), gene)| match (gene == a, gene == b) {
+            (true, true) => 0.5,
+            (true, false) => 1.0,
+            _ => 0.0,
+        })
+        .sum();
+
+    from_a / child.len() as f32
 }
 
-pub trait CrossoverMethod{
-    fn crossover(
-        &self,
-        rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome
-    ) -> Chromosome;
+/// Computes ages of a child's genes - see: `evolve_with_gene_ages()`;
+/// `crossed` is the child right after crossover, `child` - after mutation.
+fn age_genes(
+    (parent_a, ages_a): (&Chromosome, &[u16]),
+    (parent_b, ages_b): (&Chromosome, &[u16]),
+    crossed: &Chromosome,
+    child: &Chromosome,
+) -> Vec<u16> {
+    (0..child.len())
+        .map(|idx| {
+            let gene = crossed[idx];
+
+            let age = match (gene == parent_a[idx], gene == parent_b[idx]) {
+                (true, true) => ages_a[idx].max(ages_b[idx]),
+                (true, false) => ages_a[idx],
+                (false, true) => ages_b[idx],
+                (false, false) => return 0,
+            };
+
+            if child[idx] == gene {
+                age.saturating_add(1)
+            } else {
+                0
+            }
+        })
+        .collect()
 }
+
+/// Child produced during `evolve_sampled()`, before and after mutation.
 #[derive(Clone, Debug)]
-pub struct UniformCrossover;
-impl CrossoverMethod for UniformCrossover{
-    fn crossover(
-            &self,
-            rng: &mut dyn RngCore,
-            parent_a: &Chromosome,
-            parent_b: &Chromosome
-        ) -> Chromosome {
-            assert_eq!(parent_a.len(), parent_b.len());
-            parent_a
-                .iter()
-                .zip(parent_b.iter())
-                .map(|(&a, &b) | if rng.gen_bool(0.5) {a} else {b})
-                .collect()
+pub struct MutationSample {
+    pub unmutated: Chromosome,
+    pub mutated: Chromosome,
+}
+
+/// Whether `idx`-th child should be sampled, so that `sample_rate` of
+/// all children get sampled, spread evenly across the population.
+fn is_sampled(idx: usize, sample_rate: f32) -> bool {
+    // Both are non-negative, so truncating is flooring - without needing
+    // `f32::floor()`, which requires `std`
+    let before = (idx as f32 * sample_rate) as usize;
+    let after = ((idx + 1) as f32 * sample_rate) as usize;
+
+    after > before
+}
+
+/// What `GeneticAlgorithm::reproduce()` should keep track of, on top of
+/// producing the child.
+#[derive(Default)]
+struct Tracking<'a> {
+    sample: bool,
+    gene_ages: bool,
+    diagnostics: Option<&'a mut EvolveDiagnostics>,
+}
+
+/// Which part of a (possibly) paired crossover `GeneticAlgorithm::reproduce()`
+/// is about - see: `GeneticAlgorithm::with_paired_offspring()`.
+enum Pairing<'a> {
+    /// The child doesn't get a twin.
+    Single,
+
+    /// The child's twin (if it gets one) goes here, waiting for the next
+    /// slot.
+    First(&'a mut Option<Twin>),
+
+    /// The child is the twin of the previous one.
+    Second(Twin),
+}
+
+/// Second child of a paired crossover, waiting for its slot.
+struct Twin {
+    /// Repaired, but - unless the parents were mutated first - not
+    /// mutated yet.
+    child: Chromosome,
+
+    /// Parents the twin got crossed from, if they were mutated first.
+    parents: Option<(Chromosome, Chromosome)>,
+}
+
+/// Child produced by `GeneticAlgorithm::reproduce()`.
+struct Offspring {
+    child: Chromosome,
+
+    /// Child right after the crossover (and repair), if it's been tracked;
+    /// see: `age_genes()`.
+    crossed: Option<Chromosome>,
+
+    sample: Option<MutationSample>,
+}
+
+/// Random number generator(s) used during a single `evolve()`.
+enum Rngs<'a> {
+    Shared(&'a mut dyn RngCore),
+    Split {
+        rng_sel: &'a mut dyn RngCore,
+        rng_mut: &'a mut dyn RngCore,
+    },
+}
+impl Rngs<'_> {
+    fn selection(&mut self) -> &mut dyn RngCore {
+        match self {
+            Self::Shared(rng) => *rng,
+            Self::Split { rng_sel, .. } => *rng_sel,
+        }
+    }
+
+    fn mutation(&mut self) -> &mut dyn RngCore {
+        match self {
+            Self::Shared(rng) => *rng,
+            Self::Split { rng_mut, .. } => *rng_mut,
+        }
     }
 }
 
-pub trait MutationMethod{
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+/// Something that can evolve.
+///
+/// `GeneticAlgorithm` asks for each individual's fitness exactly once per
+/// evolution, remembering it for the selection & statistics (which need
+/// it over and over) - so `fitness()` is free to be expensive, e.g. to run
+/// a whole rollout; it's selection methods called directly that ask for
+/// it as many times as they see fit.
+pub trait Individual {
+    fn fitness(&self) -> Float;
+    fn chromosome(&self) -> &Chromosome;
+    fn create(chromosome: Chromosome) -> Self;
 }
-#[derive(Clone, Debug)]
-pub struct GaussianMutation {
-    /// Probability of changing a gene:
-    /// - 0.0 = no genes will be touched
-    /// - 1.0 = all genes will be touched
-    chance: f32,
 
-    /// Magnitude of that change:
-    /// - 0.0 = touched genes will not be modified
-    /// - 3.0 = touched genes will be += or -= by at most 3.0
-    coeff: f32,
+pub trait SelectionMethod {
+    /// Selects an individual and returns where it sits in `population` -
+    /// e.g. to trace ancestry (which, within evolution, is what
+    /// `Evolution::parents` is for).
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual;
+
+    /// Like `select_index()`, but returns the selected individual itself.
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual,
+    {
+        &population[self.select_index(rng, population)]
+    }
+
+    /// Whether `GeneticAlgorithm` should select all parents of a generation
+    /// at once, through `select_many()`, instead of one by one; the latter
+    /// (default) interleaves selection with crossover, which is what
+    /// seeded runs have always done.
+    const BATCHED: bool = false;
+
+    /// Same as `BATCHED`, just through a method - which, unlike the
+    /// constant, a boxed `DynSelectionMethod` can forward to whatever it
+    /// holds.
+    fn is_batched(&self) -> bool {
+        Self::BATCHED
+    }
+
+    /// Selects `n` individuals at once - e.g. all the parents a generation
+    /// needs - returning their indices; by default, that's just `n`
+    /// independent `select_index()`-es, but methods that spread their picks
+    /// over the whole set (such as `StochasticUniversalSampling`) can
+    /// override it.
+    fn select_many<I>(&self, rng: &mut dyn RngCore, population: &[I], n: usize) -> Vec<usize>
+    where
+        I: Individual,
+    {
+        (0..n).map(|_| self.select_index(rng, population)).collect()
+    }
+
+    /// Selects both parents of a child, making sure they are two different
+    /// individuals (unless the population has just one); by default,
+    /// parent_b gets re-selected until it's not parent_a - falling back to
+    /// a uniform pick among the rest once that's taking too long.
+    ///
+    /// Returns indices of both parents.
+    ///
+    /// Methods that can exclude an individual directly (such as
+    /// `RouletteWheelSelection`) can override it.
+    fn select_pair<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (usize, usize)
+    where
+        I: Individual,
+    {
+        let idx_a = self.select_index(rng, population);
+        let idx_b = select_other(rng, population.len(), idx_a, |rng| self.select_index(rng, population));
+
+        (idx_a, idx_b)
+    }
+
+    /// Binds this method to `population`, so that picking many times from
+    /// the same population doesn't have to recompute what the method needs
+    /// to know about it on each pick - that's what `GeneticAlgorithm` picks
+    /// parents through.
+    ///
+    /// By default, that's whatever `prepare_owned()` returns or, if it's
+    /// nothing, just the method itself; either way, the prepared selection
+    /// has to pick exactly what the method itself would.
+    fn prepare<'a, I>(&'a self, population: &'a [I]) -> Box<dyn PreparedSelection + 'a>
+    where
+        Self: Sized,
+        I: Individual,
+    {
+        match self.prepare_owned(population) {
+            Some(prepared) => prepared,
+            None => Box::new(Unprepared {
+                method: self,
+                population,
+            }),
+        }
+    }
+
+    /// Like `prepare()`, but for prepared selections that don't borrow the
+    /// population - which is what methods that would otherwise go through
+    /// the whole population on each pick (such as `RouletteWheelSelection`)
+    /// override, so that they keep their precomputation even when picked
+    /// through a boxed `DynSelectionMethod`.
+    ///
+    /// Defaults to `None`, i.e. nothing worth precomputing.
+    fn prepare_owned<I>(&self, _population: &[I]) -> Option<Box<dyn PreparedSelection + '_>>
+    where
+        I: Individual,
+    {
+        None
+    }
 }
-impl GaussianMutation{
-    pub fn new(chance:f32, coeff:f32) -> Self {
-        assert!(chance >= 0.0 && chance <= 1.0);
-        Self { chance, coeff }
+
+/// Selection method bound to a population; see: `SelectionMethod::prepare()`.
+pub trait PreparedSelection {
+    /// See: `SelectionMethod::select_index()`.
+    fn select_index(&self, rng: &mut dyn RngCore) -> usize;
+
+    /// See: `SelectionMethod::select_pair()`.
+    fn select_pair(&self, rng: &mut dyn RngCore) -> (usize, usize);
+}
+
+struct Unprepared<'a, S, I> {
+    method: &'a S,
+    population: &'a [I],
+}
+
+impl<S, I> PreparedSelection for Unprepared<'_, S, I>
+where
+    S: SelectionMethod,
+    I: Individual,
+{
+    fn select_index(&self, rng: &mut dyn RngCore) -> usize {
+        self.method.select_index(rng, self.population)
+    }
+
+    fn select_pair(&self, rng: &mut dyn RngCore) -> (usize, usize) {
+        self.method.select_pair(rng, self.population)
     }
 }
-impl MutationMethod for GaussianMutation{
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
-        for gene in child.iter_mut(){
-            let sign = if rng.gen_bool(0.5) {-1.0} else {1.0};
 
-            if rng.gen_bool(self.chance as f64){
-                *gene += sign * self.coeff * rng.gen::<f32>();
+/// How many times `select_other()` re-selects before giving up on the
+/// selection method.
+const SELECT_OTHER_RETRIES: usize = 16;
+
+/// Selects (through `select`) an index other than `other`, out of `len`
+/// ones - unless there's nothing else to select.
+fn select_other(
+    rng: &mut dyn RngCore,
+    len: usize,
+    other: usize,
+    mut select: impl FnMut(&mut dyn RngCore) -> usize,
+) -> usize {
+    if len < 2 {
+        return other;
+    }
+
+    for _ in 0..SELECT_OTHER_RETRIES {
+        let selected = select(rng);
+
+        if selected != other {
+            return selected;
+        }
+    }
+
+    let idx = rng.gen_range(0..len - 1);
+    if idx < other { idx } else { idx + 1 }
+}
+
+/// Like `select_other()`, but avoids all of `others` - unless there's
+/// nothing else to select.
+fn select_distinct(
+    rng: &mut dyn RngCore,
+    len: usize,
+    others: &[usize],
+    mut select: impl FnMut(&mut dyn RngCore) -> usize,
+) -> usize {
+    for _ in 0..SELECT_OTHER_RETRIES {
+        let selected = select(rng);
+
+        if !others.contains(&selected) {
+            return selected;
+        }
+    }
+
+    (0..len)
+        .filter(|idx| !others.contains(idx))
+        .choose(rng)
+        .unwrap_or_else(|| select(rng))
+}
+
+/// Rearranges batched picks, consumed two at a time as parent_a and
+/// parent_b, so that no pair consists of the same individual twice -
+/// without discarding or adding any pick, so that e.g. the spread
+/// `StochasticUniversalSampling` guarantees survives.
+///
+/// A duplicated pair trades its second pick with a pair that doesn't
+/// contain that individual at all, trying later pairs first; there's
+/// always one, unless the individual makes up more than half the picks -
+/// such pairs stay as they are.
+fn separate_pairs(picks: &mut [usize]) {
+    let pairs = picks.len() / 2;
+
+    for pair in 0..pairs {
+        let dup = picks[2 * pair];
+
+        if picks[2 * pair + 1] != dup {
+            continue;
+        }
+
+        let other = (pair + 1..pairs)
+            .chain(0..pair)
+            .find(|&other| picks[2 * other] != dup && picks[2 * other + 1] != dup);
+
+        if let Some(other) = other {
+            picks.swap(2 * pair + 1, 2 * other);
+        }
+    }
+}
+
+/// Object-safe counterpart of `SelectionMethod`, implemented for every one
+/// of them - so that the selection method can be picked at runtime (e.g.
+/// from a configuration file) as a `Box<dyn DynSelectionMethod>`, which is
+/// a `SelectionMethod` itself.
+///
+/// Through it, selection methods see the individuals' fitness and
+/// chromosomes (`fitnesses[idx]` and `chromosomes[idx]` belong to the same
+/// individual), which is all an `Individual` has to offer them.
+pub trait DynSelectionMethod {
+    /// See: `SelectionMethod::select_index()`.
+    fn dyn_select_index(&self, rng: &mut dyn RngCore, fitnesses: &[Float], chromosomes: &[&Chromosome]) -> usize;
+
+    /// See: `SelectionMethod::select_many()`.
+    fn dyn_select_many(
+        &self,
+        rng: &mut dyn RngCore,
+        fitnesses: &[Float],
+        chromosomes: &[&Chromosome],
+        n: usize,
+    ) -> Vec<usize>;
+
+    /// See: `SelectionMethod::select_pair()`.
+    fn dyn_select_pair(&self, rng: &mut dyn RngCore, fitnesses: &[Float], chromosomes: &[&Chromosome]) -> (usize, usize);
+
+    /// See: `SelectionMethod::BATCHED`.
+    fn dyn_is_batched(&self) -> bool;
+
+    /// See: `SelectionMethod::prepare()`.
+    fn dyn_prepare<'a>(&'a self, fitnesses: &[Float], chromosomes: &[&'a Chromosome]) -> Box<dyn PreparedSelection + 'a>;
+}
+
+impl<S> DynSelectionMethod for S
+where
+    S: SelectionMethod,
+{
+    fn dyn_select_index(&self, rng: &mut dyn RngCore, fitnesses: &[Float], chromosomes: &[&Chromosome]) -> usize {
+        self.select_index(rng, &scored(fitnesses, chromosomes))
+    }
+
+    fn dyn_select_many(
+        &self,
+        rng: &mut dyn RngCore,
+        fitnesses: &[Float],
+        chromosomes: &[&Chromosome],
+        n: usize,
+    ) -> Vec<usize> {
+        self.select_many(rng, &scored(fitnesses, chromosomes), n)
+    }
+
+    fn dyn_select_pair(&self, rng: &mut dyn RngCore, fitnesses: &[Float], chromosomes: &[&Chromosome]) -> (usize, usize) {
+        self.select_pair(rng, &scored(fitnesses, chromosomes))
+    }
+
+    fn dyn_is_batched(&self) -> bool {
+        self.is_batched()
+    }
+
+    fn dyn_prepare<'a>(&'a self, fitnesses: &[Float], chromosomes: &[&'a Chromosome]) -> Box<dyn PreparedSelection + 'a> {
+        let population = scored(fitnesses, chromosomes);
+
+        match self.prepare_owned(&population) {
+            Some(prepared) => prepared,
+
+            // Unlike `Unprepared`, it has to own whatever it picks from -
+            // but at least the individuals don't get re-scored on each pick
+            None => Box::new(ScoredUnprepared {
+                method: self,
+                population,
+            }),
+        }
+    }
+}
+
+struct ScoredUnprepared<'a, S> {
+    method: &'a S,
+    population: Vec<Scored<'a>>,
+}
+
+impl<S> PreparedSelection for ScoredUnprepared<'_, S>
+where
+    S: SelectionMethod,
+{
+    fn select_index(&self, rng: &mut dyn RngCore) -> usize {
+        self.method.select_index(rng, &self.population)
+    }
+
+    fn select_pair(&self, rng: &mut dyn RngCore) -> (usize, usize) {
+        self.method.select_pair(rng, &self.population)
+    }
+}
+
+impl<S> SelectionMethod for Box<S>
+where
+    S: DynSelectionMethod + ?Sized,
+{
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual,
+    {
+        (**self).dyn_select_index(rng, &fitnesses(population), &chromosomes(population))
+    }
+
+    fn is_batched(&self) -> bool {
+        (**self).dyn_is_batched()
+    }
+
+    fn select_many<I>(&self, rng: &mut dyn RngCore, population: &[I], n: usize) -> Vec<usize>
+    where
+        I: Individual,
+    {
+        (**self).dyn_select_many(rng, &fitnesses(population), &chromosomes(population), n)
+    }
+
+    fn select_pair<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (usize, usize)
+    where
+        I: Individual,
+    {
+        (**self).dyn_select_pair(rng, &fitnesses(population), &chromosomes(population))
+    }
+
+    /// Scores the population just once, instead of on each pick.
+    fn prepare<'a, I>(&'a self, population: &'a [I]) -> Box<dyn PreparedSelection + 'a>
+    where
+        I: Individual,
+    {
+        (**self).dyn_prepare(&fitnesses(population), &chromosomes(population))
+    }
+}
+
+fn fitnesses<I>(population: &[I]) -> Vec<Float>
+where
+    I: Individual,
+{
+    population.iter().map(|individual| individual.fitness()).collect()
+}
+
+fn chromosomes<I>(population: &[I]) -> Vec<&Chromosome>
+where
+    I: Individual,
+{
+    population.iter().map(|individual| individual.chromosome()).collect()
+}
+
+fn scored<'a>(fitnesses: &[Float], chromosomes: &[&'a Chromosome]) -> Vec<Scored<'a>> {
+    assert_eq!(fitnesses.len(), chromosomes.len(), "every individual needs both fitness and chromosome");
+
+    fitnesses
+        .iter()
+        .zip(chromosomes)
+        .map(|(&fitness, &chromosome)| Scored { fitness, chromosome })
+        .collect()
+}
+
+/// Individual as seen through `DynSelectionMethod`.
+struct Scored<'a> {
+    fitness: Float,
+    chromosome: &'a Chromosome,
+}
+
+impl Individual for Scored<'_> {
+    fn fitness(&self) -> Float {
+        self.fitness
+    }
+
+    fn chromosome(&self) -> &Chromosome {
+        self.chromosome
+    }
+
+    fn create(_: Chromosome) -> Self {
+        unreachable!("scored individuals only ever get selected")
+    }
+}
+
+pub struct RouletteWheelSelection;
+impl SelectionMethod for RouletteWheelSelection {
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual,
+    {
+        RouletteWheel::new(population).select_index(rng)
+    }
+
+    fn select_many<I>(&self, rng: &mut dyn RngCore, population: &[I], n: usize) -> Vec<usize>
+    where
+        I: Individual,
+    {
+        let wheel = RouletteWheel::new(population);
+        (0..n).map(|_| wheel.select_index(rng)).collect()
+    }
+
+    fn select_pair<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (usize, usize)
+    where
+        I: Individual,
+    {
+        RouletteWheel::new(population).select_pair(rng)
+    }
+
+    fn prepare_owned<I>(&self, population: &[I]) -> Option<Box<dyn PreparedSelection + '_>>
+    where
+        I: Individual,
+    {
+        Some(Box::new(RouletteWheel::new(population)))
+    }
+}
+
+/// `RouletteWheelSelection`'s cumulative fitness table, so that each spin
+/// is just a binary search.
+struct RouletteWheel {
+    fitnesses: Vec<Float>,
+
+    /// `None` when nobody has any fitness (e.g. no bird managed to find any
+    /// food) - all individuals are equally good then, so instead of giving
+    /// up, the wheel picks one uniformly.
+    weights: Option<WeightedIndex<Float>>,
+}
+
+impl RouletteWheel {
+    fn new<I>(population: &[I]) -> Self
+    where
+        I: Individual,
+    {
+        Self::from_fitnesses(population.iter().map(|indiv| indiv.fitness()).collect())
+    }
+
+    fn from_fitnesses(fitnesses: Vec<Float>) -> Self {
+        assert!(!fitnesses.is_empty(), "got an empty population");
+
+        let weights = (!fitnesses.iter().all(|&fitness| fitness == 0.0))
+            .then(|| WeightedIndex::new(&fitnesses).expect("got an empty population"));
+
+        Self { fitnesses, weights }
+    }
+}
+
+impl PreparedSelection for RouletteWheel {
+    // Draws just what `choose()` & `choose_weighted()` on the population
+    // would, so seeded runs select the same individuals as they always have
+    fn select_index(&self, rng: &mut dyn RngCore) -> usize {
+        match &self.weights {
+            Some(weights) => weights.sample(rng),
+            None => (0..self.fitnesses.len()).choose(rng).expect("got an empty population"),
+        }
+    }
+
+    /// When parent_b comes out the same as parent_a, spins a wheel without
+    /// parent_a instead - it's the same distribution as spinning until a
+    /// different one comes out, just without the (potentially many) spins
+    /// in between.
+    fn select_pair(&self, rng: &mut dyn RngCore) -> (usize, usize) {
+        let idx_a = self.select_index(rng);
+        let idx_b = self.select_index(rng);
+
+        if self.fitnesses.len() < 2 || idx_a != idx_b {
+            return (idx_a, idx_b);
+        }
+
+        let others: Vec<_> = (0..self.fitnesses.len()).filter(|&idx| idx != idx_a).collect();
+
+        let idx_b = if others.iter().all(|&idx| self.fitnesses[idx] == 0.0) {
+            others.choose(rng)
+        } else {
+            others.choose_weighted(rng, |&idx| self.fitnesses[idx]).ok()
+        };
+
+        (idx_a, *idx_b.expect("population has at least two individuals"))
+    }
+}
+
+/// Picks `size` distinct individuals at random and returns the fittest of
+/// them; unlike `RouletteWheelSelection`, it depends only on the order of
+/// fitnesses - so it keeps up the pressure even when they're close together.
+///
+/// Larger tournaments mean stronger pressure; a size of 1 is a uniform pick
+/// (0 is treated as 1) and tournaments larger than the population get
+/// capped at its length.
+#[derive(Clone, Copy, Debug)]
+pub struct TournamentSelection {
+    pub size: usize,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize) -> Self {
+        Self { size }
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty(), "got an empty population");
+
+        let size = self.size.clamp(1, population.len());
+
+        // Draws just what `choose_multiple()` on the population would
+        rand::seq::index::sample(rng, population.len(), size)
+            .into_iter()
+            .max_by(|&a, &b| population[a].fitness().total_cmp(&population[b].fitness()))
+            .expect("got an empty population")
+    }
+}
+
+/// Picks individuals with probability proportional to
+/// `exp(fitness / temperature)`: high temperatures make it close to a
+/// uniform pick (exploration), low ones close to always picking the best
+/// individual (exploitation) - so annealing the temperature between
+/// generations (see: `GeneticAlgorithm::selection_method_mut()`) shifts
+/// the run from one to the other.
+///
+/// Needs `exp()`, so it's available only with the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct BoltzmannSelection {
+    pub temperature: f32,
+}
+
+#[cfg(feature = "std")]
+impl BoltzmannSelection {
+    pub fn new(temperature: f32) -> Self {
+        assert!(temperature > 0.0);
+
+        Self { temperature }
+    }
+
+    pub fn set_temperature(&mut self, temperature: f32) {
+        assert!(temperature > 0.0);
+
+        self.temperature = temperature;
+    }
+}
+
+#[cfg(feature = "std")]
+impl BoltzmannSelection {
+    /// Roulette wheel over each individual's `exp(fitness / temperature)`,
+    /// so that weights get computed once per population.
+    fn wheel<I>(&self, population: &[I]) -> RouletteWheel
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty(), "got an empty population");
+
+        let temperature = self.temperature as Float;
+
+        // Shifting all the fitnesses so that the best one is zero doesn't
+        // change the probabilities, but keeps `exp()` from overflowing -
+        // and the best individual always has a weight of 1
+        let max_fitness = population
+            .iter()
+            .map(|indiv| indiv.fitness())
+            .fold(Float::NEG_INFINITY, Float::max);
+
+        RouletteWheel::from_fitnesses(
+            population
+                .iter()
+                .map(|indiv| ((indiv.fitness() - max_fitness) / temperature).exp())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl SelectionMethod for BoltzmannSelection {
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual,
+    {
+        self.wheel(population).select_index(rng)
+    }
+
+    fn select_many<I>(&self, rng: &mut dyn RngCore, population: &[I], n: usize) -> Vec<usize>
+    where
+        I: Individual,
+    {
+        let wheel = self.wheel(population);
+        (0..n).map(|_| wheel.select_index(rng)).collect()
+    }
+
+    fn select_pair<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (usize, usize)
+    where
+        I: Individual,
+    {
+        self.wheel(population).select_pair(rng)
+    }
+
+    fn prepare_owned<I>(&self, population: &[I]) -> Option<Box<dyn PreparedSelection + '_>>
+    where
+        I: Individual,
+    {
+        Some(Box::new(self.wheel(population)))
+    }
+}
+
+/// Lets only the fittest `fraction` of the population breed, picking
+/// uniformly among them - e.g. 0.25 keeps the top quarter; at least one
+/// individual always survives, however small the population.
+///
+/// Ties at the cutoff go to individuals that come first in the population.
+#[derive(Clone, Copy, Debug)]
+pub struct TruncationSelection {
+    pub fraction: f32,
+}
+
+impl TruncationSelection {
+    pub fn new(fraction: f32) -> Self {
+        assert!(fraction > 0.0 && fraction <= 1.0);
+
+        Self { fraction }
+    }
+
+    fn survivors<I>(&self, population: &[I]) -> Survivors
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty(), "got an empty population");
+        assert!(self.fraction > 0.0 && self.fraction <= 1.0);
+
+        let survivors = ((population.len() as f32 * self.fraction) as usize).max(1);
+        let fitnesses: Vec<_> = population.iter().map(|indiv| indiv.fitness()).collect();
+
+        let mut ranked: Vec<_> = (0..population.len()).collect();
+        ranked.sort_by(|&a, &b| fitnesses[b].total_cmp(&fitnesses[a]));
+        ranked.truncate(survivors);
+
+        Survivors {
+            ranked,
+            len: population.len(),
+        }
+    }
+}
+
+impl SelectionMethod for TruncationSelection {
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual,
+    {
+        self.survivors(population).select_index(rng)
+    }
+
+    fn select_many<I>(&self, rng: &mut dyn RngCore, population: &[I], n: usize) -> Vec<usize>
+    where
+        I: Individual,
+    {
+        let survivors = self.survivors(population);
+        (0..n).map(|_| survivors.select_index(rng)).collect()
+    }
+
+    fn prepare_owned<I>(&self, population: &[I]) -> Option<Box<dyn PreparedSelection + '_>>
+    where
+        I: Individual,
+    {
+        Some(Box::new(self.survivors(population)))
+    }
+}
+
+/// `TruncationSelection`'s ranking, so that the population gets sorted
+/// once per generation instead of on each pick.
+struct Survivors {
+    /// Indices of the fittest individuals, fittest first.
+    ranked: Vec<usize>,
+
+    /// Length of the whole population.
+    len: usize,
+}
+
+impl PreparedSelection for Survivors {
+    fn select_index(&self, rng: &mut dyn RngCore) -> usize {
+        *self.ranked.choose(rng).expect("at least one individual survives")
+    }
+
+    /// Same as `SelectionMethod::select_pair()`'s default.
+    fn select_pair(&self, rng: &mut dyn RngCore) -> (usize, usize) {
+        let idx_a = self.select_index(rng);
+        let idx_b = select_other(rng, self.len, idx_a, |rng| self.select_index(rng));
+
+        (idx_a, idx_b)
+    }
+}
+
+/// Picks individuals with probability that grows linearly with their rank:
+/// out of `n` picks, the best individual is expected to get `pressure` of
+/// them and the worst one `2 - pressure` - so 1.0 is a uniform pick and
+/// 2.0 never picks the worst individual at all.
+///
+/// Like `TournamentSelection`, it depends only on the order of fitnesses;
+/// ties get ranked in the order of the population.
+#[derive(Clone, Copy, Debug)]
+pub struct LinearRankSelection {
+    pub pressure: f32,
+}
+
+impl LinearRankSelection {
+    pub fn new(pressure: f32) -> Self {
+        assert!((1.0..=2.0).contains(&pressure));
+
+        Self { pressure }
+    }
+
+    /// Weight of the individual at `rank` (0 being the worst one) out of
+    /// `n`; weights add up to `n`.
+    fn weight(&self, rank: usize, n: usize) -> Float {
+        let pressure = self.pressure as Float;
+
+        if n < 2 {
+            return 1.0;
+        }
+
+        (2.0 - pressure) + 2.0 * (pressure - 1.0) * rank as Float / (n - 1) as Float
+    }
+
+    fn ranking<I>(&self, population: &[I]) -> Ranking
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty(), "got an empty population");
+        assert!((1.0..=2.0).contains(&self.pressure));
+
+        let n = population.len();
+        let fitnesses: Vec<_> = population.iter().map(|indiv| indiv.fitness()).collect();
+
+        // Worst first; sorting is stable, so ties keep their order
+        let mut ranked: Vec<_> = (0..n).collect();
+        ranked.sort_by(|&a, &b| fitnesses[a].total_cmp(&fitnesses[b]));
+
+        let weights = WeightedIndex::new((0..n).map(|rank| self.weight(rank, n)))
+            .expect("weights of the best individuals are positive");
+
+        Ranking { ranked, weights }
+    }
+}
+
+impl SelectionMethod for LinearRankSelection {
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual,
+    {
+        self.ranking(population).select_index(rng)
+    }
+
+    fn select_many<I>(&self, rng: &mut dyn RngCore, population: &[I], n: usize) -> Vec<usize>
+    where
+        I: Individual,
+    {
+        let ranking = self.ranking(population);
+        (0..n).map(|_| ranking.select_index(rng)).collect()
+    }
+
+    fn prepare_owned<I>(&self, population: &[I]) -> Option<Box<dyn PreparedSelection + '_>>
+    where
+        I: Individual,
+    {
+        Some(Box::new(self.ranking(population)))
+    }
+}
+
+/// `LinearRankSelection`'s ranking & weights, so that the population gets
+/// sorted once per generation instead of on each pick.
+struct Ranking {
+    /// Indices of all individuals, worst first.
+    ranked: Vec<usize>,
+
+    /// Weight of each rank.
+    weights: WeightedIndex<Float>,
+}
+
+impl PreparedSelection for Ranking {
+    fn select_index(&self, rng: &mut dyn RngCore) -> usize {
+        self.ranked[self.weights.sample(rng)]
+    }
+
+    /// Same as `SelectionMethod::select_pair()`'s default.
+    fn select_pair(&self, rng: &mut dyn RngCore) -> (usize, usize) {
+        let idx_a = self.select_index(rng);
+        let idx_b = select_other(rng, self.ranked.len(), idx_a, |rng| self.select_index(rng));
+
+        (idx_a, idx_b)
+    }
+}
+
+/// Delegates each selection to `a` with probability `probability`, and to
+/// `b` otherwise - e.g. to blend exploitation with exploration by using
+/// `TournamentSelection::new(3)` most of the time and a uniform pick
+/// (`TournamentSelection::new(1)`) for the rest.
+///
+/// Every pick is a separate draw, so it's never batched - even when `a` or
+/// `b` would be.
+#[derive(Clone, Copy, Debug)]
+pub struct MixedSelection<A, B> {
+    pub a: A,
+    pub b: B,
+    pub probability: f32,
+}
+
+impl<A, B> MixedSelection<A, B> {
+    pub fn new(a: A, b: B, probability: f32) -> Self {
+        assert!((0.0..=1.0).contains(&probability));
+
+        Self { a, b, probability }
+    }
+}
+
+impl<A, B> SelectionMethod for MixedSelection<A, B>
+where
+    A: SelectionMethod,
+    B: SelectionMethod,
+{
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual,
+    {
+        assert!((0.0..=1.0).contains(&self.probability));
+
+        if rng.gen_bool(self.probability as f64) {
+            self.a.select_index(rng, population)
+        } else {
+            self.b.select_index(rng, population)
+        }
+    }
+
+    /// Prepares both `a` and `b`, so that they keep their precomputation.
+    fn prepare<'a, I>(&'a self, population: &'a [I]) -> Box<dyn PreparedSelection + 'a>
+    where
+        I: Individual,
+    {
+        Box::new(Mixture {
+            a: self.a.prepare(population),
+            b: self.b.prepare(population),
+            probability: self.probability,
+            len: population.len(),
+        })
+    }
+
+    /// Prepares both `a` and `b`, provided that neither of them needs to
+    /// borrow the population.
+    fn prepare_owned<I>(&self, population: &[I]) -> Option<Box<dyn PreparedSelection + '_>>
+    where
+        I: Individual,
+    {
+        Some(Box::new(Mixture {
+            a: self.a.prepare_owned(population)?,
+            b: self.b.prepare_owned(population)?,
+            probability: self.probability,
+            len: population.len(),
+        }))
+    }
+}
+
+/// `MixedSelection`'s prepared `a` & `b`.
+struct Mixture<'a> {
+    a: Box<dyn PreparedSelection + 'a>,
+    b: Box<dyn PreparedSelection + 'a>,
+    probability: f32,
+    len: usize,
+}
+
+impl PreparedSelection for Mixture<'_> {
+    fn select_index(&self, rng: &mut dyn RngCore) -> usize {
+        assert!((0.0..=1.0).contains(&self.probability));
+
+        if rng.gen_bool(self.probability as f64) {
+            self.a.select_index(rng)
+        } else {
+            self.b.select_index(rng)
+        }
+    }
+
+    /// Same as `SelectionMethod::select_pair()`'s default.
+    fn select_pair(&self, rng: &mut dyn RngCore) -> (usize, usize) {
+        let idx_a = self.select_index(rng);
+        let idx_b = select_other(rng, self.len, idx_a, |rng| self.select_index(rng));
+
+        (idx_a, idx_b)
+    }
+}
+
+/// Picks individuals with probability proportional to their novelty -
+/// the average (euclidean) distance between their chromosome and the `k`
+/// nearest other ones - so it selects for genomes that are different,
+/// ignoring fitness altogether (e.g. for open-ended experiments).
+///
+/// When the population has no more than `k` other individuals, novelty is
+/// the average distance to all of them; when everybody's the same, picks
+/// are uniform. All chromosomes must have the same length.
+///
+/// Computing novelty takes `O(n² · chromosome length)`, which is why it
+/// only happens once per population when picking through `prepare()` (or
+/// `select_many()`).
+///
+/// Needs `sqrt()`, so it's available only with the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct NoveltySelection {
+    pub k: usize,
+}
+
+#[cfg(feature = "std")]
+impl NoveltySelection {
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0);
+
+        Self { k }
+    }
+
+    /// Novelty of each individual of `population`.
+    pub fn novelty<I>(&self, population: &[I]) -> Vec<Float>
+    where
+        I: Individual,
+    {
+        assert!(self.k > 0);
+
+        let len = population.first().map_or(0, |individual| individual.chromosome().len());
+
+        assert!(
+            population.iter().all(|individual| individual.chromosome().len() == len),
+            "chromosomes must have the same length",
+        );
+
+        let distance = |a: &Chromosome, b: &Chromosome| {
+            a.iter().zip(b.iter()).map(|(a, b)| (a - b) * (a - b)).sum::<Float>().sqrt()
+        };
+
+        (0..population.len())
+            .map(|idx| {
+                let mut distances: Vec<_> = (0..population.len())
+                    .filter(|&other| other != idx)
+                    .map(|other| distance(population[idx].chromosome(), population[other].chromosome()))
+                    .collect();
+
+                if distances.is_empty() {
+                    return 0.0;
+                }
+
+                distances.sort_by(Float::total_cmp);
+                distances.truncate(self.k);
+
+                distances.iter().sum::<Float>() / distances.len() as Float
+            })
+            .collect()
+    }
+
+    fn wheel<I>(&self, population: &[I]) -> RouletteWheel
+    where
+        I: Individual,
+    {
+        RouletteWheel::from_fitnesses(self.novelty(population))
+    }
+}
+
+#[cfg(feature = "std")]
+impl SelectionMethod for NoveltySelection {
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual,
+    {
+        self.wheel(population).select_index(rng)
+    }
+
+    fn select_many<I>(&self, rng: &mut dyn RngCore, population: &[I], n: usize) -> Vec<usize>
+    where
+        I: Individual,
+    {
+        let wheel = self.wheel(population);
+        (0..n).map(|_| wheel.select_index(rng)).collect()
+    }
+
+    fn select_pair<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (usize, usize)
+    where
+        I: Individual,
+    {
+        self.wheel(population).select_pair(rng)
+    }
+
+    fn prepare_owned<I>(&self, population: &[I]) -> Option<Box<dyn PreparedSelection + '_>>
+    where
+        I: Individual,
+    {
+        Some(Box::new(self.wheel(population)))
+    }
+}
+
+/// Spins the roulette wheel just once, with `n` equally spaced pointers,
+/// so that over a generation each individual gets selected as many times
+/// as its share of the fitness says, give or take one - whereas in small
+/// populations, `RouletteWheelSelection` sometimes doesn't pick the best
+/// individual at all.
+///
+/// Picks get shuffled, so that neighbouring pointers (which tend to land on
+/// the same individual) don't end up as each other's partners; a lone
+/// `select()` is the same as `RouletteWheelSelection`'s.
+#[derive(Clone, Copy, Debug)]
+pub struct StochasticUniversalSampling;
+
+impl SelectionMethod for StochasticUniversalSampling {
+    const BATCHED: bool = true;
+
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual,
+    {
+        RouletteWheelSelection.select_index(rng, population)
+    }
+
+    fn select_many<I>(&self, rng: &mut dyn RngCore, population: &[I], n: usize) -> Vec<usize>
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty(), "got an empty population");
+
+        if n == 0 {
+            return Vec::new();
+        }
+
+        // Just like with `RouletteWheelSelection`, when nobody has any
+        // fitness, everybody's equally good
+        let uniform = population.iter().all(|indiv| indiv.fitness() == 0.0);
+        let weight = |indiv: &I| if uniform { 1.0 } else { indiv.fitness() };
+
+        let total: Float = population.iter().map(weight).sum();
+        let spacing = total / n as Float;
+        let mut pointer = rng.gen::<Float>() * spacing;
+        let mut reached = 0.0;
+        let mut picks = Vec::with_capacity(n);
+
+        for (idx, indiv) in population.iter().enumerate() {
+            reached += weight(indiv);
+
+            while picks.len() < n && pointer < reached {
+                picks.push(idx);
+                pointer += spacing;
+            }
+        }
+
+        // Rounding errors can leave the last pointer just past the end
+        let last = (0..population.len())
+            .rev()
+            .find(|&idx| weight(&population[idx]) > 0.0)
+            .expect("got a population without any weight");
+
+        picks.resize(n, last);
+        picks.shuffle(rng);
+        picks
+    }
+}
+
+/// Rescales fitness before handing the population over to `inner`: each
+/// individual counts as `1 + (fitness - mean) / (2 * sigma)`, but no less
+/// than `floor` (0.1 by default) - so that a single outlier doesn't take
+/// over the whole generation, and so that small differences still matter
+/// once the population has converged.
+///
+/// Scaled fitnesses get computed once per `select_many()`, which is why
+/// this is always batched (see: `SelectionMethod::BATCHED`), whatever
+/// `inner` is.
+///
+/// Needs `sqrt()`, so it's available only with the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct SigmaScaledSelection<S> {
+    pub inner: S,
+    pub floor: f32,
+}
+
+#[cfg(feature = "std")]
+impl<S> SigmaScaledSelection<S> {
+    pub fn new(inner: S) -> Self {
+        Self { inner, floor: 0.1 }
+    }
+
+    pub fn with_floor(mut self, floor: f32) -> Self {
+        assert!(floor > 0.0);
+
+        self.floor = floor;
+        self
+    }
+
+    fn scale<'a, I>(&self, population: &'a [I]) -> Vec<Evaluated<'a, I>>
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty(), "got an empty population");
+
+        let n = population.len() as Float;
+        let mean = population.iter().map(I::fitness).sum::<Float>() / n;
+
+        let sigma = (population
+            .iter()
+            .map(|indiv| (indiv.fitness() - mean).powi(2))
+            .sum::<Float>()
+            / n)
+            .sqrt();
+
+        population
+            .iter()
+            .map(|individual| Evaluated {
+                individual,
+
+                // Everybody's equally good when there's no spread at all
+                fitness: if sigma > 0.0 {
+                    (1.0 + (individual.fitness() - mean) / (2.0 * sigma)).max(self.floor as Float)
+                } else {
+                    1.0
+                },
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S> SelectionMethod for SigmaScaledSelection<S>
+where
+    S: SelectionMethod,
+{
+    const BATCHED: bool = true;
+
+    fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+    where
+        I: Individual,
+    {
+        self.inner.select_index(rng, &self.scale(population))
+    }
+
+    fn select_many<I>(&self, rng: &mut dyn RngCore, population: &[I], n: usize) -> Vec<usize>
+    where
+        I: Individual,
+    {
+        self.inner.select_many(rng, &self.scale(population), n)
+    }
+}
+
+/// Individual whose fitness has been settled up front - evaluated once
+/// per evolution (see: `Individual`) or rescaled by `SigmaScaledSelection`.
+struct Evaluated<'a, I> {
+    individual: &'a I,
+    fitness: Float,
+}
+
+impl<'a, I> Evaluated<'a, I>
+where
+    I: Individual,
+{
+    fn new(individual: &'a I) -> Self {
+        Self {
+            individual,
+            fitness: individual.fitness(),
+        }
+    }
+}
+
+impl<I> Individual for Evaluated<'_, I>
+where
+    I: Individual,
+{
+    fn fitness(&self) -> Float {
+        self.fitness
+    }
+
+    fn chromosome(&self) -> &Chromosome {
+        self.individual.chromosome()
+    }
+
+    fn create(_: Chromosome) -> Self {
+        unreachable!("evaluated individuals only ever get selected")
+    }
+}
+
+
+#[derive(Clone, Debug)]
+pub struct Chromosome { //Carrying properties of our birds
+    genes: Vec<Float>,
+}
+impl Chromosome {
+    pub fn len(&self) -> usize {
+        self.genes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.genes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Float> {
+        self.genes.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Float> {
+        self.genes.iter_mut()
+    }
+
+    /// Adds `delta` to the gene at `index` - e.g. to nudge a seeded
+    /// population towards some behavior, while still leaving it up to
+    /// evolution whether to keep it.
+    pub fn offset_gene(&mut self, index: usize, delta: Float) {
+        self.genes[index] += delta;
+    }
+
+    /// Returns genes that differ between `self` (a) and `other` (b) by more
+    /// than `epsilon`, so that e.g. two champions can be compared without
+    /// drowning in rounding noise.
+    pub fn diff(&self, other: &Self, epsilon: Float) -> Result<Vec<GeneDiff>, ChromosomeError> {
+        assert!(epsilon >= 0.0);
+
+        if self.len() != other.len() {
+            return Err(ChromosomeError::LengthMismatch {
+                a: self.len(),
+                b: other.len(),
+            });
+        }
+
+        Ok(self
+            .iter()
+            .zip(other.iter())
+            .enumerate()
+            .map(|(index, (&a, &b))| GeneDiff { index, a, b, delta: b - a })
+            .filter(|diff| diff.delta.abs() > epsilon)
+            .collect())
+    }
+
+    /// Overwrites genes listed in `diffs` with their value from `source` -
+    /// e.g. to take only the output layer of one champion into another one,
+    /// pass just the diffs whose indices fall into that layer.
+    ///
+    /// Nothing gets changed if any of the indices is out of bounds.
+    pub fn patch(&mut self, diffs: &[GeneDiff], source: PatchSource) -> Result<(), ChromosomeError> {
+        if let Some(diff) = diffs.iter().find(|diff| diff.index >= self.len()) {
+            return Err(ChromosomeError::IndexOutOfBounds {
+                index: diff.index,
+                len: self.len(),
+            });
+        }
+
+        for diff in diffs {
+            self.genes[diff.index] = match source {
+                PatchSource::A => diff.a,
+                PatchSource::B => diff.b,
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Genes before `mid` and genes from `mid` onwards - e.g. a brain's
+    /// weights and the step sizes `SelfAdaptiveMutation` keeps after them.
+    pub fn split_at(&self, mid: usize) -> (&[Float], &[Float]) {
+        self.genes.split_at(mid)
+    }
+
+    /// Appends `count` step size genes, all starting at `sigma`; see:
+    /// `SelfAdaptiveMutation`.
+    pub fn with_sigmas(mut self, sigma: Float, count: usize) -> Self {
+        assert!(sigma >= 0.0);
+
+        self.genes.extend(core::iter::repeat_n(sigma, count));
+        self
+    }
+
+    /// Drops the last `count` genes - i.e. undoes `with_sigmas()`, e.g. to
+    /// turn a self-adapted chromosome back into a brain.
+    pub fn without_sigmas(mut self, count: usize) -> Self {
+        assert!(count <= self.len(), "chromosome has fewer genes than sigmas to drop");
+
+        self.genes.truncate(self.len() - count);
+        self
+    }
+}
+
+/// Single gene that differs between two chromosomes; see:
+/// `Chromosome::diff()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeneDiff {
+    pub index: usize,
+    pub a: Float,
+    pub b: Float,
+
+    /// `b - a`
+    pub delta: Float,
+}
+
+/// Which side of a `GeneDiff` `Chromosome::patch()` takes genes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatchSource {
+    A,
+    B,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChromosomeError {
+    LengthMismatch { a: usize, b: usize },
+    IndexOutOfBounds { index: usize, len: usize },
+}
+
+impl fmt::Display for ChromosomeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { a, b } => {
+                write!(f, "chromosomes have different lengths ({} vs {})", a, b)
+            }
+            Self::IndexOutOfBounds { index, len } => {
+                write!(f, "gene {} is out of bounds of a chromosome with {} genes", index, len)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChromosomeError {}
+
+/*Bunch of useful properties for easy handling of chromosomes : */
+
+// ---------------------------------------------------------------------
+// | this is the type of expression you expect inside the square brackets
+// |
+// | e.g. if you implemented `Index<&str>`, you could write:
+// |   chromosome["yass"]
+// ---------------------------------------------------------------------
+impl Index<usize> for Chromosome {
+    type Output = Float;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.genes[index]
+    }
+}
+// ------------------------------------------------------------------------------------------
+// | this is the type of the item an iterator should provide in order to be compatible
+// | with our chromosome
+// |
+// | (sometimes it's called the type an iterator *yields*)
+// |
+// | intuitively, since our chromosome is built of of floating-point numbers, we
+// | expect floating-point numbers in here as well
+// -------------- ---------------------------------------------------------------------------
+impl FromIterator<Float> for Chromosome {
+    fn from_iter<T: IntoIterator<Item = Float>>(iter: T) -> Self {
+        Self {
+            genes: iter.into_iter().collect(),
+        }
+    }
+}
+// works in the opposite way - it converts a type into an iterator
+impl IntoIterator for Chromosome {
+    type Item = Float;
+    type IntoIter = alloc::vec::IntoIter<Float>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.genes.into_iter()
+    }
+}
+
+pub trait CrossoverMethod{
+    /// Fails when the parents can't be crossed over - e.g. when one of
+    /// them comes from a corrupted save file and has a different length.
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome
+    ) -> Result<Chromosome, CrossoverError>;
+
+    /// Number of distinct parents each child gets crossed from; `evolve()`
+    /// selects the ones past the first two (as long as the population is
+    /// large enough to have them distinct) and passes all of them to
+    /// `crossover_many()`.
+    ///
+    /// Defaults to 2.
+    fn parent_count(&self) -> usize {
+        2
+    }
+
+    /// Crosses `parent_count()` parents over - for crossovers that need
+    /// more than two of them, e.g. `DifferentialRecombination`.
+    ///
+    /// By default it's `crossover()` of the first two.
+    fn crossover_many(&self, rng: &mut dyn RngCore, parents: &[&Chromosome]) -> Result<Chromosome, CrossoverError> {
+        match parents {
+            [parent_a, parent_b, ..] => self.crossover(rng, parent_a, parent_b),
+            _ => Err(CrossoverError::NotEnoughParents {
+                needed: 2,
+                got: parents.len(),
+            }),
+        }
+    }
+
+    /// Produces two children at once - see:
+    /// `GeneticAlgorithm::with_paired_offspring()`.
+    ///
+    /// By default it's just two independent crossovers (the second one
+    /// with the parents swapped); crossovers that pick each gene from
+    /// either parent override it to return true complements, where the
+    /// second child gets exactly the genes the first one didn't.
+    fn crossover_pair(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<(Chromosome, Chromosome), CrossoverError> {
+        Ok((self.crossover(rng, parent_a, parent_b)?, self.crossover(rng, parent_b, parent_a)?))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CrossoverError {
+    /// The parents have different numbers of genes.
+    LengthMismatch { a: usize, b: usize },
+
+    /// The parents' genes don't add up to the segments the crossover has
+    /// been configured with - see: `SegmentedCrossover`.
+    SegmentMismatch { segments: usize, genes: usize },
+
+    /// The crossover needs more parents than it's been given - see:
+    /// `CrossoverMethod::parent_count()`.
+    NotEnoughParents { needed: usize, got: usize },
+}
+
+impl CrossoverError {
+    /// Makes sure both parents have the same number of genes, which all
+    /// the built-in crossovers require.
+    pub fn check_lengths(parent_a: &Chromosome, parent_b: &Chromosome) -> Result<(), Self> {
+        if parent_a.len() == parent_b.len() {
+            Ok(())
+        } else {
+            Err(Self::LengthMismatch {
+                a: parent_a.len(),
+                b: parent_b.len(),
+            })
+        }
+    }
+}
+
+impl fmt::Display for CrossoverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { a, b } => {
+                write!(f, "parents have different lengths ({} vs {} genes)", a, b)
+            }
+            Self::SegmentMismatch { segments, genes } => {
+                write!(f, "segments cover {} genes, but parents have {}", segments, genes)
+            }
+            Self::NotEnoughParents { needed, got } => {
+                write!(f, "crossover needs {} parents, got {}", needed, got)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CrossoverError {}
+
+/// So that the crossover method can be picked at runtime, as a
+/// `Box<dyn CrossoverMethod>`.
+impl<C> CrossoverMethod for Box<C>
+where
+    C: CrossoverMethod + ?Sized,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        (**self).crossover(rng, parent_a, parent_b)
+    }
+
+    fn parent_count(&self) -> usize {
+        (**self).parent_count()
+    }
+
+    fn crossover_many(&self, rng: &mut dyn RngCore, parents: &[&Chromosome]) -> Result<Chromosome, CrossoverError> {
+        (**self).crossover_many(rng, parents)
+    }
+
+    fn crossover_pair(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<(Chromosome, Chromosome), CrossoverError> {
+        (**self).crossover_pair(rng, parent_a, parent_b)
+    }
+}
+/// Crossover that picks each gene from either parent independently, with
+/// even odds; see `with_bias()` to favor one of the parents.
+#[derive(Clone, Debug)]
+pub struct UniformCrossover;
+
+impl UniformCrossover {
+    /// Returns a crossover that picks each gene from `parent_a` with
+    /// probability `p_a`, e.g. to favor the fitter parent.
+    pub fn with_bias(p_a: f32) -> BiasedUniformCrossover {
+        BiasedUniformCrossover::new(p_a)
+    }
+}
+
+impl CrossoverMethod for UniformCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        BiasedUniformCrossover::new(0.5).crossover(rng, parent_a, parent_b)
+    }
+
+    fn crossover_pair(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<(Chromosome, Chromosome), CrossoverError> {
+        BiasedUniformCrossover::new(0.5).crossover_pair(rng, parent_a, parent_b)
+    }
+}
+
+/// `UniformCrossover` that picks each gene from `parent_a` with probability
+/// `p_a` (and from `parent_b` otherwise).
+#[derive(Clone, Copy, Debug)]
+pub struct BiasedUniformCrossover {
+    pub p_a: f32,
+}
+
+impl BiasedUniformCrossover {
+    pub fn new(p_a: f32) -> Self {
+        assert!((0.0..=1.0).contains(&p_a));
+
+        Self { p_a }
+    }
+}
+
+impl CrossoverMethod for BiasedUniformCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+
+        let child = parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| if rng.gen_bool(self.p_a as f64) { a } else { b })
+            .collect();
+
+        Ok(child)
+    }
+
+    fn crossover_pair(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<(Chromosome, Chromosome), CrossoverError> {
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+
+        let picks_a: Vec<_> = parent_a.iter().map(|_| rng.gen_bool(self.p_a as f64)).collect();
+
+        let child = |first: bool| -> Chromosome {
+            parent_a
+                .iter()
+                .zip(parent_b.iter())
+                .zip(&picks_a)
+                .map(|((&a, &b), &pick_a)| if pick_a == first { a } else { b })
+                .collect()
+        };
+
+        Ok((child(true), child(false)))
+    }
+}
+
+/// Crossover that cuts both parents at the same random point and glues
+/// `parent_a`'s genes before the cut with `parent_b`'s genes after it -
+/// unlike `UniformCrossover`, it keeps contiguous runs of genes (e.g. a
+/// neural network's layer) together.
+///
+/// The cut can land at either end, in which case the child is a clone of
+/// one of the parents.
+#[derive(Clone, Debug)]
+pub struct SinglePointCrossover;
+impl CrossoverMethod for SinglePointCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+
+        let cut = rng.gen_range(0..=parent_a.len());
+
+        Ok(Self::splice(parent_a, parent_b, cut))
+    }
+
+    /// Returns both halves' combinations, with the same cut.
+    fn crossover_pair(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<(Chromosome, Chromosome), CrossoverError> {
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+
+        let cut = rng.gen_range(0..=parent_a.len());
+
+        Ok((Self::splice(parent_a, parent_b, cut), Self::splice(parent_b, parent_a, cut)))
+    }
+}
+
+impl SinglePointCrossover {
+    fn splice(head: &Chromosome, tail: &Chromosome, cut: usize) -> Chromosome {
+        head.iter().take(cut).chain(tail.iter().skip(cut)).copied().collect()
+    }
+}
+
+/// Crossover that cuts both parents at `k` distinct random points and
+/// glues together the segments in between, alternating between the parents
+/// (starting with `parent_a`) - the middle ground between
+/// `SinglePointCrossover` and `UniformCrossover`.
+///
+/// Cuts land only between genes, so the child consists of exactly `k + 1`
+/// non-empty runs; `k` larger than that allows (i.e. than the length minus
+/// one) gets capped and `k = 0` returns a clone of `parent_a`.
+#[derive(Clone, Copy, Debug)]
+pub struct KPointCrossover {
+    pub k: usize,
+}
+
+impl KPointCrossover {
+    pub fn new(k: usize) -> Self {
+        Self { k }
+    }
+
+    /// Returns sorted indices of the genes where new segments start.
+    fn cuts(&self, rng: &mut dyn RngCore, len: usize) -> Vec<usize> {
+        let gaps = len.saturating_sub(1);
+
+        let mut cuts: Vec<_> = rand::seq::index::sample(rng, gaps, self.k.min(gaps))
+            .into_iter()
+            .map(|gap| gap + 1)
+            .collect();
+
+        cuts.sort_unstable();
+        cuts
+    }
+
+    /// Glues together the segments, starting with `first`'s.
+    fn alternate(first: &Chromosome, second: &Chromosome, cuts: &[usize]) -> Chromosome {
+        let mut cuts = cuts.iter().copied().peekable();
+        let mut from_first = true;
+
+        first
+            .iter()
+            .zip(second.iter())
+            .enumerate()
+            .map(|(idx, (&a, &b))| {
+                if cuts.next_if_eq(&idx).is_some() {
+                    from_first = !from_first;
+                }
+
+                if from_first {
+                    a
+                } else {
+                    b
+                }
+            })
+            .collect()
+    }
+}
+
+impl CrossoverMethod for KPointCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+
+        let cuts = self.cuts(rng, parent_a.len());
+
+        Ok(Self::alternate(parent_a, parent_b, &cuts))
+    }
+
+    /// Returns both alternations, with the same cuts.
+    fn crossover_pair(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<(Chromosome, Chromosome), CrossoverError> {
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+
+        let cuts = self.cuts(rng, parent_a.len());
+
+        Ok((Self::alternate(parent_a, parent_b, &cuts), Self::alternate(parent_b, parent_a, &cuts)))
+    }
+}
+
+/// Crossover that inherits whole contiguous segments of genes (e.g. a
+/// neural network's layers, see: `Brain::segment_lengths()` in the
+/// simulation) from either parent, picking the parent for each segment
+/// independently - so that, unlike `UniformCrossover`, it never splits a
+/// segment between the parents.
+///
+/// The segments have to add up to the parents' length, otherwise the
+/// crossover fails with `CrossoverError::SegmentMismatch`.
+#[derive(Clone, Debug)]
+pub struct SegmentedCrossover {
+    segment_lengths: Vec<usize>,
+}
+
+impl SegmentedCrossover {
+    pub fn new(segment_lengths: Vec<usize>) -> Self {
+        Self { segment_lengths }
+    }
+
+    /// Number of genes the parents have to have to be crossed.
+    pub fn genes(&self) -> usize {
+        self.segment_lengths.iter().sum()
+    }
+
+    /// Picks the parent for each segment - `true` meaning `parent_a`.
+    fn picks(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Vec<bool>, CrossoverError> {
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+
+        if self.genes() != parent_a.len() {
+            return Err(CrossoverError::SegmentMismatch {
+                segments: self.genes(),
+                genes: parent_a.len(),
+            });
+        }
+
+        Ok(self.segment_lengths.iter().map(|_| rng.gen_bool(0.5)).collect())
+    }
+
+    /// Glues the segments together, taking `parent_a`'s ones where
+    /// `picks` say `first`.
+    fn glue(&self, parent_a: &Chromosome, parent_b: &Chromosome, picks: &[bool], first: bool) -> Chromosome {
+        let picks = self
+            .segment_lengths
+            .iter()
+            .zip(picks)
+            .flat_map(|(&len, &pick_a)| core::iter::repeat_n(pick_a == first, len));
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .zip(picks)
+            .map(|((&a, &b), from_a)| if from_a { a } else { b })
+            .collect()
+    }
+}
+
+impl CrossoverMethod for SegmentedCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        let picks = self.picks(rng, parent_a, parent_b)?;
+
+        Ok(self.glue(parent_a, parent_b, &picks, true))
+    }
+
+    /// Returns both children of the same picks.
+    fn crossover_pair(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<(Chromosome, Chromosome), CrossoverError> {
+        let picks = self.picks(rng, parent_a, parent_b)?;
+
+        Ok((
+            self.glue(parent_a, parent_b, &picks, true),
+            self.glue(parent_a, parent_b, &picks, false),
+        ))
+    }
+}
+
+/// Differential evolution's recombination (DE/rand/1/bin), crossing three
+/// distinct parents into `parent_a + f * (parent_b - parent_c)` - with
+/// `cr` being the chance for each gene to come from that mutant vector,
+/// rather than straight from `parent_a` (see: `crossover_many()`).
+///
+/// Needs three parents, so plain two-parent `crossover()` fails with
+/// `CrossoverError::NotEnoughParents`.
+#[derive(Clone, Copy, Debug)]
+pub struct DifferentialRecombination {
+    pub f: f32,
+    pub cr: f32,
+}
+
+impl DifferentialRecombination {
+    pub fn new(f: f32, cr: f32) -> Self {
+        assert!(f >= 0.0);
+        assert!((0.0..=1.0).contains(&cr));
+
+        Self { f, cr }
+    }
+}
+
+impl CrossoverMethod for DifferentialRecombination {
+    fn crossover(
+        &self,
+        _rng: &mut dyn RngCore,
+        _parent_a: &Chromosome,
+        _parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        Err(CrossoverError::NotEnoughParents { needed: 3, got: 2 })
+    }
+
+    fn parent_count(&self) -> usize {
+        3
+    }
+
+    /// Binomial crossover mask: each gene comes from the mutant vector
+    /// with probability `cr` - and one randomly chosen gene always does,
+    /// so that the child never ends up a plain clone of `parent_a`.
+    fn crossover_many(&self, rng: &mut dyn RngCore, parents: &[&Chromosome]) -> Result<Chromosome, CrossoverError> {
+        let &[parent_a, parent_b, parent_c, ..] = parents else {
+            return Err(CrossoverError::NotEnoughParents {
+                needed: 3,
+                got: parents.len(),
+            });
+        };
+
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+        CrossoverError::check_lengths(parent_a, parent_c)?;
+
+        let forced = (!parent_a.is_empty()).then(|| rng.gen_range(0..parent_a.len()));
+        let f = self.f as Float;
+
+        let child = parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .zip(parent_c.iter())
+            .enumerate()
+            .map(|(idx, ((&a, &b), &c))| {
+                if Some(idx) == forced || rng.gen_bool(self.cr as f64) {
+                    a + f * (b - c)
+                } else {
+                    a
+                }
+            })
+            .collect();
+
+        Ok(child)
+    }
+}
+
+/// Crossover that blends the parents gene by gene, as in:
+/// `child[i] = alpha * parent_a[i] + (1 - alpha) * parent_b[i]` - so,
+/// unlike the other crossovers, it can produce genes neither parent has.
+///
+/// `alpha` of 1.0 returns `parent_a`, 0.0 returns `parent_b` and 0.5 their
+/// midpoint; `None` draws a separate alpha (from `<0, 1>`) for each gene.
+#[derive(Clone, Copy, Debug)]
+pub struct ArithmeticCrossover {
+    pub alpha: Option<f32>,
+}
+
+impl ArithmeticCrossover {
+    pub fn new(alpha: f32) -> Self {
+        assert!((0.0..=1.0).contains(&alpha));
+
+        Self { alpha: Some(alpha) }
+    }
+
+    /// Crossover that draws a separate alpha for each gene.
+    pub fn random() -> Self {
+        Self { alpha: None }
+    }
+}
+
+impl CrossoverMethod for ArithmeticCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+
+        let child = parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let alpha = self.alpha.unwrap_or_else(|| rng.gen_range(0.0..=1.0)) as Float;
+
+                alpha * a + (1.0 - alpha) * b
+            })
+            .collect();
+
+        Ok(child)
+    }
+}
+
+/// BLX-α crossover - draws each gene uniformly from the parents' range,
+/// widened by `alpha` times its width on both sides, i.e. from:
+/// `[min(a, b) - alpha * d, max(a, b) + alpha * d]`, where `d = |a - b|`.
+///
+/// Genes both parents agree on get passed on as they are.
+#[derive(Clone, Copy, Debug)]
+pub struct BlxAlphaCrossover {
+    pub alpha: f32,
+}
+
+impl BlxAlphaCrossover {
+    pub fn new(alpha: f32) -> Self {
+        assert!(alpha >= 0.0);
+
+        Self { alpha }
+    }
+}
+
+impl CrossoverMethod for BlxAlphaCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+
+        let child = parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                if a == b {
+                    return a;
+                }
+
+                let margin = self.alpha as Float * (a - b).abs();
+
+                rng.gen_range((a.min(b) - margin)..=(a.max(b) + margin))
+            })
+            .collect();
+
+        Ok(child)
+    }
+}
+
+/// Simulated binary crossover (SBX) - for each gene, flips a coin to
+/// either copy `parent_a`'s gene or to spread both parents' genes apart by
+/// a random factor `beta`, and take the one that lands on `parent_a`'s
+/// side:
+///
+/// `child = 0.5 * ((1 + beta) * a + (1 - beta) * b)`
+///
+/// The larger `eta` (the distribution index), the closer to 1.0 `beta`
+/// tends to be and so the closer children stay to their parents; small
+/// `eta`s spread them out.
+///
+/// Needs `powf()`, so it's available only with the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct SimulatedBinaryCrossover {
+    pub eta: f32,
+}
+
+#[cfg(feature = "std")]
+impl SimulatedBinaryCrossover {
+    pub fn new(eta: f32) -> Self {
+        assert!(eta >= 0.0 && eta.is_finite());
+
+        Self { eta }
+    }
+
+    /// Draws the spread factor.
+    fn beta(&self, rng: &mut dyn RngCore) -> Float {
+        let u: Float = rng.gen();
+        let exponent = 1.0 / (self.eta as Float + 1.0);
+
+        if u <= 0.5 {
+            (2.0 * u).powf(exponent)
+        } else {
+            (1.0 / (2.0 * (1.0 - u))).powf(exponent)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl CrossoverMethod for SimulatedBinaryCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check_lengths(parent_a, parent_b)?;
+
+        let child = parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                // There's nothing to spread when parents (nearly) agree
+                if !rng.gen_bool(0.5) || (a - b).abs() <= Float::EPSILON * a.abs().max(b.abs()) {
+                    return a;
+                }
+
+                let beta = self.beta(rng);
+
+                0.5 * ((1.0 + beta) * a + (1.0 - beta) * b)
+            })
+            .collect();
+
+        Ok(child)
+    }
+}
+
+/// Crossover that ignores `parent_b` and returns a clone of `parent_a`.
+///
+/// Note that with it, `evolve()` still selects two parents per child; to
+/// skip selecting the second one, see: `ReproductionMode::Asexual`.
+#[derive(Clone, Debug)]
+pub struct AsexualReproduction;
+impl CrossoverMethod for AsexualReproduction {
+    fn crossover(
+        &self,
+        _rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        _parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        Ok(parent_a.clone())
+    }
+}
+
+/// Picks one of several crossovers for each child, with probability
+/// proportional to its weight - e.g. `UniformCrossover` for 70% of the
+/// matings and `ArithmeticCrossover` for the rest.
+///
+/// A paired crossover (see: `CrossoverMethod::crossover_pair()`) picks one
+/// operator for both children, so that they stay complements; each child
+/// gets crossed from as many parents as the hungriest operator needs,
+/// with the other operators using just the ones they do.
+pub struct CompositeCrossover {
+    operators: Vec<(f32, Box<dyn CrossoverMethod>)>,
+    weights: WeightedIndex<f32>,
+}
+
+impl CompositeCrossover {
+    pub fn new(operators: Vec<(f32, Box<dyn CrossoverMethod>)>) -> Self {
+        assert!(!operators.is_empty(), "composite crossover requires at least one operator");
+
+        for (weight, _) in &operators {
+            assert!(weight.is_finite() && *weight > 0.0, "crossover weights must be positive");
+        }
+
+        let weights = WeightedIndex::new(operators.iter().map(|(weight, _)| *weight))
+            .expect("weights are positive");
+
+        Self { operators, weights }
+    }
+
+    fn pick(&self, rng: &mut dyn RngCore) -> &dyn CrossoverMethod {
+        &*self.operators[self.weights.sample(rng)].1
+    }
+}
+
+impl CrossoverMethod for CompositeCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        self.pick(rng).crossover(rng, parent_a, parent_b)
+    }
+
+    fn parent_count(&self) -> usize {
+        self.operators
+            .iter()
+            .map(|(_, operator)| operator.parent_count())
+            .max()
+            .unwrap_or(2)
+    }
+
+    fn crossover_many(&self, rng: &mut dyn RngCore, parents: &[&Chromosome]) -> Result<Chromosome, CrossoverError> {
+        self.pick(rng).crossover_many(rng, parents)
+    }
+
+    fn crossover_pair(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<(Chromosome, Chromosome), CrossoverError> {
+        self.pick(rng).crossover_pair(rng, parent_a, parent_b)
+    }
+}
+
+/// Fixes up a child right after its crossover (and before its mutation,
+/// see: `OperatorOrder`) - e.g. to keep genes that don't make sense outside
+/// of some range within it, or to renormalize a segment of them.
+pub trait RepairMethod {
+    fn repair(&self, child: &mut Chromosome);
+}
+
+/// So that the repair method can be picked at runtime, as a
+/// `Box<dyn RepairMethod>`.
+impl<R> RepairMethod for Box<R>
+where
+    R: RepairMethod + ?Sized,
+{
+    fn repair(&self, child: &mut Chromosome) {
+        (**self).repair(child)
+    }
+}
+
+/// Repair method that leaves children as they are (the default one).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoRepair;
+
+impl RepairMethod for NoRepair {
+    fn repair(&self, _child: &mut Chromosome) {}
+}
+
+/// Ranges the first genes of a chromosome have to stay within - e.g. a
+/// header of parameters; genes past the last range are unbounded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneBounds {
+    ranges: Vec<RangeInclusive<Float>>,
+}
+
+impl GeneBounds {
+    pub fn new(ranges: Vec<RangeInclusive<Float>>) -> Self {
+        assert!(ranges.iter().all(|range| range.start() <= range.end()));
+        Self { ranges }
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<Float>] {
+        &self.ranges
+    }
+
+    /// Whether the bounded genes of `chromosome` are all within their
+    /// ranges.
+    pub fn contain(&self, chromosome: &Chromosome) -> bool {
+        chromosome.iter().zip(&self.ranges).all(|(gene, range)| range.contains(gene))
+    }
+}
+
+/// Clamps genes into their bounds.
+#[derive(Clone, Debug)]
+pub struct ClampRepair(pub GeneBounds);
+
+impl RepairMethod for ClampRepair {
+    fn repair(&self, child: &mut Chromosome) {
+        for (gene, range) in child.iter_mut().zip(&self.0.ranges) {
+            *gene = gene.clamp(*range.start(), *range.end());
+        }
+    }
+}
+
+pub trait MutationMethod{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+
+    /// Like `mutate()` (drawing the same numbers from `rng`), but reports
+    /// what has changed; by default it compares the child before and after
+    /// the mutation.
+    fn mutate_reporting(&self, rng: &mut dyn RngCore, child: &mut Chromosome) -> MutationReport {
+        let before = child.clone();
+        self.mutate(rng, child);
+
+        MutationReport::diff(&before, child)
+    }
+
+    /// Mutates many children at once; by default it's the same as calling
+    /// `mutate()` on each of them in order, but implementations may
+    /// override it to draw random numbers in bulk.
+    fn mutate_batch(&self, rng: &mut dyn RngCore, children: &mut [Chromosome]) {
+        for child in children {
+            self.mutate(rng, child);
+        }
+    }
+}
+
+/// So that the mutation method can be picked at runtime, as a
+/// `Box<dyn MutationMethod>`.
+impl<M> MutationMethod for Box<M>
+where
+    M: MutationMethod + ?Sized,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        (**self).mutate(rng, child)
+    }
+
+    fn mutate_reporting(&self, rng: &mut dyn RngCore, child: &mut Chromosome) -> MutationReport {
+        (**self).mutate_reporting(rng, child)
+    }
+
+    fn mutate_batch(&self, rng: &mut dyn RngCore, children: &mut [Chromosome]) {
+        (**self).mutate_batch(rng, children)
+    }
+}
+
+/// What a single `MutationMethod::mutate_reporting()` did.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MutationReport {
+    /// Number of genes that got changed.
+    pub genes_touched: usize,
+
+    /// Sum of absolute changes.
+    pub total_delta: Float,
+}
+
+impl MutationReport {
+    pub fn diff(before: &Chromosome, after: &Chromosome) -> Self {
+        assert_eq!(before.len(), after.len());
+
+        before
+            .iter()
+            .zip(after.iter())
+            .filter(|(before, after)| before != after)
+            .fold(Self::default(), |report, (before, after)| Self {
+                genes_touched: report.genes_touched + 1,
+                total_delta: report.total_delta + (after - before).abs(),
+            })
+    }
+}
+/// Mutation that nudges genes by a random amount - which, despite the
+/// name, is drawn uniformly from `<-coeff, coeff>`; for normally
+/// distributed nudges, see: `NormalMutation`.
+#[derive(Clone, Debug)]
+pub struct GaussianMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    /// Magnitude of that change:
+    /// - 0.0 = touched genes will not be modified
+    /// - 3.0 = touched genes will be += or -= by at most 3.0
+    coeff: Float,
+
+    /// Whether a child that got no genes touched should get one touched
+    /// anyway; see: `with_guaranteed_change()`.
+    guaranteed_change: bool,
+}
+impl GaussianMutation{
+    pub fn new(chance:f32, coeff:Float) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        Self { chance, coeff, guaranteed_change: false }
+    }
+
+    /// Makes each child get at least one gene touched - if `chance` hasn't
+    /// picked any, a uniformly chosen one gets nudged (by the same `coeff`
+    /// logic) - so that, with low chances, children aren't mostly exact
+    /// copies of what crossover has produced.
+    ///
+    /// Defaults to false.
+    pub fn with_guaranteed_change(mut self, guaranteed_change: bool) -> Self {
+        self.guaranteed_change = guaranteed_change;
+        self
+    }
+
+    /// Nudges a uniformly chosen gene, if the child has any.
+    fn force_change(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        if child.is_empty() {
+            return;
+        }
+
+        let idx = rng.gen_range(0..child.len());
+        let sign = if rng.gen_bool(0.5) {-1.0} else {1.0};
+
+        child.genes[idx] += sign * self.coeff * rng.gen::<Float>();
+    }
+}
+impl MutationMethod for GaussianMutation{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let mut touched = false;
+
+        for gene in child.iter_mut(){
+            let sign = if rng.gen_bool(0.5) {-1.0} else {1.0};
+
+            if rng.gen_bool(self.chance as f64){
+                *gene += sign * self.coeff * rng.gen::<Float>();
+                touched = true;
+            }
+        }
+
+        if self.guaranteed_change && !touched {
+            self.force_change(rng, child);
+        }
+    }
+
+    /// Statistically equivalent to calling `mutate()` on each child, but
+    /// draws random bytes in chunks (instead of up to three calls to `rng`
+    /// per gene) - so, for the same `rng`, the outcome is different than
+    /// the one of `mutate()`.
+    fn mutate_batch(&self, rng: &mut dyn RngCore, children: &mut [Chromosome]) {
+        const CHUNK: usize = 256;
+
+        // Each gene consumes two random u32s: one decides whether to touch
+        // the gene, the other provides the sign (lowest bit) and the
+        // magnitude (highest 24 bits, i.e. `f32`'s precision - which is
+        // good enough for `f64` genes, too)
+        let threshold = self.chance as f64 * (1u64 << 32) as f64;
+        let mut bytes = [0u8; CHUNK * 8];
+        let mut touched: Vec<_> = children.iter().map(|_| false).collect();
+
+        // Scoped, so that `children` can be borrowed again below
+        {
+            let mut genes = children
+                .iter_mut()
+                .enumerate()
+                .flat_map(|(child_idx, child)| child.iter_mut().map(move |gene| (child_idx, gene)))
+                .peekable();
+
+            while genes.peek().is_some() {
+                rng.fill_bytes(&mut bytes);
+
+                for ((child_idx, gene), draw) in genes.by_ref().take(CHUNK).zip(bytes.chunks_exact(8)) {
+                    let touch = u32::from_le_bytes([draw[0], draw[1], draw[2], draw[3]]);
+                    let perturbation = u32::from_le_bytes([draw[4], draw[5], draw[6], draw[7]]);
+
+                    if (touch as f64) < threshold {
+                        let sign = if perturbation & 1 == 1 { -1.0 } else { 1.0 };
+                        let magnitude = (perturbation >> 8) as Float / (1u32 << 24) as Float;
+
+                        *gene += sign * self.coeff * magnitude;
+                        touched[child_idx] = true;
+                    }
+                }
+            }
+        }
+
+        if self.guaranteed_change {
+            for (child, touched) in children.iter_mut().zip(touched) {
+                if !touched {
+                    self.force_change(rng, child);
+                }
+            }
+        }
+    }
+}
+
+/// `GaussianMutation` whose coeff shrinks as generations go by - by
+/// `decay` each generation, down to `min_coeff` - for aggressive
+/// exploration early on and fine-tuning later.
+///
+/// `mutate()` takes `&self`, so the generation doesn't advance on its own:
+/// whoever owns the genetic algorithm calls `advance_generation()` (through
+/// `GeneticAlgorithm::mutation_method_mut()`) once per evolution, or
+/// `set_generation()` to jump straight to some generation.
+#[derive(Clone, Debug)]
+pub struct DecayingMutation {
+    /// Mutation of generation zero.
+    base: GaussianMutation,
+
+    /// Multiplier of coeff applied each generation:
+    /// - 1.0 = coeff stays the same
+    /// - 0.9 = coeff loses 10% each generation
+    decay: Float,
+
+    min_coeff: Float,
+    generation: usize,
+
+    /// Mutation of the current generation.
+    current: GaussianMutation,
+}
+
+impl DecayingMutation {
+    pub fn new(base: GaussianMutation, decay: Float) -> Self {
+        assert!(decay > 0.0 && decay <= 1.0);
+
+        Self {
+            current: base.clone(),
+            base,
+            decay,
+            min_coeff: 0.0,
+            generation: 0,
+        }
+    }
+
+    /// Keeps coeff from decaying below `min_coeff`.
+    ///
+    /// Defaults to 0.0.
+    pub fn with_min_coeff(mut self, min_coeff: Float) -> Self {
+        assert!(min_coeff >= 0.0);
+
+        self.min_coeff = min_coeff;
+        self.set_generation(self.generation);
+        self
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Coeff mutations of the current generation use.
+    pub fn coeff(&self) -> Float {
+        self.current.coeff
+    }
+
+    pub fn advance_generation(&mut self) {
+        self.generation += 1;
+        self.current.coeff = (self.current.coeff * self.decay).max(self.min_coeff);
+    }
+
+    pub fn set_generation(&mut self, generation: usize) {
+        let mut coeff = self.base.coeff;
+
+        // `powi()` requires `std` - and it's once per generation anyway
+        for _ in 0..generation {
+            if coeff <= self.min_coeff {
+                break;
+            }
+
+            coeff *= self.decay;
+        }
+
+        self.generation = generation;
+        self.current.coeff = coeff.max(self.min_coeff);
+    }
+}
+
+impl MutationMethod for DecayingMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        self.current.mutate(rng, child);
+    }
+
+    fn mutate_batch(&self, rng: &mut dyn RngCore, children: &mut [Chromosome]) {
+        self.current.mutate_batch(rng, children);
+    }
+}
+
+/// `GaussianMutation` whose coeff adapts to the progress: each time the
+/// best fitness stagnates for `patience` generations in a row, coeff gets
+/// multiplied by `grow` (to shake the population out of a local optimum),
+/// and each time it improves - by `shrink` (to fine-tune what's been
+/// found).
+///
+/// Like with `DecayingMutation`, whoever owns the genetic algorithm feeds
+/// it each generation's statistics through `observe()` (see:
+/// `GeneticAlgorithm::mutation_method_mut()`); the first statistics just
+/// set the baseline, without changing anything.
+#[derive(Clone, Debug)]
+pub struct AdaptiveMutation {
+    /// Generations without improvement it takes to grow coeff.
+    patience: usize,
+
+    /// Multiplier applied after `patience` stagnant generations; >= 1.0.
+    grow: Float,
+
+    /// Multiplier applied after an improvement; within `(0, 1>`.
+    shrink: Float,
+
+    /// Range coeff gets clamped into.
+    coeffs: RangeInclusive<Float>,
+
+    /// Best fitness observed so far.
+    best: Option<Float>,
+
+    /// Generations since the best fitness has last improved.
+    stagnation: usize,
+
+    current: GaussianMutation,
+}
+
+impl AdaptiveMutation {
+    pub fn new(base: GaussianMutation, patience: usize, grow: Float, shrink: Float) -> Self {
+        assert!(patience > 0);
+        assert!(grow >= 1.0);
+        assert!(shrink > 0.0 && shrink <= 1.0);
+
+        Self {
+            patience,
+            grow,
+            shrink,
+            coeffs: 0.0..=Float::MAX,
+            best: None,
+            stagnation: 0,
+            current: base,
+        }
+    }
+
+    /// Keeps coeff within `<min, max>`, no matter how long the population
+    /// stagnates (or improves) for.
+    ///
+    /// Defaults to no limits.
+    pub fn with_coeff_range(mut self, min: Float, max: Float) -> Self {
+        assert!(0.0 <= min && min <= max);
+
+        self.coeffs = min..=max;
+        self.current.coeff = self.current.coeff.clamp(min, max);
+        self
+    }
+
+    /// Coeff mutations of the current generation use.
+    pub fn coeff(&self) -> Float {
+        self.current.coeff
+    }
+
+    /// Adapts coeff to the latest generation's statistics; non-finite
+    /// fitness gets ignored.
+    pub fn observe(&mut self, stats: &Statistics) {
+        let fitness = stats.max_fitness;
+
+        if !fitness.is_finite() {
+            return;
+        }
+
+        let Some(best) = self.best else {
+            self.best = Some(fitness);
+            return;
+        };
+
+        if fitness > best {
+            self.best = Some(fitness);
+            self.stagnation = 0;
+            self.current.coeff *= self.shrink;
+        } else {
+            self.stagnation += 1;
+
+            if self.stagnation >= self.patience {
+                self.stagnation = 0;
+                self.current.coeff *= self.grow;
+            }
+        }
+
+        self.current.coeff = self.current.coeff.clamp(*self.coeffs.start(), *self.coeffs.end());
+    }
+}
+
+impl MutationMethod for AdaptiveMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        self.current.mutate(rng, child);
+    }
+
+    fn mutate_batch(&self, rng: &mut dyn RngCore, children: &mut [Chromosome]) {
+        self.current.mutate_batch(rng, children);
+    }
+}
+
+/// Mutation that nudges genes by normally distributed amounts, of mean
+/// 0.0 and standard deviation `coeff` - so, unlike `GaussianMutation`'s
+/// uniform ones, they're mostly small, with an occasional large jump.
+///
+/// Needs `ln()`, `sqrt()` and `cos()`, so it's available only with the
+/// `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct NormalMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    /// Standard deviation of that change:
+    /// - 0.0 = touched genes will not be modified
+    /// - 3.0 = touched genes will be changed by less than 3.0 about 68%
+    ///   of the time, and by less than 6.0 about 95% of the time
+    coeff: Float,
+}
+
+#[cfg(feature = "std")]
+impl NormalMutation {
+    pub fn new(chance: f32, coeff: Float) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(coeff >= 0.0);
+
+        Self { chance, coeff }
+    }
+
+    /// Draws from the standard normal distribution (Box-Muller).
+    fn standard_normal(rng: &mut dyn RngCore) -> Float {
+        // `gen()` is in <0, 1), so this is in (0, 1> - keeping `ln()` finite
+        let u1 = 1.0 - rng.gen::<Float>();
+        let u2 = rng.gen::<Float>();
+
+        (-2.0 * u1.ln()).sqrt() * (2.0 * core::f64::consts::PI as Float * u2).cos()
+    }
+}
+
+#[cfg(feature = "std")]
+impl MutationMethod for NormalMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene += self.coeff * Self::standard_normal(rng);
+            }
+        }
+    }
+}
+
+/// How many step sizes a chromosome evolved with `SelfAdaptiveMutation`
+/// carries after its genes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepSizes {
+    /// Single sigma, shared by all the genes.
+    Shared,
+
+    /// One sigma per gene, in the same order as the genes.
+    PerGene,
+}
+
+impl StepSizes {
+    /// Number of sigmas to append (see: `Chromosome::with_sigmas()`) to a
+    /// chromosome of `genes` genes.
+    pub fn count(&self, genes: usize) -> usize {
+        match self {
+            Self::Shared => 1,
+            Self::PerGene => genes,
+        }
+    }
+
+    /// Number of genes (i.e. not sigmas) in a chromosome of `len` genes
+    /// in total.
+    pub fn genes(&self, len: usize) -> usize {
+        match self {
+            Self::Shared => len.saturating_sub(1),
+            Self::PerGene => {
+                assert!(len.is_multiple_of(2), "per-gene step sizes require an even number of genes");
+                len / 2
+            }
+        }
+    }
+}
+
+/// Evolution strategy style mutation, where each chromosome carries its
+/// own step sizes (see: `StepSizes`) that evolve along with it: first the
+/// sigmas get multiplied by a log-normally distributed factor and then
+/// every gene gets nudged by a normally distributed amount of (the new)
+/// sigma's standard deviation.
+///
+/// Chromosomes have to be created with the sigmas already appended (see:
+/// `Chromosome::with_sigmas()`) - and, since crossover and the rest of the
+/// algorithm don't tell sigmas apart from genes, whatever consumes the
+/// chromosome has to strip them first (see: `Chromosome::split_at()`).
+///
+/// Learning rates follow the usual recommendations for `n` genes: `1 /
+/// sqrt(n)` for a shared sigma and, for per-gene ones, `1 / sqrt(2n)`
+/// (for the factor shared by all the sigmas) with `1 / sqrt(2 sqrt(n))`
+/// (for each sigma's own factor).
+///
+/// Needs `exp()` and `sqrt()`, so it's available only with the `std`
+/// feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct SelfAdaptiveMutation {
+    step_sizes: StepSizes,
+
+    /// Sigma never shrinks below this one, so that a chromosome can't
+    /// freeze itself in place.
+    min_sigma: Float,
+}
+
+#[cfg(feature = "std")]
+impl SelfAdaptiveMutation {
+    pub fn new(step_sizes: StepSizes) -> Self {
+        Self {
+            step_sizes,
+            min_sigma: 0.0,
+        }
+    }
+
+    /// Defaults to zero.
+    pub fn with_min_sigma(mut self, min_sigma: Float) -> Self {
+        assert!(min_sigma >= 0.0);
+
+        self.min_sigma = min_sigma;
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl MutationMethod for SelfAdaptiveMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let genes = self.step_sizes.genes(child.len());
+
+        if genes == 0 {
+            return;
+        }
+
+        let (genes, sigmas) = child.genes.split_at_mut(genes);
+        let n = genes.len() as Float;
+        let normal = NormalMutation::standard_normal;
+
+        match self.step_sizes {
+            StepSizes::Shared => {
+                let tau = 1.0 / n.sqrt();
+                let sigma = &mut sigmas[0];
+
+                *sigma = (*sigma * (tau * normal(rng)).exp()).max(self.min_sigma);
+
+                for gene in genes {
+                    *gene += *sigma * normal(rng);
+                }
+            }
+
+            StepSizes::PerGene => {
+                let tau_shared = 1.0 / (2.0 * n).sqrt();
+                let tau = 1.0 / (2.0 * n.sqrt()).sqrt();
+                let shared = tau_shared * normal(rng);
+
+                for (gene, sigma) in genes.iter_mut().zip(sigmas) {
+                    *sigma = (*sigma * (shared + tau * normal(rng)).exp()).max(self.min_sigma);
+                    *gene += *sigma * normal(rng);
+                }
+            }
+        }
+    }
+}
+
+/// Mutation that nudges genes by Cauchy-distributed amounts, centered at
+/// 0.0 and of given `scale` (half of the changes are smaller than it) - so
+/// that, unlike with `GaussianMutation`, an occasional huge jump gets a
+/// chance to escape a local optimum.
+///
+/// Cauchy has no variance to speak of, so a single change can be big
+/// enough to wreck a good genome; `with_max_step()` caps it.
+///
+/// Needs `tan()`, so it's available only with the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct CauchyMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    scale: Float,
+    max_step: Option<Float>,
+}
+
+#[cfg(feature = "std")]
+impl CauchyMutation {
+    pub fn new(chance: f32, scale: Float) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(scale >= 0.0);
+
+        Self {
+            chance,
+            scale,
+            max_step: None,
+        }
+    }
+
+    /// Caps each change at `max_step` (in either direction).
+    ///
+    /// Defaults to no cap.
+    pub fn with_max_step(mut self, max_step: Float) -> Self {
+        assert!(max_step >= 0.0);
+
+        self.max_step = Some(max_step);
+        self
+    }
+
+    /// Draws a change (through the inverse of Cauchy's CDF).
+    fn step(&self, rng: &mut dyn RngCore) -> Float {
+        let u: Float = rng.gen();
+        let step = self.scale * (core::f64::consts::PI as Float * (u - 0.5)).tan();
+
+        match self.max_step {
+            Some(max_step) => step.clamp(-max_step, max_step),
+            None => step,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl MutationMethod for CauchyMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene += self.step(rng);
+            }
+        }
+    }
+}
+
+/// `GaussianMutation` with a separate chance and coeff for each gene -
+/// e.g. to mutate output-layer weights, where small changes matter a lot,
+/// more gently than the rest.
+///
+/// Genes past the end of `chances` (or `coeffs`) use its last value, so
+/// e.g. `new(vec![0.0, 0.1], vec![1.0])` leaves the first gene alone and
+/// mutates the rest alike.
+#[derive(Clone, Debug)]
+pub struct PerGeneMutation {
+    chances: Vec<f32>,
+    coeffs: Vec<Float>,
+}
+
+impl PerGeneMutation {
+    pub fn new(chances: Vec<f32>, coeffs: Vec<Float>) -> Self {
+        assert!(!chances.is_empty() && !coeffs.is_empty(), "per-gene mutation requires at least one chance and coeff");
+        assert!(chances.iter().all(|chance| (0.0..=1.0).contains(chance)), "chances must be within <0, 1>");
+
+        Self { chances, coeffs }
+    }
+
+    fn chance(&self, gene: usize) -> f32 {
+        self.chances.get(gene).copied().unwrap_or(self.chances[self.chances.len() - 1])
+    }
+
+    fn coeff(&self, gene: usize) -> Float {
+        self.coeffs.get(gene).copied().unwrap_or(self.coeffs[self.coeffs.len() - 1])
+    }
+}
+
+impl MutationMethod for PerGeneMutation {
+    /// Draws the same numbers as `GaussianMutation::mutate()` - so with a
+    /// single chance and coeff, it's the same as the plain mutation.
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for (idx, gene) in child.iter_mut().enumerate() {
+            let sign = if rng.gen_bool(0.5) {-1.0} else {1.0};
+
+            if rng.gen_bool(self.chance(idx) as f64) {
+                *gene += sign * self.coeff(idx) * rng.gen::<Float>();
+            }
+        }
+    }
+}
+
+/// Mutation that exchanges the values of two random (distinct) gene
+/// positions, instead of perturbing them - for chromosomes where position
+/// encodes something structural; the values themselves stay the same.
+///
+/// With probability `chance`, each child gets `swaps` swaps (one by
+/// default); chromosomes of less than two genes are left alone.
+#[derive(Clone, Debug)]
+pub struct SwapMutation {
+    chance: f32,
+    swaps: usize,
+}
+
+impl SwapMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance, swaps: 1 }
+    }
+
+    /// Makes each mutated child get `swaps` swaps, instead of one.
+    pub fn with_swaps(mut self, swaps: usize) -> Self {
+        self.swaps = swaps;
+        self
+    }
+}
+
+impl MutationMethod for SwapMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        if child.len() < 2 || !rng.gen_bool(self.chance as f64) {
+            return;
+        }
+
+        for _ in 0..self.swaps {
+            let positions = rand::seq::index::sample(rng, child.len(), 2);
+
+            child.genes.swap(positions.index(0), positions.index(1));
+        }
+    }
+}
+
+/// Shrinks every gene towards zero by multiplying it with `1 - decay`,
+/// keeping networks small-magnitude; meant to be combined with another
+/// mutation through `CompositeMutation`.
+#[derive(Clone, Debug)]
+pub struct WeightDecayMutation {
+    /// - 0.0 = genes will not be modified
+    /// - 0.1 = genes will lose 10% of their magnitude
+    decay: Float,
+}
+impl WeightDecayMutation {
+    pub fn new(decay: Float) -> Self {
+        // Anything larger would wipe out the genome in a few dozen of
+        // generations
+        assert!((0.0..=0.1).contains(&decay));
+        Self { decay }
+    }
+}
+impl MutationMethod for WeightDecayMutation {
+    fn mutate(&self, _rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            *gene *= 1.0 - self.decay;
+        }
+    }
+}
+
+/// Polynomial mutation (Deb's formulation, as in NSGA-II) - the usual
+/// companion of `SimulatedBinaryCrossover`: with probability `chance`,
+/// each gene gets perturbed by an amount drawn from a polynomial
+/// distribution spanning `<lower, upper>`, and skewed so that the result
+/// never leaves it.
+///
+/// The larger `eta` (the distribution index), the smaller the typical
+/// perturbation. Genes sitting at a bound can only move away from it;
+/// genes outside of the bounds get clamped into them first.
+///
+/// Needs `powf()`, so it's available only with the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct PolynomialMutation {
+    chance: f32,
+    eta: f32,
+    lower: Float,
+    upper: Float,
+}
+
+#[cfg(feature = "std")]
+impl PolynomialMutation {
+    pub fn new(chance: f32, eta: f32, lower: Float, upper: Float) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(eta >= 0.0 && eta.is_finite());
+        assert!(lower <= upper, "lower bound must not be greater than upper one");
+
+        Self { chance, eta, lower, upper }
+    }
+
+    fn perturb(&self, rng: &mut dyn RngCore, gene: Float) -> Float {
+        let gene = gene.clamp(self.lower, self.upper);
+        let range = self.upper - self.lower;
+
+        if range == 0.0 {
+            return gene;
+        }
+
+        let exponent = self.eta as Float + 1.0;
+        let u: Float = rng.gen();
+
+        // The closer the gene is to a bound, the less it can move that way
+        let delta_q = if u < 0.5 {
+            let delta = (gene - self.lower) / range;
+            let val = 2.0 * u + (1.0 - 2.0 * u) * (1.0 - delta).powf(exponent);
+
+            val.powf(1.0 / exponent) - 1.0
+        } else {
+            let delta = (self.upper - gene) / range;
+            let val = 2.0 * (1.0 - u) + 2.0 * (u - 0.5) * (1.0 - delta).powf(exponent);
+
+            1.0 - val.powf(1.0 / exponent)
+        };
+
+        (gene + delta_q * range).clamp(self.lower, self.upper)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MutationMethod for PolynomialMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene = self.perturb(rng, *gene);
+            }
+        }
+    }
+}
+
+/// Mutation that replaces genes altogether with fresh ones, drawn
+/// uniformly from `<min, max>` - so that, unlike with small nudges, genes
+/// stuck at a large magnitude can still get back into range.
+#[derive(Clone, Debug)]
+pub struct UniformResetMutation {
+    /// Probability of replacing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    min: Float,
+    max: Float,
+}
+
+impl UniformResetMutation {
+    pub fn new(chance: f32, min: Float, max: Float) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(min <= max, "min must not be greater than max");
+
+        Self { chance, min, max }
+    }
+}
+
+impl MutationMethod for UniformResetMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene = rng.gen_range(self.min..=self.max);
+            }
+        }
+    }
+}
+
+/// Gaussian mutation whose magnitude depends on where in the chromosome a
+/// gene lies - e.g. so that output-layer weights, which affect behavior
+/// the most, get mutated more gently than input-layer ones.
+///
+/// Each of `scales` maps a contiguous range of genes onto a multiplier of
+/// `base`'s coeff; together, the ranges have to tile the chromosome
+/// exactly - without any gaps or overlaps.
+#[derive(Clone, Debug)]
+pub struct LayerScaledMutation {
+    base: GaussianMutation,
+    scales: Vec<(Range<usize>, Float)>,
+}
+impl LayerScaledMutation {
+    pub fn new(base: GaussianMutation, scales: Vec<(Range<usize>, Float)>) -> Self {
+        let mut end = 0;
+
+        for (range, scale) in &scales {
+            assert_eq!(range.start, end, "gene ranges must tile the chromosome without gaps or overlaps");
+            assert!(range.start <= range.end);
+            assert!(*scale >= 0.0);
+
+            end = range.end;
+        }
+
+        Self { base, scales }
+    }
+
+    /// Like `new()`, but with ranges given by their lengths, one after
+    /// another - e.g. the lengths of a network's layers.
+    pub fn from_lengths(base: GaussianMutation, lengths: &[(usize, Float)]) -> Self {
+        let mut start = 0;
+
+        let scales = lengths
+            .iter()
+            .map(|&(len, scale)| {
+                let range = start..(start + len);
+                start += len;
+                (range, scale)
+            })
+            .collect();
+
+        Self::new(base, scales)
+    }
+
+    /// Number of genes a chromosome has to have to be mutated.
+    pub fn genes(&self) -> usize {
+        self.scales.last().map_or(0, |(range, _)| range.end)
+    }
+}
+impl MutationMethod for LayerScaledMutation {
+    /// Draws the same numbers as `GaussianMutation::mutate()` - so with all
+    /// scales equal to 1.0, it's the same as the base mutation.
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        assert_eq!(child.len(), self.genes(), "chromosome doesn't match the gene ranges");
+
+        let scales = self
+            .scales
+            .iter()
+            .flat_map(|(range, scale)| range.clone().map(move |_| *scale));
+
+        for (gene, scale) in child.iter_mut().zip(scales) {
+            let sign = if rng.gen_bool(0.5) {-1.0} else {1.0};
+
+            if rng.gen_bool(self.base.chance as f64) {
+                *gene += sign * self.base.coeff * scale * rng.gen::<Float>();
+            }
+        }
+    }
+}
+
+/// Applies `first` and then `second` mutation to the same child.
+#[derive(Clone, Debug)]
+pub struct CompositeMutation<A, B> {
+    first: A,
+    second: B,
+}
+impl<A, B> CompositeMutation<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+impl<A, B> MutationMethod for CompositeMutation<A, B>
+where
+    A: MutationMethod,
+    B: MutationMethod,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        self.first.mutate(rng, child);
+        self.second.mutate(rng, child);
+    }
+
+    /// Applies `first` to all children and only then `second`, so that
+    /// both can take advantage of their batched implementations.
+    fn mutate_batch(&self, rng: &mut dyn RngCore, children: &mut [Chromosome]) {
+        self.first.mutate_batch(rng, children);
+        self.second.mutate_batch(rng, children);
+    }
+}
+
+/// Like `CompositeMutation`, but for any number of operators, picked at
+/// runtime - e.g. a bounded Gaussian nudge followed by a rare uniform
+/// reset; operators get applied in the order they are listed.
+pub struct ChainedMutation {
+    operators: Vec<Box<dyn MutationMethod>>,
+}
+
+impl ChainedMutation {
+    pub fn new(operators: Vec<Box<dyn MutationMethod>>) -> Self {
+        assert!(!operators.is_empty(), "chained mutation requires at least one operator");
+
+        Self { operators }
+    }
+}
+
+impl MutationMethod for ChainedMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for operator in &self.operators {
+            operator.mutate(rng, child);
+        }
+    }
+
+    /// Applies each operator to all children before moving onto the next
+    /// one, so that all of them can take advantage of their batched
+    /// implementations.
+    fn mutate_batch(&self, rng: &mut dyn RngCore, children: &mut [Chromosome]) {
+        for operator in &self.operators {
+            operator.mutate_batch(rng, children);
+        }
+    }
+}
+
+/// Applies `inner` mutation and then clamps every gene into `<min, max>` -
+/// e.g. to keep a network's weights from blowing up over hundreds of
+/// generations.
+///
+/// Unlike `ClampRepair`, which runs right after the crossover, the clamp
+/// comes last - so mutation can't push genes out of the range either.
+#[derive(Clone, Debug)]
+pub struct BoundedMutation<M> {
+    inner: M,
+    min: Float,
+    max: Float,
+}
+
+impl<M> BoundedMutation<M> {
+    pub fn new(inner: M, min: Float, max: Float) -> Self {
+        assert!(min <= max, "min must not be greater than max");
+
+        Self { inner, min, max }
+    }
+
+    fn clamp(&self, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            *gene = gene.clamp(self.min, self.max);
+        }
+    }
+}
+
+impl<M> MutationMethod for BoundedMutation<M>
+where
+    M: MutationMethod,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        self.inner.mutate(rng, child);
+        self.clamp(child);
+    }
+
+    fn mutate_batch(&self, rng: &mut dyn RngCore, children: &mut [Chromosome]) {
+        self.inner.mutate_batch(rng, children);
+
+        for child in children {
+            self.clamp(child);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Statistics {
+    pub min_fitness: Float,
+    pub max_fitness: Float,
+    pub avg_fitness: Float,
+}
+impl Statistics {
+    pub fn is_finite(&self) -> bool {
+        self.min_fitness.is_finite()
+            && self.max_fitness.is_finite()
+            && self.avg_fitness.is_finite()
+    }
+
+    #[cfg(test)]
+    fn new<I>(population: &[I]) -> Self
+    where
+        I: Individual,
+    {
+        let mut stats = StatisticsBuilder::default();
+
+        for individual in population {
+            stats.observe(individual.fitness());
+        }
+
+        stats.finish()
+    }
+}
+
+/// Builds `Statistics` incrementally, one fitness at a time - e.g. while
+/// individuals are being evaluated, without keeping them all around.
+#[derive(Clone, Debug, Default)]
+pub struct StatisticsBuilder {
+    count: usize,
+    min_fitness: Float,
+    max_fitness: Float,
+    sum_fitness: Float,
+}
+impl StatisticsBuilder {
+    pub fn observe(&mut self, fitness: Float) {
+        if self.count == 0 {
+            self.min_fitness = fitness;
+            self.max_fitness = fitness;
+        }
+
+        self.min_fitness = self.min_fitness.min(fitness);
+        self.max_fitness = self.max_fitness.max(fitness);
+        self.sum_fitness += fitness;
+        self.count += 1;
+    }
+
+    /// Combines fitnesses observed by both builders, e.g. when parts of
+    /// the population were evaluated separately.
+    pub fn merge(&mut self, other: StatisticsBuilder) {
+        if other.count == 0 {
+            return;
+        }
+
+        if self.count == 0 {
+            *self = other;
+            return;
+        }
+
+        self.min_fitness = self.min_fitness.min(other.min_fitness);
+        self.max_fitness = self.max_fitness.max(other.max_fitness);
+        self.sum_fitness += other.sum_fitness;
+        self.count += other.count;
+    }
+
+    /// Panics if nothing has been observed, since there are no statistics
+    /// of an empty population.
+    pub fn finish(self) -> Statistics {
+        assert!(self.count > 0, "cannot compute statistics without observing any fitness");
+
+        Statistics {
+            min_fitness: self.min_fitness,
+            max_fitness: self.max_fitness,
+            avg_fitness: self.sum_fitness / (self.count as Float),
+        }
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min={:.2}, max={:.2}, avg={:.2}",
+            self.min_fitness, self.max_fitness, self.avg_fitness,
+        )
+    }
+}
+
+// Testing the rand.SliceRandom and not leaving it on Developer's Trust
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum TestIndividual {
+        /// For tests that require access to the chromosome
+        WithChromosome { chromosome: Chromosome },
+        /// For tests that don't require access to the chromosome
+        WithFitness { fitness: Float },
+    }
+    impl TestIndividual {
+        fn new(fitness: Float) -> Self {
+            Self::WithFitness { fitness }
+        }
+    }
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self::WithChromosome { chromosome }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            match self {
+                Self::WithChromosome { chromosome } => chromosome,
+                Self::WithFitness { .. } => {
+                    panic!("not supported for TestIndividual::WithFitness")
+                }
+            }
+        }
+
+        fn fitness(&self) -> Float {
+            match self {
+                Self::WithChromosome { chromosome } => {
+                    chromosome.iter().sum()
+                    // ^ the simplest fitness function ever - we're just summing all the genes together
+                }
+                Self::WithFitness { fitness } => *fitness,
+            }
+        }
+    }
+    impl PartialEq for Chromosome {
+        fn eq(&self, other: &Self) -> bool {
+            approx::relative_eq!(self.genes.as_slice(), other.genes.as_slice())
+        }
+    }
+
+
+    use std::collections::BTreeMap;
+    use std::iter::FromIterator;
+
+    
+    #[test]
+    fn roulette_wheel_selection() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(4.0),
+            TestIndividual::new(3.0),
+        ];
+
+        let mut actual_histogram = BTreeMap::new();
+
+        //          /--| nothing special about this thousand;
+        //          v  | a number as low as fifty might do the trick, too
+        for _ in 0..1000 {
+            let fitness = RouletteWheelSelection
+                .select(&mut rng, &population)
+                .fitness() as i32;
+
+            *actual_histogram
+                .entry(fitness)
+                .or_insert(0) += 1;
+        }
+
+        // (fitness, how many times this fitness has been chosen)
+        #[cfg(not(feature = "precision-f64"))]
+        let expected_histogram = BTreeMap::from_iter([
+            (1, 98),
+            (2, 202),
+            (3, 278),
+            (4, 422),
+        ]);
+
+        // Weights get drawn as `f64`s, which consumes the rng differently
+        #[cfg(feature = "precision-f64")]
+        let expected_histogram = BTreeMap::from_iter([
+            (1, 98),
+            (2, 191),
+            (3, 280),
+            (4, 431),
+        ]);
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    mod tournament_selection {
+        use super::*;
+
+        fn histogram(size: usize, population: &[TestIndividual]) -> BTreeMap<i32, usize> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut histogram = BTreeMap::new();
+
+            for _ in 0..1000 {
+                let fitness = TournamentSelection::new(size)
+                    .select(&mut rng, population)
+                    .fitness() as i32;
+
+                *histogram.entry(fitness).or_insert(0) += 1;
+            }
+
+            histogram
+        }
+
+        fn population() -> Vec<TestIndividual> {
+            // Fitnesses close together, which is where roulette struggles
+            [4.0, 1.0, 7.0, 2.0, 8.0, 5.0, 3.0, 6.0]
+                .into_iter()
+                .map(|fitness| TestIndividual::new(10.0 + fitness))
+                .collect()
+        }
+
+        #[test]
+        fn size_2() {
+            // The worst individual can never win, while the best one wins
+            // every tournament it takes part in - that is: 2/8 of them
+            let expected_histogram = BTreeMap::from_iter([
+                (12, 51),
+                (13, 53),
+                (14, 99),
+                (15, 153),
+                (16, 184),
+                (17, 214),
+                (18, 246),
+            ]);
+
+            assert_eq!(histogram(2, &population()), expected_histogram);
+        }
+
+        #[test]
+        fn size_5() {
+            // Much stronger pressure - only the top four ever win, with the
+            // best one winning 5/8 of the time
+            let expected_histogram = BTreeMap::from_iter([
+                (15, 22),
+                (16, 87),
+                (17, 281),
+                (18, 610),
+            ]);
+
+            assert_eq!(histogram(5, &population()), expected_histogram);
+        }
+
+        #[test]
+        fn size_1_picks_uniformly() {
+            let histogram = histogram(1, &population());
+
+            assert_eq!(histogram.len(), 8);
+            assert!(histogram.values().all(|&count| (90..=160).contains(&count)));
+            assert_eq!(histogram, self::histogram(0, &population()));
+        }
+
+        #[test]
+        fn oversized_tournament_picks_the_best() {
+            assert_eq!(histogram(100, &population()), BTreeMap::from_iter([(18, 1000)]));
+        }
+
+        #[test]
+        #[should_panic(expected = "got an empty population")]
+        fn empty_population() {
+            histogram(2, &[]);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod boltzmann_selection {
+        use super::*;
+
+        fn histogram(temperature: f32, population: &[TestIndividual]) -> BTreeMap<i32, usize> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut histogram = BTreeMap::new();
+
+            for _ in 0..1000 {
+                let fitness = BoltzmannSelection::new(temperature)
+                    .select(&mut rng, population)
+                    .fitness() as i32;
+
+                *histogram.entry(fitness).or_insert(0) += 1;
+            }
+
+            histogram
+        }
+
+        fn population(fitnesses: &[Float]) -> Vec<TestIndividual> {
+            fitnesses.iter().map(|&fitness| TestIndividual::new(fitness)).collect()
+        }
+
+        #[test]
+        fn high_temperature_is_nearly_uniform() {
+            let histogram = histogram(100.0, &population(&[2.0, 1.0, 4.0, 3.0]));
+
+            assert_eq!(histogram.len(), 4);
+            assert!(histogram.values().all(|&count| (200..=300).contains(&count)), "{:?}", histogram);
+        }
+
+        #[test]
+        fn low_temperature_is_nearly_greedy() {
+            assert_eq!(
+                histogram(0.05, &population(&[2.0, 1.0, 4.0, 3.0])),
+                BTreeMap::from_iter([(4, 1000)])
+            );
+        }
+
+        #[test]
+        fn large_fitnesses_dont_overflow() {
+            // exp(1e6) is infinite, but exp(-1) vs exp(0) is about 27% vs 73%
+            let histogram = histogram(1.0, &population(&[1e6, 1e6 + 1.0]));
+
+            assert!((220..=320).contains(&histogram[&1_000_000]), "{:?}", histogram);
+            assert_eq!(histogram.values().sum::<usize>(), 1000);
+        }
+
+        #[test]
+        fn temperature_can_be_annealed() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Letting the fittest one mate with itself, so that it can be the
+            // only parent around
+            let mut ga = GeneticAlgorithm::new(
+                BoltzmannSelection::new(100.0),
+                UniformCrossover,
+                GaussianMutation::new(0.0, 0.0),
+            )
+            .with_self_mating(true);
+
+            let population = vec![
+                TestIndividual::create(Chromosome::from_iter([0.0, 1.0])),
+                TestIndividual::create(Chromosome::from_iter([1.0, 1.0])),
+                TestIndividual::create(Chromosome::from_iter([4.0, 2.0])),
+                TestIndividual::create(Chromosome::from_iter([2.0, 1.0])),
+            ];
+
+            let parents = |ga: &GeneticAlgorithm<_, _, _>, rng: &mut ChaCha8Rng| -> Vec<usize> {
+                (0..50)
+                    .flat_map(|_| ga.evolve_traced(rng, &population, 0.0).unwrap().parents)
+                    .flat_map(|(a, b)| [a, b])
+                    .collect()
+            };
+
+            assert!(parents(&ga, &mut rng).iter().any(|&parent| parent != 2));
+
+            ga.selection_method_mut().set_temperature(0.01);
+
+            assert!(parents(&ga, &mut rng).iter().all(|&parent| parent == 2));
+        }
+
+        #[test]
+        #[should_panic]
+        fn zero_temperature() {
+            BoltzmannSelection::new(1.0).set_temperature(0.0);
+        }
+    }
+
+    mod truncation_selection {
+        use super::*;
+
+        fn histogram(fraction: f32, population: &[TestIndividual]) -> BTreeMap<i32, usize> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut histogram = BTreeMap::new();
+
+            for _ in 0..1000 {
+                let fitness = TruncationSelection::new(fraction)
+                    .select(&mut rng, population)
+                    .fitness() as i32;
+
+                *histogram.entry(fitness).or_insert(0) += 1;
+            }
+
+            histogram
+        }
+
+        fn population(len: usize) -> Vec<TestIndividual> {
+            // Shuffled, so that the order of the population doesn't matter
+            (0..len)
+                .map(|idx| TestIndividual::new(((idx * 7) % len) as Float))
+                .collect()
+        }
+
+        #[test]
+        fn top_quarter() {
+            let histogram = histogram(0.25, &population(16));
+
+            // Only 12..=15 ever get picked, each about a quarter of the time
+            assert_eq!(histogram.keys().copied().collect::<Vec<_>>(), vec![12, 13, 14, 15]);
+            assert!(histogram.values().all(|&count| (200..=300).contains(&count)));
+        }
+
+        #[test]
+        fn everybody() {
+            assert_eq!(histogram(1.0, &population(5)).len(), 5);
+        }
+
+        #[test]
+        fn tiny_population_keeps_the_best() {
+            assert_eq!(histogram(0.25, &population(3)), BTreeMap::from_iter([(2, 1000)]));
+            assert_eq!(histogram(0.01, &population(1)), BTreeMap::from_iter([(0, 1000)]));
+        }
+
+        #[test]
+        #[should_panic]
+        fn zero_fraction() {
+            TruncationSelection::new(0.0);
+        }
+
+        #[test]
+        #[should_panic]
+        fn fraction_above_one() {
+            TruncationSelection::new(1.5);
+        }
+
+        #[test]
+        #[should_panic(expected = "got an empty population")]
+        fn empty_population() {
+            histogram(0.5, &[]);
+        }
+    }
+
+    mod mixed_selection {
+        use super::*;
+
+        /// Always selects the individual at given index.
+        #[derive(Clone, Copy, Debug)]
+        struct Fixed(usize);
+
+        impl SelectionMethod for Fixed {
+            fn select_index<I>(&self, _: &mut dyn RngCore, _: &[I]) -> usize
+            where
+                I: Individual,
+            {
+                self.0
+            }
+        }
+
+        fn counts(probability: f32) -> [usize; 2] {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = [TestIndividual::new(1.0), TestIndividual::new(2.0)];
+            let method = MixedSelection::new(Fixed(0), Fixed(1), probability);
+            let mut counts = [0; 2];
+
+            for _ in 0..10_000 {
+                counts[method.select_index(&mut rng, &population)] += 1;
+            }
+
+            counts
+        }
+
+        #[test]
+        fn delegates_in_given_ratio() {
+            let [a, b] = counts(0.8);
+
+            assert!((7800..=8200).contains(&a), "a = {}", a);
+            assert_eq!(a + b, 10_000);
+        }
+
+        #[test]
+        fn extremes_delegate_to_one_method() {
+            assert_eq!(counts(1.0), [10_000, 0]);
+            assert_eq!(counts(0.0), [0, 10_000]);
+        }
+
+        #[test]
+        fn plugs_into_genetic_algorithm() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let ga = GeneticAlgorithm::new(
+                MixedSelection::new(TournamentSelection::new(3), TournamentSelection::new(1), 0.8),
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            );
+
+            let population: Vec<_> = (0..10)
+                .map(|idx| TestIndividual::create((0..3).map(|gene| (idx + gene) as Float).collect()))
+                .collect();
+
+            let (evolved, stats) = ga.evolve(&mut rng, &population).unwrap();
+
+            assert_eq!(evolved.len(), 10);
+            assert_eq!(stats.max_fitness, 9.0 + 10.0 + 11.0);
+        }
+
+        #[test]
+        #[should_panic]
+        fn probability_above_one() {
+            MixedSelection::new(Fixed(0), Fixed(1), 1.5);
+        }
+
+        #[test]
+        #[should_panic]
+        fn negative_probability() {
+            MixedSelection::new(Fixed(0), Fixed(1), -0.1);
+        }
+    }
+
+    mod linear_rank_selection {
+        use super::*;
+
+        fn histogram(pressure: f32, population: &[TestIndividual]) -> BTreeMap<i32, usize> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut histogram = BTreeMap::new();
+
+            for _ in 0..4000 {
+                let fitness = LinearRankSelection::new(pressure)
+                    .select(&mut rng, population)
+                    .fitness() as i32;
+
+                *histogram.entry(fitness).or_insert(0) += 1;
+            }
+
+            histogram
+        }
+
+        fn population(len: usize) -> Vec<TestIndividual> {
+            // Shuffled, so that the order of the population doesn't matter
+            (0..len)
+                .map(|idx| TestIndividual::new(((idx * 3) % len) as Float))
+                .collect()
+        }
+
+        #[test]
+        fn max_pressure_skips_the_worst() {
+            let histogram = histogram(2.0, &population(4));
+
+            // Weights of 0, 2/3, 4/3 and 2 - out of 4
+            assert_eq!(histogram.get(&0), None);
+            assert!((550..=800).contains(&histogram[&1]), "{:?}", histogram);
+            assert!((1200..=1450).contains(&histogram[&2]), "{:?}", histogram);
+            assert!((1850..=2150).contains(&histogram[&3]), "{:?}", histogram);
+        }
+
+        #[test]
+        fn min_pressure_is_uniform() {
+            let histogram = histogram(1.0, &population(4));
+
+            assert_eq!(histogram.len(), 4);
+            assert!(histogram.values().all(|&count| (900..=1100).contains(&count)), "{:?}", histogram);
+        }
+
+        #[test]
+        fn fitness_magnitude_doesnt_matter() {
+            let squashed = [1000.0, 1000.1, 1000.2, 1000.3].map(TestIndividual::new);
+            let spread = [1.0, 10.0, 100.0, 1000.0].map(TestIndividual::new);
+
+            let indices = |population: &[TestIndividual]| {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+                (0..100)
+                    .map(|_| LinearRankSelection::new(1.5).select_index(&mut rng, population))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(indices(&squashed), indices(&spread));
+        }
+
+        #[test]
+        fn single_individual() {
+            assert_eq!(histogram(2.0, &population(1)), BTreeMap::from_iter([(0, 4000)]));
+        }
+
+        #[test]
+        #[should_panic]
+        fn pressure_below_one() {
+            LinearRankSelection::new(0.5);
+        }
+
+        #[test]
+        #[should_panic]
+        fn pressure_above_two() {
+            LinearRankSelection::new(2.5);
+        }
+
+        #[test]
+        #[should_panic(expected = "got an empty population")]
+        fn empty_population() {
+            histogram(1.5, &[]);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod sigma_scaled_selection {
+        use super::*;
+
+        /// How many times each individual (by index) gets picked out of
+        /// `population` in 10k picks.
+        fn counts<S: SelectionMethod>(method: S, population: &[TestIndividual]) -> Vec<usize> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut counts = vec![0; population.len()];
+
+            for pick in method.select_many(&mut rng, population, 10_000) {
+                counts[pick] += 1;
+            }
+
+            counts
+        }
+
+        fn population(fitnesses: &[Float]) -> Vec<TestIndividual> {
+            fitnesses.iter().map(|&fitness| TestIndividual::new(fitness)).collect()
+        }
+
+        #[test]
+        fn outlier_no_longer_takes_over() {
+            let population = population(&[1.0, 2.0, 1000.0]);
+
+            let raw = counts(RouletteWheelSelection, &population);
+            let scaled = counts(SigmaScaledSelection::new(RouletteWheelSelection), &population);
+
+            assert!(raw[2] > 9_900, "{:?}", raw);
+
+            // 0.65 : 0.65 : 1.71
+            assert!((5_300..=6_100).contains(&scaled[2]), "{:?}", scaled);
+            assert!(scaled[0] > 1_800 && scaled[1] > 1_800, "{:?}", scaled);
+        }
+
+        #[test]
+        fn without_spread_everybody_is_equal() {
+            let counts = counts(SigmaScaledSelection::new(RouletteWheelSelection), &population(&[3.0; 4]));
+
+            assert!(counts.iter().all(|&count| (2_300..=2_700).contains(&count)), "{:?}", counts);
+        }
+
+        #[test]
+        fn floor_keeps_everybody_selectable() {
+            // The last individual is four sigmas below the mean
+            let mut fitnesses = vec![10.0; 16];
+            fitnesses.push(-6.0);
+
+            let counts = counts(
+                SigmaScaledSelection::new(StochasticUniversalSampling).with_floor(0.5),
+                &population(&fitnesses),
+            );
+
+            assert!(counts[16] > 0, "{:?}", counts);
+            assert!(counts[16] < counts[0], "{:?}", counts);
+        }
+
+        #[test]
+        fn lone_select_matches_batch() {
+            let population = population(&[1.0, 2.0, 1000.0]);
+            let method = SigmaScaledSelection::new(RouletteWheelSelection);
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let lone = method.select_index(&mut rng, &population);
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let batch = method.select_many(&mut rng, &population, 1)[0];
+
+            assert_eq!(lone, batch);
+        }
+    }
+
+    mod stochastic_universal_sampling {
+        use super::*;
+
+        /// How many times each individual (by index) gets picked out of
+        /// `population`, in each of `runs` generations of `n` picks.
+        fn counts(population: &[TestIndividual], n: usize, runs: u64) -> Vec<Vec<usize>> {
+            (0..runs)
+                .map(|seed| {
+                    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                    let mut counts = vec![0; population.len()];
+
+                    for pick in StochasticUniversalSampling.select_many(&mut rng, population, n) {
+                        counts[pick] += 1;
+                    }
+
+                    counts
+                })
+                .collect()
+        }
+
+        fn population(fitnesses: &[Float]) -> Vec<TestIndividual> {
+            fitnesses.iter().map(|&fitness| TestIndividual::new(fitness)).collect()
+        }
+
+        #[test]
+        fn counts_match_expected_values() {
+            let fitnesses = [2.0, 1.0, 4.0, 3.0, 0.5, 9.5];
+            let total: Float = fitnesses.iter().sum();
+
+            for n in [1, 4, 6, 13, 40] {
+                for counts in counts(&population(&fitnesses), n, 200) {
+                    assert_eq!(counts.iter().sum::<usize>(), n);
+
+                    for (&count, &fitness) in counts.iter().zip(&fitnesses) {
+                        let expected = fitness / total * n as Float;
+
+                        assert!(
+                            (count as Float - expected).abs() <= 1.0,
+                            "n = {}, fitness = {}, count = {}",
+                            n,
+                            fitness,
+                            count,
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn best_individual_always_gets_picked() {
+            // Expected to get picked 2.4 times out of 4; roulette misses it
+            // once per ~40 generations
+            let population = population(&[1.0, 1.0, 1.0, 4.5]);
+
+            for counts in counts(&population, 4, 1000) {
+                assert!((2..=3).contains(&counts[3]), "counts = {:?}", counts);
+            }
+        }
+
+        #[test]
+        fn zero_fitness_picks_uniformly() {
+            for counts in counts(&population(&[0.0, 0.0, 0.0]), 6, 100) {
+                assert_eq!(counts, vec![2, 2, 2]);
+            }
+        }
+
+        #[test]
+        fn picks_get_shuffled() {
+            let population = population(&[1.0, 1.0]);
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let orders: Vec<Vec<_>> = (0..20)
+                .map(|_| {
+                    StochasticUniversalSampling.select_many(&mut rng, &population, 4)
+                })
+                .collect();
+
+            assert!(orders.iter().any(|order| order != &orders[0]));
+        }
+
+        #[test]
+        fn evolve_picks_parents_in_one_pass() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Keeping the picks just as they come out of the pass
+            let ga = GeneticAlgorithm::new(
+                StochasticUniversalSampling,
+                UniformCrossover,
+                GaussianMutation::new(0.0, 0.0),
+            )
+            .with_self_mating(true);
+
+            let population = vec![
+                TestIndividual::create(Chromosome::from_iter([0.5, 0.5])),
+                TestIndividual::create(Chromosome::from_iter([1.0, 1.0])),
+                TestIndividual::create(Chromosome::from_iter([1.0, 2.0])),
+                TestIndividual::create(Chromosome::from_iter([2.0, 4.0])),
+            ];
+
+            for _ in 0..100 {
+                let mut counts = [0usize; 4];
+
+                for (a, b) in ga.evolve_traced(&mut rng, &population, 0.0).unwrap().parents {
+                    counts[a] += 1;
+                    counts[b] += 1;
+                }
+
+                // 8 picks, out of a total fitness of 12
+                assert!((0..=1).contains(&counts[0]), "counts = {:?}", counts);
+                assert!((1..=2).contains(&counts[1]), "counts = {:?}", counts);
+                assert!((2..=3).contains(&counts[2]), "counts = {:?}", counts);
+                assert_eq!(counts[3], 4, "counts = {:?}", counts);
+            }
+        }
+
+        #[test]
+        fn evolve_keeps_picks_without_self_mating() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Duplicated pairs trade picks instead of re-selecting parent_b
+            let ga = GeneticAlgorithm::new(
+                StochasticUniversalSampling,
+                UniformCrossover,
+                GaussianMutation::new(0.0, 0.0),
+            );
+
+            let population = vec![
+                TestIndividual::create(Chromosome::from_iter([0.5, 0.5])),
+                TestIndividual::create(Chromosome::from_iter([1.0, 1.0])),
+                TestIndividual::create(Chromosome::from_iter([1.0, 2.0])),
+                TestIndividual::create(Chromosome::from_iter([2.0, 4.0])),
+            ];
+
+            for _ in 0..100 {
+                let mut counts = [0usize; 4];
+
+                for (a, b) in ga.evolve_traced(&mut rng, &population, 0.0).unwrap().parents {
+                    assert_ne!(a, b);
+
+                    counts[a] += 1;
+                    counts[b] += 1;
+                }
+
+                // 8 picks, out of a total fitness of 12
+                assert!((0..=1).contains(&counts[0]), "counts = {:?}", counts);
+                assert!((1..=2).contains(&counts[1]), "counts = {:?}", counts);
+                assert!((2..=3).contains(&counts[2]), "counts = {:?}", counts);
+                assert_eq!(counts[3], 4, "counts = {:?}", counts);
+            }
+        }
+
+        #[test]
+        fn separate_pairs_trades_picks() {
+            let mut picks = [3, 3, 1, 2, 0, 0, 3, 1];
+            separate_pairs(&mut picks);
+            assert_eq!(picks, [3, 1, 3, 2, 0, 3, 0, 1]);
+
+            // 3 makes up more than half the picks, so one pair has to stay
+            let mut picks = [3, 3, 1, 2, 3, 0, 3, 3];
+            separate_pairs(&mut picks);
+            assert_eq!(picks, [3, 1, 3, 2, 3, 0, 3, 3]);
+        }
+
+        #[test]
+        #[should_panic(expected = "got an empty population")]
+        fn empty_population() {
+            counts(&[], 3, 1);
+        }
+    }
+
+    #[test]
+    fn roulette_wheel_selection_with_zero_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            TestIndividual::new(0.0),
+            TestIndividual::new(0.0),
+            TestIndividual::new(0.0),
+        ];
+
+        // Would panic if we passed all-zero weights to `choose_weighted()`
+        for _ in 0..100 {
+            let fitness = RouletteWheelSelection
+                .select(&mut rng, &population)
+                .fitness();
+
+            assert_eq!(fitness, 0.0);
+        }
+    }
+
+    #[test]
+    fn uniform_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a = (1..=100).map(|n| n as Float).collect();
+        let parent_b = (1..=100).map(|n| -n as Float).collect();
+        let child = UniformCrossover.crossover(&mut rng, &parent_a, &parent_b).unwrap();
+
+        // Number of genes different between 'child' and 'parent_a'
+        let diff_a = child.iter().zip(parent_a).filter(|(c,p)| *c != p).count();
+        let diff_b = child.iter().zip(parent_b).filter(|(c,p)| *c != p).count();
+
+        assert_eq!(diff_a, 49); // Child inherited 49% of parent_a's genes
+        assert_eq!(diff_b, 51); // Child inherited 51% of parent_b's genes
+    }
+
+    #[test]
+    fn biased_uniform_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=1000).map(|n| n as Float).collect();
+        let parent_b: Chromosome = (1..=1000).map(|n| -n as Float).collect();
+        let child = UniformCrossover::with_bias(0.9).crossover(&mut rng, &parent_a, &parent_b).unwrap();
+
+        let from_a = child.iter().zip(parent_a.iter()).filter(|(c, p)| c == p).count();
+        let from_b = child.iter().zip(parent_b.iter()).filter(|(c, p)| c == p).count();
+
+        assert_eq!(from_a + from_b, 1000);
+        assert!((870..=930).contains(&from_a), "{}", from_a);
+
+        // Extreme biases copy a single parent
+        let child = UniformCrossover::with_bias(1.0).crossover(&mut rng, &parent_a, &parent_b).unwrap();
+        assert!(child.iter().eq(parent_a.iter()));
+
+        let child = UniformCrossover::with_bias(0.0).crossover(&mut rng, &parent_a, &parent_b).unwrap();
+        assert!(child.iter().eq(parent_b.iter()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn uniform_crossover_rejects_bias_outside_unit_range() {
+        UniformCrossover::with_bias(1.1);
+    }
+
+    #[test]
+    fn single_point_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=100).map(|n| n as Float).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as Float).collect();
+
+        for _ in 0..20 {
+            let child = SinglePointCrossover.crossover(&mut rng, &parent_a, &parent_b).unwrap();
+            let cut = child.iter().take_while(|gene| **gene > 0.0).count();
+
+            assert_eq!(child.len(), 100);
+            assert!(child.iter().take(cut).eq(parent_a.iter().take(cut)));
+            assert!(child.iter().skip(cut).eq(parent_b.iter().skip(cut)));
+        }
+
+        // With a single gene, the cut can only land at either end
+        let parent_a: Chromosome = [1.0].into_iter().collect();
+        let parent_b: Chromosome = [-1.0].into_iter().collect();
+
+        let children: Vec<_> = (0..20)
+            .map(|_| SinglePointCrossover.crossover(&mut rng, &parent_a, &parent_b).unwrap()[0])
+            .collect();
+
+        assert!(children.contains(&1.0) && children.contains(&-1.0));
+
+        let empty = Chromosome::from_iter([]);
+        assert_eq!(SinglePointCrossover.crossover(&mut rng, &empty, &empty).unwrap().len(), 0);
+    }
+
+    mod k_point_crossover {
+        use super::*;
+
+        fn parents(len: usize) -> (Chromosome, Chromosome) {
+            (
+                (1..=len).map(|n| n as Float).collect(),
+                (1..=len).map(|n| -(n as Float)).collect(),
+            )
+        }
+
+        /// Lengths of the child's runs of genes taken from the same parent,
+        /// asserting that they alternate (starting with `parent_a`) and
+        /// that each gene sits where it did in its parent.
+        fn runs(child: &Chromosome, parent_a: &Chromosome, parent_b: &Chromosome) -> Vec<usize> {
+            let mut runs: Vec<(bool, usize)> = Vec::new();
+
+            for (idx, &gene) in child.iter().enumerate() {
+                let from_a = gene > 0.0;
+
+                assert_eq!(gene, if from_a { parent_a[idx] } else { parent_b[idx] });
+
+                match runs.last_mut() {
+                    Some((run_from_a, len)) if *run_from_a == from_a => *len += 1,
+                    _ => runs.push((from_a, 1)),
+                }
+            }
+
+            for (idx, (from_a, _)) in runs.iter().enumerate() {
+                assert_eq!(*from_a, idx % 2 == 0);
+            }
+
+            runs.into_iter().map(|(_, len)| len).collect()
+        }
+
+        #[test]
+        fn child_consists_of_k_plus_one_alternating_runs() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents(50);
+
+            for k in [1, 2, 5, 49] {
+                for _ in 0..20 {
+                    let child = KPointCrossover::new(k).crossover(&mut rng, &parent_a, &parent_b).unwrap();
+                    let runs = runs(&child, &parent_a, &parent_b);
+
+                    assert_eq!(runs.len(), k + 1);
+                    assert_eq!(runs.iter().sum::<usize>(), 50);
+                }
+            }
+        }
+
+        #[test]
+        fn zero_k_clones_parent_a() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents(10);
+            let child = KPointCrossover::new(0).crossover(&mut rng, &parent_a, &parent_b).unwrap();
+
+            assert!(child.iter().eq(parent_a.iter()));
+        }
+
+        #[test]
+        fn k_past_length_gets_capped() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents(5);
+            let child = KPointCrossover::new(100).crossover(&mut rng, &parent_a, &parent_b).unwrap();
+
+            assert_eq!(runs(&child, &parent_a, &parent_b), [1, 1, 1, 1, 1]);
+
+            for len in [0, 1] {
+                let (parent_a, parent_b) = parents(len);
+                let child = KPointCrossover::new(3).crossover(&mut rng, &parent_a, &parent_b).unwrap();
+
+                assert!(child.iter().eq(parent_a.iter()));
+            }
+        }
+    }
+
+    mod segmented_crossover {
+        use super::*;
+
+        const SEGMENTS: [usize; 4] = [3, 0, 10, 5];
+
+        fn parents() -> (Chromosome, Chromosome) {
+            (
+                (1..=18).map(|n| n as Float).collect(),
+                (1..=18).map(|n| -(n as Float)).collect(),
+            )
+        }
+
+        /// Returns, for each segment, whether the child got it from
+        /// `parent_a` - asserting that it's bit-identical to one of the
+        /// parents' segments.
+        fn sources(child: &Chromosome, parent_a: &Chromosome, parent_b: &Chromosome) -> Vec<bool> {
+            let mut start = 0;
+
+            SEGMENTS
+                .iter()
+                .filter(|&&len| len > 0)
+                .map(|&len| {
+                    let range = start..(start + len);
+                    start += len;
+
+                    let genes = |chromosome: &Chromosome| -> Vec<_> {
+                        chromosome.iter().skip(range.start).take(len).map(|gene| gene.to_bits()).collect()
+                    };
+
+                    let from_a = genes(child) == genes(parent_a);
+
+                    assert!(from_a || genes(child) == genes(parent_b), "{:?}", range);
+                    from_a
+                })
+                .collect()
+        }
+
+        #[test]
+        fn segments_are_inherited_whole() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+            let crossover = SegmentedCrossover::new(SEGMENTS.to_vec());
+            let mut seen = Vec::new();
+
+            for _ in 0..50 {
+                let child = crossover.crossover(&mut rng, &parent_a, &parent_b).unwrap();
+                seen.extend(sources(&child, &parent_a, &parent_b));
+            }
+
+            // Each segment's parent gets picked independently
+            assert!(seen.contains(&true) && seen.contains(&false));
+        }
+
+        #[test]
+        fn pair_gets_complementary_segments() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+
+            let (child_a, child_b) = SegmentedCrossover::new(SEGMENTS.to_vec())
+                .crossover_pair(&mut rng, &parent_a, &parent_b)
+                .unwrap();
+
+            for (a, b) in sources(&child_a, &parent_a, &parent_b).into_iter().zip(sources(&child_b, &parent_a, &parent_b)) {
+                assert_ne!(a, b);
+            }
+        }
+
+        #[test]
+        fn segments_have_to_cover_the_parents() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+
+            for segments in [vec![3, 10], vec![18, 1], vec![]] {
+                let crossover = SegmentedCrossover::new(segments);
+
+                assert_eq!(
+                    crossover.crossover(&mut rng, &parent_a, &parent_b),
+                    Err(CrossoverError::SegmentMismatch {
+                        segments: crossover.genes(),
+                        genes: 18,
+                    }),
+                );
+            }
+        }
+    }
+
+    mod differential_recombination {
+        use super::*;
+        use core::cell::RefCell;
+
+        fn parents() -> [Chromosome; 3] {
+            [
+                [1.0, 2.0, 3.0].into_iter().collect(),
+                [4.0, 4.0, 4.0].into_iter().collect(),
+                [2.0, 3.0, 5.0].into_iter().collect(),
+            ]
+        }
+
+        #[test]
+        fn full_mask_takes_the_mutant_vector() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let [a, b, c] = parents();
+
+            let child = DifferentialRecombination::new(0.5, 1.0)
+                .crossover_many(&mut rng, &[&a, &b, &c])
+                .unwrap();
+
+            assert_eq!(child, [2.0, 2.5, 2.5].into_iter().collect());
+        }
+
+        #[test]
+        fn empty_mask_still_takes_one_gene() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let [a, b, c] = parents();
+            let crossover = DifferentialRecombination::new(1.0, 0.0);
+
+            for _ in 0..10 {
+                let child = crossover.crossover_many(&mut rng, &[&a, &b, &c]).unwrap();
+                let changed: Vec<_> = (0..3).filter(|&idx| child[idx] != a[idx]).collect();
+
+                let [idx] = changed[..] else {
+                    panic!("{:?}", child);
+                };
+
+                assert_eq!(child[idx], a[idx] + b[idx] - c[idx]);
+            }
+        }
+
+        #[test]
+        fn needs_three_parents() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let [a, b, c] = parents();
+            let crossover = DifferentialRecombination::new(0.5, 0.5);
+
+            assert_eq!(
+                crossover.crossover(&mut rng, &a, &b),
+                Err(CrossoverError::NotEnoughParents { needed: 3, got: 2 }),
+            );
+
+            assert_eq!(
+                crossover.crossover_many(&mut rng, &[&a, &b]),
+                Err(CrossoverError::NotEnoughParents { needed: 3, got: 2 }),
+            );
+
+            let short: Chromosome = [1.0].into_iter().collect();
+
+            assert_eq!(
+                crossover.crossover_many(&mut rng, &[&a, &c, &short]),
+                Err(CrossoverError::LengthMismatch { a: 3, b: 1 }),
+            );
+        }
+
+        /// Three-parent crossover that remembers who it's crossed.
+        #[derive(Default)]
+        struct RecordingCrossover {
+            parents: RefCell<Vec<Vec<Float>>>,
+        }
+
+        impl CrossoverMethod for RecordingCrossover {
+            fn crossover(
+                &self,
+                _rng: &mut dyn RngCore,
+                _parent_a: &Chromosome,
+                _parent_b: &Chromosome,
+            ) -> Result<Chromosome, CrossoverError> {
+                unreachable!()
+            }
+
+            fn parent_count(&self) -> usize {
+                3
+            }
+
+            fn crossover_many(&self, _rng: &mut dyn RngCore, parents: &[&Chromosome]) -> Result<Chromosome, CrossoverError> {
+                self.parents.borrow_mut().push(parents.iter().map(|parent| parent[0]).collect());
+                Ok(parents[0].clone())
+            }
+        }
+
+        fn population() -> Vec<TestIndividual> {
+            (1..=5)
+                .map(|idx| TestIndividual::create([idx as Float].into_iter().collect()))
+                .collect()
+        }
+
+        #[test]
+        fn evolve_selects_three_distinct_parents() {
+            for order in [OperatorOrder::CrossoverFirst, OperatorOrder::MutationFirst] {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let crossover = RecordingCrossover::default();
+
+                let ga = GeneticAlgorithm::new(RouletteWheelSelection, crossover, GaussianMutation::new(0.0, 0.0))
+                    .with_operator_order(order)
+                    .with_paired_offspring(true);
+
+                let evolution = ga.evolve_traced(&mut rng, &population(), 0.0).unwrap();
+                let parents = ga.crossover_method.parents.into_inner();
+
+                // ... without twins
+                assert_eq!(parents.len(), 5);
+
+                for (parents, (idx_a, idx_b)) in parents.iter().zip(&evolution.parents) {
+                    assert_eq!(parents.len(), 3);
+                    assert_eq!((parents[0], parents[1]), ((idx_a + 1) as Float, (idx_b + 1) as Float));
+                    assert!(parents[0] != parents[1] && parents[0] != parents[2] && parents[1] != parents[2]);
+                }
+            }
+        }
+
+        #[test]
+        fn evolve_and_breed() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let [a, b, _] = parents();
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                DifferentialRecombination::new(0.5, 0.9),
+                GaussianMutation::new(0.0, 0.0),
+            );
+
+            let population: Vec<_> = (0..10)
+                .map(|idx| TestIndividual::create((0..3).map(|gene| (idx + gene) as Float).collect()))
+                .collect();
+
+            assert_eq!(ga.evolve(&mut rng, &population).unwrap().0.len(), 10);
+
+            assert_eq!(
+                ga.breed(&mut rng, &a, &b),
+                Err(CrossoverError::NotEnoughParents { needed: 3, got: 2 }),
+            );
+        }
+    }
+
+    mod composite_crossover {
+        use super::*;
+
+        fn composite(weights: [f32; 2]) -> CompositeCrossover {
+            // Arithmetic crossover of alpha 1.0 returns parent_a, and of
+            // alpha 0.0 - parent_b
+            CompositeCrossover::new(vec![
+                (weights[0], Box::new(ArithmeticCrossover::new(1.0))),
+                (weights[1], Box::new(ArithmeticCrossover::new(0.0))),
+            ])
+        }
+
+        #[test]
+        fn operators_get_picked_by_weight() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = [1.0, 2.0].into_iter().collect();
+            let parent_b: Chromosome = [-1.0, -2.0].into_iter().collect();
+            let crossover = composite([7.0, 3.0]);
+
+            let from_a = (0..10_000)
+                .filter(|_| crossover.crossover(&mut rng, &parent_a, &parent_b).unwrap() == parent_a)
+                .count();
+
+            assert!((6_800..=7_200).contains(&from_a), "{}", from_a);
+        }
+
+        #[test]
+        fn parent_count_is_the_largest_one() {
+            assert_eq!(composite([1.0, 1.0]).parent_count(), 2);
+
+            let crossover = CompositeCrossover::new(vec![
+                (1.0, Box::new(UniformCrossover)),
+                (1.0, Box::new(DifferentialRecombination::new(0.5, 0.5))),
+            ]);
+
+            assert_eq!(crossover.parent_count(), 3);
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population: Vec<_> = (0..10)
+                .map(|idx| TestIndividual::create((0..3).map(|gene| (idx + gene) as Float).collect()))
+                .collect();
+
+            let ga = GeneticAlgorithm::new(RouletteWheelSelection, crossover, GaussianMutation::new(0.5, 0.5));
+
+            assert_eq!(ga.evolve(&mut rng, &population).unwrap().0.len(), 10);
+        }
+
+        #[test]
+        #[should_panic(expected = "crossover weights must be positive")]
+        fn rejects_zero_weights() {
+            composite([1.0, 0.0]);
+        }
+
+        #[test]
+        #[should_panic(expected = "composite crossover requires at least one operator")]
+        fn rejects_no_operators() {
+            CompositeCrossover::new(vec![]);
+        }
+    }
+
+    mod arithmetic_crossover {
+        use super::*;
+
+        fn parents() -> (Chromosome, Chromosome) {
+            (
+                [1.0, -2.0, 3.0, 0.5].into_iter().collect(),
+                [3.0, 2.0, -1.0, 0.5].into_iter().collect(),
+            )
+        }
+
+        fn genes(chromosome: Chromosome) -> Vec<Float> {
+            chromosome.into_iter().collect()
+        }
+
+        #[test]
+        fn fixed_alpha() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+            let mut child = |alpha| genes(ArithmeticCrossover::new(alpha).crossover(&mut rng, &parent_a, &parent_b).unwrap());
+
+            assert_eq!(child(0.0), genes(parent_b.clone()));
+            assert_eq!(child(1.0), genes(parent_a.clone()));
+            assert_eq!(child(0.5), [2.0, 0.0, 1.0, 0.5]);
+        }
+
+        #[test]
+        fn random_alpha_stays_between_parents() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+            let child = genes(ArithmeticCrossover::random().crossover(&mut rng, &parent_a, &parent_b).unwrap());
+
+            for ((child, a), b) in child.iter().zip(parent_a.iter()).zip(parent_b.iter()) {
+                assert!(a.min(*b) <= *child && *child <= a.max(*b), "{} vs {} & {}", child, a, b);
+            }
+
+            assert_ne!(child, [2.0, 0.0, 1.0, 0.5]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_alpha_outside_unit_range() {
+            ArithmeticCrossover::new(1.5);
+        }
+    }
+
+    mod blx_alpha_crossover {
+        use super::*;
+
+        fn parents() -> (Chromosome, Chromosome) {
+            (
+                (0..100).map(|n| n as Float / 10.0).collect(),
+                (0..100).map(|n| (n % 7) as Float).collect(),
+            )
+        }
+
+        #[test]
+        fn child_stays_within_widened_range() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+            let child = BlxAlphaCrossover::new(0.5).crossover(&mut rng, &parent_a, &parent_b).unwrap();
+
+            let mut outside = 0;
+
+            for ((&child, &a), &b) in child.iter().zip(parent_a.iter()).zip(parent_b.iter()) {
+                let d = (a - b).abs();
+
+                assert!(a.min(b) - 0.5 * d <= child && child <= a.max(b) + 0.5 * d);
+
+                if child < a.min(b) || child > a.max(b) {
+                    outside += 1;
+                }
+            }
+
+            assert!(outside > 0);
+        }
+
+        #[test]
+        fn zero_alpha_stays_within_parents() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+            let child = BlxAlphaCrossover::new(0.0).crossover(&mut rng, &parent_a, &parent_b).unwrap();
+
+            for ((&child, &a), &b) in child.iter().zip(parent_a.iter()).zip(parent_b.iter()) {
+                assert!(a.min(b) <= child && child <= a.max(b));
+            }
+        }
+
+        #[test]
+        fn identical_genes_get_passed_on() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent: Chromosome = [1.0, -2.0, 0.0].into_iter().collect();
+            let child = BlxAlphaCrossover::new(2.0).crossover(&mut rng, &parent, &parent).unwrap();
+
+            assert!(child.iter().eq(parent.iter()));
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_negative_alpha() {
+            BlxAlphaCrossover::new(-0.1);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod simulated_binary_crossover {
+        use super::*;
+
+        fn parents() -> (Chromosome, Chromosome) {
+            (
+                (0..200).map(|n| (n % 10) as Float).collect(),
+                (0..200).map(|n| (n % 10) as Float + 1.0).collect(),
+            )
+        }
+
+        /// Mean distance between the child's genes and `parent_a`'s, over
+        /// the genes that have been crossed at all.
+        fn spread(eta: f32) -> Float {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+            let child = SimulatedBinaryCrossover::new(eta).crossover(&mut rng, &parent_a, &parent_b).unwrap();
+
+            let distances: Vec<_> = child
+                .iter()
+                .zip(parent_a.iter())
+                .map(|(child, a)| (child - a).abs())
+                .filter(|&distance| distance > 0.0)
+                .collect();
+
+            // Roughly half of the genes get copied
+            assert!((70..=130).contains(&distances.len()), "{}", distances.len());
+
+            distances.iter().sum::<Float>() / distances.len() as Float
+        }
+
+        #[test]
+        fn large_eta_keeps_children_close_to_parents() {
+            assert!(spread(100.0) < 0.02, "{}", spread(100.0));
+        }
+
+        #[test]
+        fn small_eta_spreads_children_out() {
+            assert!(spread(0.5) > 0.25, "{}", spread(0.5));
+            assert!(spread(0.5) > 10.0 * spread(100.0));
+        }
+
+        #[test]
+        fn identical_parents_produce_identical_child() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent: Chromosome = [0.0, 1.5, -3.0, 1e-30].into_iter().collect();
+
+            for eta in [0.0, 2.0, 20.0] {
+                let child = SimulatedBinaryCrossover::new(eta).crossover(&mut rng, &parent, &parent).unwrap();
+
+                assert!(child.iter().eq(parent.iter()));
+            }
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_negative_eta() {
+            SimulatedBinaryCrossover::new(-1.0);
+        }
+    }
+
+    mod crossover_error {
+        use super::*;
+
+        fn crossovers() -> Vec<Box<dyn CrossoverMethod>> {
+            #[cfg_attr(not(feature = "std"), allow(unused_mut))]
+            let mut crossovers: Vec<Box<dyn CrossoverMethod>> = vec![
+                Box::new(UniformCrossover),
+                Box::new(SinglePointCrossover),
+                Box::new(KPointCrossover::new(2)),
+                Box::new(ArithmeticCrossover::new(0.5)),
+                Box::new(BlxAlphaCrossover::new(0.5)),
+                Box::new(SegmentedCrossover::new(vec![1, 2])),
+            ];
+
+            #[cfg(feature = "std")]
+            crossovers.push(Box::new(SimulatedBinaryCrossover::new(2.0)));
+
+            crossovers
+        }
+
+        #[test]
+        fn built_in_crossovers_reject_different_lengths() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = [1.0, 2.0, 3.0].into_iter().collect();
+            let parent_b: Chromosome = [1.0].into_iter().collect();
+
+            for crossover in crossovers() {
+                assert_eq!(
+                    crossover.crossover(&mut rng, &parent_a, &parent_b),
+                    Err(CrossoverError::LengthMismatch { a: 3, b: 1 }),
+                );
+
+                assert_eq!(crossover.crossover(&mut rng, &parent_a, &parent_a).unwrap().len(), 3);
+            }
+
+            // ... except for the one that ignores parent_b anyway
+            assert!(AsexualReproduction.crossover(&mut rng, &parent_a, &parent_b).is_ok());
+        }
+
+        /// Population with one malformed individual, e.g. loaded from a
+        /// corrupted save.
+        fn population() -> Vec<TestIndividual> {
+            let mut population: Vec<_> = (0..6)
+                .map(|idx| TestIndividual::create((0..3).map(|gene| (idx + gene) as Float).collect()))
+                .collect();
+
+            population.push(TestIndividual::create([1.0].into_iter().collect()));
+            population
+        }
+
+        fn ga() -> GeneticAlgorithm<RouletteWheelSelection, UniformCrossover, GaussianMutation> {
+            GeneticAlgorithm::new(RouletteWheelSelection, UniformCrossover, GaussianMutation::new(0.5, 0.5))
+        }
+
+        #[test]
+        fn evolve_propagates_mismatch() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Every individual gets picked as a parent sooner or later
+            let err = ga().evolve_to(&mut rng, &population(), 100).unwrap_err();
+
+            assert!(matches!(err, CrossoverError::LengthMismatch { .. }), "{:?}", err);
+            assert!(err.to_string().starts_with("parents have different lengths"));
+
+            let (a, b) = (&population()[0], &population()[6]);
+            assert_eq!(
+                ga().breed(&mut rng, a.chromosome(), b.chromosome()),
+                Err(CrossoverError::LengthMismatch { a: 3, b: 1 }),
+            );
+        }
+
+        #[test]
+        fn asexual_evolution_doesnt_cross_anybody() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let ga = ga().with_reproduction(ReproductionMode::Asexual);
+
+            assert_eq!(ga.evolve_to(&mut rng, &population(), 100).unwrap().population.len(), 100);
+        }
+    }
+
+    mod gaussian_mutation {
+        use super::*;
+
+        fn actual(chance: f32, coeff: Float) -> Vec<Float> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+
+            GaussianMutation::new(chance, coeff).mutate(&mut rng, &mut child);
+
+            child.into_iter().collect()
+        }
+        mod given_zero_chance {
+            use approx::assert_relative_eq;
+
+            fn actual(coeff: crate::Float) -> Vec<crate::Float> {
+                super::actual(0.0, coeff)
+            }
+
+            mod and_zero_coefficient {
+                use super::*;
+
+                #[test]
+                fn does_not_change_the_original_chromosome() {
+                    let actual = actual(0.0);
+                    let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
+                }
+            }
+
+            mod and_nonzero_coefficient {
+                use super::*;
+
+                #[test]
+                fn does_not_change_the_original_chromosome() {
+                    let actual = actual(0.5);
+                    let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
+                }
+            }
+        }
+
+        mod given_fifty_fifty_chance {
+            use approx::assert_relative_eq;
+
+            fn actual(coeff: crate::Float) -> Vec<crate::Float> {
+                super::actual(0.5, coeff)
+            }
+
+            mod and_zero_coefficient {
+                use super::*;
+
+                #[test]
+                fn does_not_change_the_original_chromosome() {
+                    let actual = actual(0.0);
+                    let expected = vec![1.0,2.0,3.0,4.0,5.0];
+                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
+                }
+
+            }
+
+            mod and_nonzero_coefficient {
+                use super::*;
+
+                #[test]
+                fn slightly_changes_the_original_chromosome() {
+                    let actual = actual(0.5);
+
+                    #[cfg(not(feature = "precision-f64"))]
+                    let expected = vec![1.0, 1.7756249, 3.0, 4.1596804, 5.0];
+
+                    #[cfg(feature = "precision-f64")]
+                    let expected = vec![1.0, 1.8721990071413166, 2.8691555244472555, 4.0, 5.298544100860873];
+
+                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
+                }
+            }
+        }
+
+        mod given_max_chance {
+            use approx::assert_relative_eq;
+
+            fn actual(coeff: crate::Float) -> Vec<crate::Float> {
+                super::actual(1.0, coeff)
+            }
+            
+            mod and_zero_coefficient {
+                use super::*;
+                
+                #[test]
+                fn does_not_change_the_original_chromosome() {
+                    let actual = actual(0.0);
+                    let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
+                }
+            }
+
+            mod and_nonzero_coefficient {
+                use super::*;
+                
+                #[test]
+                fn entirely_changes_the_original_chromosome() {
+                    let actual = actual(0.5);
+
+                    #[cfg(not(feature = "precision-f64"))]
+                    let expected = vec![1.4545316, 2.1162078, 2.7756248, 3.9505124, 4.638691];
+
+                    #[cfg(feature = "precision-f64")]
+                    let expected = vec![1.3157122470332616, 1.9412044901942502, 2.950512490537528, 3.8691555244472555, 4.660843312574409];
+
+                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
+                }
+            }
+        }
+
+        mod with_guaranteed_change {
+            use super::*;
+
+            fn parent() -> Chromosome {
+                (0..20).map(|idx| idx as Float).collect()
+            }
+
+            fn changed_genes(before: &Chromosome, after: &Chromosome) -> usize {
+                MutationReport::diff(before, after).genes_touched
+            }
+
+            #[test]
+            fn changes_exactly_one_gene_given_zero_chance() {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let mutation = GaussianMutation::new(0.0, 1.0).with_guaranteed_change(true);
+
+                for _ in 0..100 {
+                    let mut child = parent();
+                    mutation.mutate(&mut rng, &mut child);
+
+                    assert_eq!(changed_genes(&parent(), &child), 1);
+                }
+
+                let mut children: Vec<_> = (0..100).map(|_| parent()).collect();
+                mutation.mutate_batch(&mut rng, &mut children);
+
+                for child in &children {
+                    assert_eq!(changed_genes(&parent(), child), 1);
+                }
+            }
+
+            #[test]
+            fn changed_gene_is_picked_uniformly() {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let mutation = GaussianMutation::new(0.0, 1.0).with_guaranteed_change(true);
+                let mut picks = [0; 20];
+
+                for _ in 0..10_000 {
+                    let mut child = parent();
+                    mutation.mutate(&mut rng, &mut child);
+
+                    let idx = (0..20).find(|&idx| child[idx] != parent()[idx]).unwrap();
+                    picks[idx] += 1;
+                }
+
+                assert!(picks.iter().all(|picks| (400..=600).contains(picks)), "{:?}", picks);
+            }
+
+            #[test]
+            fn disabled_matches_plain_mutation() {
+                for chance in [0.0, 0.05, 1.0] {
+                    let plain = GaussianMutation::new(chance, 1.0);
+                    let disabled = plain.clone().with_guaranteed_change(false);
+
+                    let mut expected = parent();
+                    plain.mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut expected);
+
+                    let mut actual = parent();
+                    disabled.mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut actual);
+
+                    assert_eq!(actual, expected);
+
+                    let mut expected = vec![parent(), parent()];
+                    plain.mutate_batch(&mut ChaCha8Rng::from_seed(Default::default()), &mut expected);
+
+                    let mut actual = vec![parent(), parent()];
+                    disabled.mutate_batch(&mut ChaCha8Rng::from_seed(Default::default()), &mut actual);
+
+                    assert_eq!(actual, expected);
+                }
+
+                let mut child = parent();
+                GaussianMutation::new(0.0, 1.0).mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut child);
+
+                assert_eq!(child, parent());
+            }
+
+            #[test]
+            fn touched_children_are_left_alone() {
+                let plain = GaussianMutation::new(1.0, 1.0);
+
+                let mut expected = parent();
+                plain.mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut expected);
+
+                let mut actual = parent();
+                plain
+                    .with_guaranteed_change(true)
+                    .mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut actual);
+
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    mod layer_scaled_mutation {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        fn zeros(len: usize) -> Chromosome {
+            vec![0.0; len].into_iter().collect()
+        }
+
+        #[test]
+        fn unit_scales_match_base_mutation() {
+            let base = GaussianMutation::new(0.5, 0.3);
+            let scaled = LayerScaledMutation::from_lengths(base.clone(), &[(40, 1.0), (0, 2.0), (60, 1.0)]);
+
+            let mut expected = zeros(100);
+            base.mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut expected);
+
+            let mut actual = zeros(100);
+            scaled.mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut actual);
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn perturbations_follow_scales() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let scales = [(3000, 1.0), (3000, 0.25), (3000, 3.0)];
+            let mutation = LayerScaledMutation::from_lengths(GaussianMutation::new(1.0, 1.0), &scales);
+
+            let mut child = zeros(9000);
+            mutation.mutate(&mut rng, &mut child);
+
+            let genes: Vec<Float> = child.into_iter().collect();
+
+            for (range, &(_, scale)) in genes.chunks(3000).zip(&scales) {
+                let mean = range.iter().map(|gene| gene.abs()).sum::<Float>() / range.len() as Float;
+                let max = range.iter().fold(0.0 as Float, |max, gene| max.max(gene.abs()));
+
+                // Magnitudes are uniform over `0..coeff * scale`
+                assert_relative_eq!(mean, 0.5 * scale, max_relative = 0.05);
+                assert!(max <= scale);
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "without gaps or overlaps")]
+        fn rejects_gaps() {
+            LayerScaledMutation::new(GaussianMutation::new(0.5, 0.3), vec![(0..10, 1.0), (11..20, 2.0)]);
+        }
+
+        #[test]
+        #[should_panic(expected = "without gaps or overlaps")]
+        fn rejects_overlaps() {
+            LayerScaledMutation::new(GaussianMutation::new(0.5, 0.3), vec![(0..10, 1.0), (9..20, 2.0)]);
+        }
+
+        #[test]
+        #[should_panic(expected = "without gaps or overlaps")]
+        fn rejects_ranges_not_starting_at_zero() {
+            LayerScaledMutation::new(GaussianMutation::new(0.5, 0.3), vec![(1..10, 1.0)]);
+        }
+
+        #[test]
+        #[should_panic(expected = "doesn't match the gene ranges")]
+        fn rejects_chromosomes_of_other_lengths() {
+            let mutation = LayerScaledMutation::from_lengths(GaussianMutation::new(0.5, 0.3), &[(10, 1.0)]);
+            mutation.mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut zeros(11));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod cauchy_mutation {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        fn actual(mutation: CauchyMutation) -> Vec<Float> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+
+            mutation.mutate(&mut rng, &mut child);
+
+            child.into_iter().collect()
+        }
+
+        #[test]
+        fn zero_chance_does_not_change_the_original_chromosome() {
+            let actual = actual(CauchyMutation::new(0.0, 1.0));
+
+            assert_relative_eq!(actual.as_slice(), [1.0, 2.0, 3.0, 4.0, 5.0].as_slice());
+        }
+
+        #[test]
+        fn zero_scale_does_not_change_the_original_chromosome() {
+            let actual = actual(CauchyMutation::new(1.0, 0.0));
+
+            assert_relative_eq!(actual.as_slice(), [1.0, 2.0, 3.0, 4.0, 5.0].as_slice());
+        }
+
+        #[test]
+        fn max_chance_entirely_changes_the_original_chromosome() {
+            let actual = actual(CauchyMutation::new(1.0, 0.5));
+
+            #[cfg(not(feature = "precision-f64"))]
+            let expected = vec![0.25037533, 2.8890088, 4.702293, 4.2190313, 5.5389924];
+
+            #[cfg(feature = "precision-f64")]
+            let expected = vec![1.8890090699967745, 2.21903110092525, 2.441464556548047, 2.7086763629809028, 4.517296538467206];
+
+            assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn max_step_caps_changes() {
+            let uncapped = actual(CauchyMutation::new(1.0, 0.5));
+            let capped = actual(CauchyMutation::new(1.0, 0.5).with_max_step(0.1));
+
+            for (idx, (uncapped, capped)) in uncapped.iter().zip(&capped).enumerate() {
+                let before = (idx + 1) as Float;
+
+                assert_relative_eq!(capped - before, (uncapped - before).clamp(-0.1, 0.1), epsilon = 1e-6);
+            }
+        }
+
+        #[test]
+        fn tails_are_heavy() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = (0..10_000).map(|_| 0.0).collect();
+
+            CauchyMutation::new(1.0, 1.0).mutate(&mut rng, &mut child);
+
+            let within_scale = child.iter().filter(|gene| gene.abs() < 1.0).count();
+
+            // Half of the changes stay within the scale, while ~0.6% go
+            // past 50 of it - which a normal distribution would never do
+            assert!((4_800..=5_200).contains(&within_scale), "{}", within_scale);
+            assert!(child.iter().filter(|gene| gene.abs() > 50.0).count() > 30);
+        }
+    }
+
+    mod swap_mutation {
+        use super::*;
+
+        fn parent() -> Chromosome {
+            (0..20).map(|idx| (idx * idx) as Float).collect()
+        }
+
+        /// Gene values, sorted - to compare as multisets.
+        fn values(chromosome: &Chromosome) -> Vec<Float> {
+            let mut values: Vec<_> = chromosome.iter().copied().collect();
+            values.sort_by(|a, b| a.total_cmp(b));
+            values
+        }
+
+        #[test]
+        fn values_are_preserved() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            for swaps in [1, 3] {
+                let mutation = SwapMutation::new(1.0).with_swaps(swaps);
+                let mut child = parent();
+
+                for _ in 0..50 {
+                    let before = child.clone();
+                    mutation.mutate(&mut rng, &mut child);
+
+                    assert_eq!(values(&child), values(&parent()));
+
+                    // A single swap always moves two genes (all of them being
+                    // different); more of them can undo each other
+                    if swaps == 1 {
+                        assert_eq!(child.iter().zip(before.iter()).filter(|(a, b)| a != b).count(), 2);
+                    }
+                }
+            }
+
+            let mut children = vec![parent(); 10];
+            SwapMutation::new(0.5).mutate_batch(&mut rng, &mut children);
+
+            assert!(children.iter().all(|child| values(child) == values(&parent())));
+            assert!(children.iter().any(|child| *child != parent()));
+            assert!(children.iter().any(|child| *child == parent()));
+        }
+
+        #[test]
+        fn zero_chance_leaves_genes_untouched() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = parent();
+
+            SwapMutation::new(0.0).mutate(&mut rng, &mut child);
+
+            assert!(child.iter().eq(parent().iter()));
+        }
+
+        #[test]
+        fn short_chromosomes_are_left_alone() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            for len in [0, 1] {
+                let mut child: Chromosome = (0..len).map(|_| 1.0).collect();
+                SwapMutation::new(1.0).mutate(&mut rng, &mut child);
+
+                assert_eq!(child.len(), len);
+            }
+        }
+    }
+
+    mod chained_mutation {
+        use super::*;
+
+        fn parent() -> Chromosome {
+            (0..50).map(|idx| idx as Float / 10.0).collect()
+        }
+
+        fn chained() -> ChainedMutation {
+            ChainedMutation::new(vec![
+                Box::new(BoundedMutation::new(GaussianMutation::new(0.5, 1.0), -2.0, 2.0)),
+                Box::new(UniformResetMutation::new(0.1, -10.0, 10.0)),
+                Box::new(SwapMutation::new(1.0)),
+            ])
+        }
+
+        #[test]
+        fn applies_operators_one_after_another() {
+            let mut actual = parent();
+            chained().mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut actual);
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut expected = parent();
+
+            BoundedMutation::new(GaussianMutation::new(0.5, 1.0), -2.0, 2.0).mutate(&mut rng, &mut expected);
+            UniformResetMutation::new(0.1, -10.0, 10.0).mutate(&mut rng, &mut expected);
+            SwapMutation::new(1.0).mutate(&mut rng, &mut expected);
+
+            assert_eq!(actual, expected);
+            assert_ne!(actual, parent());
+        }
+
+        #[test]
+        fn batch_applies_operators_one_after_another() {
+            let mut actual = vec![parent(), parent(), parent()];
+            chained().mutate_batch(&mut ChaCha8Rng::from_seed(Default::default()), &mut actual);
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut expected = vec![parent(), parent(), parent()];
+
+            BoundedMutation::new(GaussianMutation::new(0.5, 1.0), -2.0, 2.0).mutate_batch(&mut rng, &mut expected);
+            UniformResetMutation::new(0.1, -10.0, 10.0).mutate_batch(&mut rng, &mut expected);
+            SwapMutation::new(1.0).mutate_batch(&mut rng, &mut expected);
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        #[should_panic(expected = "chained mutation requires at least one operator")]
+        fn rejects_no_operators() {
+            ChainedMutation::new(Vec::new());
+        }
+    }
+
+    mod bounded_mutation {
+        use super::*;
+
+        #[test]
+        fn genes_never_escape_the_range() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mutation = BoundedMutation::new(GaussianMutation::new(1.0, 5.0), -50.0, 50.0);
+            let mut child: Chromosome = (0..100).map(|idx| if idx % 2 == 0 { 49.0 } else { -49.0 }).collect();
+
+            for _ in 0..200 {
+                mutation.mutate(&mut rng, &mut child);
+
+                assert!(child.iter().all(|gene| (-50.0..=50.0).contains(gene)));
+            }
+
+            // ... even though, without the clamp, they would have
+            assert!(child.iter().any(|gene| gene.abs() == 50.0));
+
+            let mut children = vec![child.clone(), child];
+            mutation.mutate_batch(&mut rng, &mut children);
+
+            assert!(children.iter().flat_map(|child| child.iter()).all(|gene| (-50.0..=50.0).contains(gene)));
+        }
+
+        #[test]
+        fn genes_inside_the_range_are_unaffected() {
+            let parent: Chromosome = (0..100).map(|idx| idx as Float / 10.0 - 5.0).collect();
+            let inner = GaussianMutation::new(0.5, 1.0);
+
+            let mut expected = parent.clone();
+            inner.mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut expected);
+
+            let mut actual = parent;
+            BoundedMutation::new(inner, -50.0, 50.0).mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut actual);
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        #[should_panic(expected = "min must not be greater than max")]
+        fn rejects_inverted_bounds() {
+            BoundedMutation::new(GaussianMutation::new(0.5, 1.0), 1.0, -1.0);
+        }
+    }
+
+    mod per_gene_mutation {
+        use super::*;
+
+        fn zeros(n: usize) -> Chromosome {
+            (0..n).map(|_| 0.0).collect()
+        }
+
+        #[test]
+        fn each_gene_gets_its_own_chance() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mutation = PerGeneMutation::new(vec![0.0, 1.0, 0.0], vec![1.0]);
+
+            for _ in 0..100 {
+                let mut child = zeros(3);
+                mutation.mutate(&mut rng, &mut child);
+
+                assert_eq!(child[0], 0.0);
+                assert_ne!(child[1], 0.0);
+                assert_eq!(child[2], 0.0);
+            }
+        }
+
+        #[test]
+        fn genes_past_the_end_use_last_values() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = zeros(100);
+
+            PerGeneMutation::new(vec![0.0, 1.0], vec![10.0, 0.01]).mutate(&mut rng, &mut child);
+
+            assert_eq!(child[0], 0.0);
+            assert!(child.iter().skip(1).all(|gene| *gene != 0.0 && gene.abs() <= 0.01));
+        }
+
+        #[test]
+        fn single_values_match_gaussian_mutation() {
+            let mut expected = zeros(100);
+            GaussianMutation::new(0.5, 2.0).mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut expected);
+
+            let mut actual = zeros(100);
+            PerGeneMutation::new(vec![0.5], vec![2.0]).mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut actual);
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        #[should_panic(expected = "chances must be within <0, 1>")]
+        fn rejects_chances_outside_unit_range() {
+            PerGeneMutation::new(vec![0.5, 1.5], vec![1.0]);
+        }
+
+        #[test]
+        #[should_panic(expected = "per-gene mutation requires at least one chance and coeff")]
+        fn rejects_empty_coeffs() {
+            PerGeneMutation::new(vec![0.5], vec![]);
+        }
+    }
+
+    mod adaptive_mutation {
+        use super::*;
+
+        fn stats(max_fitness: Float) -> Statistics {
+            Statistics {
+                min_fitness: 0.0,
+                max_fitness,
+                avg_fitness: max_fitness / 2.0,
+            }
+        }
+
+        fn mutation() -> AdaptiveMutation {
+            AdaptiveMutation::new(GaussianMutation::new(0.5, 1.0), 3, 2.0, 0.5)
+        }
+
+        /// Coeffs after observing each of `fitnesses`.
+        fn coeffs(mut mutation: AdaptiveMutation, fitnesses: &[Float]) -> Vec<Float> {
+            fitnesses
+                .iter()
+                .map(|&fitness| {
+                    mutation.observe(&stats(fitness));
+                    mutation.coeff()
+                })
+                .collect()
+        }
+
+        #[test]
+        fn stagnation_ramps_coeff_up() {
+            assert_eq!(
+                coeffs(mutation(), &[5.0; 10]),
+                [1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 4.0, 4.0, 4.0, 8.0],
+            );
+        }
+
+        #[test]
+        fn improvement_decays_coeff_back() {
+            let mut mutation = mutation();
+
+            for _ in 0..7 {
+                mutation.observe(&stats(5.0));
+            }
+
+            assert_eq!(mutation.coeff(), 4.0);
+
+            // Worse generations don't count as an improvement either
+            assert_eq!(
+                coeffs(mutation, &[6.0, 7.0, 8.0, 7.5, 9.0]),
+                [2.0, 1.0, 0.5, 0.5, 0.25],
+            );
+        }
+
+        #[test]
+        fn first_generation_sets_the_baseline() {
+            assert_eq!(coeffs(mutation(), &[100.0]), [1.0]);
+            assert_eq!(coeffs(mutation(), &[Float::NAN, 1.0, 2.0]), [1.0, 1.0, 0.5]);
+        }
+
+        #[test]
+        fn coeff_stays_within_range() {
+            let mutation = mutation().with_coeff_range(0.25, 3.0);
+
+            assert_eq!(coeffs(mutation.clone(), &[5.0; 10]).last(), Some(&3.0));
+            assert_eq!(coeffs(mutation, &[1.0, 2.0, 3.0, 4.0, 5.0]).last(), Some(&0.25));
+        }
+    }
+
+    mod decaying_mutation {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        fn mutation() -> DecayingMutation {
+            DecayingMutation::new(GaussianMutation::new(1.0, 2.0), 0.5)
+        }
+
+        #[test]
+        fn coeff_shrinks_each_generation() {
+            let mut mutation = mutation();
+            let mut coeffs = vec![mutation.coeff()];
+
+            for _ in 0..3 {
+                mutation.advance_generation();
+                coeffs.push(mutation.coeff());
+            }
+
+            assert_eq!(coeffs, [2.0, 1.0, 0.5, 0.25]);
+            assert_eq!(mutation.generation(), 3);
+
+            // ... which jumping straight to a generation agrees with
+            let mut jumped = self::mutation();
+
+            jumped.set_generation(3);
+            assert_eq!(jumped.coeff(), 0.25);
+
+            jumped.set_generation(0);
+            assert_eq!(jumped.coeff(), 2.0);
+        }
+
+        #[test]
+        fn coeff_stops_at_min() {
+            let mut mutation = mutation().with_min_coeff(0.3);
+
+            for _ in 0..10 {
+                mutation.advance_generation();
+            }
+
+            assert_eq!(mutation.coeff(), 0.3);
+
+            mutation.set_generation(1_000_000);
+            assert_eq!(mutation.coeff(), 0.3);
+        }
+
+        #[test]
+        fn mutations_shrink_over_generations() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Identical parents, so that only mutation changes the genes
+            let population: Vec<_> = (0..20)
+                .map(|_| TestIndividual::create((0..50).map(|_| 1.0).collect()))
+                .collect();
+
+            let mut ga = GeneticAlgorithm::new(RouletteWheelSelection, UniformCrossover, mutation());
+            let mut deltas = Vec::new();
+
+            for _ in 0..4 {
+                let evolution = ga.evolve_traced(&mut rng, &population, 0.0).unwrap();
+
+                deltas.push(evolution.diagnostics.total_delta / evolution.diagnostics.genes as Float);
+                ga.mutation_method_mut().advance_generation();
+            }
+
+            // A touched gene changes by half of the coeff on average
+            for (delta, coeff) in deltas.into_iter().zip([2.0, 1.0, 0.5, 0.25]) {
+                assert_relative_eq!(delta, coeff / 2.0, max_relative = 0.1);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod normal_mutation {
+        use super::*;
+
+        /// Mean and standard deviation of the changes `mutation` makes to
+        /// a chromosome of zeros.
+        fn mutated_zeros(mutation: &NormalMutation) -> (Chromosome, Float, Float) {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = (0..20_000).map(|_| 0.0).collect();
+
+            mutation.mutate(&mut rng, &mut child);
+
+            let n = child.len() as Float;
+            let mean = child.iter().sum::<Float>() / n;
+            let std = (child.iter().map(|gene| (gene - mean).powi(2)).sum::<Float>() / n).sqrt();
+
+            (child, mean, std)
+        }
+
+        #[test]
+        fn changes_have_configured_std() {
+            for sigma in [0.1, 0.5, 3.0] {
+                let (child, mean, std) = mutated_zeros(&NormalMutation::new(1.0, sigma));
+
+                assert!(mean.abs() < 0.03 * sigma, "{}", mean);
+                assert!((std - sigma).abs() < 0.02 * sigma, "{} vs {}", std, sigma);
+
+                // About 68% of the changes stay within a sigma, and - unlike
+                // with an uniform distribution - some go past it
+                let within = child.iter().filter(|gene| gene.abs() < sigma).count() as Float / child.len() as Float;
+
+                assert!((0.66..=0.70).contains(&within), "{}", within);
+                assert!(child.iter().any(|gene| gene.abs() > 3.0 * sigma));
+            }
+        }
+
+        #[test]
+        fn chance_limits_touched_genes() {
+            let (child, _, _) = mutated_zeros(&NormalMutation::new(0.25, 1.0));
+            let touched = child.iter().filter(|gene| **gene != 0.0).count();
+
+            assert!((4_700..=5_300).contains(&touched), "{}", touched);
+
+            let (child, _, _) = mutated_zeros(&NormalMutation::new(1.0, 0.0));
+            assert!(child.iter().all(|gene| *gene == 0.0));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod self_adaptive_mutation {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        fn zeros(genes: usize, sigma: Float, step_sizes: StepSizes) -> Chromosome {
+            (0..genes)
+                .map(|_| 0.0)
+                .collect::<Chromosome>()
+                .with_sigmas(sigma, step_sizes.count(genes))
+        }
+
+        #[test]
+        fn step_size_drifts() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mutation = SelfAdaptiveMutation::new(StepSizes::Shared);
+            let mut sigmas = Vec::new();
+
+            for _ in 0..100 {
+                let mut child = zeros(4, 1.0, StepSizes::Shared);
+                mutation.mutate(&mut rng, &mut child);
+
+                sigmas.push(child[4]);
+            }
+
+            assert!(sigmas.iter().all(|sigma| *sigma > 0.0));
+            assert!(sigmas.iter().any(|sigma| *sigma < 0.7), "{:?}", sigmas);
+            assert!(sigmas.iter().any(|sigma| *sigma > 1.4), "{:?}", sigmas);
+
+            // ... by a log-normal factor of `1 / sqrt(4)` learning rate
+            let ln_sigmas: Vec<_> = (0..10_000)
+                .map(|_| {
+                    let mut child = zeros(4, 1.0, StepSizes::Shared);
+                    mutation.mutate(&mut rng, &mut child);
+
+                    child[4].ln()
+                })
+                .collect();
+
+            let mean = ln_sigmas.iter().sum::<Float>() / ln_sigmas.len() as Float;
+            let std = (ln_sigmas.iter().map(|ln| (ln - mean).powi(2)).sum::<Float>() / ln_sigmas.len() as Float).sqrt();
+
+            assert!(mean.abs() < 0.02, "{}", mean);
+            assert_relative_eq!(std, 0.5, max_relative = 0.03);
+        }
+
+        #[test]
+        fn genes_follow_shared_sigma() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            for sigma in [0.1, 1.0, 5.0] {
+                let mut child = zeros(20_000, sigma, StepSizes::Shared);
+                SelfAdaptiveMutation::new(StepSizes::Shared).mutate(&mut rng, &mut child);
+
+                let (genes, sigmas) = child.split_at(20_000);
+                let std = (genes.iter().map(|gene| gene * gene).sum::<Float>() / genes.len() as Float).sqrt();
+
+                assert_ne!(sigmas[0], sigma);
+                assert_relative_eq!(std, sigmas[0], max_relative = 0.02);
+            }
+        }
+
+        #[test]
+        fn genes_follow_their_own_sigmas() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = (0..20_000).map(|_| 0.0).collect();
+
+            child = child.with_sigmas(0.01, 10_000).with_sigmas(10.0, 10_000);
+            SelfAdaptiveMutation::new(StepSizes::PerGene).mutate(&mut rng, &mut child);
+
+            let (genes, sigmas) = child.split_at(20_000);
+
+            // Each change, in the units of its gene's sigma, is standard
+            // normal
+            let z2 = genes.iter().zip(sigmas).map(|(gene, sigma)| (gene / sigma).powi(2));
+            assert_relative_eq!(z2.sum::<Float>() / 20_000.0, 1.0, max_relative = 0.03);
+
+            let mean_change = |genes: &[Float]| genes.iter().map(|gene| gene.abs()).sum::<Float>() / genes.len() as Float;
+
+            assert!(mean_change(&genes[..10_000]) < 0.02);
+            assert!(mean_change(&genes[10_000..]) > 5.0);
+        }
+
+        #[test]
+        fn min_sigma_is_respected() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mutation = SelfAdaptiveMutation::new(StepSizes::PerGene).with_min_sigma(0.5);
+            let mut child = zeros(10, 0.5, StepSizes::PerGene);
+
+            for _ in 0..100 {
+                mutation.mutate(&mut rng, &mut child);
+
+                assert!(child.split_at(10).1.iter().all(|sigma| *sigma >= 0.5));
+            }
+        }
+
+        #[test]
+        fn sigmas_round_trip() {
+            let chromosome: Chromosome = [1.0, 2.0, 3.0].into_iter().collect();
+            let with_sigmas = chromosome.clone().with_sigmas(0.5, StepSizes::PerGene.count(3));
+
+            assert_eq!(with_sigmas.len(), 6);
+            assert_eq!(StepSizes::PerGene.genes(6), 3);
+            assert_eq!(StepSizes::Shared.genes(4), 3);
+            assert_eq!(with_sigmas.split_at(3), (&[1.0, 2.0, 3.0][..], &[0.5, 0.5, 0.5][..]));
+            assert_eq!(with_sigmas.without_sigmas(3), chromosome);
+        }
+
+        #[test]
+        #[should_panic(expected = "per-gene step sizes require an even number of genes")]
+        fn per_gene_rejects_odd_lengths() {
+            let mut child: Chromosome = [1.0, 2.0, 3.0].into_iter().collect();
+            SelfAdaptiveMutation::new(StepSizes::PerGene).mutate(&mut ChaCha8Rng::from_seed(Default::default()), &mut child);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod polynomial_mutation {
+        use super::*;
+
+        /// Genes spread across `<-1, 1>`, including both bounds.
+        fn genes() -> Chromosome {
+            (0..=2000).map(|idx| idx as Float / 1000.0 - 1.0).collect()
+        }
+
+        fn mean_perturbation(eta: f32) -> Float {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = genes();
+
+            PolynomialMutation::new(1.0, eta, -1.0, 1.0).mutate(&mut rng, &mut child);
+
+            assert!(child.iter().all(|gene| (-1.0..=1.0).contains(gene)));
+
+            child.iter().zip(genes().iter()).map(|(after, before)| (after - before).abs()).sum::<Float>()
+                / child.len() as Float
+        }
+
+        #[test]
+        fn genes_stay_within_bounds() {
+            for eta in [0.0, 1.0, 20.0, 100.0] {
+                assert!(mean_perturbation(eta) > 0.0);
+            }
+        }
+
+        #[test]
+        fn larger_eta_perturbs_less() {
+            let perturbations: Vec<_> = [0.5, 5.0, 20.0, 100.0].into_iter().map(mean_perturbation).collect();
+
+            for pair in perturbations.windows(2) {
+                assert!(pair[0] > 1.5 * pair[1], "{:?}", perturbations);
+            }
+        }
+
+        #[test]
+        fn genes_at_bounds_only_move_inwards() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mutation = PolynomialMutation::new(1.0, 1.0, -1.0, 1.0);
+
+            let mut at_lower: Chromosome = (0..500).map(|_| -1.0).collect();
+            let mut at_upper: Chromosome = (0..500).map(|_| 1.0).collect();
+
+            mutation.mutate(&mut rng, &mut at_lower);
+            mutation.mutate(&mut rng, &mut at_upper);
+
+            assert!(at_lower.iter().all(|gene| (-1.0..=1.0).contains(gene)));
+            assert!(at_lower.iter().any(|gene| *gene > -1.0));
+            assert!(at_upper.iter().all(|gene| (-1.0..=1.0).contains(gene)));
+            assert!(at_upper.iter().any(|gene| *gene < 1.0));
+
+            // Out-of-bounds genes get clamped, degenerate bounds pin them
+            let mut outside: Chromosome = [-5.0, 5.0].into_iter().collect();
+            mutation.mutate(&mut rng, &mut outside);
+            assert!(outside.iter().all(|gene| (-1.0..=1.0).contains(gene)));
+
+            let mut pinned: Chromosome = [-5.0, 0.5].into_iter().collect();
+            PolynomialMutation::new(1.0, 1.0, 0.5, 0.5).mutate(&mut rng, &mut pinned);
+            assert_eq!(pinned, [0.5, 0.5].into_iter().collect());
+        }
+
+        #[test]
+        fn zero_chance_leaves_genes_untouched() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = [-5.0, 0.0, 5.0].into_iter().collect();
+
+            PolynomialMutation::new(0.0, 1.0, -1.0, 1.0).mutate(&mut rng, &mut child);
+
+            assert_eq!(child, [-5.0, 0.0, 5.0].into_iter().collect());
+        }
+    }
+
+    mod uniform_reset_mutation {
+        use super::*;
+
+        fn mutated(chance: f32, min: Float, max: Float) -> Chromosome {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = (0..1000).map(|idx| 100.0 + idx as Float).collect();
+
+            UniformResetMutation::new(chance, min, max).mutate(&mut rng, &mut child);
+            child
+        }
+
+        #[test]
+        fn max_chance_resets_every_gene_into_bounds() {
+            let child = mutated(1.0, -0.5, 2.0);
+
+            assert!(child.iter().all(|gene| (-0.5..=2.0).contains(gene)));
+
+            // ... spread over the whole range
+            assert!(child.iter().any(|gene| *gene < 0.0));
+            assert!(child.iter().any(|gene| *gene > 1.5));
+
+            assert!(mutated(1.0, 0.25, 0.25).iter().all(|gene| *gene == 0.25));
+        }
+
+        #[test]
+        fn zero_chance_leaves_genes_untouched() {
+            let child = mutated(0.0, -1.0, 1.0);
+
+            assert!(child.iter().eq((0..1000).map(|idx| 100.0 + idx as Float).collect::<Chromosome>().iter()));
+        }
+
+        #[test]
+        #[should_panic(expected = "min must not be greater than max")]
+        fn rejects_inverted_bounds() {
+            UniformResetMutation::new(0.5, 1.0, -1.0);
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_chance_outside_unit_range() {
+            UniformResetMutation::new(1.5, -1.0, 1.0);
+        }
+    }
+
+    mod weight_decay_mutation {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        /// Every individual is equally fit, so that only mutation gets to
+        /// affect the genes
+        #[derive(Clone, Debug)]
+        struct FlatIndividual {
+            chromosome: Chromosome,
+        }
+        impl Individual for FlatIndividual {
+            fn create(chromosome: Chromosome) -> Self {
+                Self { chromosome }
+            }
+
+            fn chromosome(&self) -> &Chromosome {
+                &self.chromosome
+            }
+
+            fn fitness(&self) -> Float {
+                1.0
+            }
+        }
+
+        fn mean_abs_gene(population: &[FlatIndividual]) -> Float {
+            let genes: Vec<_> = population
+                .iter()
+                .flat_map(|individual| individual.chromosome.iter())
+                .collect();
+
+            genes.iter().map(|gene| gene.abs()).sum::<Float>() / (genes.len() as Float)
+        }
+
+        fn evolve<M>(mutation: M, generations: usize) -> Vec<FlatIndividual>
+        where
+            M: MutationMethod,
+        {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let ga = GeneticAlgorithm::new(RouletteWheelSelection, UniformCrossover, mutation);
+
+            let mut population: Vec<_> = (0..20)
+                .map(|_| FlatIndividual::create(vec![1.0, -2.0, 3.0].into_iter().collect()))
+                .collect();
+
+            for _ in 0..generations {
+                (population, _) = ga.evolve(&mut rng, &population).unwrap();
+            }
+
+            population
+        }
+
+        #[test]
+        fn shrinks_genes_geometrically() {
+            let population = evolve(
+                CompositeMutation::new(GaussianMutation::new(0.0, 0.0), WeightDecayMutation::new(0.05)),
+                10,
+            );
+
+            let factor = (0.95 as Float).powi(10);
+            let expected = vec![1.0 * factor, -2.0 * factor, 3.0 * factor];
+
+            for individual in population {
+                let actual: Vec<_> = individual.chromosome.into_iter().collect();
+                assert_relative_eq!(actual.as_slice(), expected.as_slice(), epsilon = 1e-6);
+            }
+        }
+
+        #[test]
+        fn keeps_weights_from_growing_unboundedly() {
+            let gaussian = || GaussianMutation::new(1.0, 0.5);
+
+            let without_decay = mean_abs_gene(&evolve(gaussian(), 100));
+
+            let with_decay = mean_abs_gene(&evolve(
+                CompositeMutation::new(gaussian(), WeightDecayMutation::new(0.1)),
+                100,
+            ));
+
+            // With the decay, genes settle around a magnitude where the
+            // decay cancels out the mutation (for these parameters: ~0.4)
+            assert!(with_decay < 1.0, "with_decay = {}", with_decay);
+            assert!(without_decay > 2.0 * with_decay, "without_decay = {}", without_decay);
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_too_large_decay() {
+            WeightDecayMutation::new(0.2);
+        }
+    }
+
+    mod evolve_split {
+        use super::*;
+        use std::cell::RefCell;
+
+        /// Roulette wheel selection that remembers fitness of every
+        /// individual it has selected
+        #[derive(Default)]
+        struct RecordingSelection {
+            selected: RefCell<Vec<Float>>,
+        }
+        impl SelectionMethod for RecordingSelection {
+            fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+            where
+                I: Individual,
+            {
+                let selected = RouletteWheelSelection.select_index(rng, population);
+                self.selected.borrow_mut().push(population[selected].fitness());
+                selected
+            }
+        }
+
+        fn evolve(mutation_seed: u64) -> (Vec<Float>, Vec<TestIndividual>) {
+            let mut rng_sel = ChaCha8Rng::seed_from_u64(1);
+            let mut rng_mut = ChaCha8Rng::seed_from_u64(mutation_seed);
+
+            let ga = GeneticAlgorithm::new(
+                RecordingSelection::default(),
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_self_mating(true);
+
+            // Each individual has a distinct fitness, so that fitness
+            // identifies the selected parent
+            let population: Vec<_> = (1..=8)
+                .map(|n| TestIndividual::create(vec![n as Float; 3].into_iter().collect()))
+                .collect();
+
+            let (children, _) = ga.evolve_split(&mut rng_sel, &mut rng_mut, &population).unwrap();
+
+            (ga.selection_method.selected.into_inner(), children)
+        }
+
+        #[test]
+        fn different_mutation_seeds_select_identical_parents() {
+            let (parents_a, children_a) = evolve(10);
+            let (parents_b, children_b) = evolve(20);
+
+            assert_eq!(parents_a.len(), 16);
+            assert_eq!(parents_a, parents_b);
+            assert_ne!(children_a, children_b);
+        }
+    }
+
+    mod evolve_sampled {
+        use super::*;
+
+        fn evolve(chance: f32, sample_rate: f32) -> (Vec<TestIndividual>, Vec<MutationSample>) {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(chance, 0.5),
+            );
+
+            let population: Vec<_> = (1..=10)
+                .map(|n| TestIndividual::create(vec![n as Float; 3].into_iter().collect()))
+                .collect();
+
+            let (children, _, samples) = ga.evolve_sampled(&mut rng, &population, sample_rate).unwrap();
+            (children, samples)
+        }
+
+        #[test]
+        fn samples_requested_fraction_of_children() {
+            assert_eq!(evolve(0.5, 0.0).1.len(), 0);
+            assert_eq!(evolve(0.5, 0.3).1.len(), 3);
+            assert_eq!(evolve(0.5, 1.0).1.len(), 10);
+        }
+
+        #[test]
+        fn does_not_affect_evolution() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            );
+
+            let population: Vec<_> = (1..=10)
+                .map(|n| TestIndividual::create(vec![n as Float; 3].into_iter().collect()))
+                .collect();
+
+            let (expected, _) = ga.evolve(&mut rng, &population).unwrap();
+
+            assert_eq!(evolve(0.5, 0.5).0, expected);
+        }
+
+        #[test]
+        fn given_zero_chance_samples_are_unmutated() {
+            for sample in evolve(0.0, 1.0).1 {
+                assert_eq!(sample.unmutated, sample.mutated);
+            }
+        }
+
+        #[test]
+        fn given_max_chance_samples_are_mutated() {
+            for sample in evolve(1.0, 1.0).1 {
+                assert_ne!(sample.unmutated, sample.mutated);
+            }
+        }
+    }
+
+    mod genetic_algorithm {
+        use super::*;
+
+        fn individual(genes: &[Float]) -> TestIndividual {
+            TestIndividual::create(genes.iter().cloned().collect())
+        }
+
+        fn population() -> Vec<TestIndividual> {
+            vec![
+                individual(&[0.0, 0.0, 0.0]),
+                individual(&[1.0, 1.0, 1.0]),
+                individual(&[1.0, 2.0, 1.0]),
+                individual(&[1.0, 2.0, 4.0]),
+            ]
+        }
+
+        fn evolve(self_mating: bool) -> Vec<TestIndividual> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_self_mating(self_mating);
+
+            let mut population = population();
+
+            // We evolve the population over a few generations, so that the differences between the
+            // input and output population are easier to spot.
+            //
+            // No particular reason for a number of 10 - this test would be fine for 5, 20 or
+            // even 1000 generations - the only thing that'd change is the magnitude of the
+            // difference between the populations.
+            let mut _stats = Statistics::new(&population);
+            for _ in 0..10 {
+                (population, _stats) = ga.evolve(&mut rng, &population).unwrap();
+            }
+
+            population
+        }
+
+        #[test]
+        fn with_self_mating() {
+            #[cfg(not(feature = "precision-f64"))]
+            let expected_population = vec![
+                individual(&[0.4476949, 2.0648358, 4.3058133]),
+                individual(&[1.2126867, 1.5538777, 2.886911]),
+                individual(&[1.0617678, 2.265739, 4.428764]),
+                individual(&[0.95909685, 2.4618788, 4.024733]),
+            ];
+
+            #[cfg(feature = "precision-f64")]
+            let expected_population = vec![
+                individual(&[1.064055903649652, 2.0072487420816967, 5.077696026901516]),
+                individual(&[1.1510998995734667, 1.1675076280104353, 4.129851371695811]),
+                individual(&[1.4931699769516658, 1.9945567776389714, 5.077696026901516]),
+                individual(&[1.5463183523168809, 1.7135153225819943, 5.077696026901516]),
+            ];
+
+            // Same population as before parents got to be distinct, so the
+            // knob really restores the old behavior
+            assert_eq!(evolve(true), expected_population); // expected has better fitness for each individual so evolve function is working
+        }
+
+        #[test]
+        fn with_distinct_parents() {
+            #[cfg(not(feature = "precision-f64"))]
+            let expected_population = vec![
+                individual(&[0.6398392, 1.8465477, 3.1638165]),
+                individual(&[1.7636812, 2.4653432, 3.4260502]),
+                individual(&[1.7636812, 1.9597623, 3.0389836]),
+                individual(&[1.7636812, 2.0338802, 3.4260502]),
+            ];
+
+            #[cfg(feature = "precision-f64")]
+            let expected_population = vec![
+                individual(&[0.6925155525681466, 1.5819660117393401, 4.040695011283553]),
+                individual(&[1.2441125061374576, 1.259009969710857, 4.264128070750088]),
+                individual(&[0.6825375463281673, 1.259009969710857, 3.8895400599914898]),
+                individual(&[0.30343210312944763, 1.5819660117393401, 4.629266982417666]),
+            ];
+
+            assert_eq!(evolve(false), expected_population);
+        }
+
+        #[test]
+        fn distinct_parents_are_the_default() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Identical chromosomes would hide which parent a gene came from
+            let population: Vec<_> = (0..4)
+                .map(|idx| TestIndividual::create(vec![idx as Float + 1.0; 3].into_iter().collect()))
+                .collect();
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.0, 0.0),
+            );
+
+            for _ in 0..20 {
+                let evolution = ga.evolve_traced(&mut rng, &population, 0.0).unwrap();
+
+                for &(a, b) in &evolution.parents {
+                    assert_ne!(a, b);
+                }
+            }
+
+            // ... whereas, with self mating, some individuals mate with
+            // themselves
+            let ga = ga.with_self_mating(true);
+
+            let self_mated = (0..20)
+                .flat_map(|_| ga.evolve_traced(&mut rng, &population, 0.0).unwrap().parents)
+                .filter(|(a, b)| a == b)
+                .count();
+
+            assert!(self_mated > 0);
+        }
+    }
+
+    #[test]
+    fn statistics_display() {
+        let stats = Statistics {
+            min_fitness: 1.0,
+            max_fitness: 12.5,
+            avg_fitness: 4.5,
+        };
+
+        assert_eq!(stats.to_string(), "min=1.00, max=12.50, avg=4.50");
+    }
+
+    mod statistics_builder {
+        use super::*;
+
+        #[test]
+        #[should_panic(expected = "without observing any fitness")]
+        fn finishing_empty_builder_panics() {
+            StatisticsBuilder::default().finish();
+        }
+
+        #[test]
+        fn merging_matches_observing_everything() {
+            let fitnesses = [3.0, 1.0, 4.0, 1.5, 9.0, 2.5];
+
+            let mut a = StatisticsBuilder::default();
+            let mut b = StatisticsBuilder::default();
+            let mut all = StatisticsBuilder::default();
+
+            for (idx, &fitness) in fitnesses.iter().enumerate() {
+                if idx < 2 {
+                    a.observe(fitness);
+                } else {
+                    b.observe(fitness);
+                }
+
+                all.observe(fitness);
+            }
+
+            a.merge(b);
+
+            let (merged, all) = (a.finish(), all.finish());
+
+            assert_eq!(merged.min_fitness, all.min_fitness);
+            assert_eq!(merged.max_fitness, all.max_fitness);
+            assert_eq!(merged.avg_fitness, all.avg_fitness);
+        }
+
+        #[test]
+        fn merging_into_empty_builder() {
+            let mut a = StatisticsBuilder::default();
+            let mut b = StatisticsBuilder::default();
+            b.observe(-2.0);
+            b.observe(5.0);
+
+            a.merge(b);
+            a.merge(StatisticsBuilder::default());
+
+            let stats = a.finish();
+
+            assert_eq!((stats.min_fitness, stats.max_fitness, stats.avg_fitness), (-2.0, 5.0, 1.5));
+        }
+    }
+
+    mod evolve_traced {
+        use super::*;
+
+        #[test]
+        fn parents_point_at_the_selected_individuals() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Individuals have chromosomes of 0.0s, 1.0s, 2.0s etc., so
+            // it's easy to tell which parent each gene came from
+            let population: Vec<_> = (0..5)
+                .map(|idx| TestIndividual::WithChromosome {
+                    chromosome: vec![idx as Float; 3].into_iter().collect(),
+                })
+                .collect();
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.0, 0.0),
+            );
+
+            let evolution = ga.evolve_traced(&mut rng, &population, 0.0).unwrap();
+
+            assert_eq!(evolution.parents.len(), 5);
+
+            for (child, &(a, b)) in evolution.population.iter().zip(&evolution.parents) {
+                // The first individual has a fitness of zero
+                assert!(a != 0 && b != 0);
+
+                for (gene, (&gene_a, &gene_b)) in child
+                    .chromosome()
+                    .iter()
+                    .zip(population[a].chromosome().iter().zip(population[b].chromosome().iter()))
+                {
+                    assert!(*gene == gene_a || *gene == gene_b);
+                }
+            }
+        }
+    }
+
+    mod brood_size {
+        use super::*;
+        use std::collections::HashSet;
+
+        fn check(n: usize) {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let population: Vec<_> = (0..n)
+                .map(|idx| TestIndividual::WithChromosome {
+                    chromosome: vec![idx as Float; 3].into_iter().collect(),
+                })
+                .collect();
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_brood_size(4);
+
+            let evolution = ga.evolve_traced(&mut rng, &population, 0.0).unwrap();
+
+            assert_eq!(evolution.population.len(), n);
+            assert_eq!(evolution.parents.len(), n);
+
+            for brood in evolution.parents.chunks(4) {
+                assert!(brood.iter().all(|pair| *pair == brood[0]));
+            }
+
+            let pairs: HashSet<_> = evolution.parents.iter().collect();
+
+            assert_eq!(pairs.len(), n.div_ceil(4));
+        }
+
+        #[test]
+        fn each_pair_gets_a_brood() {
+            check(20);
+        }
+
+        #[test]
+        fn last_brood_gets_truncated() {
+            check(23);
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_empty_broods() {
+            GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_brood_size(0);
+        }
+    }
+
+    mod paired_offspring {
+        use super::*;
+
+        fn parents(len: usize) -> (Chromosome, Chromosome) {
+            (
+                (1..=len).map(|n| n as Float).collect(),
+                (1..=len).map(|n| -(n as Float)).collect(),
+            )
+        }
+
+        /// Asserts that, at each position, one child got `parent_a`'s gene
+        /// and the other one got `parent_b`'s.
+        fn assert_complementary(children: (&Chromosome, &Chromosome), parents: (&Chromosome, &Chromosome)) {
+            let (child_a, child_b) = children;
+            let (parent_a, parent_b) = parents;
+
+            assert_eq!(child_a.len(), parent_a.len());
+            assert_eq!(child_b.len(), parent_a.len());
+
+            for idx in 0..parent_a.len() {
+                let (a, b) = (parent_a[idx], parent_b[idx]);
+
+                assert!(
+                    (child_a[idx], child_b[idx]) == (a, b) || (child_a[idx], child_b[idx]) == (b, a),
+                    "gene {}: {:?} vs {:?}",
+                    idx,
+                    (child_a[idx], child_b[idx]),
+                    (a, b),
+                );
+            }
+        }
+
+        #[test]
+        fn uniform_children_are_complements() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents(1000);
+
+            for bias in [0.5, 0.9] {
+                let (child_a, child_b) = UniformCrossover::with_bias(bias)
+                    .crossover_pair(&mut rng, &parent_a, &parent_b)
+                    .unwrap();
+
+                assert_complementary((&child_a, &child_b), (&parent_a, &parent_b));
+
+                // ... still favoring parent_a in the first child
+                let from_a = child_a.iter().filter(|gene| **gene > 0.0).count();
+                assert!(from_a > 400, "{}", from_a);
+            }
+        }
+
+        #[test]
+        fn first_child_matches_single_crossover() {
+            let (parent_a, parent_b) = parents(50);
+
+            let single = UniformCrossover
+                .crossover(&mut ChaCha8Rng::from_seed(Default::default()), &parent_a, &parent_b)
+                .unwrap();
+
+            let (first, _) = UniformCrossover
+                .crossover_pair(&mut ChaCha8Rng::from_seed(Default::default()), &parent_a, &parent_b)
+                .unwrap();
+
+            assert!(first.iter().eq(single.iter()));
+        }
+
+        #[test]
+        fn point_children_are_complements() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents(50);
+
+            for _ in 0..20 {
+                let (child_a, child_b) = SinglePointCrossover.crossover_pair(&mut rng, &parent_a, &parent_b).unwrap();
+                assert_complementary((&child_a, &child_b), (&parent_a, &parent_b));
+
+                let (child_a, child_b) = KPointCrossover::new(4).crossover_pair(&mut rng, &parent_a, &parent_b).unwrap();
+                assert_complementary((&child_a, &child_b), (&parent_a, &parent_b));
+                assert!(child_b[0] < 0.0);
+            }
+        }
+
+        #[test]
+        fn default_pair_swaps_the_parents() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents(3);
+
+            let (child_a, child_b) = ArithmeticCrossover::new(0.75)
+                .crossover_pair(&mut rng, &parent_a, &parent_b)
+                .unwrap();
+
+            assert_eq!(child_a, [0.5, 1.0, 1.5].into_iter().collect());
+            assert_eq!(child_b, [-0.5, -1.0, -1.5].into_iter().collect());
+
+            let (short, _) = parents(2);
+
+            assert_eq!(
+                BlxAlphaCrossover::new(0.5).crossover_pair(&mut rng, &parent_a, &short).unwrap_err(),
+                CrossoverError::LengthMismatch { a: 3, b: 2 },
+            );
+        }
+
+        fn evolve(n: usize, order: OperatorOrder) -> (Vec<TestIndividual>, Evolution<TestIndividual>) {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let population: Vec<_> = (0..16)
+                .map(|idx| TestIndividual::WithChromosome {
+                    chromosome: (0..8).map(|gene| (100 * idx + gene) as Float).collect(),
+                })
+                .collect();
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.0, 0.0),
+            )
+            .with_operator_order(order)
+            .with_paired_offspring(true);
+
+            let evolution = ga.evolve_to(&mut rng, &population, n).unwrap();
+
+            (population, evolution)
+        }
+
+        #[test]
+        fn twins_fill_consecutive_slots() {
+            for order in [OperatorOrder::CrossoverFirst, OperatorOrder::MutationFirst] {
+                // Odd size - the last twin gets discarded
+                let (population, evolution) = evolve(15, order);
+
+                assert_eq!(evolution.population.len(), 15);
+                assert_eq!(evolution.parents.len(), 15);
+
+                for (children, parents) in evolution.population.chunks(2).zip(evolution.parents.chunks(2)) {
+                    assert!(parents.iter().all(|pair| *pair == parents[0]));
+
+                    if let [child_a, child_b] = children {
+                        let (idx_a, idx_b) = parents[0];
+
+                        assert_complementary(
+                            (child_a.chromosome(), child_b.chromosome()),
+                            (population[idx_a].chromosome(), population[idx_b].chromosome()),
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn brood_holds_twice_as_many_children() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let population: Vec<_> = (0..20)
+                .map(|idx| TestIndividual::WithChromosome {
+                    chromosome: vec![idx as Float; 3].into_iter().collect(),
+                })
+                .collect();
+
+            let evolution = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_brood_size(3)
+            .with_paired_offspring(true)
+            .evolve_traced(&mut rng, &population, 0.0)
+            .unwrap();
+
+            assert_eq!(evolution.population.len(), 20);
+
+            for brood in evolution.parents.chunks(6) {
+                assert!(brood.iter().all(|pair| *pair == brood[0]));
+            }
+        }
+    }
+
+    mod diagnostics {
+        use super::*;
+
+        fn diagnose(chance: f32) -> EvolveDiagnostics {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Distinct genes everywhere, so that it's always clear which
+            // parent a gene came from
+            let population: Vec<_> = (1..=20)
+                .map(|idx| TestIndividual::WithChromosome {
+                    chromosome: (0..50).map(|gene| (idx * 100 + gene) as Float).collect(),
+                })
+                .collect();
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(chance, 0.5),
+            );
+
+            ga.evolve_traced(&mut rng, &population, 0.0).unwrap().diagnostics
+        }
+
+        #[test]
+        fn full_mutation_touches_every_gene() {
+            let diagnostics = diagnose(1.0);
+
+            assert_eq!(diagnostics.genes, 20 * 50);
+            assert_eq!(diagnostics.genes_touched, diagnostics.genes);
+            assert!(diagnostics.total_delta > 0.0);
+        }
+
+        #[test]
+        fn no_mutation_touches_nothing() {
+            let diagnostics = diagnose(0.0);
+
+            assert_eq!(diagnostics.genes_touched, 0);
+            assert_eq!(diagnostics.total_delta, 0.0);
+        }
+
+        #[test]
+        fn uniform_crossover_inherits_half_from_each_parent() {
+            let diagnostics = diagnose(0.0);
+            let mean = diagnostics.inheritance.iter().sum::<f32>() / diagnostics.inheritance.len() as f32;
+
+            assert_eq!(diagnostics.inheritance.len(), 20);
+            approx::assert_relative_eq!(mean, 0.5, epsilon = 0.05);
+        }
+
+        #[test]
+        fn selection_differential_of_given_parents() {
+            let fitnesses = [1.0, 2.0, 3.0, 4.0];
+
+            assert_eq!(selection_differential(&fitnesses, &[(2, 3), (3, 2)]), 1.0);
+            assert_eq!(selection_differential(&fitnesses, &[(0, 0), (3, 3)]), 0.0);
+            assert_eq!(selection_differential(&fitnesses, &[(0, 1)]), -1.0);
+            assert_eq!(selection_differential(&fitnesses, &[]), 0.0);
+        }
+
+        #[test]
+        fn uniform_selection_has_no_differential() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population: Vec<_> = (0..10).map(|idx| TestIndividual::create([idx as Float].into_iter().collect())).collect();
+
+            let ga = GeneticAlgorithm::new(TournamentSelection::new(1), UniformCrossover, GaussianMutation::new(0.0, 0.0));
+
+            let mean = (0..200)
+                .map(|_| ga.evolve_traced(&mut rng, &population, 0.0).unwrap().diagnostics.selection_differential)
+                .sum::<Float>()
+                / 200.0;
+
+            approx::assert_relative_eq!(mean, 0.0, epsilon = 0.1);
+        }
+
+        #[test]
+        fn truncation_selection_has_exact_differential() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population: Vec<_> = [1.0, 4.0, 2.0, 3.0].into_iter().map(|fitness| TestIndividual::create([fitness].into_iter().collect())).collect();
+
+            let ga = GeneticAlgorithm::new(TruncationSelection::new(0.5), UniformCrossover, GaussianMutation::new(0.0, 0.0));
+
+            // Every pair consists of the top two (3.0 and 4.0), so parents
+            // average 3.5 - against the population's 2.5
+            for _ in 0..20 {
+                assert_eq!(ga.evolve_traced(&mut rng, &population, 0.0).unwrap().diagnostics.selection_differential, 1.0);
+            }
+        }
+
+        #[test]
+        fn diagnostics_dont_affect_evolution() {
+            let population: Vec<_> = (1..=10)
+                .map(|idx| TestIndividual::WithChromosome {
+                    chromosome: vec![idx as Float; 5].into_iter().collect(),
+                })
+                .collect();
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            );
+
+            let (plain, _) = ga.evolve(&mut ChaCha8Rng::from_seed(Default::default()), &population).unwrap();
+            let traced = ga.evolve_traced(&mut ChaCha8Rng::from_seed(Default::default()), &population, 0.0).unwrap();
+
+            assert_eq!(plain, traced.population);
+        }
+
+        #[test]
+        fn report_diffs_chromosomes() {
+            let before: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+            let after: Chromosome = vec![1.5, 2.0, 2.0].into_iter().collect();
+
+            assert_eq!(
+                MutationReport::diff(&before, &after),
+                MutationReport {
+                    genes_touched: 2,
+                    total_delta: 1.5,
+                },
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod novelty_selection {
+        use super::*;
+
+        fn individual(genes: &[Float]) -> TestIndividual {
+            TestIndividual::create(genes.iter().copied().collect())
+        }
+
+        /// Two tight clusters of fit individuals, plus an unfit outlier far
+        /// away from both of them (last).
+        fn population() -> Vec<TestIndividual> {
+            let jitter = |idx: usize| (idx % 5) as Float * 0.02;
+
+            (0..10)
+                .map(|idx| individual(&[5.0 + jitter(idx), 5.0 - jitter(idx)]))
+                .chain((0..9).map(|idx| individual(&[9.0 - jitter(idx), 9.0 + jitter(idx)])))
+                .chain([individual(&[0.0, 0.5])])
+                .collect()
+        }
+
+        fn counts<S: SelectionMethod>(method: S, population: &[TestIndividual]) -> Vec<usize> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut counts = vec![0; population.len()];
+
+            for _ in 0..1000 {
+                counts[method.select_index(&mut rng, population)] += 1;
+            }
+
+            counts
+        }
+
+        #[test]
+        fn outlier_gets_selected_despite_its_fitness() {
+            let population = population();
+            let outlier = population.len() - 1;
+
+            assert!(counts(RouletteWheelSelection, &population)[outlier] < 10);
+            assert!(counts(NoveltySelection::new(3), &population)[outlier] > 700);
+        }
+
+        #[test]
+        fn novelty_is_average_distance_to_nearest_neighbours() {
+            let population = [individual(&[0.0, 0.0]), individual(&[3.0, 4.0]), individual(&[0.0, 1.0])];
+            let far = (18.0 as Float).sqrt();
+
+            assert_eq!(NoveltySelection::new(1).novelty(&population), [1.0, far, 1.0]);
+
+            // Fewer neighbours than k - all of them count
+            assert_eq!(
+                NoveltySelection::new(5).novelty(&population),
+                [3.0, (5.0 + far) / 2.0, (1.0 + far) / 2.0],
+            );
+
+            assert_eq!(NoveltySelection::new(5).novelty(&population[..1]), [0.0]);
+        }
+
+        #[test]
+        fn identical_genomes_get_selected_uniformly() {
+            let population = vec![individual(&[1.0, 2.0]); 4];
+            let counts = counts(NoveltySelection::new(2), &population);
+
+            assert!(counts.iter().all(|&count| (200..=300).contains(&count)), "{:?}", counts);
+        }
+
+        #[test]
+        #[should_panic(expected = "chromosomes must have the same length")]
+        fn rejects_chromosomes_of_different_lengths() {
+            NoveltySelection::new(2).novelty(&[individual(&[1.0, 2.0]), individual(&[1.0])]);
+        }
+    }
+
+    mod repair {
+        use super::*;
+        use std::cell::Cell;
+
+        /// Counts how many times it's been called.
+        #[derive(Default)]
+        struct CountingRepair {
+            repairs: Cell<usize>,
+        }
+
+        impl RepairMethod for &CountingRepair {
+            fn repair(&self, _child: &mut Chromosome) {
+                self.repairs.set(self.repairs.get() + 1);
+            }
+        }
+
+        /// BLX-0.5 - each gene gets picked from the range spanned by the
+        /// parents' genes, extended by half of its length on each side; so
+        /// children can end up outside of their parents' range.
+        struct BlendCrossover;
+
+        impl CrossoverMethod for BlendCrossover {
+            fn crossover(
+                &self,
+                rng: &mut dyn RngCore,
+                parent_a: &Chromosome,
+                parent_b: &Chromosome,
+            ) -> Result<Chromosome, CrossoverError> {
+                Ok(parent_a
+                    .iter()
+                    .zip(parent_b.iter())
+                    .map(|(&a, &b)| a + (b - a) * rng.gen_range(-0.5..=1.5))
+                    .collect())
+            }
+        }
+
+        fn population() -> Vec<TestIndividual> {
+            (0..20)
+                .map(|idx| TestIndividual::create((0..4).map(|gene| ((idx * 7 + gene * 3) % 11) as Float / 10.0).collect()))
+                .collect()
+        }
+
+        fn genes(chromosome: &Chromosome) -> Vec<Float> {
+            chromosome.iter().copied().collect()
+        }
+
+        #[test]
+        fn repairs_each_child_once() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let repair = CountingRepair::default();
+
+            for (mode, order) in [
+                (ReproductionMode::Sexual, OperatorOrder::CrossoverFirst),
+                (ReproductionMode::Sexual, OperatorOrder::MutationFirst),
+                (ReproductionMode::Asexual, OperatorOrder::CrossoverFirst),
+            ] {
+                repair.repairs.set(0);
+
+                let ga = GeneticAlgorithm::new(RouletteWheelSelection, UniformCrossover, GaussianMutation::new(0.5, 0.1))
+                    .with_reproduction(mode)
+                    .with_operator_order(order)
+                    .with_brood_size(3)
+                    .with_repair(&repair);
+
+                ga.evolve_to(&mut rng, &population(), 25).unwrap();
+                assert_eq!(repair.repairs.get(), 25);
+
+                ga.breed(&mut rng, population()[0].chromosome(), population()[1].chromosome()).unwrap();
+                assert_eq!(repair.repairs.get(), 26);
+            }
+        }
+
+        #[test]
+        fn clamp_repair_keeps_bounded_genes_in_bounds() {
+            fn children(repair: impl RepairMethod) -> Vec<Chromosome> {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+                let (children, _) =
+                    GeneticAlgorithm::new(RouletteWheelSelection, BlendCrossover, GaussianMutation::new(0.0, 0.0))
+                        .with_repair(repair)
+                        .evolve(&mut rng, &population()).unwrap();
+
+                children.iter().map(|child| child.chromosome().clone()).collect()
+            }
+
+            let bounds = GeneBounds::new(vec![0.0..=1.0, 0.2..=0.8]);
+            let unrepaired = children(NoRepair);
+            let repaired = children(ClampRepair(bounds.clone()));
+
+            // Without the repair, blending strays out of bounds ...
+            assert!(!unrepaired.iter().all(|child| bounds.contain(child)));
+
+            // ... with it, it doesn't - while unbounded genes stay as they
+            // were blended
+            for (unrepaired, repaired) in unrepaired.iter().zip(&repaired) {
+                assert!(bounds.contain(repaired));
+                assert_eq!(genes(unrepaired)[2..], genes(repaired)[2..]);
+            }
+        }
+
+        #[test]
+        fn mutation_first_mutates_parents_before_crossover() {
+            let mutation = GaussianMutation::new(0.5, 0.5);
+            let (parent_a, parent_b) = (population()[0].chromosome().clone(), population()[1].chromosome().clone());
+
+            let breed = |order: OperatorOrder| {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+                GeneticAlgorithm::new(RouletteWheelSelection, UniformCrossover, mutation.clone())
+                    .with_operator_order(order)
+                    .breed(&mut rng, &parent_a, &parent_b).unwrap()
+            };
+
+            let expected = {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let (mut mutated_a, mut mutated_b) = (parent_a.clone(), parent_b.clone());
+
+                mutation.mutate(&mut rng, &mut mutated_a);
+                mutation.mutate(&mut rng, &mut mutated_b);
+                UniformCrossover.crossover(&mut rng, &mutated_a, &mutated_b).unwrap()
+            };
+
+            assert_eq!(genes(&breed(OperatorOrder::MutationFirst)), genes(&expected));
+            assert_ne!(genes(&breed(OperatorOrder::CrossoverFirst)), genes(&expected));
+
+            // ... which changes how the whole population evolves, too
+            let evolve = |order: OperatorOrder| {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+                let (children, _) = GeneticAlgorithm::new(RouletteWheelSelection, UniformCrossover, mutation.clone())
+                    .with_operator_order(order)
+                    .evolve(&mut rng, &population()).unwrap();
+
+                children.iter().map(|child| genes(child.chromosome())).collect::<Vec<_>>()
+            };
+
+            assert_ne!(evolve(OperatorOrder::MutationFirst), evolve(OperatorOrder::CrossoverFirst));
+        }
+    }
+
+    mod fitness_evaluation {
+        use super::*;
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        /// Individual that counts how many times its fitness gets computed.
+        struct Counting {
+            chromosome: Chromosome,
+            evaluations: Rc<Cell<usize>>,
+        }
+
+        impl Individual for Counting {
+            fn fitness(&self) -> Float {
+                self.evaluations.set(self.evaluations.get() + 1);
+                self.chromosome.iter().sum()
+            }
+
+            fn chromosome(&self) -> &Chromosome {
+                &self.chromosome
+            }
+
+            fn create(chromosome: Chromosome) -> Self {
+                Self {
+                    chromosome,
+                    evaluations: Default::default(),
+                }
+            }
+        }
+
+        fn assert_evaluated_once<S: SelectionMethod>(ga: GeneticAlgorithm<S, UniformCrossover, GaussianMutation>) {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut population: Vec<_> = (0..10)
+                .map(|idx| Counting::create((0..3).map(|gene| (idx + gene) as Float).collect()))
+                .collect();
+
+            for _ in 0..5 {
+                let evolution = ga.evolve_traced(&mut rng, &population, 0.5).unwrap();
+
+                for individual in &population {
+                    assert_eq!(individual.evaluations.get(), 1);
+                }
+
+                population = evolution.population;
+            }
+        }
+
+        fn ga<S: SelectionMethod>(method: S) -> GeneticAlgorithm<S, UniformCrossover, GaussianMutation> {
+            GeneticAlgorithm::new(method, UniformCrossover, GaussianMutation::new(0.5, 0.5))
+        }
+
+        #[test]
+        fn each_individual_is_evaluated_once_per_generation() {
+            assert_evaluated_once(ga(RouletteWheelSelection));
+            assert_evaluated_once(ga(TournamentSelection::new(3)));
+            assert_evaluated_once(ga(TruncationSelection::new(0.5)));
+            assert_evaluated_once(ga(LinearRankSelection::new(1.5)));
+            assert_evaluated_once(ga(StochasticUniversalSampling));
+            assert_evaluated_once(ga(RouletteWheelSelection).with_brood_size(3));
+
+            #[cfg(feature = "std")]
+            assert_evaluated_once(ga(SigmaScaledSelection::new(RouletteWheelSelection)));
+        }
+    }
+
+    mod dyn_operators {
+        use super::*;
+
+        type DynGeneticAlgorithm =
+            GeneticAlgorithm<Box<dyn DynSelectionMethod>, Box<dyn CrossoverMethod>, Box<dyn MutationMethod>>;
+
+        /// What picking operators out of e.g. a configuration file could
+        /// look like.
+        fn by_name(selection: &str, crossover: &str, mutation: &str) -> Option<DynGeneticAlgorithm> {
+            let selection: Box<dyn DynSelectionMethod> = match selection {
+                "roulette" => Box::new(RouletteWheelSelection),
+                "tournament" => Box::new(TournamentSelection::new(2)),
+                "sus" => Box::new(StochasticUniversalSampling),
+                _ => return None,
+            };
+
+            let crossover: Box<dyn CrossoverMethod> = match crossover {
+                "uniform" => Box::new(UniformCrossover),
+                _ => return None,
+            };
+
+            let mutation: Box<dyn MutationMethod> = match mutation {
+                "gaussian" => Box::new(GaussianMutation::new(0.5, 0.5)),
+                "none" => Box::new(GaussianMutation::new(0.0, 0.0)),
+                _ => return None,
+            };
+
+            Some(GeneticAlgorithm::new(selection, crossover, mutation))
+        }
+
+        fn population() -> Vec<TestIndividual> {
+            (0..8)
+                .map(|idx| TestIndividual::create((0..3).map(|gene| ((idx * 3 + gene) % 5) as Float).collect()))
+                .collect()
+        }
+
+        fn evolve<S, C, M>(ga: &GeneticAlgorithm<S, C, M>) -> Evolution<TestIndividual>
+        where
+            S: SelectionMethod,
+            C: CrossoverMethod,
+            M: MutationMethod,
+        {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut evolution = ga.evolve_traced(&mut rng, &population(), 0.0).unwrap();
+
+            for _ in 0..4 {
+                evolution = ga.evolve_traced(&mut rng, &evolution.population, 0.0).unwrap();
+            }
+
+            evolution
+        }
+
+        fn assert_same<S, C, M>(boxed: &DynGeneticAlgorithm, ga: &GeneticAlgorithm<S, C, M>)
+        where
+            S: SelectionMethod,
+            C: CrossoverMethod,
+            M: MutationMethod,
+        {
+            let (boxed, evolution) = (evolve(boxed), evolve(ga));
+
+            assert_eq!(boxed.population, evolution.population);
+            assert_eq!(boxed.parents, evolution.parents);
+            assert_eq!(boxed.diagnostics, evolution.diagnostics);
+        }
+
+        #[test]
+        fn boxed_operators_evolve_like_static_ones() {
+            assert_same(
+                &by_name("roulette", "uniform", "gaussian").unwrap(),
+                &GeneticAlgorithm::new(RouletteWheelSelection, UniformCrossover, GaussianMutation::new(0.5, 0.5)),
+            );
+
+            assert_same(
+                &by_name("tournament", "uniform", "none").unwrap(),
+                &GeneticAlgorithm::new(TournamentSelection::new(2), UniformCrossover, GaussianMutation::new(0.0, 0.0)),
+            );
+
+            // Batched selection stays batched, even when boxed
+            assert_same(
+                &by_name("sus", "uniform", "gaussian").unwrap(),
+                &GeneticAlgorithm::new(StochasticUniversalSampling, UniformCrossover, GaussianMutation::new(0.5, 0.5)),
+            );
+        }
+
+        /// Asserts that `selection`, once boxed, evolves just like it does
+        /// when it's not.
+        fn assert_boxes<S>(selection: impl Fn() -> S)
+        where
+            S: SelectionMethod + 'static,
+        {
+            let boxed: DynGeneticAlgorithm = GeneticAlgorithm::new(
+                Box::new(selection()),
+                Box::new(UniformCrossover),
+                Box::new(GaussianMutation::new(0.5, 0.5)),
+            );
+
+            assert_same(
+                &boxed,
+                &GeneticAlgorithm::new(selection(), UniformCrossover, GaussianMutation::new(0.5, 0.5)),
+            );
+        }
+
+        #[test]
+        fn every_built_in_selection_can_be_boxed() {
+            assert_boxes(|| RouletteWheelSelection);
+            assert_boxes(|| TournamentSelection::new(3));
+            assert_boxes(|| TruncationSelection::new(0.5));
+            assert_boxes(|| LinearRankSelection::new(1.5));
+            assert_boxes(|| MixedSelection::new(RouletteWheelSelection, TournamentSelection::new(2), 0.5));
+            assert_boxes(|| StochasticUniversalSampling);
+
+            #[cfg(feature = "std")]
+            {
+                assert_boxes(|| BoltzmannSelection::new(2.0));
+                assert_boxes(|| SigmaScaledSelection::new(RouletteWheelSelection));
+
+                // ... including the ones that look at chromosomes
+                assert_boxes(|| NoveltySelection::new(2));
+            }
+        }
+
+        #[test]
+        fn boxed_selection_keeps_overrides() {
+            let boxed: Box<dyn DynSelectionMethod> = Box::new(StochasticUniversalSampling);
+            assert!(boxed.is_batched());
+
+            let boxed: Box<dyn DynSelectionMethod> = Box::new(RouletteWheelSelection);
+            assert!(!boxed.is_batched());
+
+            // (boxed methods see chromosomes, too)
+            let population = [0.0, 5.0].map(|fitness| TestIndividual::create([fitness].into_iter().collect()));
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            for _ in 0..100 {
+                let (a, b) = boxed.select_pair(&mut rng, &population);
+                assert_ne!(a, b);
+            }
+        }
+
+        #[test]
+        fn unknown_names() {
+            assert!(by_name("roulette", "uniform", "gaussian").is_some());
+            assert!(by_name("lottery", "uniform", "gaussian").is_none());
+            assert!(by_name("roulette", "single-point", "gaussian").is_none());
+        }
+    }
+
+    mod select_index {
+        use super::*;
+
+        fn population() -> Vec<TestIndividual> {
+            [2.0, 1.0, 4.0, 3.0].into_iter().map(TestIndividual::new).collect()
+        }
+
+        /// Asserts that `select_index()` draws just what `select()` does.
+        fn assert_matches_select<S: SelectionMethod>(method: S, population: &[TestIndividual]) {
+            let mut rng_a = ChaCha8Rng::from_seed(Default::default());
+            let mut rng_b = ChaCha8Rng::from_seed(Default::default());
+
+            for _ in 0..100 {
+                let selected = method.select(&mut rng_a, population);
+                let idx = method.select_index(&mut rng_b, population);
+
+                assert!(core::ptr::eq(selected, &population[idx]));
+            }
+        }
+
+        #[test]
+        fn indices_match_selected_individuals() {
+            assert_matches_select(RouletteWheelSelection, &population());
+            assert_matches_select(RouletteWheelSelection, &[TestIndividual::new(0.0), TestIndividual::new(0.0)]);
+            assert_matches_select(TournamentSelection::new(2), &population());
+            assert_matches_select(TruncationSelection::new(0.5), &population());
+        }
+
+        #[test]
+        fn roulette_indices_follow_fitness() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population();
+            let mut counts = [0; 4];
+
+            for _ in 0..1000 {
+                counts[RouletteWheelSelection.select_index(&mut rng, &population)] += 1;
+            }
+
+            // Same draws as in `roulette_wheel_selection()`
+            #[cfg(not(feature = "precision-f64"))]
+            assert_eq!(counts, [202, 98, 422, 278]);
+
+            assert!(counts[1] < counts[0] && counts[0] < counts[3] && counts[3] < counts[2]);
+        }
+    }
+
+    mod prepared_selection {
+        use super::*;
+
+        fn population(fitnesses: &[Float]) -> Vec<TestIndividual> {
+            fitnesses.iter().copied().map(TestIndividual::new).collect()
+        }
+
+        /// Asserts that `prepare()`-d selection draws just what the method
+        /// itself does.
+        fn assert_matches_method<S: SelectionMethod>(method: S, population: &[TestIndividual]) {
+            let mut rng_a = ChaCha8Rng::from_seed(Default::default());
+            let mut rng_b = ChaCha8Rng::from_seed(Default::default());
+            let prepared = method.prepare(population);
+
+            for _ in 0..100 {
+                assert_eq!(
+                    method.select_index(&mut rng_a, population),
+                    prepared.select_index(&mut rng_b)
+                );
+
+                assert_eq!(method.select_pair(&mut rng_a, population), prepared.select_pair(&mut rng_b));
+            }
+        }
+
+        #[test]
+        fn prepared_selection_matches_method() {
+            assert_matches_method(RouletteWheelSelection, &population(&[2.0, 1.0, 4.0, 3.0]));
+            assert_matches_method(RouletteWheelSelection, &population(&[0.0, 0.0, 0.0]));
+            assert_matches_method(RouletteWheelSelection, &population(&[5.0, 0.0, 0.0]));
+            assert_matches_method(RouletteWheelSelection, &population(&[1.0]));
+            assert_matches_method(TournamentSelection::new(2), &population(&[2.0, 1.0, 4.0, 3.0]));
+            assert_matches_method(LinearRankSelection::new(1.5), &population(&[2.0, 1.0, 4.0, 3.0]));
+            assert_matches_method(LinearRankSelection::new(2.0), &population(&[2.0, 1.0, 2.0, 3.0, 1.0]));
+            assert_matches_method(LinearRankSelection::new(1.0), &population(&[1.0]));
+            assert_matches_method(TruncationSelection::new(0.5), &population(&[2.0, 1.0, 4.0, 3.0, 4.0]));
+            assert_matches_method(TruncationSelection::new(0.1), &population(&[2.0, 1.0, 4.0]));
+            assert_matches_method(
+                MixedSelection::new(RouletteWheelSelection, TournamentSelection::new(2), 0.7),
+                &population(&[2.0, 1.0, 4.0, 3.0]),
+            );
+            assert_matches_method(
+                MixedSelection::new(LinearRankSelection::new(1.5), TruncationSelection::new(0.5), 0.3),
+                &population(&[2.0, 1.0, 4.0, 3.0]),
+            );
+
+            #[cfg(feature = "std")]
+            assert_matches_method(BoltzmannSelection::new(0.5), &population(&[2.0, 1.0, 4.0, 3.0]));
+
+            #[cfg(feature = "std")]
+            assert_matches_method(
+                NoveltySelection::new(2),
+                &[1.0, 2.0, 5.0, 9.0].map(|gene| TestIndividual::create([gene, -gene].into_iter().collect())),
+            );
+        }
+
+        #[test]
+        fn boxed_prepared_selection_matches_method() {
+            let boxed = |method: Box<dyn DynSelectionMethod>| method;
+            let population = [2.0, 1.0, 4.0, 3.0].map(|gene| TestIndividual::create([gene].into_iter().collect()));
+
+            assert_matches_method(boxed(Box::new(RouletteWheelSelection)), &population);
+            assert_matches_method(boxed(Box::new(TournamentSelection::new(2))), &population);
+            assert_matches_method(boxed(Box::new(LinearRankSelection::new(1.5))), &population);
+            assert_matches_method(boxed(Box::new(TruncationSelection::new(0.5))), &population);
+            assert_matches_method(
+                boxed(Box::new(MixedSelection::new(RouletteWheelSelection, TournamentSelection::new(2), 0.7))),
+                &population,
+            );
+            assert_matches_method(
+                boxed(Box::new(MixedSelection::new(RouletteWheelSelection, TruncationSelection::new(0.5), 0.7))),
+                &population,
+            );
+
+            #[cfg(feature = "std")]
+            assert_matches_method(boxed(Box::new(BoltzmannSelection::new(0.5))), &population);
+
+            #[cfg(feature = "std")]
+            assert_matches_method(boxed(Box::new(NoveltySelection::new(2))), &population);
+        }
+
+        /// Individual that counts how many times its fitness gets asked for.
+        struct Counting {
+            chromosome: Chromosome,
+            calls: core::cell::Cell<usize>,
+        }
+
+        impl Individual for Counting {
+            fn fitness(&self) -> Float {
+                self.calls.set(self.calls.get() + 1);
+                self.chromosome[0]
+            }
+
+            fn chromosome(&self) -> &Chromosome {
+                &self.chromosome
+            }
+
+            fn create(chromosome: Chromosome) -> Self {
+                Self {
+                    chromosome,
+                    calls: Default::default(),
+                }
+            }
+        }
+
+        #[test]
+        fn boxed_roulette_scores_population_once() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population: Vec<_> = (1..=10).map(|fitness| Counting::create([fitness as Float].into_iter().collect())).collect();
+            let boxed: Box<dyn DynSelectionMethod> = Box::new(RouletteWheelSelection);
+            let prepared = boxed.prepare(&population);
+
+            for _ in 0..1000 {
+                prepared.select_index(&mut rng);
+                prepared.select_pair(&mut rng);
+            }
+
+            // ... instead of once per pick, which would make a generation
+            // take O(n²)
+            for individual in &population {
+                assert_eq!(individual.calls.get(), 1);
+            }
+        }
+
+        #[test]
+        fn mixed_prepares_inner_methods_once() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population: Vec<_> = (1..=10).map(|fitness| Counting::create([fitness as Float].into_iter().collect())).collect();
+            let mixed = MixedSelection::new(RouletteWheelSelection, LinearRankSelection::new(1.5), 0.5);
+            let prepared = mixed.prepare(&population);
+
+            for _ in 0..1000 {
+                prepared.select_index(&mut rng);
+                prepared.select_pair(&mut rng);
+            }
+
+            // Once for the roulette, once for the ranking
+            for individual in &population {
+                assert_eq!(individual.calls.get(), 2);
+            }
+        }
+
+        #[test]
+        fn truncation_ranks_population_once() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population: Vec<_> = (1..=10).map(|fitness| Counting::create([fitness as Float].into_iter().collect())).collect();
+            let truncation = TruncationSelection::new(0.3);
+            let prepared = truncation.prepare(&population);
+
+            for _ in 0..1000 {
+                assert!(prepared.select_index(&mut rng) >= 7);
+
+                let (idx_a, idx_b) = prepared.select_pair(&mut rng);
+                assert!(idx_a >= 7 && idx_b >= 7 && idx_a != idx_b);
+            }
+
+            for individual in &population {
+                assert_eq!(individual.calls.get(), 1);
+            }
+        }
+
+        #[test]
+        #[cfg(feature = "std")]
+        fn boltzmann_weighs_population_once() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population: Vec<_> = (1..=10).map(|fitness| Counting::create([fitness as Float].into_iter().collect())).collect();
+            let boltzmann = BoltzmannSelection::new(1.0);
+            let prepared = boltzmann.prepare(&population);
+
+            for _ in 0..1000 {
+                prepared.select_index(&mut rng);
+
+                let (idx_a, idx_b) = prepared.select_pair(&mut rng);
+                assert!(idx_a != idx_b);
+            }
+
+            // Once for the max fitness, once for the weights
+            for individual in &population {
+                assert_eq!(individual.calls.get(), 2);
+            }
+        }
+
+        #[test]
+        fn linear_rank_ranks_population_once() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population: Vec<_> = (1..=10).map(|fitness| Counting::create([fitness as Float].into_iter().collect())).collect();
+            let linear_rank = LinearRankSelection::new(2.0);
+            let prepared = linear_rank.prepare(&population);
+
+            for _ in 0..1000 {
+                // Pressure of 2.0 never picks the worst individual
+                assert_ne!(prepared.select_index(&mut rng), 0);
+
+                let (idx_a, idx_b) = prepared.select_pair(&mut rng);
+                assert!(idx_a != idx_b);
+            }
+
+            for individual in &population {
+                assert_eq!(individual.calls.get(), 1);
+            }
+        }
+
+        #[test]
+        fn roulette_select_many_matches_select() {
+            let population = population(&[2.0, 1.0, 4.0, 3.0]);
+            let mut rng_a = ChaCha8Rng::from_seed(Default::default());
+            let mut rng_b = ChaCha8Rng::from_seed(Default::default());
+
+            let many = RouletteWheelSelection.select_many(&mut rng_a, &population, 1000);
+
+            let one_by_one: Vec<_> = (0..1000)
+                .map(|_| RouletteWheelSelection.select_index(&mut rng_b, &population))
+                .collect();
+
+            assert_eq!(many, one_by_one);
+        }
+    }
+
+    mod select_pair {
+        use super::*;
+
+        /// Always selects the first individual.
+        struct Stubborn;
+
+        impl SelectionMethod for Stubborn {
+            fn select_index<I>(&self, _: &mut dyn RngCore, _: &[I]) -> usize
+            where
+                I: Individual,
+            {
+                0
+            }
+        }
+
+        fn assert_distinct<S: SelectionMethod>(method: S, population: &[TestIndividual]) {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            for _ in 0..1000 {
+                let (a, b) = method.select_pair(&mut rng, population);
+                assert_ne!(a, b);
+            }
+        }
+
+        #[test]
+        fn pairs_are_distinct() {
+            let population = vec![TestIndividual::new(1.0), TestIndividual::new(50.0), TestIndividual::new(0.0)];
+
+            assert_distinct(RouletteWheelSelection, &population);
+            assert_distinct(TournamentSelection::new(3), &population);
+            #[cfg(feature = "std")]
+            assert_distinct(BoltzmannSelection::new(0.01), &population);
+            assert_distinct(TruncationSelection::new(0.3), &population);
+            assert_distinct(Stubborn, &population);
+        }
+
+        #[test]
+        fn roulette_picks_others_by_fitness() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = vec![TestIndividual::new(1.0), TestIndividual::new(50.0), TestIndividual::new(0.0)];
+            let mut counts = [0; 3];
+
+            for _ in 0..1000 {
+                let (_, b) = RouletteWheelSelection.select_pair(&mut rng, &population);
+                counts[b] += 1;
+            }
+
+            // Whenever parent_a is the fittest one, parent_b has to be the
+            // only other one with any fitness
+            assert_eq!(counts[2], 0);
+            assert!(counts[0] > 400, "counts = {:?}", counts);
+
+            // ... even when nobody else has any
+            let population = vec![TestIndividual::new(0.0), TestIndividual::new(1.0), TestIndividual::new(0.0)];
+            assert_distinct(RouletteWheelSelection, &population);
+        }
+
+        #[test]
+        fn single_individual_mates_with_itself() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = vec![TestIndividual::new(1.0)];
+
+            assert_eq!(RouletteWheelSelection.select_pair(&mut rng, &population), (0, 0));
+            assert_eq!(Stubborn.select_pair(&mut rng, &population), (0, 0));
+        }
+
+        fn self_matings<S: SelectionMethod>(ga: GeneticAlgorithm<S, UniformCrossover, GaussianMutation>) -> usize {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let population = vec![
+                TestIndividual::create(Chromosome::from_iter([1.0])),
+                TestIndividual::create(Chromosome::from_iter([8.0])),
+                TestIndividual::create(Chromosome::from_iter([2.0])),
+            ];
+
+            (0..100)
+                .flat_map(|_| ga.evolve_traced(&mut rng, &population, 0.0).unwrap().parents)
+                .filter(|(a, b)| a == b)
+                .count()
+        }
+
+        fn ga<S: SelectionMethod>(method: S) -> GeneticAlgorithm<S, UniformCrossover, GaussianMutation> {
+            GeneticAlgorithm::new(method, UniformCrossover, GaussianMutation::new(0.0, 0.0))
+        }
+
+        #[test]
+        fn evolve_selects_distinct_parents() {
+            assert_eq!(self_matings(ga(RouletteWheelSelection)), 0);
+            assert_eq!(self_matings(ga(TournamentSelection::new(2))), 0);
+            assert_eq!(self_matings(ga(StochasticUniversalSampling)), 0);
+            assert_eq!(self_matings(ga(RouletteWheelSelection).with_brood_size(2)), 0);
+        }
+
+        #[test]
+        fn self_mating_can_be_restored() {
+            assert!(self_matings(ga(RouletteWheelSelection).with_self_mating(true)) > 0);
+            assert!(self_matings(ga(StochasticUniversalSampling).with_self_mating(true)) > 0);
+        }
+    }
+
+    mod compatibility {
+        use super::*;
+
+        fn ga(policy: CompatibilityPolicy) -> GeneticAlgorithm<RouletteWheelSelection, UniformCrossover, GaussianMutation> {
+            GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.0, 0.0),
+            )
+            .with_compatibility(policy, 3)
+        }
+
+        /// Two clusters of ten individuals each, around `[10, 0, 10, 0]`
+        /// and `[0, 10, 0, 10]` - far apart, but equally fit.
+        fn population() -> Vec<TestIndividual> {
+            (0..20)
+                .map(|idx| {
+                    let center = |gene: usize| if (idx < 10) == gene.is_multiple_of(2) { 10.0 } else { 0.0 };
+
+                    TestIndividual::WithChromosome {
+                        chromosome: (0..4).map(|gene| center(gene) + (idx * 4 + gene) as Float / 100.0).collect(),
+                    }
+                })
+                .collect()
+        }
+
+        #[test]
+        fn rejecting_everything_clones_single_parents() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population();
+            let evolution = ga(CompatibilityPolicy::Predicate(|_, _| false)).evolve_traced(&mut rng, &population, 0.0).unwrap();
+
+            assert_eq!(evolution.diagnostics.asexual, 20);
+            assert_eq!(evolution.diagnostics.compatibility_retries, 20 * 3);
+
+            for (child, &(parent_a, parent_b)) in evolution.population.iter().zip(&evolution.parents) {
+                assert_eq!(parent_a, parent_b);
+                assert_eq!(child, &population[parent_a]);
+            }
+        }
+
+        #[test]
+        fn distance_threshold_keeps_clusters_apart() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let evolution = ga(CompatibilityPolicy::MaxDistance(1.0)).evolve_traced(&mut rng, &population(), 0.0).unwrap();
+
+            assert!(evolution.diagnostics.compatibility_retries > 0);
+
+            for &(parent_a, parent_b) in &evolution.parents {
+                assert_eq!(parent_a < 10, parent_b < 10);
             }
         }
-    }
-}
-
-#[derive(Clone, Debug)]
-pub struct Statistics {
-    pub min_fitness: f32,
-    pub max_fitness: f32,
-    pub avg_fitness: f32,
-}
-impl Statistics {
-    fn new<I>(population: &[I]) -> Self
-    where
-        I: Individual,
-    {
-        assert!(!population.is_empty());
 
-        let mut min_fitness = population[0].fitness();
-        let mut max_fitness = min_fitness;
-        let mut sum_fitness = 0.0;
+        #[test]
+        fn any_policy_matches_default_evolution() {
+            let population = population();
+            let (plain, _) = ga(CompatibilityPolicy::Any).evolve(&mut ChaCha8Rng::from_seed(Default::default()), &population).unwrap();
 
-        for individual in population {
-            let fitness = individual.fitness();
+            let (default, _) = GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.0, 0.0),
+            )
+            .evolve(&mut ChaCha8Rng::from_seed(Default::default()), &population).unwrap();
 
-            min_fitness = min_fitness.min(fitness);
-            max_fitness = max_fitness.max(fitness);
-            sum_fitness += fitness;
+            assert_eq!(plain, default);
         }
 
-        Self {
-            min_fitness,
-            max_fitness,
-            avg_fitness: sum_fitness / (population.len() as f32),
+        #[test]
+        fn distance_requires_equal_lengths() {
+            let a: Chromosome = vec![1.0, 2.0].into_iter().collect();
+            let b: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+
+            assert!(CompatibilityPolicy::MaxDistance(100.0).allows(&a, &a));
+            assert!(!CompatibilityPolicy::MaxDistance(100.0).allows(&a, &b));
         }
     }
-}
 
-// Testing the rand.SliceRandom and not leaving it on Developer's Trust
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rand::SeedableRng;
-    use rand_chacha::ChaCha8Rng;
+    mod evolve_to {
+        use super::*;
 
+        fn ga() -> GeneticAlgorithm<RouletteWheelSelection, UniformCrossover, GaussianMutation> {
+            GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            )
+        }
 
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum TestIndividual {
-        /// For tests that require access to the chromosome
-        WithChromosome { chromosome: Chromosome },
-        /// For tests that don't require access to the chromosome
-        WithFitness { fitness: f32 },
-    }
-    impl TestIndividual {
-        fn new(fitness: f32) -> Self {
-            Self::WithFitness { fitness }
+        fn population() -> Vec<TestIndividual> {
+            (0..6)
+                .map(|idx| TestIndividual::WithChromosome {
+                    chromosome: (0..3).map(|gene| (idx * 3 + gene) as Float).collect(),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn grows_and_shrinks_population() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population();
+
+            for size in [1, 4, 6, 15] {
+                let evolution = ga().evolve_to(&mut rng, &population, size).unwrap();
+
+                assert_eq!(evolution.population.len(), size);
+                assert_eq!(evolution.parents.len(), size);
+                assert_eq!(evolution.diagnostics.genes, size * 3);
+
+                // Statistics still describe the whole original population
+                assert_eq!(evolution.stats.min_fitness, 0.0 + 1.0 + 2.0);
+                assert_eq!(evolution.stats.max_fitness, 15.0 + 16.0 + 17.0);
+            }
+        }
+
+        #[test]
+        fn same_size_matches_evolve() {
+            let population = population();
+            let evolution = ga().evolve_to(&mut ChaCha8Rng::from_seed(Default::default()), &population, 6).unwrap();
+            let (evolved, _) = ga().evolve(&mut ChaCha8Rng::from_seed(Default::default()), &population).unwrap();
+
+            assert_eq!(evolution.population, evolved);
         }
     }
-    impl Individual for TestIndividual {
-        fn create(chromosome: Chromosome) -> Self {
-            Self::WithChromosome { chromosome }
+
+    mod reproduction_mode {
+        use super::*;
+        use core::cell::Cell;
+
+        /// Counts how many individuals have been selected.
+        #[derive(Default)]
+        struct CountingSelection {
+            calls: Cell<usize>,
         }
 
-        fn chromosome(&self) -> &Chromosome {
-            match self {
-                Self::WithChromosome { chromosome } => chromosome,
-                Self::WithFitness { .. } => {
-                    panic!("not supported for TestIndividual::WithFitness")
-                }
+        impl SelectionMethod for CountingSelection {
+            fn select_index<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> usize
+            where
+                I: Individual,
+            {
+                self.calls.set(self.calls.get() + 1);
+                RouletteWheelSelection.select_index(rng, population)
             }
         }
 
-        fn fitness(&self) -> f32 {
-            match self {
-                Self::WithChromosome { chromosome } => {
-                    chromosome.iter().sum()
-                    // ^ the simplest fitness function ever - we're just summing all the genes together
-                }
-                Self::WithFitness { fitness } => *fitness,
+        fn population() -> Vec<TestIndividual> {
+            (0..10)
+                .map(|idx| TestIndividual::WithChromosome {
+                    chromosome: (0..5).map(|gene| (idx * 5 + gene) as Float).collect(),
+                })
+                .collect()
+        }
+
+        fn ga(
+            mode: ReproductionMode,
+            mutation_chance: f32,
+        ) -> GeneticAlgorithm<CountingSelection, UniformCrossover, GaussianMutation> {
+            GeneticAlgorithm::new(
+                CountingSelection::default(),
+                UniformCrossover,
+                GaussianMutation::new(mutation_chance, 0.5),
+            )
+            .with_reproduction(mode)
+        }
+
+        #[test]
+        fn asexual_mode_selects_one_parent_per_child() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let asexual = ga(ReproductionMode::Asexual, 0.0);
+            let evolution = asexual.evolve_traced(&mut rng, &population(), 0.0).unwrap();
+
+            assert_eq!(asexual.selection_method.calls.get(), 10);
+            assert_eq!(evolution.diagnostics.asexual, 10);
+            assert!(evolution.parents.iter().all(|(a, b)| a == b));
+
+            let sexual = ga(ReproductionMode::Sexual, 0.0).with_self_mating(true);
+            sexual.evolve(&mut rng, &population()).unwrap();
+
+            assert_eq!(sexual.selection_method.calls.get(), 20);
+        }
+
+        #[test]
+        fn asexual_children_differ_only_by_mutation() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population();
+
+            let evolution = ga(ReproductionMode::Asexual, 0.0).evolve_traced(&mut rng, &population, 0.0).unwrap();
+
+            for (child, &(parent, _)) in evolution.population.iter().zip(&evolution.parents) {
+                assert_eq!(child, &population[parent]);
+            }
+
+            let evolution = ga(ReproductionMode::Asexual, 0.5).evolve_traced(&mut rng, &population, 1.0).unwrap();
+
+            for (sample, &(parent, _)) in evolution.samples.iter().zip(&evolution.parents) {
+                assert_eq!(sample.unmutated, *population[parent].chromosome());
             }
+
+            assert!(evolution.diagnostics.genes_touched > 0);
         }
-    }
-    impl PartialEq for Chromosome {
-        fn eq(&self, other: &Self) -> bool {
-            approx::relative_eq!(self.genes.as_slice(), other.genes.as_slice())
+
+        #[test]
+        fn asexual_breeding_clones_parent_a() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population();
+            let (a, b) = (population[1].chromosome(), population[2].chromosome());
+
+            assert_eq!(ga(ReproductionMode::Asexual, 0.0).breed(&mut rng, a, b).unwrap(), *a);
+            assert_eq!(AsexualReproduction.crossover(&mut rng, a, b).unwrap(), *a);
         }
     }
 
+    mod gene_ages {
+        use super::*;
 
-    use std::collections::BTreeMap;
-    use std::iter::FromIterator;
+        fn ga(chance: f32) -> GeneticAlgorithm<RouletteWheelSelection, UniformCrossover, GaussianMutation> {
+            GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                UniformCrossover,
+                GaussianMutation::new(chance, 0.5),
+            )
+        }
 
-    
-    #[test]
-    fn roulette_wheel_selection() {
-        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        /// Evolves a single individual (which can only get crossed with
+        /// itself) for `generations` generations.
+        fn ages(chance: f32, generations: usize) -> Vec<Vec<Vec<u16>>> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let ga = ga(chance);
 
-        let population = vec![
-            TestIndividual::new(2.0),
-            TestIndividual::new(1.0),
-            TestIndividual::new(4.0),
-            TestIndividual::new(3.0),
-        ];
+            let mut population = vec![TestIndividual::WithChromosome {
+                chromosome: (0..5).map(|gene| gene as Float).collect(),
+            }];
 
-        let mut actual_histogram = BTreeMap::new();
+            let mut ages = vec![vec![0; 5]];
+            let mut history = Vec::new();
 
-        //          /--| nothing special about this thousand;
-        //          v  | a number as low as fifty might do the trick, too
-        for _ in 0..1000 {
-            let fitness = RouletteWheelSelection
-                .select(&mut rng, &population)
-                .fitness() as i32;
+            for _ in 0..generations {
+                let evolution = ga.evolve_with_gene_ages(&mut rng, &population, 0.0, &ages).unwrap();
 
-            *actual_histogram
-                .entry(fitness)
-                .or_insert(0) += 1;
+                population = evolution.population;
+                ages = evolution.gene_ages;
+                history.push(ages.clone());
+            }
+
+            history
         }
 
-        let expected_histogram = BTreeMap::from_iter([
-            // (fitness, how many times this fitness has been chosen)
-            (1, 98),
-            (2, 202),
-            (3, 278),
-            (4, 422),
-        ]);
+        #[test]
+        fn cloned_genes_grow_older() {
+            assert_eq!(
+                ages(0.0, 3),
+                vec![vec![vec![1; 5]], vec![vec![2; 5]], vec![vec![3; 5]]],
+            );
+        }
 
-        assert_eq!(actual_histogram, expected_histogram);
-    }
+        #[test]
+        fn mutated_genes_start_over() {
+            assert_eq!(ages(1.0, 3), vec![vec![vec![0; 5]]; 3]);
+        }
 
-    #[test]
-    fn uniform_crossover() {
-        let mut rng = ChaCha8Rng::from_seed(Default::default());
-        let parent_a = (1..=100).map(|n| n as f32).collect();
-        let parent_b = (1..=100).map(|n| -n as f32).collect();
-        let child = UniformCrossover.crossover(&mut rng, &parent_a, &parent_b);
+        #[test]
+        fn genes_inherit_their_parents_age() {
+            let a: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+            let b: Chromosome = vec![1.0, 5.0, 6.0].into_iter().collect();
+            let crossed: Chromosome = vec![1.0, 5.0, 3.5].into_iter().collect();
+            let child: Chromosome = vec![1.0, 5.0, 3.5].into_iter().collect();
 
-        // Number of genes different between 'child' and 'parent_a'
-        let diff_a = child.iter().zip(parent_a).filter(|(c,p)| *c != p).count();
-        let diff_b = child.iter().zip(parent_b).filter(|(c,p)| *c != p).count();
+            assert_eq!(
+                age_genes((&a, &[4, 4, 4]), (&b, &[7, 7, 7]), &crossed, &child),
+                vec![8, 8, 0],
+            );
+        }
 
-        assert_eq!(diff_a, 49); // Child inherited 49% of parent_a's genes
-        assert_eq!(diff_b, 51); // Child inherited 51% of parent_b's genes
+        #[test]
+        fn gene_ages_dont_affect_evolution() {
+            let population: Vec<_> = (1..=10)
+                .map(|idx| TestIndividual::WithChromosome {
+                    chromosome: vec![idx as Float; 5].into_iter().collect(),
+                })
+                .collect();
+
+            let ga = ga(0.5);
+            let ages = vec![vec![0; 5]; 10];
+
+            let (plain, _) = ga.evolve(&mut ChaCha8Rng::from_seed(Default::default()), &population).unwrap();
+            let aged = ga.evolve_with_gene_ages(&mut ChaCha8Rng::from_seed(Default::default()), &population, 0.0, &ages).unwrap();
+
+            assert_eq!(plain, aged.population);
+            assert_eq!(aged.gene_ages.len(), 10);
+        }
     }
 
-    mod gaussian_mutation {
+    mod offset_gene {
         use super::*;
 
-        fn actual(chance: f32, coeff: f32) -> Vec<f32> {
-            let mut rng = ChaCha8Rng::from_seed(Default::default());
-            let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+        #[test]
+        fn only_touches_given_gene() {
+            let mut chromosome: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
 
-            GaussianMutation::new(chance, coeff).mutate(&mut rng, &mut child);
+            chromosome.offset_gene(1, 0.5);
+            chromosome.offset_gene(2, -3.0);
 
-            child.into_iter().collect()
+            assert_eq!(chromosome, vec![1.0, 2.5, 0.0].into_iter().collect());
         }
-        mod given_zero_chance {
-            use approx::assert_relative_eq;
 
-            fn actual(coeff: f32) -> Vec<f32> {
-                super::actual(0.0, coeff)
-            }
+        #[test]
+        #[should_panic]
+        fn panics_out_of_bounds() {
+            let mut chromosome: Chromosome = vec![1.0].into_iter().collect();
+            chromosome.offset_gene(1, 0.5);
+        }
+    }
 
-            mod and_zero_coefficient {
-                use super::*;
+    mod chromosome_diff {
+        use super::*;
 
-                #[test]
-                fn does_not_change_the_original_chromosome() {
-                    let actual = actual(0.0);
-                    let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        fn chromosome(genes: &[Float]) -> Chromosome {
+            genes.iter().copied().collect()
+        }
 
-                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
-                }
+        #[test]
+        fn identical_chromosomes_have_no_diff() {
+            let a = chromosome(&[1.0, -2.5, 0.0, 3.75]);
+
+            for epsilon in [0.0, 1e-6, 0.5, 100.0] {
+                assert_eq!(a.diff(&a.clone(), epsilon), Ok(vec![]));
             }
+        }
 
-            mod and_nonzero_coefficient {
-                use super::*;
+        #[test]
+        fn ignores_differences_below_epsilon() {
+            let a = chromosome(&[1.0, 2.0, 3.0]);
+            let b = chromosome(&[1.001, 2.5, 1.0]);
 
-                #[test]
-                fn does_not_change_the_original_chromosome() {
-                    let actual = actual(0.5);
-                    let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+            assert_eq!(
+                a.diff(&b, 0.01),
+                Ok(vec![
+                    GeneDiff { index: 1, a: 2.0, b: 2.5, delta: 0.5 },
+                    GeneDiff { index: 2, a: 3.0, b: 1.0, delta: -2.0 },
+                ]),
+            );
+        }
 
-                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
-                }
-            }
+        #[test]
+        fn full_patch_reproduces_source() {
+            let a = chromosome(&[0.1, 0.2, 0.3, 0.4, 0.5]);
+            let mut b = chromosome(&[0.1, -0.2, 0.3, 4.0, -5.0]);
+            let diffs = a.diff(&b, 0.0).unwrap();
+
+            b.patch(&diffs, PatchSource::A).unwrap();
+
+            assert_eq!(b, a);
         }
 
-        mod given_fifty_fifty_chance {
-            use approx::assert_relative_eq;
+        #[test]
+        fn partial_patch_only_touches_listed_genes() {
+            let a = chromosome(&[1.0, 2.0, 3.0, 4.0]);
+            let mut b = chromosome(&[-1.0, -2.0, -3.0, -4.0]);
 
-            fn actual(coeff: f32) -> Vec<f32>{
-                super::actual(0.5, coeff)
-            }
+            // e.g. "take the output layer from a"
+            let diffs: Vec<_> = a.diff(&b, 0.0).unwrap().into_iter().filter(|diff| diff.index >= 2).collect();
 
-            mod and_zero_coefficient {
-                use super::*;
+            b.patch(&diffs, PatchSource::A).unwrap();
 
-                #[test]
-                fn does_not_change_the_original_chromosome() {
-                    let actual = actual(0.0);
-                    let expected = vec![1.0,2.0,3.0,4.0,5.0];
-                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
-                }
+            assert_eq!(b, chromosome(&[-1.0, -2.0, 3.0, 4.0]));
+        }
 
-            }
+        #[test]
+        fn rejects_length_mismatch() {
+            let a = chromosome(&[1.0, 2.0]);
+            let mut b = chromosome(&[1.0]);
 
-            mod and_nonzero_coefficient {
-                use super::*;
+            assert_eq!(a.diff(&b, 0.0), Err(ChromosomeError::LengthMismatch { a: 2, b: 1 }));
 
-                #[test]
-                fn slightly_changes_the_original_chromosome() {
-                    let actual = actual(0.5);
-                    let expected = vec![1.0, 1.7756249, 3.0, 4.1596804, 5.0];
-                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
-                }
-            }
+            let diffs = a.diff(&chromosome(&[0.0, 0.0]), 0.0).unwrap();
+
+            assert_eq!(
+                b.patch(&diffs, PatchSource::A),
+                Err(ChromosomeError::IndexOutOfBounds { index: 1, len: 1 }),
+            );
+
+            // ... leaving the chromosome untouched
+            assert_eq!(b, chromosome(&[1.0]));
         }
+    }
 
-        mod given_max_chance {
-            use approx::assert_relative_eq;
+    mod mutate_batch {
+        use super::*;
+        use approx::assert_relative_eq;
 
-            fn actual(coeff:f32) -> Vec<f32>{
-                super::actual(1.0, coeff)
-            }
-            
-            mod and_zero_coefficient {
-                use super::*;
-                
-                #[test]
-                fn does_not_change_the_original_chromosome() {
-                    let actual = actual(0.0);
-                    let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
-                }
+        /// Relies on the default `mutate_batch()`.
+        struct PerChild(GaussianMutation);
+
+        impl MutationMethod for PerChild {
+            fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+                self.0.mutate(rng, child);
             }
+        }
 
-            mod and_nonzero_coefficient {
-                use super::*;
-                
-                #[test]
-                fn entirely_changes_the_original_chromosome() {
-                    let actual = actual(0.5);
-                    let expected = vec![1.4545316, 2.1162078, 2.7756248, 3.9505124, 4.638691];
-                    assert_relative_eq!(actual.as_slice(), expected.as_slice());
-                }
+        fn children() -> Vec<Chromosome> {
+            (0..1000).map(|_| vec![0.0; 100].into_iter().collect()).collect()
+        }
+
+        #[test]
+        fn default_matches_mutating_one_by_one() {
+            let mutation = PerChild(GaussianMutation::new(0.3, 0.5));
+
+            let mut batched = children();
+            mutation.mutate_batch(&mut ChaCha8Rng::from_seed(Default::default()), &mut batched);
+
+            let mut one_by_one = children();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            for child in &mut one_by_one {
+                mutation.mutate(&mut rng, child);
             }
+
+            assert_eq!(batched, one_by_one);
         }
-    }
 
+        #[test]
+        fn gaussian_matches_configured_distribution() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut children = children();
 
+            GaussianMutation::new(0.3, 0.5).mutate_batch(&mut rng, &mut children);
 
-    #[test]
-    fn genetic_algorithm() {
+            let deltas: Vec<Float> = children
+                .iter()
+                .flat_map(|child| child.iter().copied())
+                .filter(|&gene| gene != 0.0)
+                .collect();
 
-        fn individual(genes: &[f32]) -> TestIndividual {
-            TestIndividual::create(genes.iter().cloned().collect())
+            let total = 1000.0 * 100.0;
+            let mean = |values: &mut dyn Iterator<Item = Float>| values.sum::<Float>() / deltas.len() as Float;
+
+            // Touch rate
+            assert_relative_eq!(deltas.len() as Float / total, 0.3, epsilon = 0.01);
+
+            // Both signs are equally likely...
+            assert_relative_eq!(mean(&mut deltas.iter().map(|d| d.signum())), 0.0, epsilon = 0.02);
+
+            // ... and magnitudes are uniform within <0, coeff)
+            assert_relative_eq!(mean(&mut deltas.iter().map(|d| d.abs())), 0.25, epsilon = 0.005);
+            assert!(deltas.iter().all(|d| d.abs() < 0.5));
         }
 
-        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        #[test]
+        fn gaussian_respects_extreme_chances() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
 
-        let ga = GeneticAlgorithm::new(
-            RouletteWheelSelection,
-            UniformCrossover,
-            GaussianMutation::new(0.5, 0.5),
-        );
+            let mut untouched = children();
+            GaussianMutation::new(0.0, 0.5).mutate_batch(&mut rng, &mut untouched);
+            assert_eq!(untouched, children());
 
-        let mut population = vec![
-            individual(&[0.0, 0.0, 0.0]),
-            individual(&[1.0, 1.0, 1.0]),
-            individual(&[1.0, 2.0, 1.0]),
-            individual(&[1.0, 2.0, 4.0]),
-        ];
+            let mut touched = children();
+            GaussianMutation::new(1.0, 0.5).mutate_batch(&mut rng, &mut touched);
 
-        // We're running `.evolve()` a few times, so that the differences between the
-        // input and output population are easier to spot.
-        //
-        // No particular reason for a number of 10 - this test would be fine for 5, 20 or
-        // even 1000 generations - the only thing that'd change is the magnitude of the
-        // difference between the populations.
-        let mut _stats = Statistics::new(&population);
-        for _ in 0..10 {
-            (population, _stats) = ga.evolve(&mut rng, &population);
-        }
-
-        let expected_population = vec![
-            individual(&[0.44769490, 2.0648358, 4.3058133]),
-            individual(&[1.21268670, 1.5538777, 2.8869110]),
-            individual(&[1.06176780, 2.2657390, 4.4287640]),
-            individual(&[0.95909685, 2.4618788, 4.0247330]),
-        ];
+            let zeros = touched.iter().flat_map(|child| child.iter()).filter(|&&gene| gene == 0.0).count();
+            assert!(zeros < 10);
+        }
+    }
 
-        assert_eq!(population, expected_population); // expected has better fitness for each individual so evolve function is working
+    #[test]
+    fn float_matches_precision_feature() {
+        #[cfg(not(feature = "precision-f64"))]
+        assert_eq!(std::any::type_name::<Float>(), "f32");
+
+        #[cfg(feature = "precision-f64")]
+        assert_eq!(std::any::type_name::<Float>(), "f64");
     }
-    
-}
\ No newline at end of file
+}
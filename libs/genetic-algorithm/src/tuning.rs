@@ -0,0 +1,149 @@
+//! Searching for good hyperparameters (e.g. mutation chance & coeff).
+
+use alloc::vec::Vec;
+
+/// Outcome of `successive_halving()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HalvingResult<P> {
+    /// Candidates that made it through the final round, along with their
+    /// final-round scores, from the best one.
+    pub survivors: Vec<(P, f32)>,
+
+    /// What happened in each round.
+    pub rounds: Vec<HalvingRound>,
+
+    /// Sum of budgets passed to `eval` across all rounds.
+    pub budget_spent: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HalvingRound {
+    /// Number of candidates evaluated in this round.
+    pub candidates: usize,
+
+    /// Budget each of them got.
+    pub budget: usize,
+}
+
+/// Finds the best candidate through successive halving:
+///
+/// - in round `r` (counting from 0), each remaining candidate gets
+///   evaluated with a budget of `2^r` (e.g. generations to train for),
+/// - the top `keep_fraction` of them (rounding up, but at least one)
+///   advances to the next round.
+///
+/// This way most of the budget gets spent on promising candidates, while
+/// the hopeless ones get eliminated cheaply; `eval` should return a score
+/// where higher means better.
+///
+/// Candidates with equal scores keep their original order, so the
+/// outcome depends only on the scores.
+pub fn successive_halving<P: Clone>(
+    candidates: Vec<P>,
+    mut eval: impl FnMut(&P, usize) -> f32,
+    rounds: usize,
+    keep_fraction: f32,
+) -> HalvingResult<P> {
+    assert!(!candidates.is_empty());
+    assert!(rounds > 0);
+    assert!(keep_fraction > 0.0 && keep_fraction <= 1.0);
+
+    let mut survivors: Vec<(P, f32)> = candidates.into_iter().map(|c| (c, 0.0)).collect();
+    let mut history = Vec::with_capacity(rounds);
+    let mut budget_spent = 0;
+
+    for round in 0..rounds {
+        if round > 0 {
+            let keep = ceil(survivors.len() as f32 * keep_fraction).max(1);
+            survivors.truncate(keep);
+        }
+
+        let budget = 1 << round;
+
+        for (candidate, score) in &mut survivors {
+            *score = eval(candidate, budget);
+        }
+
+        budget_spent += survivors.len() * budget;
+
+        history.push(HalvingRound {
+            candidates: survivors.len(),
+            budget,
+        });
+
+        // Stable, so that ties keep their original order
+        survivors.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    }
+
+    HalvingResult {
+        survivors,
+        rounds: history,
+        budget_spent,
+    }
+}
+
+/// `f32::ceil()` for non-negative numbers, which - unlike the former -
+/// works without `std`.
+fn ceil(x: f32) -> usize {
+    let truncated = x as usize;
+
+    if (truncated as f32) < x {
+        truncated + 1
+    } else {
+        truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strictly_best_candidate_survives() {
+        // Candidate 5 is the best one at every budget
+        let result = successive_halving(
+            (0..8).collect(),
+            |&c, budget| if c == 5 { 100.0 * budget as f32 } else { (c * budget) as f32 },
+            4,
+            0.5,
+        );
+
+        assert_eq!(result.survivors.len(), 1);
+        assert_eq!(result.survivors[0].0, 5);
+    }
+
+    #[test]
+    fn budget_matches_formula() {
+        let mut spent = 0;
+
+        let result = successive_halving(
+            (0..8).collect(),
+            |&c, budget| {
+                spent += budget;
+                c as f32
+            },
+            3,
+            0.5,
+        );
+
+        // 8 candidates * 1 + 4 candidates * 2 + 2 candidates * 4
+        assert_eq!(result.budget_spent, 8 + 4 * 2 + 2 * 4);
+        assert_eq!(result.budget_spent, spent);
+
+        assert_eq!(
+            result.rounds,
+            vec![
+                HalvingRound { candidates: 8, budget: 1 },
+                HalvingRound { candidates: 4, budget: 2 },
+                HalvingRound { candidates: 2, budget: 4 },
+            ]
+        );
+    }
+
+    #[test]
+    fn ties_are_broken_by_original_order() {
+        let result = successive_halving(vec!['a', 'b', 'c', 'd', 'e'], |_, _| 1.0, 2, 0.4);
+
+        assert_eq!(result.survivors, vec![('a', 1.0), ('b', 1.0)]);
+    }
+}
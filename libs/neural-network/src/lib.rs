@@ -1,6 +1,14 @@
-use rand::Rng;
+use rand::{Rng, RngCore};
 use std::iter::once;
 
+/// Type of weights & signals - `f32`, unless the `precision-f64` feature
+/// is enabled.
+#[cfg(not(feature = "precision-f64"))]
+pub type Float = f32;
+
+#[cfg(feature = "precision-f64")]
+pub type Float = f64;
+
 #[derive(Debug)]
 pub struct LayerTopology {
     pub neurons: usize,
@@ -12,29 +20,29 @@ impl LayerTopology {
 }
 
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Network{
     layers: Vec<Layer>,
 }
 impl Network{
-    pub fn random(layers: &[LayerTopology]) -> Self {
+    pub fn random(rng: &mut dyn RngCore, layers: &[LayerTopology]) -> Self {
         assert!(layers.len() > 1);
         
         let layers = layers
             .windows(2)
-            .map(|layers| Layer::random(layers[0].neurons, layers[1].neurons))
+            .map(|layers| Layer::random(rng, layers[0].neurons, layers[1].neurons))
             .collect();
 
         Self { layers }
     }
 
-    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+    pub fn propagate(&self, inputs: Vec<Float>) -> Vec<Float> {
         self.layers
             .iter()
             .fold(inputs, |inputs, layer| layer.propagate(inputs))
     }
 
-    pub fn weights(&self) -> impl Iterator<Item = f32> + '_ {
+    pub fn weights(&self) -> impl Iterator<Item = Float> + '_ {
         self.layers
             .iter()
             .flat_map(|layer| layer.neurons.iter())
@@ -44,7 +52,7 @@ impl Network{
 
     pub fn from_weights(
         layers: &[LayerTopology],
-        weights: impl IntoIterator<Item = f32>,
+        weights: impl IntoIterator<Item = Float>,
     ) -> Self {
         assert!(layers.len() > 1);
 
@@ -67,22 +75,96 @@ impl Network{
 
         Self { layers }
     }
+
+    /// Rewires the network from `old_n` inputs to `new_n` ones, as if the
+    /// inputs were samples of a signal that now gets sampled more (or less)
+    /// densely - see: `interpolate()`.
+    ///
+    /// Only the leading `old_n` inputs get remapped, the remaining ones
+    /// (if any) stay as they are.
+    pub fn remap_inputs(&mut self, old_n: usize, new_n: usize) {
+        self.remap_inputs_at(0, old_n, new_n);
+    }
+
+    /// Like `remap_inputs()`, but for `old_n` inputs starting at `offset`.
+    pub fn remap_inputs_at(&mut self, offset: usize, old_n: usize, new_n: usize) {
+        for neuron in &mut self.layers[0].neurons {
+            assert!(offset + old_n <= neuron.weights.len());
+
+            let remapped = interpolate(&neuron.weights[offset..offset + old_n], new_n);
+            neuron.weights.splice(offset..offset + old_n, remapped);
+        }
+    }
+
+    /// Grows or shrinks given layer (0 being the first one after the input
+    /// layer) to `neurons` neurons.
+    ///
+    /// New neurons start dormant - with zero bias and zero weights, both
+    /// in and out - so growing a layer doesn't change what the network
+    /// returns; shrinking drops the last neurons, along with whatever they
+    /// contributed.
+    pub fn resize_layer(&mut self, layer: usize, neurons: usize) {
+        let input_size = self.layers[layer].neurons[0].weights.len();
+
+        self.layers[layer].neurons.resize(
+            neurons,
+            Neuron {
+                bias: 0.0,
+                weights: vec![0.0; input_size],
+            },
+        );
+
+        if let Some(next) = self.layers.get_mut(layer + 1) {
+            for neuron in &mut next.neurons {
+                neuron.weights.resize(neurons, 0.0);
+            }
+        }
+    }
+
+    /// Replaces all of given layer's weights with random ones, keeping its
+    /// shape.
+    pub fn randomize_layer(&mut self, rng: &mut dyn RngCore, layer: usize) {
+        let layer = &mut self.layers[layer];
+        let input_size = layer.neurons[0].weights.len();
+
+        *layer = Layer::random(rng, input_size, layer.neurons.len());
+    }
 }
 
+/// Resamples `values` into `len` ones through linear interpolation, treating
+/// each value as the center of an equally wide bin - e.g. to map weights of
+/// 9 eye cells onto 18 of them.
+pub fn interpolate(values: &[Float], len: usize) -> Vec<Float> {
+    assert!(!values.is_empty());
 
-#[derive(Debug)]
+    let n = values.len();
+
+    (0..len)
+        .map(|idx| {
+            let pos = ((idx as Float + 0.5) * n as Float / len as Float - 0.5).clamp(0.0, (n - 1) as Float);
+            let lo = pos.floor() as usize;
+            let hi = (lo + 1).min(n - 1);
+            let t = pos - lo as Float;
+
+            values[lo] * (1.0 - t) + values[hi] * t
+        })
+        .collect()
+}
+
+
+#[derive(Clone, Debug)]
 struct Layer{
     neurons: Vec<Neuron>,
 }
 impl Layer{
-    fn random(input_size: usize, output_size: usize) -> Self{
+    fn random(rng: &mut dyn RngCore, input_size: usize, output_size: usize) -> Self{
         let neurons = (0..output_size)
-            .map(|_| Neuron::random(input_size))
+            .map(|_| Neuron::random(rng, input_size))
             .collect();
 
         Self { neurons }
     }
-    fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+    fn propagate(&self, inputs: Vec<Float>) -> Vec<Float> {
         self.neurons
             .iter()
             .map(|neuron| neuron.propagate(&inputs))
@@ -91,7 +173,7 @@ impl Layer{
     fn from_weights(
         input_size: usize,
         output_size: usize,
-        weights: &mut dyn Iterator<Item = f32>,
+        weights: &mut dyn Iterator<Item = Float>,
     ) -> Self {
         let neurons = (0..output_size)
             .map(|_| Neuron::from_weights(input_size, weights))
@@ -102,14 +184,13 @@ impl Layer{
 }
 
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Neuron{
-    bias: f32,
-    weights: Vec<f32>,
+    bias: Float,
+    weights: Vec<Float>,
 }
 impl Neuron{
-    fn random(input_size: usize) -> Self{
-        let mut rng = rand::thread_rng();
+    fn random(rng: &mut dyn RngCore, input_size: usize) -> Self{
         let bias = rng.gen_range(-1.0..=1.0);
 
         let weights = (0..input_size)
@@ -119,14 +200,14 @@ impl Neuron{
         Self { bias, weights }
     }
 
-    fn propagate(&self, inputs: &[f32]) -> f32{
+    fn propagate(&self, inputs: &[Float]) -> Float{
         assert_eq!(inputs.len(), self.weights.len());
 
         let mut output = inputs
             .iter()
             .zip(&self.weights)
             .map(|(input, weight)| input * weight)
-            .sum::<f32>();
+            .sum::<Float>();
 
         output += self.bias;
 
@@ -135,7 +216,7 @@ impl Neuron{
     }
     fn from_weights(
         input_size: usize,
-        weights: &mut dyn Iterator<Item = f32>,
+        weights: &mut dyn Iterator<Item = Float>,
     ) -> Self {
         let bias = weights.next().expect("got not enough weights");
 
@@ -189,4 +270,69 @@ mod tests {
 
         assert_relative_eq!(actual.as_slice(), weights.as_slice());
     }
+
+    #[test]
+    fn interpolate() {
+        let cases: [(&[Float], usize, &[Float]); 4] = [
+            (&[1.0, 2.0, 3.0], 3, &[1.0, 2.0, 3.0]),
+            (&[1.0, 3.0], 4, &[1.0, 1.5, 2.5, 3.0]),
+            (&[1.0, 2.0, 3.0, 4.0], 2, &[1.5, 3.5]),
+            (&[5.0], 3, &[5.0, 5.0, 5.0]),
+        ];
+
+        for (values, len, expected) in cases {
+            let actual = super::interpolate(values, len);
+
+            assert_relative_eq!(actual.as_slice(), expected);
+        }
+    }
+
+    #[test]
+    fn remap_inputs_keeps_trailing_inputs() {
+        let layers = &[
+            LayerTopology { neurons: 3 },
+            LayerTopology { neurons: 1 },
+        ];
+
+        let mut network = Network::from_weights(layers, vec![0.1, 1.0, 3.0, 0.7]);
+        network.remap_inputs(2, 4);
+
+        let actual: Vec<_> = network.weights().collect();
+        let expected = vec![0.1, 1.0, 1.5, 2.5, 3.0, 0.7];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn growing_layer_keeps_outputs() {
+        let mut rng = rand::rngs::mock::StepRng::new(1 << 60, 1 << 58);
+
+        let layers = &[
+            LayerTopology { neurons: 3 },
+            LayerTopology { neurons: 4 },
+            LayerTopology { neurons: 2 },
+        ];
+
+        let network = Network::random(&mut rng, layers);
+        let mut grown = network.clone();
+
+        grown.resize_layer(0, 7);
+
+        assert_eq!(grown.weights().count(), network.weights().count() + 3 * 4 + 2 * 3);
+
+        for inputs in [vec![0.0, 0.0, 0.0], vec![0.5, 1.0, 0.25], vec![1.0, 0.0, 2.0]] {
+            let actual = grown.propagate(inputs.clone());
+            let expected = network.propagate(inputs);
+
+            assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        grown.resize_layer(0, 4);
+
+        // Shrinking back drops exactly what growing has added
+        let actual: Vec<_> = grown.weights().collect();
+        let expected: Vec<_> = network.weights().collect();
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
 }
\ No newline at end of file
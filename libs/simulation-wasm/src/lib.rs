@@ -19,21 +19,25 @@ impl Simulation{
         World::from(self.sim.world())
     }
     pub fn step(&mut self) -> String {
-        let stats = self.sim.step(&mut self.rng);
-        match stats {
+        let summary = self.sim.step(&mut self.rng);
+        match summary {
             Some(x) => {
                 format!(
                     "min={:.2} max={:.2} avg={:.2}",
-                    x.min_fitness,
-                    x.max_fitness,
-                    x.avg_fitness,
+                    x.stats.min_fitness,
+                    x.stats.max_fitness,
+                    x.stats.avg_fitness,
                 )
             }
             None => "".to_string()
         }
     }
+    /// Phase of the day/night cycle: 0.0 = noon, 1.0 = midnight.
+    pub fn darkness(&self) -> f32 {
+        self.sim.darkness()
+    }
     pub fn train(&mut self) -> String {
-        let stats = self.sim.train(&mut self.rng);
+        let stats = self.sim.train(&mut self.rng).stats;
 
         format!(
             "min={:.2} max={:.2} avg={:.2}",
@@ -44,6 +48,12 @@ impl Simulation{
     }
 }
 
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Clone, Debug)]
 pub struct World {
@@ -59,6 +69,16 @@ pub struct Animal {
     pub x: f32,
     pub y: f32,
     pub rotation: f32,
+    pub lineage: u64,
+    /// CSS color of the bird's lineage - the same across generations.
+    #[wasm_bindgen(getter_with_clone)]
+    pub color: String,
+    pub hibernated_steps: usize,
+    /// Whether the bird's foods don't count towards its fitness yet.
+    pub in_nursery: bool,
+    pub spinner: bool,
+    pub wall_hugger: bool,
+    pub idler: bool,
 }
 
 #[wasm_bindgen]
@@ -84,6 +104,13 @@ impl From<&sim::Animal> for Animal {
             x: animal.position().x,
             y: animal.position().y,
             rotation: animal.rotation().angle(),
+            lineage: animal.lineage().get(),
+            color: animal.lineage().color().to_string(),
+            hibernated_steps: animal.hibernated_steps(),
+            in_nursery: animal.in_nursery(),
+            spinner: animal.behavior().spinner,
+            wall_hugger: animal.behavior().wall_hugger,
+            idler: animal.behavior().idler,
         }
     }
 }
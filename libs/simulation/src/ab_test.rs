@@ -0,0 +1,424 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Splits the population into two arms, each evolved by a genetic
+/// algorithm of its own - arm A by `SimulationConfig::ga`, arm B by
+/// `AbTest::b` - so that e.g. two mutation rates can be compared within
+/// a single run, with both arms living in the same world.
+///
+/// Arm A takes the first half of the slots, arm B the rest (so it gets
+/// the odd bird out); each arm selects parents only among its own birds
+/// and its children take over its own slots, so lineages never cross the
+/// arms - this goes for birds bred by hand, too (see:
+/// `BreedError::DifferentArms`).
+///
+/// Arms have to stay put, so it can't be combined with
+/// `SimulationConfig::habitats`, `SimulationConfig::population_control`
+/// or `AutoRestart`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AbTest {
+    /// Parameters of arm B's genetic algorithm.
+    pub b: GaParams,
+}
+
+impl AbTest {
+    pub(crate) fn try_validate(&self, n_animals: usize) -> Result<(), ConfigError> {
+        ensure!(n_animals >= 2, "A/B test requires at least two animals");
+        self.b.try_validate()?;
+
+        Ok(())
+    }
+
+    /// Slots of birds belonging to given arm.
+    pub(crate) fn slots(arm: Arm, n_animals: usize) -> Range<usize> {
+        let split = n_animals / 2;
+
+        match arm {
+            Arm::A => 0..split,
+            Arm::B => split..n_animals,
+        }
+    }
+
+    /// Arm the bird living in given slot belongs to.
+    pub(crate) fn arm_of(slot: usize, n_animals: usize) -> Arm {
+        if slot < n_animals / 2 {
+            Arm::A
+        } else {
+            Arm::B
+        }
+    }
+}
+
+/// Half of the population in an A/B test; see: `AbTest`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Arm {
+    A,
+    B,
+}
+
+/// How the arms of an A/B test did during a generation.
+#[derive(Clone, Debug)]
+pub struct AbTestSummary {
+    /// Fitness statistics of arm A's birds.
+    pub a: ga::Statistics,
+
+    /// Fitness statistics of arm B's birds.
+    pub b: ga::Statistics,
+
+    /// Number of generations so far (including this one) in which arm A
+    /// had a higher average fitness than arm B; ties count for nobody.
+    pub a_wins: usize,
+
+    /// Same as `a_wins`, but for arm B.
+    pub b_wins: usize,
+
+    /// Number of generations the test has been running for, including
+    /// this one.
+    pub generations: usize,
+}
+
+impl AbTestSummary {
+    pub fn stats(&self, arm: Arm) -> &ga::Statistics {
+        match arm {
+            Arm::A => &self.a,
+            Arm::B => &self.b,
+        }
+    }
+
+    /// Fraction of generations so far won by given arm.
+    pub fn win_rate(&self, arm: Arm) -> f32 {
+        let wins = match arm {
+            Arm::A => self.a_wins,
+            Arm::B => self.b_wins,
+        };
+
+        wins as f32 / self.generations as f32
+    }
+}
+
+/// Arm B's genetic algorithm, along with the running tally of wins.
+pub(crate) struct AbState {
+    pub(crate) ga: Ga,
+    a_wins: usize,
+    b_wins: usize,
+    generations: usize,
+}
+
+impl AbState {
+    pub(crate) fn new(config: &SimulationConfig, ab_test: &AbTest) -> Self {
+        Self {
+            ga: genetic_algorithm(config, &ab_test.b),
+            a_wins: 0,
+            b_wins: 0,
+            generations: 0,
+        }
+    }
+}
+
+impl Animal {
+    /// Arm of the A/B test this bird belongs to; `None` unless
+    /// `SimulationConfig::ab_test` is enabled.
+    pub fn arm(&self) -> Option<Arm> {
+        self.arm
+    }
+}
+
+impl World {
+    /// Labels birds with the arms their slots belong to.
+    pub(crate) fn assign_arms(&mut self, config: &SimulationConfig) {
+        let n_animals = self.animals.len();
+
+        for (slot, animal) in self.animals.iter_mut().enumerate() {
+            animal.arm = config.ab_test.as_ref().map(|_| AbTest::arm_of(slot, n_animals));
+        }
+    }
+}
+
+impl Simulation {
+    /// Arm of the A/B test given slot belongs to; `None` unless
+    /// `SimulationConfig::ab_test` is enabled.
+    pub(crate) fn arm_of(&self, slot: usize) -> Option<Arm> {
+        self.config
+            .ab_test
+            .as_ref()
+            .map(|_| AbTest::arm_of(slot, self.config.n_animals))
+    }
+
+    /// Genetic algorithm given arm gets evolved with.
+    pub(crate) fn ga_of(&self, arm: Option<Arm>) -> &Ga {
+        match (arm, &self.ab_test) {
+            (Some(Arm::B), Some(state)) => &state.ga,
+            _ => &self.ga,
+        }
+    }
+
+    /// Evolves each arm separately, with its own genetic algorithm, and
+    /// stitches the results back together.
+    pub(crate) fn evolve_ab(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[AnimalIndividual],
+        sample_rate: f32,
+    ) -> ga::Evolution<AnimalIndividual> {
+        let n_animals = population.len();
+        let mut stats = ga::StatisticsBuilder::default();
+
+        for individual in population {
+            stats.observe(individual.fitness());
+        }
+
+        let mut evolution = ga::Evolution {
+            population: Vec::with_capacity(n_animals),
+            stats: stats.finish(),
+            samples: Vec::new(),
+            parents: Vec::with_capacity(n_animals),
+            diagnostics: Default::default(),
+            gene_ages: Vec::new(),
+        };
+
+        for arm in [Arm::A, Arm::B] {
+            let slots = AbTest::slots(arm, n_animals);
+            let offset = slots.start;
+
+            let gene_ages: Vec<_> = slots
+                .clone()
+                .filter_map(|slot| self.gene_ages.get(slot).cloned())
+                .collect();
+
+            let bred = self.evolve_aged(self.ga_of(Some(arm)), rng, &population[slots], sample_rate, &gene_ages);
+
+            evolution.population.extend(bred.population);
+            evolution.samples.extend(bred.samples);
+            evolution.gene_ages.extend(bred.gene_ages);
+
+            evolution
+                .parents
+                .extend(bred.parents.into_iter().map(|(a, b)| (offset + a, offset + b)));
+
+            let diagnostics = &mut evolution.diagnostics;
+            diagnostics.genes += bred.diagnostics.genes;
+            diagnostics.genes_touched += bred.diagnostics.genes_touched;
+            diagnostics.total_delta += bred.diagnostics.total_delta;
+            diagnostics.compatibility_retries += bred.diagnostics.compatibility_retries;
+            diagnostics.asexual += bred.diagnostics.asexual;
+            diagnostics.inheritance.extend(bred.diagnostics.inheritance);
+        }
+
+        evolution
+    }
+
+    /// Scores the arms of the generation that has just ended, given its
+    /// birds' fitnesses (by slot).
+    pub(crate) fn summarize_ab(&mut self, fitnesses: &[ga::Float]) -> Option<AbTestSummary> {
+        let state = self.ab_test.as_mut()?;
+
+        let [a, b] = [Arm::A, Arm::B].map(|arm| {
+            let mut stats = ga::StatisticsBuilder::default();
+
+            for &fitness in &fitnesses[AbTest::slots(arm, fitnesses.len())] {
+                stats.observe(fitness);
+            }
+
+            stats.finish()
+        });
+
+        state.generations += 1;
+
+        if a.avg_fitness > b.avg_fitness {
+            state.a_wins += 1;
+        } else if b.avg_fitness > a.avg_fitness {
+            state.b_wins += 1;
+        }
+
+        Some(AbTestSummary {
+            a,
+            b,
+            a_wins: state.a_wins,
+            b_wins: state.b_wins,
+            generations: state.generations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::{Arc, Mutex};
+
+    fn config(n_animals: usize) -> SimulationConfig {
+        SimulationConfig {
+            n_animals,
+            generation_length: 20,
+            ab_test: Some(AbTest {
+                b: GaParams {
+                    mutation_chance: 0.5,
+                    reproduction: ga::ReproductionMode::Asexual,
+                    ..Default::default()
+                },
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn arms(sim: &Simulation) -> Vec<Arm> {
+        sim.world().animal().iter().map(|animal| animal.arm().unwrap()).collect()
+    }
+
+    #[test]
+    fn arm_sizes_stay_fixed() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(7), &mut rng);
+
+        for _ in 0..5 {
+            let parents: Vec<_> = sim.world().animal().iter().map(Animal::id).collect();
+
+            // Children bred by hand stay within their parents' arm
+            sim.breed(&mut rng, parents[4], parents[6], 2).unwrap();
+            sim.train(&mut rng);
+
+            let arms = arms(&sim);
+
+            assert_eq!(arms.iter().filter(|&&arm| arm == Arm::A).count(), 3);
+            assert_eq!(arms.iter().filter(|&&arm| arm == Arm::B).count(), 4);
+        }
+
+        // ... while a disabled test labels nobody
+        let sim = Simulation::from_config(
+            SimulationConfig {
+                ab_test: None,
+                ..config(7)
+            },
+            &mut rng,
+        );
+
+        assert!(sim.world().animal().iter().all(|animal| animal.arm().is_none()));
+    }
+
+    #[test]
+    fn parents_never_come_from_different_arms() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(10), &mut rng);
+
+        for _ in 0..3 {
+            let population: Vec<_> = sim
+                .world
+                .animals
+                .iter()
+                .enumerate()
+                .map(|(slot, animal)| AnimalIndividual::from_animal_with_fitness(animal, (slot % 3 + 1) as f32))
+                .collect();
+
+            let evolution = sim.evolve_ab(&mut rng, &population, 0.0);
+
+            assert_eq!(evolution.parents.len(), 10);
+
+            for (child, &(a, b)) in evolution.parents.iter().enumerate() {
+                let arm = AbTest::arm_of(child, 10);
+
+                assert_eq!(AbTest::arm_of(a, 10), arm, "child = {}", child);
+                assert_eq!(AbTest::arm_of(b, 10), arm, "child = {}", child);
+            }
+
+            sim.train(&mut rng);
+        }
+    }
+
+    #[test]
+    fn breeding_across_arms_fails() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(4), &mut rng);
+        let ids: Vec<_> = sim.world().animal().iter().map(Animal::id).collect();
+
+        assert_eq!(
+            sim.breed(&mut rng, ids[1], ids[2], 1),
+            Err(BreedError::DifferentArms {
+                parent_a: ids[1],
+                parent_b: ids[2],
+            })
+        );
+
+        // Arm A has just two slots
+        assert_eq!(
+            sim.breed(&mut rng, ids[0], ids[1], 3),
+            Err(BreedError::TooManyOffspring {
+                queued: 3,
+                capacity: 2,
+            })
+        );
+    }
+
+    /// Gives each bird a distinct fitness, remembering all of them (by
+    /// slot).
+    struct Ranked {
+        fitnesses: Arc<Mutex<Vec<ga::Float>>>,
+    }
+
+    impl GenerationPipeline for Ranked {
+        fn before_evolution(&mut self, individuals: &mut Vec<AnimalIndividual>) {
+            let n = individuals.len();
+
+            for (idx, individual) in individuals.iter_mut().enumerate() {
+                individual.set_fitness(((idx * 7) % n) as ga::Float);
+            }
+
+            *self.fitnesses.lock().unwrap() = individuals.iter().map(|individual| individual.fitness()).collect();
+        }
+    }
+
+    #[test]
+    fn arm_stats_match_filtered_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(9), &mut rng);
+        let fitnesses = Arc::new(Mutex::new(Vec::new()));
+
+        sim.set_pipeline(Box::new(Ranked {
+            fitnesses: fitnesses.clone(),
+        }));
+
+        for generation in 1..=3 {
+            let arms = arms(&sim);
+            let summary = sim.train(&mut rng).ab_test.unwrap();
+            let fitnesses = fitnesses.lock().unwrap();
+
+            for arm in [Arm::A, Arm::B] {
+                let fitnesses: Vec<_> = fitnesses
+                    .iter()
+                    .zip(&arms)
+                    .filter(|(_, &animal_arm)| animal_arm == arm)
+                    .map(|(&fitness, _)| fitness)
+                    .collect();
+
+                let stats = summary.stats(arm);
+                let avg = fitnesses.iter().sum::<ga::Float>() / fitnesses.len() as ga::Float;
+
+                assert_eq!(stats.min_fitness, fitnesses.iter().copied().fold(ga::Float::INFINITY, ga::Float::min));
+                assert_eq!(stats.max_fitness, fitnesses.iter().copied().fold(0.0, ga::Float::max));
+                assert!((stats.avg_fitness - avg).abs() < 1e-6);
+            }
+
+            // (0, 7, 5, 3) vs (1, 8, 6, 4, 2)
+            assert_eq!(summary.generations, generation);
+            assert_eq!(summary.b_wins, generation);
+            assert_eq!(summary.a_wins, 0);
+            assert_eq!(summary.win_rate(Arm::B), 1.0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "A/B test requires two fixed, separately evolved arms")]
+    fn rejects_habitats() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Simulation::from_config(
+            SimulationConfig {
+                habitats: Some(Habitats::default()),
+                ..config(10)
+            },
+            &mut rng,
+        );
+    }
+}
@@ -1,63 +1,172 @@
 use crate::*;
 
+/// Identifies a bird for as long as it lives; ids are never reused
+/// within a simulation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AnimalId(pub(crate) u64);
+
 #[derive(Debug)]
 pub struct Animal{
+    // Assigned by the world the bird lives in (see: `World::assign_ids()`)
+    pub(crate) id: AnimalId,
+
+    // Founded by the bird itself, unless it's inherited from its parents
+    // (see: `LineageId`)
+    pub(crate) lineage: LineageId,
     pub(crate) position: na::Point2<f32>,
     pub(crate) rotation: na::Rotation2<f32>,
     pub(crate) speed: f32,
+
+    // Stays at zero unless `SimulationConfig::rotational_inertia` is
+    // enabled
+    pub(crate) angular_velocity: f32,
     pub(crate) eye: Eye,
     pub(crate) brain: Brain,
+    pub(crate) traits: Traits,
+
+    // Stays at zero unless `SimulationConfig::energy` is enabled
+    pub(crate) energy: f32,
+
+    // Displacement since the bird has spawned, as integrated from its own
+    // movements (so: not wrapped around the map's edges)
+    pub(crate) travelled: na::Vector2<f32>,
+
+    // Total length of the path flown since the bird has spawned
+    pub(crate) distance: f32,
 
     // Number of foods eaten by this birdie
     pub(crate) satiation: usize,
+
+    // Number of foods eaten since the bird has left the nursery, which is
+    // what its fitness counts; along with the number of steps it has yet
+    // to spend there, which stays at zero unless `SimulationConfig::nursery`
+    // is enabled
+    pub(crate) scored_satiation: usize,
+    pub(crate) nursery_steps: usize,
+
+    // Both stay at false / zero unless `SimulationConfig::hibernation`
+    // is enabled
+    pub(crate) hibernating: bool,
+    pub(crate) hibernated_steps: usize,
+
+    // Last foreign habitat this bird has crossed into; stays at `None`
+    // unless `SimulationConfig::habitats` is enabled
+    pub(crate) crossed_into: Option<usize>,
+
+    // Arm of the A/B test this bird belongs to; stays at `None` unless
+    // `SimulationConfig::ab_test` is enabled
+    pub(crate) arm: Option<Arm>,
+
+    // Stays empty unless `SimulationConfig::behavior` is enabled
+    pub(crate) behavior: BehaviorTracker,
 }
 
 impl Animal{
-    pub fn random(rng: &mut dyn RngCore) -> Self{
-        let eye = Eye::default();
-        let brain = Brain::random(rng, &eye);
-        Self::new(eye, brain, rng)
+    pub fn random(config: &SimulationConfig, rng: &mut dyn RngCore) -> Self{
+        let eye = config.eye();
+        let brain = Brain::random_with_extras(
+            rng,
+            &eye,
+            config.extra_inputs_len(),
+            config.extra_outputs_len(),
+            &config.brain,
+        );
+        let traits = Traits::random(config, rng);
+        Self::new(config, eye, brain, traits, rng)
     }
 
     /// "Restores" bird from a chromosome.
     ///
     /// We have to have access to the PRNG in here, because our
-    /// chromosomes encode only the brains - and while we restore the
-    /// bird, we have to also randomize its position, direction, etc.
-    /// (so it's stuff that wouldn't make sense to keep in the genome.)
+    /// chromosomes encode only the brains (and traits) - and while we
+    /// restore the bird, we have to also randomize its position,
+    /// direction, etc. (so it's stuff that wouldn't make sense to keep
+    /// in the genome.)
     pub(crate) fn from_chromosome(
+        config: &SimulationConfig,
         chromosome: ga::Chromosome,
         rng: &mut dyn RngCore,
     ) -> Self {
-        let eye = Eye::default();
-        let brain = Brain::from_chromosome(chromosome, &eye);
+        let eye = config.eye();
+        let mut genes: Vec<ga::Float> = chromosome.iter().copied().collect();
 
-        Self::new(eye, brain, rng)
+        let trait_genes = genes.split_off(genes.len().saturating_sub(Traits::genes_len(config)));
+        let traits = Traits::from_genes(config, &trait_genes);
+
+        let brain = Brain::from_chromosome_with_extras(
+            genes.into_iter().collect(),
+            &eye,
+            config.extra_inputs_len(),
+            config.extra_outputs_len(),
+        )
+        .expect("chromosome doesn't match the brain's topology");
+
+        Self::new(config, eye, brain, traits, rng)
+    }
+
+    /// Number of genes in a chromosome of a bird living in given config.
+    pub(crate) fn chromosome_len(config: &SimulationConfig) -> usize {
+        Brain::chromosome_len_with_extras(
+            &config.eye(),
+            config.extra_inputs_len(),
+            config.extra_outputs_len(),
+        )
+            + Traits::genes_len(config)
     }
 
     pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
         // We evolve only our birds' brains, but technically there's no
         // reason not to simulate e.g. physical properties such as size.
         //
-        // If that was to happen, this function could be adjusted to
-        // return a longer chromosome that encodes not only the brain,
-        // but also, say, birdie's color.
+        // That's what traits are for - their genes simply follow the
+        // brain's ones (see: `Traits`).
 
-        self.brain.as_chromosome()
+        self.brain
+            .as_chromosome()
+            .iter()
+            .copied()
+            .chain(self.traits.genes())
+            .collect()
     }
 
-    fn new(eye: Eye, brain: Brain, rng: &mut dyn RngCore) -> Self {
+    fn new(
+        config: &SimulationConfig,
+        eye: Eye,
+        brain: Brain,
+        traits: Traits,
+        rng: &mut dyn RngCore,
+    ) -> Self {
         Self {
+            id: AnimalId(0),
+            lineage: LineageId(0),
             position: rng.gen(),
             rotation: rng.gen(),
             speed: 0.002,
+            angular_velocity: 0.0,
             eye,
             brain,
+            traits,
+            energy: config.energy.map_or(0.0, |energy| energy.initial),
+            travelled: na::Vector2::zeros(),
+            distance: 0.0,
             satiation: 0,
+            scored_satiation: 0,
+            nursery_steps: config.nursery.map_or(0, |nursery| nursery.grace_steps),
+            hibernating: false,
+            hibernated_steps: 0,
+            crossed_into: None,
+            arm: None,
+            behavior: BehaviorTracker::default(),
         }
     }
     
     // Getter functions :
+    pub fn id(&self) -> AnimalId {
+        self.id
+    }
+    pub fn lineage(&self) -> LineageId {
+        self.lineage
+    }
     pub fn position(&self) -> na::Point2<f32>{
         // ------------------------------------------------------------------
         // | No need to return a reference, because na::Point2 is Copy.
@@ -75,4 +184,26 @@ impl Animal{
     pub fn rotation(&self) -> na::Rotation2<f32>{
         self.rotation
     }
+    pub fn traits(&self) -> &Traits {
+        &self.traits
+    }
+
+    /// Number of steps this bird has spent hibernating (see:
+    /// `Hibernation`).
+    pub fn hibernated_steps(&self) -> usize {
+        self.hibernated_steps
+    }
+    pub fn energy(&self) -> f32 {
+        self.energy
+    }
+
+    /// Number of foods eaten outside of the nursery (see: `Nursery`).
+    pub fn scored_satiation(&self) -> usize {
+        self.scored_satiation
+    }
+
+    /// Whether the bird is still within its grace period (see: `Nursery`).
+    pub fn in_nursery(&self) -> bool {
+        self.nursery_steps > 0
+    }
 }
\ No newline at end of file
@@ -1,20 +1,39 @@
 use crate::*;
 
+/// A bird, as seen by the genetic algorithm - see: `GenerationPipeline`.
+#[derive(Clone, Debug)]
 pub struct AnimalIndividual {
-    fitness: f32,
+    fitness: ga::Float,
     chromosome: ga::Chromosome,
 }
 
 impl AnimalIndividual {
-    pub fn from_animal(animal: &Animal) -> Self {
+    pub fn new(chromosome: ga::Chromosome, fitness: ga::Float) -> Self {
         Self {
-            fitness: animal.satiation as f32,
-            chromosome: animal.as_chromosome(),
+            fitness,
+            chromosome,
         }
     }
 
-    pub fn into_animal(self, rng: &mut dyn RngCore) -> Animal {
-        Animal::from_chromosome(self.chromosome, rng)
+    /// Fitness is computed elsewhere - see `Simulation::evaluate()`.
+    pub fn from_animal_with_fitness(animal: &Animal, fitness: f32) -> Self {
+        Self::new(animal.as_chromosome(), widen(fitness))
+    }
+
+    pub fn chromosome(&self) -> &ga::Chromosome {
+        &self.chromosome
+    }
+
+    pub fn fitness(&self) -> ga::Float {
+        self.fitness
+    }
+
+    pub fn set_fitness(&mut self, fitness: ga::Float) {
+        self.fitness = fitness;
+    }
+
+    pub fn into_animal(self, config: &SimulationConfig, rng: &mut dyn RngCore) -> Animal {
+        Animal::from_chromosome(config, self.chromosome, rng)
     }
 }
 
@@ -30,7 +49,7 @@ impl ga::Individual for AnimalIndividual {
         &self.chromosome
     }
 
-    fn fitness(&self) -> f32 {
+    fn fitness(&self) -> ga::Float {
         self.fitness
     }
 }
\ No newline at end of file
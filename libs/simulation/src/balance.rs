@@ -0,0 +1,230 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Keeps the environment's difficulty in check by adjusting the number of
+/// foods at each generation boundary: when the median bird has eaten more
+/// than `max_eaten` foods, there's going to be `rate` less of them in the
+/// next generation - and `rate` more when it has eaten less than
+/// `min_eaten`.
+///
+/// The count always changes by at least one food and stays within
+/// `min_foods..=max_foods`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoBalance {
+    pub min_eaten: f32,
+    pub max_eaten: f32,
+    pub rate: f32,
+    pub min_foods: usize,
+    pub max_foods: usize,
+}
+
+impl AutoBalance {
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(0.0 <= self.min_eaten && self.min_eaten <= self.max_eaten);
+        ensure!(self.rate > 0.0 && self.rate <= 1.0);
+        ensure!(self.min_foods <= self.max_foods);
+
+        Ok(())
+    }
+
+    /// Number of foods the next generation gets, given the current one and
+    /// the median number of foods eaten per bird.
+    pub fn adjust(&self, n_foods: usize, median_eaten: f32) -> usize {
+        let step = ((n_foods as f32 * self.rate).round() as usize).max(1);
+
+        let n_foods = if median_eaten > self.max_eaten {
+            n_foods.saturating_sub(step)
+        } else if median_eaten < self.min_eaten {
+            n_foods + step
+        } else {
+            n_foods
+        };
+
+        n_foods.clamp(self.min_foods, self.max_foods)
+    }
+}
+
+impl Default for AutoBalance {
+    fn default() -> Self {
+        Self {
+            min_eaten: 2.0,
+            max_eaten: 10.0,
+            rate: 0.05,
+            min_foods: 10,
+            max_foods: 200,
+        }
+    }
+}
+
+/// Adjustment made by `AutoBalance`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FoodBalance {
+    /// Median number of foods eaten per bird during the generation that
+    /// has just ended.
+    pub median_eaten: f32,
+
+    /// Number of foods during the generation that has just ended.
+    pub n_foods: usize,
+
+    /// Number of foods during the next generation.
+    pub next_n_foods: usize,
+}
+
+pub(crate) fn median(values: &mut [usize]) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    values.sort_unstable();
+
+    let mid = values.len() / 2;
+
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) as f32 / 2.0
+    } else {
+        values[mid] as f32
+    }
+}
+
+impl Simulation {
+    /// Adds (or removes) foods according to `SimulationConfig::auto_balance`;
+    /// `median_eaten` comes from the generation that has just ended.
+    pub(crate) fn auto_balance(
+        &mut self,
+        median_eaten: f32,
+        rng: &mut dyn RngCore,
+    ) -> Option<FoodBalance> {
+        let balance = self.config.auto_balance?;
+        let n_foods = self.config.n_foods;
+        let next_n_foods = balance.adjust(n_foods, median_eaten);
+
+        self.world.foods.truncate(next_n_foods);
+        self.food_layout.truncate(next_n_foods);
+
+        while self.world.foods.len() < next_n_foods {
+            let food = Food::random(rng);
+
+            self.food_layout.push(food.position);
+            self.world.foods.push(food);
+        }
+
+        self.config.n_foods = next_n_foods;
+
+        Some(FoodBalance {
+            median_eaten,
+            n_foods,
+            next_n_foods,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn balance() -> AutoBalance {
+        AutoBalance {
+            min_eaten: 2.0,
+            max_eaten: 4.0,
+            rate: 0.05,
+            min_foods: 50,
+            max_foods: 70,
+        }
+    }
+
+    #[test]
+    fn food_count_follows_median() {
+        let balance = balance();
+        let medians = [5.0, 5.0, 3.0, 1.0, 1.0, 1.0, 1.0, 1.0, 4.0, 6.0];
+
+        let trajectory: Vec<_> = medians
+            .iter()
+            .scan(60, |n_foods, &median| {
+                *n_foods = balance.adjust(*n_foods, median);
+                Some(*n_foods)
+            })
+            .collect();
+
+        assert_eq!(trajectory, vec![57, 54, 54, 57, 60, 63, 66, 69, 69, 66]);
+    }
+
+    #[test]
+    fn food_count_is_clamped() {
+        let balance = balance();
+
+        assert_eq!(balance.adjust(69, 0.0), 70);
+        assert_eq!(balance.adjust(70, 0.0), 70);
+        assert_eq!(balance.adjust(51, 10.0), 50);
+        assert_eq!(balance.adjust(50, 10.0), 50);
+    }
+
+    #[test]
+    fn small_food_counts_still_change() {
+        let balance = AutoBalance {
+            min_foods: 0,
+            ..balance()
+        };
+
+        assert_eq!(balance.adjust(3, 0.0), 4);
+        assert_eq!(balance.adjust(3, 10.0), 2);
+    }
+
+    #[test]
+    fn median_of_even_and_odd_counts() {
+        assert_eq!(median(&mut []), 0.0);
+        assert_eq!(median(&mut [3, 1, 2]), 2.0);
+        assert_eq!(median(&mut [4, 1, 3, 2]), 2.5);
+    }
+
+    fn train(auto_balance: Option<AutoBalance>) -> (Simulation, Vec<GenerationSummary>) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 10,
+            n_foods: 60,
+            generation_length: 50,
+            food_layout: PersistMode::KeepPositions,
+            auto_balance,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        let summaries = sim.train_many(&mut rng, 3);
+
+        (sim, summaries)
+    }
+
+    #[test]
+    fn simulation_applies_adjustments() {
+        // Nobody's going to eat a thousand foods, so there's going to be
+        // more of them with each generation
+        let (sim, summaries) = train(Some(AutoBalance {
+            min_eaten: 1000.0,
+            max_eaten: 1000.0,
+            ..balance()
+        }));
+
+        let applied: Vec<_> = summaries
+            .iter()
+            .map(|summary| {
+                let balance = summary.food_balance.unwrap();
+                (balance.n_foods, balance.next_n_foods)
+            })
+            .collect();
+
+        assert_eq!(applied, vec![(60, 63), (63, 66), (66, 69)]);
+        assert_eq!(sim.config().n_foods, 69);
+        assert_eq!(sim.world().food().len(), 69);
+    }
+
+    #[test]
+    fn disabled_auto_balance_leaves_foods_alone() {
+        let (sim, summaries) = train(None);
+
+        assert!(summaries.iter().all(|summary| summary.food_balance.is_none()));
+        assert_eq!(sim.world().food().len(), 60);
+    }
+}
@@ -0,0 +1,313 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Classifiers of degenerate strategies evolution likes to get stuck in;
+/// each bird gets classified over its last `window` steps:
+///
+/// - spinner: it has turned by at least `spinner_min_turn` radians (in one
+///   direction), while getting no further than `spinner_max_displacement`
+///   from where it started,
+///
+/// - wall-hugger: at least `wall_fraction` of the window it has spent
+///   within `wall_margin` of the map's edge,
+///
+/// - idler: its mean speed has been at most `idle_speed`.
+///
+/// Birds get flagged only once they've lived for a whole window; steps
+/// spent hibernating don't count. Flags can be inspected through
+/// `Animal::behavior()`, summed in `GenerationSummary::behaviors` and
+/// penalized through `FitnessSignal::Spinning` & co.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BehaviorConfig {
+    pub window: usize,
+    pub spinner_min_turn: f32,
+    pub spinner_max_displacement: f32,
+    pub wall_margin: f32,
+    pub wall_fraction: f32,
+    pub idle_speed: f32,
+}
+
+impl BehaviorConfig {
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(self.window > 0);
+        ensure!(self.spinner_min_turn > 0.0);
+        ensure!(self.spinner_max_displacement >= 0.0);
+        ensure!((0.0..0.5).contains(&self.wall_margin));
+        ensure!((0.0..=1.0).contains(&self.wall_fraction));
+        ensure!(self.idle_speed >= 0.0);
+
+        Ok(())
+    }
+}
+
+impl Default for BehaviorConfig {
+    fn default() -> Self {
+        Self {
+            window: 100,
+            // Two full circles ...
+            spinner_min_turn: 4.0 * std::f32::consts::PI,
+            // ... within a tenth of the best-case distance (`SPEED_MAX`
+            // over the whole window)
+            spinner_max_displacement: 0.04,
+            wall_margin: 0.02,
+            wall_fraction: 0.8,
+            // Birds cannot go slower than `SPEED_MIN` (0.001)
+            idle_speed: 0.0012,
+        }
+    }
+}
+
+/// Degenerate strategies a bird has been classified with; see:
+/// `BehaviorConfig`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BehaviorFlags {
+    pub spinner: bool,
+    pub wall_hugger: bool,
+    pub idler: bool,
+}
+
+impl BehaviorFlags {
+    pub fn any(&self) -> bool {
+        self.spinner || self.wall_hugger || self.idler
+    }
+}
+
+/// Number of birds flagged with each of the strategies at the end of a
+/// generation; a bird can be counted more than once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BehaviorReport {
+    pub spinners: usize,
+    pub wall_huggers: usize,
+    pub idlers: usize,
+}
+
+impl BehaviorReport {
+    pub(crate) fn of(animals: &[Animal]) -> Self {
+        animals
+            .iter()
+            .map(Animal::behavior)
+            .fold(Self::default(), |report, flags| Self {
+                spinners: report.spinners + flags.spinner as usize,
+                wall_huggers: report.wall_huggers + flags.wall_hugger as usize,
+                idlers: report.idlers + flags.idler as usize,
+            })
+    }
+}
+
+/// Single step of a bird's trajectory.
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    movement: na::Vector2<f32>,
+    turn: f32,
+    speed: f32,
+    near_wall: bool,
+}
+
+/// Last steps of a bird's trajectory, along with the flags they've
+/// resulted in.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BehaviorTracker {
+    samples: VecDeque<Sample>,
+    rotation: Option<na::Rotation2<f32>>,
+    flags: BehaviorFlags,
+}
+
+impl BehaviorTracker {
+    /// Records a step after which the bird is at `position`, facing
+    /// `rotation`, having just moved by `movement`.
+    pub(crate) fn observe(
+        &mut self,
+        config: &BehaviorConfig,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        movement: na::Vector2<f32>,
+    ) {
+        let turn = self.rotation.map_or(0.0, |previous| previous.angle_to(&rotation));
+        let wall_distance = position.x.min(1.0 - position.x).min(position.y).min(1.0 - position.y);
+
+        if self.samples.len() == config.window {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back(Sample {
+            movement,
+            turn,
+            speed: movement.norm(),
+            near_wall: wall_distance <= config.wall_margin,
+        });
+
+        self.rotation = Some(rotation);
+        self.flags = self.classify(config);
+    }
+
+    fn classify(&self, config: &BehaviorConfig) -> BehaviorFlags {
+        if self.samples.len() < config.window {
+            return BehaviorFlags::default();
+        }
+
+        let n = self.samples.len() as f32;
+        let turn: f32 = self.samples.iter().map(|sample| sample.turn).sum();
+        let displacement: na::Vector2<f32> = self.samples.iter().map(|sample| sample.movement).sum();
+        let near_wall = self.samples.iter().filter(|sample| sample.near_wall).count() as f32;
+        let speed = self.samples.iter().map(|sample| sample.speed).sum::<f32>() / n;
+
+        BehaviorFlags {
+            spinner: turn.abs() >= config.spinner_min_turn
+                && displacement.norm() <= config.spinner_max_displacement,
+            wall_hugger: near_wall / n >= config.wall_fraction,
+            idler: speed <= config.idle_speed,
+        }
+    }
+}
+
+impl Animal {
+    /// Degenerate strategies this bird has been classified with; all false
+    /// unless `SimulationConfig::behavior` is enabled.
+    pub fn behavior(&self) -> BehaviorFlags {
+        self.behavior.flags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Classifies a trajectory that starts at `start` and where, at each
+    /// step, the bird turns by `turn(step)` and flies `speed(step)`.
+    fn classify(
+        start: (f32, f32),
+        turn: impl Fn(usize) -> f32,
+        speed: impl Fn(usize) -> f32,
+    ) -> BehaviorFlags {
+        let config = BehaviorConfig::default();
+        let mut tracker = BehaviorTracker::default();
+        let mut position = na::Point2::new(start.0, start.1);
+        let mut rotation = na::Rotation2::new(0.0);
+
+        for step in 0..config.window {
+            rotation = na::Rotation2::new(rotation.angle() + turn(step));
+
+            let movement = rotation * na::Vector2::new(0.0, speed(step));
+
+            position += movement;
+            position.x = na::wrap(position.x, 0.0, 1.0);
+            position.y = na::wrap(position.y, 0.0, 1.0);
+
+            tracker.observe(&config, position, rotation, movement);
+        }
+
+        tracker.flags
+    }
+
+    #[test]
+    fn spinner() {
+        let flags = classify((0.5, 0.5), |_| 0.3, |_| 0.002);
+
+        assert_eq!(
+            flags,
+            BehaviorFlags {
+                spinner: true,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn wall_hugger() {
+        // Flies along the left edge
+        let flags = classify((0.01, 0.5), |_| 0.0, |_| 0.003);
+
+        assert_eq!(
+            flags,
+            BehaviorFlags {
+                wall_hugger: true,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn idler() {
+        let flags = classify((0.5, 0.3), |_| 0.0, |_| 0.001);
+
+        assert_eq!(
+            flags,
+            BehaviorFlags {
+                idler: true,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn forager() {
+        // Weaves left and right, speeding up and down
+        let flags = classify(
+            (0.5, 0.2),
+            |step| 0.2 * (step as f32 * 0.2).sin(),
+            |step| 0.003 + 0.001 * (step as f32 * 0.1).cos(),
+        );
+
+        assert_eq!(flags, BehaviorFlags::default());
+    }
+
+    #[test]
+    fn short_lives_get_no_flags() {
+        let config = BehaviorConfig::default();
+        let mut tracker = BehaviorTracker::default();
+
+        for _ in 1..config.window {
+            tracker.observe(&config, na::Point2::new(0.0, 0.0), na::Rotation2::new(0.0), na::Vector2::zeros());
+        }
+
+        assert!(!tracker.flags.any());
+
+        tracker.observe(&config, na::Point2::new(0.0, 0.0), na::Rotation2::new(0.0), na::Vector2::zeros());
+
+        assert!(tracker.flags.wall_hugger && tracker.flags.idler);
+    }
+
+    #[test]
+    fn generation_summary_counts_flags() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 20,
+            generation_length: 150,
+            behavior: Some(BehaviorConfig {
+                // Everybody's an idler
+                idle_speed: 1.0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        let report = sim.train(&mut rng).behaviors.unwrap();
+
+        assert_eq!(report.idlers, 20);
+        assert!(report.spinners < 20);
+
+        // ... while disabled classifiers flag nobody
+        let mut sim = Simulation::from_config(
+            SimulationConfig {
+                n_animals: 20,
+                generation_length: 150,
+                ..Default::default()
+            },
+            &mut rng,
+        );
+
+        for _ in 0..100 {
+            sim.step(&mut rng);
+        }
+
+        assert!(sim.world().animal().iter().all(|animal| !animal.behavior().any()));
+        assert_eq!(sim.train(&mut rng).behaviors, None);
+    }
+}
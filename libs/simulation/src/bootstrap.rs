@@ -0,0 +1,228 @@
+//! Warm-starting the initial population from a hand-coded teacher, so
+//! that evolution doesn't have to spend its first generations discovering
+//! that flying towards food is a good idea.
+
+use crate::*;
+use ga::MutationMethod;
+
+/// Number of sights the teacher demonstrates.
+const SAMPLES: usize = 64;
+
+/// What the teacher does when it sees `vision`.
+struct Lesson {
+    inputs: Vec<f32>,
+
+    /// Speed & rotation.
+    action: [f32; 2],
+}
+
+impl Lesson {
+    /// The teacher's policy: full speed ahead, steering towards the cell
+    /// that's activated the most (i.e. towards the nearest food) - or
+    /// straight ahead, when there's nothing to see.
+    fn teach(config: &SimulationConfig, vision: Vec<f32>) -> Self {
+        let cell_angle = config.fov_angle / config.eye_cells as f32;
+
+        let rotation = vision
+            .iter()
+            .enumerate()
+            .filter(|(_, &activation)| activation > 0.0)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(0.0, |(cell, _)| -config.fov_angle / 2.0 + (cell as f32 + 0.5) * cell_angle);
+
+        let inputs = vision
+            .into_iter()
+            .chain(std::iter::repeat_n(0.0, config.extra_inputs_len()))
+            .collect();
+
+        Self {
+            inputs,
+            action: [
+                config.speed_accel,
+                rotation.clamp(-config.rotation_accel, config.rotation_accel),
+            ],
+        }
+    }
+}
+
+impl Simulation {
+    /// Fits the initial population's brains to a "follow the nearest food"
+    /// teacher: each brain gets `steps` rounds of hill climbing (keeping
+    /// each random mutation that doesn't take it further from what the
+    /// teacher would do) over sights of the current world from random
+    /// spots.
+    ///
+    /// Draws numbers only from `rng`, so it's deterministic per seed; can
+    /// be called only before the first step.
+    pub fn bootstrap_population(&mut self, rng: &mut dyn RngCore, steps: usize) {
+        assert!(
+            self.generation == 0 && self.age == 0,
+            "population can be bootstrapped only before the simulation starts",
+        );
+
+        let config = &self.config;
+        let eye = config.eye();
+
+        let lessons: Vec<_> = (0..SAMPLES)
+            .map(|_| {
+                let vision = eye.process_vision(rng.gen(), rng.gen(), &self.world.foods);
+                Lesson::teach(config, vision)
+            })
+            .collect();
+
+        let mutation = ga::GaussianMutation::new(0.1, 0.5);
+
+        for animal in &mut self.world.animals {
+            let mut best = animal.brain.as_chromosome();
+            let mut best_loss = loss(&animal.brain, &lessons);
+
+            for _ in 0..steps {
+                let mut candidate = best.clone();
+                mutation.mutate(rng, &mut candidate);
+
+                let candidate_loss = loss(&brain(config, candidate.clone()), &lessons);
+
+                if candidate_loss <= best_loss {
+                    (best, best_loss) = (candidate, candidate_loss);
+                }
+            }
+
+            animal.brain = brain(config, best);
+        }
+    }
+}
+
+fn brain(config: &SimulationConfig, chromosome: ga::Chromosome) -> Brain {
+    Brain::from_chromosome_with_extras(
+        chromosome,
+        &config.eye(),
+        config.extra_inputs_len(),
+        config.extra_outputs_len(),
+    )
+    .expect("mutation doesn't change the chromosome's length")
+}
+
+/// Mean squared distance between what `brain` and the teacher do.
+fn loss(brain: &Brain, lessons: &[Lesson]) -> f32 {
+    let total: f32 = lessons
+        .iter()
+        .map(|lesson| {
+            let response = brain.propagate(lesson.inputs.clone());
+
+            lesson
+                .action
+                .iter()
+                .zip(&response)
+                .map(|(expected, actual)| (expected - actual).powi(2))
+                .sum::<f32>()
+        })
+        .sum();
+
+    total / lessons.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn config() -> SimulationConfig {
+        SimulationConfig {
+            n_animals: 20,
+            generation_length: 300,
+            ..Default::default()
+        }
+    }
+
+    fn chromosomes(sim: &Simulation) -> Vec<ga::Chromosome> {
+        sim.world().animal().iter().map(Animal::as_chromosome).collect()
+    }
+
+    #[test]
+    fn teacher_steers_towards_brightest_cell() {
+        let config = SimulationConfig {
+            eye_cells: 3,
+            fov_angle: 1.5,
+            ..Default::default()
+        };
+
+        let lesson = |vision: Vec<f32>| Lesson::teach(&config, vision).action;
+
+        assert_eq!(lesson(vec![0.0, 0.0, 0.0]), [config.speed_accel, 0.0]);
+        assert_eq!(lesson(vec![0.1, 0.9, 0.3]), [config.speed_accel, 0.0]);
+        assert_eq!(lesson(vec![0.1, 0.2, 0.3]), [config.speed_accel, 0.5]);
+        assert_eq!(lesson(vec![0.3, 0.2, 0.1]), [config.speed_accel, -0.5]);
+    }
+
+    #[test]
+    fn bootstrapped_population_starts_fitter() {
+        // A single run of 20 birds is too noisy to compare, so this
+        // averages over a few seeds
+        const SEEDS: u64 = 8;
+
+        let avg_fitness = |steps: Option<usize>| {
+            let total: ga::Float = (0..SEEDS)
+                .map(|seed| {
+                    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                    let mut sim = Simulation::from_config(config(), &mut rng);
+
+                    if let Some(steps) = steps {
+                        sim.bootstrap_population(&mut rng, steps);
+                    }
+
+                    sim.train(&mut rng).stats.avg_fitness
+                })
+                .sum();
+
+            total / SEEDS as ga::Float
+        };
+
+        let random = avg_fitness(None);
+        let bootstrapped = avg_fitness(Some(200));
+
+        assert!(bootstrapped > 1.25 * random, "bootstrapped = {}, random = {}", bootstrapped, random);
+    }
+
+    #[test]
+    fn bootstrapping_is_deterministic_and_keeps_topology() {
+        let bootstrapped = || {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = SimulationConfig {
+                extra_inputs: vec![ExtraInput::Scent],
+                hibernation: Some(Hibernation::default()),
+                ..config()
+            };
+
+            let mut sim = Simulation::from_config(config, &mut rng);
+            let random = chromosomes(&sim);
+
+            sim.bootstrap_population(&mut rng, 20);
+            (random, sim)
+        };
+
+        let (random, sim) = bootstrapped();
+        let expected = Animal::chromosome_len(sim.config());
+
+        for (random, bootstrapped) in random.iter().zip(chromosomes(&sim)) {
+            assert_eq!(bootstrapped.len(), expected);
+            assert!(!random.iter().eq(bootstrapped.iter()));
+        }
+
+        assert!(chromosomes(&sim)
+            .iter()
+            .zip(chromosomes(&bootstrapped().1))
+            .all(|(a, b)| a.iter().eq(b.iter())));
+    }
+
+    #[test]
+    #[should_panic(expected = "population can be bootstrapped only before the simulation starts")]
+    fn rejects_started_simulation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(), &mut rng);
+
+        sim.step(&mut rng);
+        sim.bootstrap_population(&mut rng, 10);
+    }
+}
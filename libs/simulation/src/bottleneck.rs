@@ -0,0 +1,303 @@
+use crate::*;
+
+/// Which birds survive a bottleneck; see: `Simulation::bottleneck()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BottleneckMode {
+    /// The fittest `k` birds survive - just as they are, so this is pure
+    /// elitism.
+    Top,
+
+    /// Random `k` birds survive, regardless of their fitness - as if the
+    /// population got struck by a disaster.
+    Random,
+}
+
+/// Change in the population's size caused by a bottleneck.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BottleneckEvent {
+    /// The population got reduced to the survivors.
+    Shrunk { from: usize, to: usize },
+
+    /// The population got evolved back to its original size.
+    Recovered { from: usize, to: usize },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Bottleneck {
+    k: usize,
+    mode: BottleneckMode,
+    recover_after: usize,
+    phase: Phase,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Phase {
+    /// Waiting for the next generation boundary.
+    Scheduled,
+
+    /// The population has shrunk from `original` and has `remaining`
+    /// generations to go before it recovers.
+    Shrunk { original: usize, remaining: usize },
+}
+
+/// What has to happen at the current generation boundary.
+pub(crate) enum BottleneckTransition {
+    Shrink { k: usize, mode: BottleneckMode },
+    Recover { to: usize },
+}
+
+impl Simulation {
+    /// Schedules a bottleneck: at the next generation boundary, the
+    /// population gets reduced to `k` birds (chosen according to `mode`),
+    /// which then evolve for `recover_after` generations - after which the
+    /// genetic algorithm breeds the population back to its original size.
+    ///
+    /// The hall of fame keeps recording champions throughout, so whatever
+    /// gets lost in the bottleneck can be brought back through
+    /// `Simulation::restart_from_best()`; birds bred by hand take the place
+    /// of the weakest survivors, as they always do (see: `Simulation::breed()`).
+    ///
+    /// Requires a population of variable size, so it can't be combined
+    /// with `SimulationConfig::habitats`, `SimulationConfig::genealogy`,
+    /// `SimulationConfig::population_control` or `SimulationConfig::ab_test`.
+    pub fn bottleneck(&mut self, k: usize, mode: BottleneckMode, recover_after: usize) {
+        assert!(
+            self.config.habitats.is_none()
+                && self.config.genealogy.is_none()
+                && self.config.population_control.is_none()
+                && self.config.ab_test.is_none(),
+            "bottleneck requires a population of variable size",
+        );
+
+        assert!(self.bottleneck.is_none(), "bottleneck is already in progress");
+        assert!(0 < k && k <= self.config.n_animals);
+        assert!(recover_after > 0);
+
+        self.bottleneck = Some(Bottleneck {
+            k,
+            mode,
+            recover_after,
+            phase: Phase::Scheduled,
+        });
+    }
+
+    /// Advances the scheduled bottleneck (if any) through the generation
+    /// boundary that's currently happening.
+    pub(crate) fn bottleneck_transition(&mut self) -> Option<BottleneckTransition> {
+        let bottleneck = self.bottleneck.as_mut()?;
+
+        match &mut bottleneck.phase {
+            Phase::Scheduled => {
+                bottleneck.phase = Phase::Shrunk {
+                    original: self.config.n_animals,
+                    remaining: bottleneck.recover_after,
+                };
+
+                Some(BottleneckTransition::Shrink {
+                    k: bottleneck.k,
+                    mode: bottleneck.mode,
+                })
+            }
+
+            Phase::Shrunk { original, remaining } => {
+                *remaining -= 1;
+
+                if *remaining > 0 {
+                    return None;
+                }
+
+                let to = *original;
+                self.bottleneck = None;
+
+                Some(BottleneckTransition::Recover { to })
+            }
+        }
+    }
+
+    /// Produces the next generation according to `transition`.
+    pub(crate) fn evolve_bottleneck(
+        &self,
+        transition: &BottleneckTransition,
+        rng: &mut dyn RngCore,
+        population: &[AnimalIndividual],
+    ) -> ga::Evolution<AnimalIndividual> {
+        let (k, mode) = match *transition {
+            BottleneckTransition::Shrink { k, mode } => (k, mode),
+            BottleneckTransition::Recover { to } => {
+                return self
+                    .ga
+                    .evolve_to(rng, population, to)
+                    .expect("birds' chromosomes have the same length");
+            }
+        };
+
+        let survivors = match mode {
+            BottleneckMode::Top => {
+                let mut order: Vec<_> = (0..population.len()).collect();
+                order.sort_by(|&a, &b| population[b].fitness().total_cmp(&population[a].fitness()));
+                order.truncate(k);
+                order
+            }
+
+            BottleneckMode::Random => rand::seq::index::sample(rng, population.len(), k).into_vec(),
+        };
+
+        let mut stats = ga::StatisticsBuilder::default();
+
+        for individual in population {
+            stats.observe(individual.fitness());
+        }
+
+        ga::Evolution {
+            population: survivors
+                .iter()
+                .map(|&idx| AnimalIndividual::new(population[idx].chromosome().clone(), 0.0))
+                .collect(),
+            stats: stats.finish(),
+            samples: Vec::new(),
+            parents: survivors.iter().map(|&idx| (idx, idx)).collect(),
+            diagnostics: Default::default(),
+            gene_ages: Vec::new(),
+        }
+    }
+}
+
+impl BottleneckTransition {
+    /// Event this transition results in, given the current population.
+    pub(crate) fn event(&self, from: usize) -> BottleneckEvent {
+        match *self {
+            Self::Shrink { k, .. } => BottleneckEvent::Shrunk { from, to: k },
+            Self::Recover { to } => BottleneckEvent::Recovered { from, to },
+        }
+    }
+}
+
+impl BottleneckEvent {
+    /// Size of the population after this event.
+    pub fn to(&self) -> usize {
+        match *self {
+            Self::Shrunk { to, .. } | Self::Recovered { to, .. } => to,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::{Arc, Mutex};
+
+    fn sim(rng: &mut ChaCha8Rng) -> Simulation {
+        let config = SimulationConfig {
+            n_animals: 10,
+            generation_length: 10,
+            ..Default::default()
+        };
+
+        Simulation::from_config(config, rng)
+    }
+
+    #[test]
+    fn population_follows_scheduled_trajectory() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng);
+
+        sim.bottleneck(3, BottleneckMode::Random, 2);
+
+        let (sizes, events): (Vec<_>, Vec<_>) = (0..5)
+            .map(|_| {
+                let summary = sim.train(&mut rng);
+
+                assert_eq!(sim.world().animal().len(), sim.config().n_animals);
+                (sim.config().n_animals, summary.bottleneck)
+            })
+            .unzip();
+
+        // Sizes of the generations that are about to begin
+        assert_eq!(sizes, vec![3, 3, 10, 10, 10]);
+
+        assert_eq!(
+            events,
+            vec![
+                Some(BottleneckEvent::Shrunk { from: 10, to: 3 }),
+                None,
+                Some(BottleneckEvent::Recovered { from: 3, to: 10 }),
+                None,
+                None,
+            ]
+        );
+
+        assert_eq!(sim.config().n_animals, 10);
+        assert_eq!(sim.world().animal().len(), 10);
+    }
+
+    /// Gives each bird a distinct fitness, remembering the chromosomes of the
+    /// fittest ones (best first).
+    struct Ranked {
+        best: Arc<Mutex<Vec<ga::Chromosome>>>,
+    }
+
+    impl GenerationPipeline for Ranked {
+        fn before_evolution(&mut self, individuals: &mut Vec<AnimalIndividual>) {
+            let n = individuals.len();
+
+            for (idx, individual) in individuals.iter_mut().enumerate() {
+                individual.set_fitness(((idx * 7) % n) as ga::Float);
+            }
+
+            let mut ranked = individuals.clone();
+            ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+            *self.best.lock().unwrap() = ranked.into_iter().map(|individual| individual.chromosome().clone()).collect();
+        }
+    }
+
+    #[test]
+    fn top_mode_keeps_fittest_chromosomes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng);
+        let best = Arc::new(Mutex::new(Vec::new()));
+
+        sim.set_pipeline(Box::new(Ranked { best: best.clone() }));
+        sim.bottleneck(4, BottleneckMode::Top, 1);
+        sim.train(&mut rng);
+
+        let best = best.lock().unwrap();
+        let survivors = sim.world().animal();
+
+        assert_eq!(survivors.len(), 4);
+
+        for (survivor, best) in survivors.iter().zip(best.iter()) {
+            assert!(survivor.as_chromosome().iter().eq(best.iter()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "bottleneck is already in progress")]
+    fn rejects_overlapping_bottlenecks() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng);
+
+        sim.bottleneck(3, BottleneckMode::Top, 2);
+        sim.train(&mut rng);
+        sim.bottleneck(2, BottleneckMode::Top, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "bottleneck requires a population of variable size")]
+    fn rejects_fixed_size_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = Simulation::from_config(
+            SimulationConfig {
+                n_animals: 10,
+                genealogy: Some(GenealogyConfig::default()),
+                ..Default::default()
+            },
+            &mut rng,
+        );
+
+        sim.bottleneck(3, BottleneckMode::Top, 2);
+    }
+}
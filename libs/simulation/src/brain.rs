@@ -1,42 +1,455 @@
 use crate::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
+/// Bird's brain - a neural network wired to the bird's eye.
+///
+/// Brains can be also used outside of the simulation, e.g. to inspect
+/// a champion saved from a previous run:
+///
+/// ```
+/// use lib_genetic_algorithm::{Chromosome, Float};
+/// use lib_simulation::{Brain, Eye};
+/// use std::f32::consts::PI;
+///
+/// let eye = Eye::new(0.25, PI, 3);
+///
+/// // e.g. loaded from a file
+/// let saved: Vec<Float> = vec![0.5; Brain::chromosome_len(&eye)];
+/// let chromosome: Chromosome = saved.into_iter().collect();
+///
+/// let brain = Brain::from_chromosome(chromosome, &eye).unwrap();
+///
+/// // One value per eye cell - here: food straight ahead
+/// let response = brain.propagate(vec![0.0, 1.0, 0.0]);
+///
+/// // Speed & rotation
+/// assert_eq!(response.len(), 2);
+/// ```
 #[derive(Debug)]
 pub struct Brain {
     pub(crate) nn: nn::Network,
 }
 
-impl Brain {
-    pub fn random(_rng: &mut dyn RngCore, eye: &Eye) -> Self {
-        Self {
-            nn: nn::Network::random( &Self::topology(eye)),
+/// How freshly random brains get wired.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BrainConfig {
+    /// Added to the bias of the output neuron controlling speed, so that
+    /// newborn birds drift forward instead of spinning in place.
+    ///
+    /// It's written into the chromosome (see: `Brain::speed_bias_gene()`),
+    /// so evolution is free to undo it.
+    pub init_forward_bias: f32,
+}
+
+impl BrainConfig {
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(self.init_forward_bias.is_finite());
+
+        Ok(())
+    }
+}
+
+/// How a brain gets carried over to an eye with a different number of
+/// cells; see: `Simulation::reconfigure_eye()`.
+///
+/// Either way, the hidden layer follows the new number of inputs: new
+/// hidden neurons start dormant (contributing nothing until mutation wakes
+/// them up), while - when the eye shrinks - the last ones get dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransplantStrategy {
+    /// Weights coming from the eye get linearly interpolated onto the new
+    /// cells, so that the brain responds to a familiar sight (see:
+    /// `Eye::resample_vision()`) roughly the way it used to.
+    Resample,
+
+    /// The hidden layer gets randomized, while the deeper layers stay
+    /// as they are.
+    Reinitialize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BrainError {
+    /// Chromosome doesn't have as many genes as the brain's topology
+    /// requires.
+    ChromosomeLengthMismatch { expected: usize, actual: usize },
+}
+
+impl fmt::Display for BrainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChromosomeLengthMismatch { expected, actual } => write!(
+                f,
+                "chromosome has {} genes, but the brain requires {}",
+                actual, expected,
+            ),
         }
     }
+}
+
+impl std::error::Error for BrainError {}
 
-    pub(crate) fn from_chromosome(
+impl Brain {
+    pub fn random(rng: &mut dyn RngCore, eye: &Eye) -> Self {
+        Self::random_with_config(rng, eye, &BrainConfig::default())
+    }
+
+    pub fn random_with_config(
+        rng: &mut dyn RngCore,
+        eye: &Eye,
+        config: &BrainConfig,
+    ) -> Self {
+        Self::random_with_extras(rng, eye, 0, 0, config)
+    }
+
+    /// Fails unless the chromosome has exactly `chromosome_len()` genes -
+    /// so a chromosome evolved with `ga::SelfAdaptiveMutation`, which
+    /// carries its step sizes after the weights, has to get them dropped
+    /// first (see: `Chromosome::without_sigmas()`); to just look at the
+    /// weights, use `Chromosome::split_at(Brain::chromosome_len(eye))`.
+    pub fn from_chromosome(
         chromosome: ga::Chromosome,
         eye: &Eye,
+    ) -> Result<Self, BrainError> {
+        Self::from_chromosome_with_extras(chromosome, eye, 0, 0)
+    }
+
+    /// Like `random()`, but for a brain that - apart from what the eye
+    /// sees - receives `extra_inputs` more values (see: `ExtraInput`) and
+    /// that, apart from speed & rotation, returns `extra_outputs` more
+    /// values (see: `Hibernation`).
+    pub(crate) fn random_with_extras(
+        rng: &mut dyn RngCore,
+        eye: &Eye,
+        extra_inputs: usize,
+        extra_outputs: usize,
+        config: &BrainConfig,
     ) -> Self {
+        let topology = Self::topology(eye, extra_inputs, extra_outputs);
+        let nn = nn::Network::random(rng, &topology);
+
+        if config.init_forward_bias == 0.0 {
+            return Self { nn };
+        }
+
+        let mut chromosome: ga::Chromosome = nn.weights().collect();
+
+        chromosome.offset_gene(
+            Self::speed_bias_gene_with_extras(eye, extra_inputs, extra_outputs),
+            widen(config.init_forward_bias),
+        );
+
         Self {
+            nn: nn::Network::from_weights(&topology, chromosome),
+        }
+    }
+
+    pub(crate) fn from_chromosome_with_extras(
+        chromosome: ga::Chromosome,
+        eye: &Eye,
+        extra_inputs: usize,
+        extra_outputs: usize,
+    ) -> Result<Self, BrainError> {
+        let expected = Self::chromosome_len_with_extras(eye, extra_inputs, extra_outputs);
+
+        if chromosome.len() != expected {
+            return Err(BrainError::ChromosomeLengthMismatch {
+                expected,
+                actual: chromosome.len(),
+            });
+        }
+
+        Ok(Self {
             nn: nn::Network::from_weights(
-                &Self::topology(eye),
+                &Self::topology(eye, extra_inputs, extra_outputs),
                 chromosome,
             ),
+        })
+    }
+
+    /// Carries this brain, wired to eye `from`, over to eye `to`; see:
+    /// `TransplantStrategy`.
+    pub fn transplant(
+        &self,
+        rng: &mut dyn RngCore,
+        from: &Eye,
+        to: &Eye,
+        strategy: TransplantStrategy,
+    ) -> Self {
+        self.transplant_inputs(rng, &[(0, from.cells(), to.cells())], to, 0, 0, strategy)
+    }
+
+    /// Like `transplant()`, but for a brain wired according to `from` -
+    /// including extra inputs that depend on the eye (see: `ExtraInput::Birds`).
+    pub(crate) fn transplant_with_extras(
+        &self,
+        rng: &mut dyn RngCore,
+        from: &SimulationConfig,
+        to: &SimulationConfig,
+        strategy: TransplantStrategy,
+    ) -> Self {
+        let mut blocks = vec![(0, from.eye_cells, to.eye_cells)];
+        let mut offset = from.eye_cells;
+
+        for input in &from.extra_inputs {
+            if *input == ExtraInput::Birds {
+                blocks.push((offset, from.eye_cells, to.eye_cells));
+            }
+
+            offset += input.len(from);
         }
+
+        self.transplant_inputs(
+            rng,
+            &blocks,
+            &to.eye(),
+            to.extra_inputs_len(),
+            to.extra_outputs_len(),
+            strategy,
+        )
     }
 
-    pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
+    /// Remaps each of `blocks` - `(offset, old_n, new_n)` - of the input
+    /// layer and reshapes the network to fit `to`.
+    fn transplant_inputs(
+        &self,
+        rng: &mut dyn RngCore,
+        blocks: &[(usize, usize, usize)],
+        to: &Eye,
+        extra_inputs: usize,
+        extra_outputs: usize,
+        strategy: TransplantStrategy,
+    ) -> Self {
+        let mut nn = self.nn.clone();
+
+        // Back to front, so that remapping a block doesn't shift the ones
+        // that are yet to be remapped
+        for &(offset, old_n, new_n) in blocks.iter().rev() {
+            nn.remap_inputs_at(offset, old_n, new_n);
+        }
+
+        let [_, hidden, _] = Self::topology(to, extra_inputs, extra_outputs);
+        nn.resize_layer(0, hidden.neurons);
+
+        if strategy == TransplantStrategy::Reinitialize {
+            nn.randomize_layer(rng, 0);
+        }
+
+        Self { nn }
+    }
+
+    pub fn as_chromosome(&self) -> ga::Chromosome {
         self.nn.weights().collect()
     }
 
-    fn topology(eye: &Eye) -> [nn::LayerTopology; 3] {
+    /// Transforms what the eye sees (see: `Eye::process_vision()`) into
+    /// bird's response - that is: speed and rotation.
+    pub fn propagate(&self, vision: Vec<f32>) -> Vec<f32> {
+        narrow_all(self.nn.propagate(widen_all(vision)))
+    }
+
+    /// Number of genes a chromosome of a brain wired to this eye has.
+    pub fn chromosome_len(eye: &Eye) -> usize {
+        Self::chromosome_len_with_extras(eye, 0, 0)
+    }
+
+    /// Index of the gene holding the bias of the output neuron that
+    /// controls speed - e.g. to nudge a seeded population forward with
+    /// `Chromosome::offset_gene()`.
+    pub fn speed_bias_gene(eye: &Eye) -> usize {
+        Self::speed_bias_gene_with_extras(eye, 0, 0)
+    }
+
+    pub(crate) fn speed_bias_gene_with_extras(
+        eye: &Eye,
+        extra_inputs: usize,
+        extra_outputs: usize,
+    ) -> usize {
+        let [_, hidden, outputs] = Self::topology(eye, extra_inputs, extra_outputs);
+
+        // Output neurons come last, speed being the first of them
+        Self::chromosome_len_with_extras(eye, extra_inputs, extra_outputs)
+            - (hidden.neurons + 1) * outputs.neurons
+    }
+
+    pub(crate) fn chromosome_len_with_extras(
+        eye: &Eye,
+        extra_inputs: usize,
+        extra_outputs: usize,
+    ) -> usize {
+        Self::segment_lengths_with_extras(eye, extra_inputs, extra_outputs)
+            .into_iter()
+            .sum()
+    }
+
+    /// Number of genes each of the brain's layers takes, in order of the
+    /// chromosome - first the hidden layer, then the output one.
+    pub fn segment_lengths(eye: &Eye) -> Vec<usize> {
+        Self::segment_lengths_with_extras(eye, 0, 0)
+    }
+
+    pub(crate) fn segment_lengths_with_extras(
+        eye: &Eye,
+        extra_inputs: usize,
+        extra_outputs: usize,
+    ) -> Vec<usize> {
+        Self::topology(eye, extra_inputs, extra_outputs)
+            .windows(2)
+            // each neuron has one bias and one weight per input
+            .map(|layers| (layers[0].neurons + 1) * layers[1].neurons)
+            .collect()
+    }
+
+    fn topology(eye: &Eye, extra_inputs: usize, extra_outputs: usize) -> [nn::LayerTopology; 3] {
+        let inputs = eye.cells() + extra_inputs;
+
         [
             nn::LayerTopology {
-                neurons: eye.cells(),
+                neurons: inputs,
+            },
+            nn::LayerTopology {
+                neurons: 2 * inputs,
             },
             nn::LayerTopology {
-                neurons: 2 * eye.cells(),
+                neurons: 2 + extra_outputs,
             },
-            nn::LayerTopology { neurons: 2 },
         ]
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const BIAS: f32 = 2.0;
+
+    fn mean_speed_after_first_step(init_forward_bias: f32) -> f32 {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 200,
+            brain: BrainConfig { init_forward_bias },
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        sim.step(&mut rng);
+
+        let animals = &sim.world().animals;
+        animals.iter().map(|animal| animal.speed).sum::<f32>() / animals.len() as f32
+    }
+
+    #[test]
+    fn biased_birds_fly_forward() {
+        // Birds start at 0.002 and can reach `SPEED_MAX` (0.004) within a
+        // single step - biased ones mostly do, unbiased ones only about
+        // half of the time
+        assert!(mean_speed_after_first_step(BIAS) > 0.0035);
+        assert!(mean_speed_after_first_step(0.0) < 0.0035);
+    }
+
+    fn speed_bias(brain: &Brain, eye: &Eye) -> ga::Float {
+        brain.as_chromosome()[Brain::speed_bias_gene(eye)]
+    }
+
+    #[test]
+    fn unbiased_brains_keep_initial_range() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let eye = Eye::default();
+
+        for _ in 0..100 {
+            let bias = speed_bias(&Brain::random(&mut rng, &eye), &eye);
+            assert!((-1.0..=1.0).contains(&bias));
+        }
+    }
+
+    #[test]
+    fn bias_survives_chromosome_round_trip() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let eye = Eye::default();
+        let config = BrainConfig { init_forward_bias: BIAS };
+
+        let brain = Brain::random_with_config(&mut rng, &eye, &config);
+        let bias = speed_bias(&brain, &eye);
+
+        assert!((1.0..=3.0).contains(&bias));
+
+        let restored = Brain::from_chromosome(brain.as_chromosome(), &eye).unwrap();
+
+        assert_eq!(speed_bias(&restored, &eye), bias);
+        assert!(restored.as_chromosome().iter().eq(brain.as_chromosome().iter()));
+    }
+
+    #[test]
+    fn speed_bias_gene_accounts_for_extras() {
+        let eye = Eye::new(0.25, 1.0, 3);
+
+        // 3 inputs, 6 hidden neurons and 2 outputs: 24 hidden genes, then
+        // 7 genes per output neuron
+        assert_eq!(Brain::chromosome_len(&eye), 38);
+        assert_eq!(Brain::speed_bias_gene(&eye), 24);
+
+        // 4 inputs, 8 hidden neurons and 3 outputs
+        assert_eq!(Brain::speed_bias_gene_with_extras(&eye, 1, 1), 40);
+    }
+    #[test]
+    fn sigma_tail_has_to_be_dropped() {
+        use ga::MutationMethod;
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let eye = Eye::default();
+        let len = Brain::chromosome_len(&eye);
+        let step_sizes = ga::StepSizes::PerGene;
+
+        let brain = Brain::random(&mut rng, &eye);
+        let mut chromosome = brain.as_chromosome().with_sigmas(0.1, step_sizes.count(len));
+
+        ga::SelfAdaptiveMutation::new(step_sizes).mutate(&mut rng, &mut chromosome);
+
+        let (weights, _) = chromosome.split_at(len);
+        let weights = weights.to_vec();
+
+        assert_eq!(
+            Brain::from_chromosome(chromosome.clone(), &eye).unwrap_err(),
+            BrainError::ChromosomeLengthMismatch {
+                expected: len,
+                actual: 2 * len,
+            },
+        );
+
+        let mutated = Brain::from_chromosome(chromosome.without_sigmas(len), &eye).unwrap();
+
+        assert!(mutated.as_chromosome().iter().eq(weights.iter()));
+        assert!(!mutated.as_chromosome().iter().eq(brain.as_chromosome().iter()));
+    }
+
+    #[test]
+    fn segmented_crossover_keeps_layers_whole() {
+        use ga::CrossoverMethod;
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let eye = Eye::default();
+        let [hidden, _] = Brain::segment_lengths(&eye)[..] else { unreachable!() };
+
+        let parent_a = Brain::random(&mut rng, &eye).as_chromosome();
+        let parent_b = Brain::random(&mut rng, &eye).as_chromosome();
+        let crossover = ga::SegmentedCrossover::new(Brain::segment_lengths(&eye));
+
+        for _ in 0..10 {
+            let child = crossover.crossover(&mut rng, &parent_a, &parent_b).unwrap();
+
+            for layer in [0..hidden, hidden..child.len()] {
+                let genes = |chromosome: &ga::Chromosome| -> Vec<_> {
+                    chromosome.iter().skip(layer.start).take(layer.len()).copied().collect()
+                };
+
+                assert!(genes(&child) == genes(&parent_a) || genes(&child) == genes(&parent_b));
+            }
+
+            assert!(Brain::from_chromosome(child, &eye).is_ok());
+        }
+    }
+}
@@ -0,0 +1,464 @@
+use crate::*;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BreedError {
+    /// There's no bird with this id in the current generation.
+    UnknownAnimal(AnimalId),
+
+    /// Parents' chromosomes have different shapes, so they can't be
+    /// crossed over.
+    IncompatibleParents { parent_a: usize, parent_b: usize },
+
+    /// Breeding that many children would leave no room for the rest of
+    /// the population.
+    TooManyOffspring { queued: usize, capacity: usize },
+
+    /// This parent doesn't have enough energy to pay its share of the
+    /// `BirthCost`.
+    CannotAffordBirth(AnimalId),
+
+    /// Parents belong to different arms of the A/B test (see: `AbTest`).
+    DifferentArms { parent_a: AnimalId, parent_b: AnimalId },
+}
+
+impl fmt::Display for BreedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownAnimal(id) => write!(f, "there's no animal #{}", id.0),
+            Self::IncompatibleParents { parent_a, parent_b } => write!(
+                f,
+                "parents have incompatible chromosomes ({} vs {} genes)",
+                parent_a, parent_b,
+            ),
+            Self::TooManyOffspring { queued, capacity } => write!(
+                f,
+                "cannot queue {} offspring in a population of {}",
+                queued, capacity,
+            ),
+            Self::CannotAffordBirth(id) => {
+                write!(f, "animal #{} cannot afford to breed", id.0)
+            }
+            Self::DifferentArms { parent_a, parent_b } => write!(
+                f,
+                "animals #{} and #{} belong to different arms",
+                parent_a.0, parent_b.0,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BreedError {}
+
+/// Child bred by hand, waiting for the next generation.
+#[derive(Clone, Debug)]
+pub(crate) struct Offspring {
+    pub(crate) id: AnimalId,
+    pub(crate) chromosome: ga::Chromosome,
+
+    /// Indices of the parents in the generation they were bred in.
+    pub(crate) parents: (usize, usize),
+
+    /// Energy passed onto the child by its parents; see: `BirthCost`.
+    pub(crate) energy: Option<f32>,
+}
+
+impl Simulation {
+    /// Breeds two hand-picked birds (e.g. chosen by a visitor), producing
+    /// `count` children through the configured crossover & mutation.
+    ///
+    /// Children don't hatch right away - they get queued and replace the
+    /// least fit birds once the current generation ends; returned ids are
+    /// the ones they'll have then.
+    ///
+    /// When `SimulationConfig::birth_cost` is enabled, parents pay for
+    /// their children right away - see: `BirthCost`.
+    ///
+    /// When `SimulationConfig::ab_test` is enabled, both parents must
+    /// belong to the same arm, whose genetic algorithm breeds the children
+    /// and whose weakest birds they replace.
+    pub fn breed(
+        &mut self,
+        rng: &mut dyn RngCore,
+        parent_a: AnimalId,
+        parent_b: AnimalId,
+        count: usize,
+    ) -> Result<Vec<AnimalId>, BreedError> {
+        let find = |id| {
+            self.world
+                .animals
+                .iter()
+                .position(|animal| animal.id == id)
+                .ok_or(BreedError::UnknownAnimal(id))
+        };
+
+        let parents = (find(parent_a)?, find(parent_b)?);
+        let arm = self.arm_of(parents.0);
+
+        if arm != self.arm_of(parents.1) {
+            return Err(BreedError::DifferentArms { parent_a, parent_b });
+        }
+
+        let parent_a = self.world.animals[parents.0].as_chromosome();
+        let parent_b = self.world.animals[parents.1].as_chromosome();
+
+        if parent_a.len() != parent_b.len() {
+            return Err(BreedError::IncompatibleParents {
+                parent_a: parent_a.len(),
+                parent_b: parent_b.len(),
+            });
+        }
+
+        let queued = self
+            .offspring
+            .iter()
+            .filter(|offspring| self.arm_of(offspring.parents.0) == arm)
+            .count()
+            + count;
+
+        let capacity = match arm {
+            Some(arm) => AbTest::slots(arm, self.config.n_animals).len(),
+            None => self.config.n_animals,
+        };
+
+        if queued > capacity {
+            return Err(BreedError::TooManyOffspring { queued, capacity });
+        }
+
+        let energy = match self.config.birth_cost {
+            Some(birth_cost) => {
+                self.pay_for_births(&birth_cost, parents, count)?;
+                Some(birth_cost.endowment())
+            }
+            None => None,
+        };
+
+        let ids = (0..count)
+            .map(|_| {
+                let id = self.world.next_id();
+                let chromosome = self
+                    .ga_of(arm)
+                    .breed(rng, &parent_a, &parent_b)
+                    .expect("parents' lengths have been checked above");
+
+                self.offspring.push(Offspring {
+                    id,
+                    chromosome,
+                    parents,
+                    energy,
+                });
+
+                id
+            })
+            .collect();
+
+        Ok(ids)
+    }
+
+    /// Charges parents for `count` children; either both parents can
+    /// afford it and they both pay, or nobody pays anything.
+    fn pay_for_births(
+        &mut self,
+        birth_cost: &BirthCost,
+        parents: (usize, usize),
+        count: usize,
+    ) -> Result<(), BreedError> {
+        let cost = birth_cost.cost * count as f32;
+
+        // A bird bred with itself pays for both parents
+        let shares = if parents.0 == parents.1 {
+            vec![(parents.0, cost)]
+        } else {
+            vec![(parents.0, cost / 2.0), (parents.1, cost / 2.0)]
+        };
+
+        for &(parent, share) in &shares {
+            let animal = &self.world.animals[parent];
+
+            if animal.energy < share {
+                return Err(BreedError::CannotAffordBirth(animal.id));
+            }
+        }
+
+        for (parent, share) in shares {
+            self.world.animals[parent].energy -= share;
+        }
+
+        self.births.births += count;
+        self.births.energy_transferred += birth_cost.endowment() * count as f32;
+        self.births.energy_lost += birth_cost.loss() * count as f32;
+
+        Ok(())
+    }
+
+    /// Replaces children in the slots of the least fit birds of the
+    /// generation that has just ended with offspring queued by `.breed()`
+    /// (within their parents' arm, if there's an A/B test going on);
+    /// returns the slots that got replaced, along with the offspring's
+    /// parents.
+    pub(crate) fn place_offspring(
+        &mut self,
+        fitnesses: &[ga::Float],
+        rng: &mut dyn RngCore,
+    ) -> Vec<(usize, (usize, usize))> {
+        if self.offspring.is_empty() {
+            return Vec::new();
+        }
+
+        let mut slots: Vec<_> = (0..fitnesses.len()).collect();
+        slots.sort_by(|&a, &b| fitnesses[a].total_cmp(&fitnesses[b]));
+
+        // The population might have shrunk (see: `PopulationControl`)
+        slots.retain(|&slot| slot < self.world.animals.len());
+
+        std::mem::take(&mut self.offspring)
+            .into_iter()
+            .filter_map(|offspring| {
+                let arm = self.arm_of(offspring.parents.0);
+                let slot = slots.remove(slots.iter().position(|&slot| self.arm_of(slot) == arm)?);
+                let mut animal = Animal::from_chromosome(&self.config, offspring.chromosome, rng);
+                animal.id = offspring.id;
+
+                if let Some(energy) = offspring.energy {
+                    animal.energy = energy;
+                }
+
+                self.world.animals[slot] = animal;
+                Some((slot, offspring.parents))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn sim(rng: &mut dyn RngCore) -> Simulation {
+        let config = SimulationConfig {
+            n_animals: 6,
+            generation_length: 20,
+            ..Default::default()
+        };
+
+        Simulation::from_config(config, rng)
+    }
+
+    fn ids(sim: &Simulation) -> Vec<AnimalId> {
+        sim.world().animal().iter().map(Animal::id).collect()
+    }
+
+    #[test]
+    fn offspring_appear_after_generation_boundary() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng);
+        let parents = ids(&sim);
+
+        let offspring = sim.breed(&mut rng, parents[0], parents[1], 2).unwrap();
+
+        assert!(offspring.iter().all(|id| !ids(&sim).contains(id)));
+
+        sim.train(&mut rng);
+
+        let ids = ids(&sim);
+
+        assert_eq!(ids.len(), 6);
+        assert!(offspring.iter().all(|id| ids.contains(id)));
+        assert!(parents.iter().all(|id| !ids.contains(id)));
+
+        for animal in sim.world().animal() {
+            assert_eq!(animal.as_chromosome().len(), Animal::chromosome_len(sim.config()));
+        }
+    }
+
+    #[test]
+    fn offspring_have_the_right_length() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng);
+        let parents = ids(&sim);
+
+        sim.breed(&mut rng, parents[2], parents[3], 3).unwrap();
+
+        for offspring in &sim.offspring {
+            assert_eq!(offspring.chromosome.len(), Animal::chromosome_len(sim.config()));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_ids_and_incompatible_parents() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng);
+        let parents = ids(&sim);
+
+        assert_eq!(
+            sim.breed(&mut rng, parents[0], AnimalId(1234), 1),
+            Err(BreedError::UnknownAnimal(AnimalId(1234))),
+        );
+
+        // A bird of another "species", with an extra trait gene
+        sim.world.animals[1].traits.sharing = Some(0.5);
+
+        assert!(matches!(
+            sim.breed(&mut rng, parents[0], parents[1], 1),
+            Err(BreedError::IncompatibleParents { .. }),
+        ));
+
+        assert_eq!(
+            sim.breed(&mut rng, parents[2], parents[3], 7),
+            Err(BreedError::TooManyOffspring { queued: 7, capacity: 6 }),
+        );
+    }
+
+    fn paid_sim(rng: &mut dyn RngCore) -> Simulation {
+        let config = SimulationConfig {
+            n_animals: 6,
+            generation_length: 20,
+            energy: Some(EnergyConfig {
+                initial: 1.0,
+                per_step: 0.0,
+                per_food: 0.0,
+            }),
+            birth_cost: Some(BirthCost {
+                cost: 0.4,
+                efficiency: 0.75,
+            }),
+            ..Default::default()
+        };
+
+        Simulation::from_config(config, rng)
+    }
+
+    #[test]
+    fn births_conserve_energy_minus_loss() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = paid_sim(&mut rng);
+        let parents = ids(&sim);
+        let before = sim.total_energy();
+
+        sim.breed(&mut rng, parents[0], parents[1], 2).unwrap();
+
+        // Each of the two children loses 0.25 * 0.4
+        assert_relative_eq!(before - sim.total_energy(), 2.0 * 0.1, epsilon = 1e-5);
+        assert_relative_eq!(sim.world().animal()[0].energy(), 0.6);
+        assert_relative_eq!(sim.world().animal()[1].energy(), 0.6);
+
+        // Breeding with itself, a bird pays for both parents
+        sim.breed(&mut rng, parents[2], parents[2], 1).unwrap();
+        assert_relative_eq!(sim.world().animal()[2].energy(), 0.6);
+
+        let summary = sim.train(&mut rng);
+        let births = summary.births.unwrap();
+
+        assert_eq!(births.births, 3);
+        assert_relative_eq!(births.energy_transferred, 3.0 * 0.3);
+        assert_relative_eq!(births.energy_lost, 3.0 * 0.1);
+
+        let newborns: Vec<_> = sim
+            .world()
+            .animal()
+            .iter()
+            .filter(|animal| animal.energy() != 1.0)
+            .map(Animal::energy)
+            .collect();
+
+        assert_eq!(newborns.len(), 3);
+        assert!(newborns.iter().all(|&energy| energy == 0.3));
+    }
+
+    #[test]
+    fn parents_below_the_cost_never_reproduce() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = paid_sim(&mut rng);
+        let parents = ids(&sim);
+
+        // Needs 0.2 per child
+        sim.world.animals[0].energy = 0.3;
+
+        assert_eq!(
+            sim.breed(&mut rng, parents[0], parents[1], 2),
+            Err(BreedError::CannotAffordBirth(parents[0])),
+        );
+
+        assert_eq!(
+            sim.breed(&mut rng, parents[1], parents[0], 2),
+            Err(BreedError::CannotAffordBirth(parents[0])),
+        );
+
+        // Nobody has paid anything
+        assert_eq!(sim.world().animal()[0].energy(), 0.3);
+        assert_eq!(sim.world().animal()[1].energy(), 1.0);
+        assert!(sim.offspring.is_empty());
+
+        let summary = sim.train(&mut rng);
+
+        assert_eq!(summary.births, Some(BirthReport::default()));
+    }
+
+    #[test]
+    fn free_births_without_cost() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng);
+        let parents = ids(&sim);
+
+        sim.breed(&mut rng, parents[0], parents[1], 2).unwrap();
+
+        assert!(sim.offspring.iter().all(|offspring| offspring.energy.is_none()));
+        assert_eq!(sim.train(&mut rng).births, None);
+    }
+
+    /// Counts how many random numbers have been drawn.
+    struct CountingRng<R> {
+        rng: R,
+        draws: usize,
+    }
+
+    impl<R: RngCore> RngCore for CountingRng<R> {
+        fn next_u32(&mut self) -> u32 {
+            self.draws += 1;
+            self.rng.next_u32()
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.draws += 1;
+            self.rng.next_u64()
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.draws += 1;
+            self.rng.fill_bytes(dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.draws += 1;
+            self.rng.try_fill_bytes(dest)
+        }
+    }
+
+    #[test]
+    fn applies_crossover_and_mutation_count_times() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 6,
+            // So that mutation draws the same numbers for every gene
+            ga: GaParams {
+                mutation_chance: 0.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        let parents = ids(&sim);
+        let genes = Animal::chromosome_len(sim.config());
+
+        let mut rng = CountingRng { rng, draws: 0 };
+        sim.breed(&mut rng, parents[0], parents[1], 4).unwrap();
+
+        // Per gene, crossover draws one number and mutation draws two
+        assert_eq!(rng.draws, 4 * genes * 3);
+    }
+}
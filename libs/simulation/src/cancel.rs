@@ -0,0 +1,239 @@
+use crate::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Flag for stopping `Simulation::train_many_cancellable()` from another
+/// thread - e.g. when the user hits "stop" while training runs in a
+/// worker; clones share the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Clears the flag, so that the token can be used for training again.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Outcome of `Simulation::train_many_cancellable()`.
+#[derive(Clone, Debug)]
+pub struct TrainingRun {
+    /// Summaries of the generations that have been completed, in order.
+    pub summaries: Vec<GenerationSummary>,
+
+    /// Whether training stopped in the middle of a generation - which is
+    /// then left intact, so that stepping (or training) picks it up right
+    /// where it got abandoned.
+    pub abandoned: bool,
+}
+
+impl Simulation {
+    /// Like `train_many()`, but stops as soon as `cancel` gets cancelled:
+    /// the flag is checked at each generation boundary and, given
+    /// `check_every`, additionally after every `check_every`-th step of a
+    /// generation.
+    ///
+    /// Whatever point it stops at, the simulation is left as if it got
+    /// stepped there by hand - so it can be resumed, saved etc.
+    pub fn train_many_cancellable(
+        &mut self,
+        rng: &mut dyn RngCore,
+        generations: usize,
+        cancel: &CancelToken,
+        check_every: Option<usize>,
+    ) -> TrainingRun {
+        assert!(check_every != Some(0));
+
+        let mut summaries = Vec::with_capacity(generations);
+
+        while summaries.len() < generations && !cancel.is_cancelled() {
+            loop {
+                if let Some(summary) = self.step(rng) {
+                    summaries.push(summary);
+                    break;
+                }
+
+                if check_every.is_some_and(|k| self.age.is_multiple_of(k)) && cancel.is_cancelled() {
+                    break;
+                }
+            }
+        }
+
+        TrainingRun {
+            summaries,
+            abandoned: self.age > 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Cancels `token` at the end of the `after`-th generation.
+    struct CancelAfter {
+        token: CancelToken,
+        after: usize,
+    }
+
+    impl GenerationPipeline for CancelAfter {
+        fn before_evolution(&mut self, _: &mut Vec<AnimalIndividual>) {
+            self.after -= 1;
+
+            if self.after == 0 {
+                self.token.cancel();
+            }
+        }
+    }
+
+    fn sim(rng: &mut ChaCha8Rng, generation_length: usize) -> Simulation {
+        let config = SimulationConfig {
+            n_animals: 10,
+            generation_length,
+            ..Default::default()
+        };
+
+        Simulation::from_config(config, rng)
+    }
+
+    fn avg_fitnesses(summaries: &[GenerationSummary]) -> Vec<ga::Float> {
+        summaries.iter().map(|summary| summary.stats.avg_fitness).collect()
+    }
+
+    #[test]
+    fn cancelling_at_boundary_keeps_completed_generations() {
+        let uninterrupted = {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            sim(&mut rng, 20).train_many(&mut rng, 10)
+        };
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng, 20);
+        let token = CancelToken::new();
+
+        sim.set_pipeline(Box::new(CancelAfter {
+            token: token.clone(),
+            after: 3,
+        }));
+
+        let run = sim.train_many_cancellable(&mut rng, 10, &token, Some(5));
+
+        assert_eq!(run.summaries.len(), 3);
+        assert!(!run.abandoned);
+        assert_eq!(sim.generation(), 3);
+        assert_eq!(sim.age(), 0);
+
+        // Resuming carries on just as if nothing happened
+        sim.set_pipeline(Box::new(DefaultPipeline));
+        token.reset();
+
+        let resumed = sim.train_many_cancellable(&mut rng, 7, &token, Some(5));
+
+        assert_eq!(resumed.summaries.len(), 7);
+        assert!(!resumed.abandoned);
+
+        assert_eq!(
+            avg_fitnesses(&[run.summaries, resumed.summaries].concat()),
+            avg_fitnesses(&uninterrupted)
+        );
+    }
+
+    #[test]
+    fn cancelled_token_stops_before_first_step() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng, 20);
+        let token = CancelToken::new();
+
+        token.cancel();
+
+        let run = sim.train_many_cancellable(&mut rng, 10, &token, None);
+
+        assert!(run.summaries.is_empty());
+        assert!(!run.abandoned);
+        assert_eq!((sim.generation(), sim.age()), (0, 0));
+    }
+
+    /// Cancels `token` (once) as soon as `draws` random numbers have been
+    /// drawn - i.e. somewhere in the middle of a step.
+    struct CancellingRng {
+        rng: ChaCha8Rng,
+        token: CancelToken,
+        draws: usize,
+    }
+
+    impl CancellingRng {
+        fn draw(&mut self) {
+            if self.draws == 1 {
+                self.token.cancel();
+            }
+
+            self.draws = self.draws.saturating_sub(1);
+        }
+    }
+
+    impl RngCore for CancellingRng {
+        fn next_u32(&mut self) -> u32 {
+            self.draw();
+            self.rng.next_u32()
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.draw();
+            self.rng.next_u64()
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.draw();
+            self.rng.fill_bytes(dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.draw();
+            self.rng.try_fill_bytes(dest)
+        }
+    }
+
+    #[test]
+    fn cancelling_mid_generation_leaves_it_resumable() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng, 10_000);
+        let token = CancelToken::new();
+
+        let mut rng = CancellingRng {
+            rng,
+            token: token.clone(),
+            draws: 100,
+        };
+
+        let run = sim.train_many_cancellable(&mut rng, 3, &token, Some(7));
+
+        assert!(run.summaries.is_empty());
+        assert!(run.abandoned);
+        assert_eq!(sim.generation(), 0);
+        assert!(0 < sim.age() && sim.age() < 10_000, "age = {}", sim.age());
+        assert!(sim.age().is_multiple_of(7), "age = {}", sim.age());
+
+        // The rest of the generation is still there to be trained through
+        token.reset();
+
+        let resumed = sim.train_many_cancellable(&mut rng, 1, &token, Some(7));
+
+        assert_eq!(resumed.summaries.len(), 1);
+        assert_eq!(resumed.summaries[0].generation, 0);
+        assert_eq!((sim.generation(), sim.age()), (1, 0));
+    }
+}
@@ -0,0 +1,637 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Knobs of the simulation.
+///
+/// `SimulationConfig::default()` reproduces the values this simulation
+/// has always been running with - see the constants they're taken from
+/// for the rationale behind each one.
+///
+/// Configs can be loaded from JSON (see: `SimulationConfig::from_json()`),
+/// where missing fields fall back to their defaults.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulationConfig {
+    /// Number of birds living in the world; at least one is required,
+    /// since there's nothing to evolve otherwise.
+    pub n_animals: usize,
+
+    /// Number of foods scattered around the world; zero is allowed (e.g.
+    /// to test pure exploration), but then everybody's fitness is zero.
+    pub n_foods: usize,
+
+    /// See: [`GENERATION_LENGTH`].
+    pub generation_length: usize,
+
+    /// See: [`SPEED_MIN`].
+    pub speed_min: f32,
+
+    /// See: [`SPEED_MAX`].
+    pub speed_max: f32,
+
+    /// See: [`SPEED_ACCEL`].
+    pub speed_accel: f32,
+
+    /// See: [`ROTATION_ACCEL`].
+    pub rotation_accel: f32,
+
+    /// Radius of a bird; a bird eats a food when they overlap, that is
+    /// when `distance <= bird_size + food_size`.
+    pub bird_size: f32,
+
+    /// Radius of a food.
+    pub food_size: f32,
+
+    /// What happens to foods when a generation ends.
+    pub food_layout: PersistMode,
+
+    /// Adjusts `n_foods` at each generation boundary; disabled by default.
+    /// See: `AutoBalance`.
+    pub auto_balance: Option<AutoBalance>,
+
+    /// Adjusts `n_animals` at each generation boundary, depending on how
+    /// long steps take; disabled by default. See: `PopulationControl`.
+    pub population_control: Option<PopulationControl>,
+
+    /// See: [`FOV_RANGE`].
+    pub fov_range: f32,
+
+    /// See: [`FOV_ANGLE`].
+    pub fov_angle: f32,
+
+    /// See: [`CELLS`].
+    pub eye_cells: usize,
+
+    /// Inputs the brain receives on top of what the eye sees; none by
+    /// default. See: `ExtraInput`.
+    pub extra_inputs: Vec<ExtraInput>,
+
+    /// Distance over which each food's contribution to `ExtraInput::Scent`
+    /// falls by a factor of `e`.
+    pub scent_range: f32,
+
+    /// What `ExtraInput::Birds` tells about the birds it sees.
+    pub bird_vision: BirdVision,
+
+    /// How the brains of random birds get initialized.
+    pub brain: BrainConfig,
+
+    /// Parameters of the genetic algorithm.
+    pub ga: GaParams,
+
+    /// How birds get scored at the end of each generation.
+    pub evaluation: Evaluation,
+
+    /// How birds' fitness gets computed out of what they've achieved;
+    /// `None` (the default) scores them by the number of foods eaten.
+    pub fitness: Option<FitnessComposer>,
+
+    /// Classifiers of degenerate strategies, such as spinning in place;
+    /// disabled by default. See: `BehaviorConfig`.
+    pub behavior: Option<BehaviorConfig>,
+
+    /// Capacity of the event log; disabled by default. See:
+    /// `Simulation::drain_events()`.
+    pub event_log: Option<usize>,
+
+    /// Recording of transitions for offline reinforcement learning;
+    /// disabled by default. See: `Simulation::export_transitions()`.
+    pub transitions: Option<TransitionConfig>,
+
+    /// Caps on the memory taken by components that keep growing as the
+    /// simulation runs (genealogy, transitions, event log); disabled by
+    /// default. See: `MemoryBudget`.
+    pub memory_budget: Option<MemoryBudget>,
+
+    /// Splitting of the population into two separately evolved arms;
+    /// disabled by default. See: `AbTest`.
+    pub ab_test: Option<AbTest>,
+
+    /// Genealogy tracking; disabled by default. See: `GenealogyConfig`.
+    pub genealogy: Option<GenealogyConfig>,
+
+    /// Whether to track how long each gene has survived unchanged;
+    /// disabled by default. See: `Simulation::gene_age_profile()`.
+    pub gene_ages: bool,
+
+    /// How many of the all-time best birds to remember; see:
+    /// `Simulation::hall_of_fame()`.
+    pub hall_of_fame_size: usize,
+
+    /// How `Simulation::restart_from_best()` repopulates the world and
+    /// whether it gets triggered automatically; see: `RestartPolicy`.
+    pub restart: RestartPolicy,
+
+    /// Whether (and how) to measure the effect of mutation on fitness;
+    /// disabled by default, since it costs extra evaluations.
+    pub mutation_impact: Option<MutationImpact>,
+
+    /// Turning inertia; disabled by default, in which case the brain
+    /// turns the bird directly (by at most `rotation_accel` per step).
+    /// See: `RotationalInertia`.
+    pub rotational_inertia: Option<RotationalInertia>,
+
+    /// Day/night cycle of the birds' field of view; disabled by default.
+    /// See: `EnvironmentCycle`.
+    pub environment_cycle: Option<EnvironmentCycle>,
+
+    /// Energy model; disabled by default. See: `EnergyConfig`.
+    pub energy: Option<EnergyConfig>,
+
+    /// Hibernation gate, which adds an output to every brain; disabled by
+    /// default. See: `Hibernation`.
+    pub hibernation: Option<Hibernation>,
+
+    /// Energy sharing between touching birds, which also adds the
+    /// "sharing" trait gene to every chromosome; disabled by default.
+    /// See: `SharingConfig`.
+    pub sharing: Option<SharingConfig>,
+
+    /// Energy price of breeding by hand; disabled by default, in which
+    /// case `Simulation::breed()` is free. See: `BirthCost`.
+    pub birth_cost: Option<BirthCost>,
+
+    /// Grace period during which birds' foods don't count towards their
+    /// fitness; disabled by default. See: `Nursery`.
+    pub nursery: Option<Nursery>,
+
+    /// Separate sub-populations confined to their own parts of the map;
+    /// disabled by default. See: `Habitats`.
+    pub habitats: Option<Habitats>,
+}
+
+/// Each generation, a `sample_rate` fraction of children gets evaluated
+/// twice - with and without mutation - in an isolated world (see:
+/// `evaluate_isolated()`) lasting `steps` steps and derived from `seed`;
+/// the mean difference is reported as `GenerationSummary::mutation_delta`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MutationImpact {
+    pub sample_rate: f32,
+    pub steps: usize,
+    pub seed: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RestartPolicy {
+    /// Fraction of the restarted population that's entirely random; the
+    /// rest comes from the hall of fame - each champion once as it is,
+    /// then as mutated variants.
+    pub random_fraction: f32,
+
+    /// Whether to restart automatically; disabled by default.
+    pub auto: Option<AutoRestart>,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            random_fraction: 0.2,
+            auto: None,
+        }
+    }
+}
+
+/// Restarts the simulation once max fitness has stayed below `threshold`
+/// for `generations` generations in a row - e.g. when the population has
+/// collapsed onto birds spinning in place.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AutoRestart {
+    pub threshold: f32,
+    pub generations: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GaParams {
+    /// Probability of mutating a gene, passed to `ga::GaussianMutation`.
+    pub mutation_chance: f32,
+
+    /// Magnitude of a mutation, passed to `ga::GaussianMutation`.
+    pub mutation_coeff: f32,
+
+    /// How much genes shrink after each mutation, passed to
+    /// `ga::WeightDecayMutation`; 0.0 disables the decay.
+    pub weight_decay: f32,
+
+    /// How many children each selected pair of parents gets, passed to
+    /// `ga::GeneticAlgorithm::with_brood_size()`.
+    pub brood_size: usize,
+
+    /// Multipliers of `mutation_coeff` for each of the brain's layers -
+    /// the hidden one, then the output one (see: `Brain::segment_lengths()`
+    /// and `ga::LayerScaledMutation`); empty (the default) mutates all the
+    /// layers alike. Trait genes always get the plain `mutation_coeff`.
+    pub layer_scales: Vec<f32>,
+
+    /// Whether children have two parents or are mutated clones of a
+    /// single one, passed to `ga::GeneticAlgorithm::with_reproduction()`;
+    /// serialized as `"Sexual"` or `"Asexual"`.
+    #[serde(with = "ReproductionModeDef")]
+    pub reproduction: ga::ReproductionMode,
+
+    /// Whether a bird can get selected as both parents of a child, passed
+    /// to `ga::GeneticAlgorithm::with_self_mating()`.
+    pub self_mating: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "ga::ReproductionMode")]
+enum ReproductionModeDef {
+    Sexual,
+    Asexual,
+}
+
+impl GaParams {
+    pub fn validate(&self) {
+        if let Err(err) = self.try_validate() {
+            panic!("{}", err);
+        }
+    }
+
+    /// Like `validate()`, but returns an error instead of panicking.
+    pub fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!((0.0..=1.0).contains(&self.mutation_chance));
+        ensure!(self.mutation_coeff >= 0.0);
+        ensure!((0.0..=0.1).contains(&self.weight_decay));
+        ensure!(self.brood_size > 0);
+        ensure!(self.layer_scales.is_empty() || self.layer_scales.len() == 2);
+        ensure!(self.layer_scales.iter().all(|&scale| scale >= 0.0));
+
+        Ok(())
+    }
+}
+
+impl Default for GaParams {
+    fn default() -> Self {
+        Self {
+            mutation_chance: 0.01,
+            mutation_coeff: 0.3,
+            weight_decay: 0.0,
+            brood_size: 1,
+            layer_scales: Vec::new(),
+            reproduction: ga::ReproductionMode::Sexual,
+            self_mating: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Evaluation {
+    /// Birds are scored by how much food they've eaten in the shared
+    /// world - which means a mediocre bird flying next to a great one
+    /// might get starved.
+    #[default]
+    Shared,
+
+    /// Each bird gets scored alone, in its own small world that lasts
+    /// for `steps` steps; all birds of a given generation see the same
+    /// food layout, derived from `seed` and the generation number.
+    ///
+    /// See: `evaluate_isolated()`.
+    Isolated { steps: usize, seed: u64 },
+}
+
+/// Determines food positions at the beginning of each generation; within
+/// a generation, eaten foods respawn at random positions regardless.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PersistMode {
+    /// Foods stay wherever they were when the previous generation ended.
+    #[default]
+    Carry,
+
+    /// Foods get scattered anew, so each generation faces a different
+    /// (and differently difficult) layout.
+    Rerandomize,
+
+    /// Foods get put back into the layout the simulation has started
+    /// with, so all generations face the same one.
+    KeepPositions,
+
+    /// Like `KeepPositions`, but the layout gets re-rolled every `k`
+    /// generations (that is: at generations `k, 2k, 3k, ...`).
+    KeepForK(usize),
+}
+
+impl SimulationConfig {
+    /// Panics if the config describes a world that cannot be simulated;
+    /// follows the same `assert!()` convention as e.g. `Eye::new()`.
+    pub fn validate(&self) {
+        if let Err(err) = self.try_validate() {
+            panic!("{}", err);
+        }
+    }
+
+    /// Like `validate()`, but returns an error instead of panicking - e.g.
+    /// for configs coming from users.
+    pub fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(self.n_animals > 0, "simulation requires at least one animal");
+        ensure!(self.generation_length > 0);
+        ensure!(self.speed_min > 0.0);
+        ensure!(self.speed_min <= self.speed_max);
+        ensure!(self.speed_accel >= 0.0);
+        ensure!(self.rotation_accel >= 0.0);
+        ensure!(self.bird_size >= 0.0);
+        ensure!(self.food_size >= 0.0);
+        ensure!(self.fov_range > 0.0);
+        ensure!(self.fov_angle > 0.0);
+        ensure!(self.eye_cells > 0);
+        ensure!(self.scent_range > 0.0);
+        self.brain.try_validate()?;
+        self.ga.try_validate()?;
+
+        if let Evaluation::Isolated { steps, .. } = self.evaluation {
+            ensure!(steps > 0);
+        }
+
+        if let PersistMode::KeepForK(k) = self.food_layout {
+            ensure!(k > 0);
+        }
+
+        if let Some(balance) = self.auto_balance {
+            balance.try_validate()?;
+        }
+
+        if let Some(control) = self.population_control {
+            ensure!(
+                self.habitats.is_none() && self.genealogy.is_none(),
+                "population control requires a population of variable size",
+            );
+
+            control.try_validate()?;
+        }
+
+        if let Some(capacity) = self.event_log {
+            ensure!(capacity > 0);
+        }
+
+        if let Some(transitions) = self.transitions {
+            transitions.try_validate()?;
+        }
+
+        if let Some(fitness) = &self.fitness {
+            fitness.try_validate()?;
+
+            ensure!(
+                self.behavior.is_some() || !fitness.terms.iter().any(|term| term.signal.is_behavior()),
+                "behavior signals require behavior classifiers",
+            );
+        }
+
+        if let Some(behavior) = self.behavior {
+            behavior.try_validate()?;
+        }
+
+        ensure!((0.0..=1.0).contains(&self.restart.random_fraction));
+
+        if let Some(auto) = self.restart.auto {
+            ensure!(auto.generations > 0);
+        }
+
+        if let Some(impact) = self.mutation_impact {
+            ensure!((0.0..=1.0).contains(&impact.sample_rate));
+            ensure!(impact.steps > 0);
+        }
+
+        if let Some(inertia) = self.rotational_inertia {
+            inertia.try_validate()?;
+        }
+
+        if let Some(cycle) = self.environment_cycle {
+            cycle.try_validate()?;
+        }
+
+        if let Some(hibernation) = self.hibernation {
+            hibernation.try_validate()?;
+        }
+
+        if let Some(energy) = self.energy {
+            ensure!(energy.initial >= 0.0);
+            ensure!(energy.per_step >= 0.0);
+            ensure!(energy.per_food >= 0.0);
+        }
+
+        if let Some(sharing) = self.sharing {
+            ensure!(self.energy.is_some(), "energy sharing requires the energy model");
+            sharing.try_validate()?;
+        }
+
+        if let Some(birth_cost) = self.birth_cost {
+            ensure!(self.energy.is_some(), "birth cost requires the energy model");
+            birth_cost.try_validate()?;
+        }
+
+        if let Some(habitats) = &self.habitats {
+            habitats.try_validate(self.n_animals)?;
+        }
+
+        if let Some(ab_test) = &self.ab_test {
+            ensure!(
+                self.habitats.is_none() && self.population_control.is_none() && self.restart.auto.is_none(),
+                "A/B test requires two fixed, separately evolved arms",
+            );
+
+            ab_test.try_validate(self.n_animals)?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads a config from JSON; the config still has to be validated
+    /// (which `Simulation::from_config()` does anyway).
+    pub fn from_json(bytes: &[u8]) -> Result<Self, ConfigError> {
+        serde_json::from_slice(bytes).map_err(|err| ConfigError::Malformed(err.to_string()))
+    }
+
+    pub fn to_json(&self) -> Vec<u8> {
+        serde_json::to_vec_pretty(self).expect("config should always serialize")
+    }
+
+    /// Number of values `extra_inputs` add to the brain's input layer.
+    pub(crate) fn extra_inputs_len(&self) -> usize {
+        self.extra_inputs.iter().map(|input| input.len(self)).sum()
+    }
+
+    /// Number of values the brain returns on top of speed & rotation.
+    pub(crate) fn extra_outputs_len(&self) -> usize {
+        self.hibernation.is_some() as usize
+    }
+
+    /// Lengths of the chromosome's segments - brain layers, then traits -
+    /// along with how strongly `ga` mutates each; see: `GaParams::layer_scales`.
+    pub(crate) fn mutation_scales(&self, ga: &GaParams) -> Vec<(usize, ga::Float)> {
+        let layers = Brain::segment_lengths_with_extras(&self.eye(), self.extra_inputs_len(), self.extra_outputs_len());
+
+        layers
+            .into_iter()
+            .enumerate()
+            .map(|(layer, len)| (len, widen(ga.layer_scales.get(layer).copied().unwrap_or(1.0))))
+            .chain([(Traits::genes_len(self), 1.0)])
+            .collect()
+    }
+
+    pub(crate) fn eye(&self) -> Eye {
+        Eye::new(self.fov_range, self.fov_angle, self.eye_cells)
+    }
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            n_animals: 40,
+            n_foods: 60,
+            generation_length: GENERATION_LENGTH,
+            speed_min: SPEED_MIN,
+            speed_max: SPEED_MAX,
+            speed_accel: SPEED_ACCEL,
+            rotation_accel: ROTATION_ACCEL,
+            // Both add up to the 0.01 eating distance we've always had;
+            // happens to match what the frontend draws, too
+            bird_size: 0.005,
+            food_size: 0.005,
+            food_layout: PersistMode::default(),
+            auto_balance: None,
+            population_control: None,
+            fov_range: FOV_RANGE,
+            fov_angle: FOV_ANGLE,
+            eye_cells: CELLS,
+            extra_inputs: Vec::new(),
+            scent_range: 0.1,
+            bird_vision: BirdVision::default(),
+            brain: BrainConfig::default(),
+            ga: GaParams::default(),
+            evaluation: Evaluation::default(),
+            fitness: None,
+            behavior: None,
+            event_log: None,
+            transitions: None,
+            memory_budget: None,
+            ab_test: None,
+            genealogy: None,
+            gene_ages: false,
+            hall_of_fame_size: 10,
+            restart: RestartPolicy::default(),
+            mutation_impact: None,
+            rotational_inertia: None,
+            environment_cycle: None,
+            energy: None,
+            hibernation: None,
+            sharing: None,
+            birth_cost: None,
+            nursery: None,
+            habitats: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigError {
+    /// Given bytes are not a JSON-encoded `SimulationConfig`.
+    Malformed(String),
+
+    /// Config describes a world that cannot be simulated; see:
+    /// `SimulationConfig::try_validate()`.
+    Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(err) => write!(f, "malformed config: {}", err),
+            Self::Invalid(err) => write!(f, "invalid config: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let config = SimulationConfig {
+            n_animals: 3,
+            evaluation: Evaluation::Isolated { steps: 10, seed: 5 },
+            energy: Some(EnergyConfig::default()),
+            ..Default::default()
+        };
+
+        assert_eq!(SimulationConfig::from_json(&config.to_json()), Ok(config));
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let config = SimulationConfig::from_json(br#"{ "n_foods": 7, "ga": { "weight_decay": 0.01 } }"#).unwrap();
+
+        assert_eq!(
+            config,
+            SimulationConfig {
+                n_foods: 7,
+                ga: GaParams {
+                    weight_decay: 0.01,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn reproduction_mode_round_trips_through_json() {
+        let config = SimulationConfig::from_json(br#"{ "ga": { "reproduction": "Asexual" } }"#).unwrap();
+
+        assert_eq!(config.ga.reproduction, ga::ReproductionMode::Asexual);
+        assert_eq!(SimulationConfig::from_json(&config.to_json()), Ok(config));
+    }
+
+    #[test]
+    fn mutation_scales_cover_chromosome() {
+        let config = SimulationConfig {
+            sharing: Some(Default::default()),
+            ga: GaParams {
+                layer_scales: vec![0.5, 2.0],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let eye = config.eye();
+        let layers = Brain::segment_lengths(&eye);
+
+        assert_eq!(
+            config.mutation_scales(&config.ga),
+            vec![(layers[0], 0.5), (layers[1], 2.0), (1, 1.0)],
+        );
+
+        assert_eq!(
+            config.mutation_scales(&config.ga).iter().map(|(len, _)| len).sum::<usize>(),
+            Brain::chromosome_len(&eye) + 1,
+        );
+    }
+
+    #[test]
+    fn reports_invalid_configs() {
+        let config = SimulationConfig {
+            speed_min: 0.0,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.try_validate(),
+            Err(ConfigError::Invalid("assertion failed: self.speed_min > 0.0".into())),
+        );
+
+        assert_eq!(SimulationConfig::default().try_validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        assert!(matches!(
+            SimulationConfig::from_json(b"{ \"n_animals\": \"lots\" }"),
+            Err(ConfigError::Malformed(_)),
+        ));
+    }
+}
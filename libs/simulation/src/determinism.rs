@@ -0,0 +1,279 @@
+//! Guardrail against accidental non-determinism.
+//!
+//! Given the same config and seed, two simulations are supposed to stay
+//! identical forever - which breaks easily, e.g. when a feature draws a
+//! random number only in some conditional path of one of them. `verify()`
+//! catches that by running two simulations in lockstep and comparing
+//! them as they go:
+//!
+//! ```
+//! use lib_simulation::{determinism, SimulationConfig};
+//!
+//! let config = SimulationConfig {
+//!     n_animals: 5,
+//!     generation_length: 20,
+//!     ..Default::default()
+//! };
+//!
+//! determinism::verify(&config, 1234, 50).unwrap();
+//! ```
+
+use crate::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// Step after which the simulations stopped being identical; 0 means
+    /// "right after they got created".
+    pub step: usize,
+
+    /// First field that differs, e.g. `animals[3].rotation`.
+    pub field: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "simulations diverged at step {} ({})", self.step, self.field)
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// Runs two simulations with the same `config` and `seed` for `steps`
+/// steps, comparing them after each one; see: `verify_every()`.
+pub fn verify(config: &SimulationConfig, seed: u64, steps: usize) -> Result<(), Divergence> {
+    verify_every(config, seed, steps, 1)
+}
+
+/// Like `verify()`, but compares the simulations only every `every` steps
+/// (and after the last one), which is a lot faster for larger worlds.
+///
+/// Apart from the world state, the number of random numbers each
+/// simulation has drawn gets compared too - so divergences get caught
+/// even before they have any visible effect.
+pub fn verify_every(
+    config: &SimulationConfig,
+    seed: u64,
+    steps: usize,
+    every: usize,
+) -> Result<(), Divergence> {
+    assert!(every > 0);
+
+    let mut a = Replica::new(config, seed);
+    let mut b = Replica::new(config, seed);
+
+    compare(0, &a, &b)?;
+
+    for step in 1..=steps {
+        a.step();
+        b.step();
+
+        if step.is_multiple_of(every) || step == steps {
+            compare(step, &a, &b)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Identifies the current population by its genes (FNV-1a over all the
+/// birds' chromosomes, slot by slot) - e.g. to pin down where a seeded run
+/// ends up, as `tests/golden_run.rs` does.
+///
+/// The fingerprint depends on `ga::Float`, so it differs between builds
+/// with and without `precision-f64`.
+pub fn population_fingerprint(sim: &Simulation) -> u64 {
+    hall_of_fame::fnv1a(sim.world.animals.iter().flat_map(Animal::as_chromosome))
+}
+
+struct Replica {
+    sim: Simulation,
+    rng: CountingRng<ChaCha8Rng>,
+}
+
+impl Replica {
+    fn new(config: &SimulationConfig, seed: u64) -> Self {
+        let mut rng = CountingRng::new(ChaCha8Rng::seed_from_u64(seed));
+        let sim = Simulation::from_config(config.clone(), &mut rng);
+
+        Self { sim, rng }
+    }
+
+    fn step(&mut self) {
+        self.sim.step(&mut self.rng);
+    }
+}
+
+fn compare(step: usize, a: &Replica, b: &Replica) -> Result<(), Divergence> {
+    let field = if a.rng.draws != b.rng.draws {
+        Some("rng.draws".to_string())
+    } else if (a.sim.generation, a.sim.age) != (b.sim.generation, b.sim.age) {
+        Some("age".to_string())
+    } else {
+        a.sim.world.state_diff(&b.sim.world)
+    };
+
+    match field {
+        Some(field) => Err(Divergence { step, field }),
+        None => Ok(()),
+    }
+}
+
+impl World {
+    /// Returns the first field in which both worlds differ, if any; floats
+    /// are compared bit-by-bit.
+    pub(crate) fn state_diff(&self, other: &Self) -> Option<String> {
+        if self.animals.len() != other.animals.len() {
+            return Some("animals.len".into());
+        }
+
+        if self.foods.len() != other.foods.len() {
+            return Some("foods.len".into());
+        }
+
+        for (idx, (a, b)) in self.animals.iter().zip(&other.animals).enumerate() {
+            if let Some(field) = a.state_diff(b) {
+                return Some(format!("animals[{}].{}", idx, field));
+            }
+        }
+
+        for (idx, (a, b)) in self.foods.iter().zip(&other.foods).enumerate() {
+            if !same_floats(a.position.iter(), b.position.iter()) {
+                return Some(format!("foods[{}].position", idx));
+            }
+        }
+
+        None
+    }
+}
+
+impl Animal {
+    /// Returns the first field in which both birds differ, if any.
+    pub(crate) fn state_diff(&self, other: &Self) -> Option<&'static str> {
+        let same = |a: f32, b: f32| a.to_bits() == b.to_bits();
+
+        if self.id != other.id {
+            Some("id")
+        } else if !same_floats(self.position.iter(), other.position.iter()) {
+            Some("position")
+        } else if !same(self.rotation.angle(), other.rotation.angle()) {
+            Some("rotation")
+        } else if !same(self.speed, other.speed) || !same(self.angular_velocity, other.angular_velocity) {
+            Some("speed")
+        } else if self.satiation != other.satiation
+            || self.scored_satiation != other.scored_satiation
+            || self.nursery_steps != other.nursery_steps
+        {
+            Some("satiation")
+        } else if !same(self.energy, other.energy) {
+            Some("energy")
+        } else if self.hibernating != other.hibernating {
+            Some("hibernating")
+        } else if !self
+            .as_chromosome()
+            .iter()
+            .map(|gene| gene.to_bits())
+            .eq(other.as_chromosome().iter().map(|gene| gene.to_bits()))
+        {
+            Some("chromosome")
+        } else {
+            None
+        }
+    }
+}
+
+fn same_floats<'a>(a: impl Iterator<Item = &'a f32>, b: impl Iterator<Item = &'a f32>) -> bool {
+    a.map(|x| x.to_bits()).eq(b.map(|x| x.to_bits()))
+}
+
+/// Counts how many random numbers have been drawn.
+struct CountingRng<R> {
+    rng: R,
+    draws: usize,
+}
+
+impl<R> CountingRng<R> {
+    fn new(rng: R) -> Self {
+        Self { rng, draws: 0 }
+    }
+}
+
+impl<R: RngCore> RngCore for CountingRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.draws += 1;
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draws += 1;
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.draws += 1;
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.draws += 1;
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replicas() -> (Replica, Replica) {
+        let config = SimulationConfig {
+            n_animals: 5,
+            n_foods: 10,
+            generation_length: 10,
+            ..Default::default()
+        };
+
+        (Replica::new(&config, 42), Replica::new(&config, 42))
+    }
+
+    #[test]
+    fn reports_first_diverging_field() {
+        let (a, mut b) = replicas();
+
+        b.sim.world.animals[2].rotation = na::Rotation2::new(1.0);
+        b.sim.world.animals[3].satiation = 7;
+
+        assert_eq!(
+            compare(0, &a, &b),
+            Err(Divergence {
+                step: 0,
+                field: "animals[2].rotation".into(),
+            }),
+        );
+    }
+
+    #[test]
+    fn catches_extra_draws_before_they_matter() {
+        let (mut a, mut b) = replicas();
+
+        b.rng.next_u32();
+
+        a.step();
+        b.step();
+
+        assert_eq!(compare(1, &a, &b).unwrap_err().field, "rng.draws");
+    }
+
+    #[test]
+    fn catches_diverging_brains() {
+        let (a, mut b) = replicas();
+        let animal = &mut b.sim.world.animals[4];
+        let mut chromosome = animal.as_chromosome();
+
+        chromosome.offset_gene(0, 0.5);
+        animal.brain = Brain::from_chromosome(chromosome, &animal.eye).unwrap();
+
+        assert_eq!(compare(0, &a, &b).unwrap_err().field, "animals[4].chromosome");
+    }
+}
@@ -0,0 +1,305 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Energy model - when enabled, each bird starts with `initial` energy,
+/// pays `per_step` for every step it lives and gains `per_food` for each
+/// food it eats.
+///
+/// Energy doesn't affect fitness (yet); it's a currency other features,
+/// such as energy sharing, can build upon.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnergyConfig {
+    pub initial: f32,
+    pub per_step: f32,
+    pub per_food: f32,
+}
+
+impl Default for EnergyConfig {
+    fn default() -> Self {
+        Self {
+            initial: 1.0,
+            per_step: 0.001,
+            per_food: 0.5,
+        }
+    }
+}
+
+/// Energy sharing between birds that touch each other.
+///
+/// Each step, for every pair of birds closer than `contact_radius` whose
+/// energies differ by more than `min_gap`, the richer bird gives away
+/// `rate * sharing * (rich - poor)` of its energy to the poorer one,
+/// where `sharing` is the richer bird's evolvable sharing gene (see:
+/// `Traits::sharing()`).
+///
+/// Requires `SimulationConfig::energy`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharingConfig {
+    pub contact_radius: f32,
+
+    /// At most 0.5, so that the poorer bird never ends up richer than
+    /// the one that has just fed it.
+    pub rate: f32,
+
+    pub min_gap: f32,
+}
+
+impl SharingConfig {
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(self.contact_radius > 0.0);
+        ensure!((0.0..=0.5).contains(&self.rate));
+        ensure!(self.min_gap >= 0.0);
+
+        Ok(())
+    }
+}
+
+impl Default for SharingConfig {
+    fn default() -> Self {
+        Self {
+            contact_radius: 0.02,
+            rate: 0.1,
+            min_gap: 0.5,
+        }
+    }
+}
+
+/// Energy price of breeding by hand (see: `Simulation::breed()`).
+///
+/// Each child costs its parents `cost` energy, split evenly between them,
+/// and starts its life with `efficiency * cost` of it (instead of
+/// `EnergyConfig::initial`) - the rest is lost. Parents that can't afford
+/// their share don't breed at all.
+///
+/// Requires `SimulationConfig::energy`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BirthCost {
+    pub cost: f32,
+    pub efficiency: f32,
+}
+
+impl BirthCost {
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(self.cost >= 0.0);
+        ensure!((0.0..=1.0).contains(&self.efficiency));
+
+        Ok(())
+    }
+
+    /// Energy a single child starts with.
+    pub fn endowment(&self) -> f32 {
+        self.efficiency * self.cost
+    }
+
+    /// Energy lost with each child.
+    pub fn loss(&self) -> f32 {
+        self.cost - self.endowment()
+    }
+}
+
+impl Default for BirthCost {
+    fn default() -> Self {
+        Self {
+            cost: 0.5,
+            efficiency: 0.8,
+        }
+    }
+}
+
+/// Births of a single generation, as paid for with `BirthCost`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BirthReport {
+    pub births: usize,
+
+    /// Total energy parents have passed onto their children.
+    pub energy_transferred: f32,
+
+    /// Total energy lost on the way.
+    pub energy_lost: f32,
+}
+
+/// Energy-sharing statistics of a single generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SharingReport {
+    /// Mean of `Traits::sharing()` across the population.
+    pub mean_sharing_gene: f32,
+
+    /// Total amount of energy that changed hands.
+    pub energy_transferred: f32,
+}
+
+impl World {
+    pub(crate) fn process_energy(&mut self, config: &SimulationConfig) {
+        trace_span!(TRACE, "energy");
+
+        let Some(energy) = config.energy else {
+            return;
+        };
+
+        for animal in &mut self.animals {
+            let cost = match config.hibernation {
+                Some(hibernation) if animal.hibernating => energy.per_step * hibernation.cost_factor,
+                _ => energy.per_step,
+            };
+
+            animal.energy = (animal.energy - cost).max(0.0);
+        }
+
+        if let Some(sharing) = config.sharing {
+            self.energy_transferred += self.process_sharing(&sharing);
+        }
+    }
+
+    /// Returns the amount of energy transferred during this step.
+    fn process_sharing(&mut self, sharing: &SharingConfig) -> f32 {
+        let positions: Vec<_> = self.animals.iter().map(|animal| animal.position).collect();
+        let mut transferred = 0.0;
+
+        for (a, b) in grid::pairs_within(&positions, sharing.contact_radius) {
+            let (rich, poor) = if self.animals[a].energy >= self.animals[b].energy {
+                (a, b)
+            } else {
+                (b, a)
+            };
+
+            let gap = self.animals[rich].energy - self.animals[poor].energy;
+
+            if gap <= sharing.min_gap {
+                continue;
+            }
+
+            let amount = sharing.rate * self.animals[rich].traits.sharing() * gap;
+
+            self.animals[rich].energy -= amount;
+            self.animals[poor].energy += amount;
+            transferred += amount;
+        }
+
+        transferred
+    }
+
+    pub(crate) fn sharing_report(&self) -> SharingReport {
+        let mean_sharing_gene = self
+            .animals
+            .iter()
+            .map(|animal| animal.traits.sharing())
+            .sum::<f32>()
+            / self.animals.len() as f32;
+
+        SharingReport {
+            mean_sharing_gene,
+            energy_transferred: self.energy_transferred,
+        }
+    }
+}
+
+impl Simulation {
+    /// Energy held by all the birds, plus the energy queued offspring are
+    /// going to start with (see: `BirthCost`).
+    pub fn total_energy(&self) -> f32 {
+        let animals: f32 = self.world.animals.iter().map(|animal| animal.energy).sum();
+        let offspring: f32 = self.offspring.iter().filter_map(|offspring| offspring.energy).sum();
+
+        animals + offspring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn config() -> SimulationConfig {
+        SimulationConfig {
+            energy: Some(EnergyConfig {
+                initial: 1.0,
+                per_step: 0.0,
+                per_food: 0.5,
+            }),
+            sharing: Some(SharingConfig {
+                contact_radius: 0.02,
+                rate: 0.25,
+                min_gap: 0.1,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn animal(config: &SimulationConfig, x: f32, energy: f32, sharing: f32) -> Animal {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(config, &mut rng);
+
+        animal.position = na::Point2::new(x, 0.5);
+        animal.energy = energy;
+        animal.traits.sharing = Some(widen(sharing));
+        animal
+    }
+
+    fn world(distance: f32) -> World {
+        let config = config();
+
+        World::new(
+            vec![
+                animal(&config, 0.5, 0.2, 0.3),
+                animal(&config, 0.5 + distance, 1.8, 0.6),
+            ],
+            vec![],
+        )
+    }
+
+    fn energies(world: &World) -> Vec<f32> {
+        world.animals.iter().map(|animal| animal.energy).collect()
+    }
+
+    #[test]
+    fn transfer_matches_formula() {
+        let mut world = world(0.01);
+        world.process_energy(&config());
+
+        // rate * richer bird's sharing * gap
+        let amount = 0.25 * 0.6 * (1.8 - 0.2);
+
+        let energies = energies(&world);
+
+        assert_relative_eq!(energies[0], 0.2 + amount);
+        assert_relative_eq!(energies[1], 1.8 - amount);
+        assert_relative_eq!(world.energy_transferred, amount);
+    }
+
+    #[test]
+    fn no_transfer_beyond_contact_radius() {
+        let mut world = world(0.03);
+        world.process_energy(&config());
+
+        assert_eq!(energies(&world), vec![0.2, 1.8]);
+        assert_eq!(world.energy_transferred, 0.0);
+    }
+
+    #[test]
+    fn energy_is_conserved() {
+        let config = config();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        // Lots of birds crammed in a tiny area, so that they touch a lot
+        let animals = (0..50)
+            .map(|idx| {
+                animal(&config, 0.5 + rng.gen_range(0.0..0.05), idx as f32 / 10.0, rng.gen())
+            })
+            .collect();
+
+        let mut world = World::new(animals, vec![]);
+        let before: f32 = energies(&world).iter().sum();
+
+        world.process_energy(&config);
+
+        let after: f32 = energies(&world).iter().sum();
+
+        assert!(world.energy_transferred > 0.0);
+        assert_relative_eq!(before, after, epsilon = 1e-4);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Day/night cycle - when enabled, birds' field of view shrinks and grows
+/// back every `period` steps:
+///
+/// ```text
+/// darkness = 0.5 * (1 - cos(2 * PI * step / period))
+/// fov_range = base_fov_range * (1 - amplitude * darkness)
+/// ```
+///
+/// ... where `step` counts from the beginning of the generation; so each
+/// generation starts at noon (full range) and reaches midnight (range
+/// reduced by `amplitude`) after `period / 2` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentCycle {
+    pub period: usize,
+
+    /// - 0.0 = no cycle at all,
+    /// - 1.0 = birds are completely blind at midnight.
+    pub amplitude: f32,
+}
+
+impl EnvironmentCycle {
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(self.period > 0);
+        ensure!((0.0..=1.0).contains(&self.amplitude));
+
+        Ok(())
+    }
+
+    /// Phase of the cycle at given step: 0.0 = noon, 1.0 = midnight.
+    pub fn darkness(&self, step: usize) -> f32 {
+        let angle = 2.0 * PI * (step % self.period) as f32 / self.period as f32;
+
+        0.5 * (1.0 - angle.cos())
+    }
+
+    /// Field of view range at given step, for an eye of `base` range.
+    pub fn fov_range(&self, base: f32, step: usize) -> f32 {
+        base * (1.0 - self.amplitude * self.darkness(step))
+    }
+}
+
+impl Default for EnvironmentCycle {
+    fn default() -> Self {
+        Self {
+            period: GENERATION_LENGTH / 4,
+            amplitude: 0.5,
+        }
+    }
+}
+
+impl Animal {
+    /// What the bird sees at given step of the generation, taking the
+    /// day/night cycle into account.
+    pub(crate) fn vision(&self, config: &SimulationConfig, foods: &[Food], step: usize) -> Vec<f32> {
+        match config.environment_cycle {
+            Some(cycle) => self.eye.process_vision_within(
+                self.position,
+                self.rotation,
+                foods,
+                cycle.fov_range(self.eye.fov_range(), step),
+            ),
+            None => self.eye.process_vision(self.position, self.rotation, foods),
+        }
+    }
+}
+
+impl Simulation {
+    /// Phase of the day/night cycle (see: `EnvironmentCycle::darkness()`)
+    /// the next step is going to be simulated at, e.g. to dim the
+    /// background; always 0.0 when the cycle is disabled.
+    pub fn darkness(&self) -> f32 {
+        self.config
+            .environment_cycle
+            .map_or(0.0, |cycle| cycle.darkness(self.age))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn fov_range_follows_the_formula() {
+        let cycle = EnvironmentCycle {
+            period: 100,
+            amplitude: 0.8,
+        };
+
+        assert_relative_eq!(cycle.fov_range(0.25, 0), 0.25);
+        assert_relative_eq!(cycle.fov_range(0.25, 25), 0.25 * (1.0 - 0.8 * 0.5), epsilon = 1e-6);
+        assert_relative_eq!(cycle.fov_range(0.25, 50), 0.25 * 0.2, epsilon = 1e-6);
+        assert_relative_eq!(cycle.fov_range(0.25, 75), 0.25 * (1.0 - 0.8 * 0.5), epsilon = 1e-6);
+        assert_relative_eq!(cycle.fov_range(0.25, 100), 0.25);
+    }
+
+    #[test]
+    fn zero_amplitude_changes_nothing() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let sim = Simulation::from_config(SimulationConfig::default(), &mut rng);
+
+        let config = SimulationConfig {
+            environment_cycle: Some(EnvironmentCycle {
+                period: 10,
+                amplitude: 0.0,
+            }),
+            ..SimulationConfig::default()
+        };
+
+        for animal in &sim.world().animals {
+            for step in 0..10 {
+                assert_eq!(
+                    animal.vision(&config, &sim.world().foods, step),
+                    animal.vision(sim.config(), &sim.world().foods, step),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn midnight_shrinks_the_field_of_view() {
+        let cycle = EnvironmentCycle {
+            period: 10,
+            amplitude: 0.5,
+        };
+
+        let eye = Eye::new(0.4, PI, 1);
+        let position = na::Point2::new(0.5, 0.5);
+        let rotation = na::Rotation2::new(0.0);
+
+        // 0.3 away - within the base range, but not within the midnight one
+        let foods = [Food {
+            position: na::Point2::new(0.5, 0.8),
+        }];
+
+        let noon = eye.process_vision_within(position, rotation, &foods, cycle.fov_range(0.4, 0));
+        let midnight = eye.process_vision_within(position, rotation, &foods, cycle.fov_range(0.4, 5));
+
+        assert!(noon[0] > 0.0);
+        assert_eq!(midnight, vec![0.0]);
+    }
+
+    #[test]
+    fn simulation_reports_darkness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 2,
+            environment_cycle: Some(EnvironmentCycle {
+                period: 4,
+                amplitude: 1.0,
+            }),
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        assert_eq!(sim.darkness(), 0.0);
+
+        sim.step(&mut rng);
+        sim.step(&mut rng);
+        assert_relative_eq!(sim.darkness(), 1.0);
+    }
+}
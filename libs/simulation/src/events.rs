@@ -0,0 +1,193 @@
+use crate::*;
+use std::collections::VecDeque;
+
+/// Something that happened inside the simulation, recorded into the
+/// `EventLog` - e.g. for the frontend to display, where `tracing` (see the
+/// `tracing` feature) is not an option.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimEvent {
+    /// Bird has eaten food #`food` during given step of given generation.
+    Eat {
+        generation: usize,
+        step: usize,
+        animal: AnimalId,
+        lineage: LineageId,
+        food: usize,
+    },
+
+    /// Generation has come to an end and got evolved.
+    GenerationEnded {
+        generation: usize,
+        max_fitness: ga::Float,
+        avg_fitness: ga::Float,
+    },
+
+    /// Simulation got restarted right after given generation (see:
+    /// `AutoRestart`).
+    Restarted { generation: usize },
+}
+
+/// Ring buffer of the most recent `SimEvent`s - once it's full, each new
+/// event pushes the oldest one out.
+#[derive(Clone, Debug)]
+pub struct EventLog {
+    capacity: usize,
+    events: VecDeque<SimEvent>,
+}
+
+impl EventLog {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+
+        Self {
+            capacity,
+            events: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, event: SimEvent) {
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+
+        self.events.push_back(event);
+    }
+
+    /// Returns all the events, oldest first, emptying the log.
+    pub fn drain(&mut self) -> Vec<SimEvent> {
+        self.events.drain(..).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl MemoryComponent for EventLog {
+    fn live_bytes(&self) -> usize {
+        self.events.len() * std::mem::size_of::<SimEvent>()
+    }
+
+    fn evict_oldest(&mut self, cap: usize) {
+        while self.live_bytes() > cap && self.events.pop_front().is_some() {}
+    }
+}
+
+impl Simulation {
+    /// Returns events recorded since the previous call, oldest first;
+    /// always empty unless `SimulationConfig::event_log` is enabled.
+    pub fn drain_events(&mut self) -> Vec<SimEvent> {
+        self.events.as_mut().map(EventLog::drain).unwrap_or_default()
+    }
+
+    /// Records an event, if the log is enabled - the event doesn't even
+    /// get built otherwise.
+    pub(crate) fn record(&mut self, event: impl FnOnce() -> SimEvent) {
+        if let Some(events) = &mut self.events {
+            events.push(event());
+        }
+    }
+
+    /// Records eat events of the most recent step.
+    pub(crate) fn record_eats(&mut self, step: usize) {
+        let Some(events) = &mut self.events else {
+            return;
+        };
+
+        for eat in &self.world.eats {
+            events.push(SimEvent::Eat {
+                generation: self.generation,
+                step,
+                animal: self.world.animals[eat.animal].id,
+                lineage: self.world.animals[eat.animal].lineage,
+                food: eat.food,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn restarted(generation: usize) -> SimEvent {
+        SimEvent::Restarted { generation }
+    }
+
+    #[test]
+    fn log_caps_at_its_capacity() {
+        let mut log = EventLog::new(3);
+
+        for generation in 0..5 {
+            log.push(restarted(generation));
+        }
+
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.drain(), vec![restarted(2), restarted(3), restarted(4)]);
+        assert!(log.is_empty());
+    }
+
+    fn sim(event_log: Option<usize>) -> (Simulation, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 5,
+            n_foods: 5,
+            generation_length: 10,
+            event_log,
+            ..Default::default()
+        };
+
+        (Simulation::from_config(config, &mut rng), rng)
+    }
+
+    #[test]
+    fn scripted_eat_gets_recorded() {
+        let (mut sim, mut rng) = sim(Some(100));
+
+        sim.world.foods[3].position = sim.world.animals[2].position;
+        sim.step(&mut rng);
+
+        let expected = SimEvent::Eat {
+            generation: 0,
+            step: 0,
+            animal: sim.world.animals[2].id,
+            lineage: sim.world.animals[2].lineage,
+            food: 3,
+        };
+
+        assert!(sim.drain_events().contains(&expected));
+        assert_eq!(sim.drain_events(), vec![]);
+    }
+
+    #[test]
+    fn generation_boundaries_get_recorded() {
+        let (mut sim, mut rng) = sim(Some(100));
+        let summary = sim.train(&mut rng);
+
+        assert_eq!(
+            sim.drain_events().last(),
+            Some(&SimEvent::GenerationEnded {
+                generation: 0,
+                max_fitness: summary.stats.max_fitness,
+                avg_fitness: summary.stats.avg_fitness,
+            }),
+        );
+    }
+
+    #[test]
+    fn disabled_log_records_nothing() {
+        let (mut sim, mut rng) = sim(None);
+
+        sim.world.foods[3].position = sim.world.animals[2].position;
+        sim.train(&mut rng);
+
+        assert_eq!(sim.drain_events(), vec![]);
+    }
+}
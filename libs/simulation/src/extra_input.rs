@@ -0,0 +1,357 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Something the brain can receive on top of what the eye sees.
+///
+/// Each extra input widens the brain's input layer (and so the whole
+/// chromosome), meaning that chromosomes evolved with different extra
+/// inputs are not compatible with each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtraInput {
+    /// Vector pointing from the bird back to where it has spawned, in the
+    /// bird's own reference frame - `(0, d)` means "home is `d` straight
+    /// ahead", `(d, 0)` means "home is `d` to the right".
+    ///
+    /// It's computed through dead reckoning, i.e. by integrating the
+    /// bird's own movements, not by peeking at its actual position - so
+    /// it doesn't get confused by the map wrapping around; it would
+    /// accumulate error if movements were noisy.
+    ///
+    /// Takes two values.
+    HomeVector,
+
+    /// Sum of `exp(-distance / scent_range)` over all the foods - a smooth
+    /// gradient birds can follow even when there's no food in sight (see:
+    /// `SimulationConfig::scent_range`).
+    ///
+    /// Takes one value.
+    Scent,
+
+    /// What the eye sees of other birds, as a second channel: each cell
+    /// gets activated by birds the same way it is by foods, scaled by
+    /// how well-fed they are (see: `BirdVision`) - so that e.g. scavengers
+    /// can learn to follow successful foragers.
+    ///
+    /// Birds are seen through the full `fov_range`, regardless of the
+    /// day/night cycle.
+    ///
+    /// Takes one value per eye cell.
+    Birds,
+}
+
+/// How other birds activate the cells of `ExtraInput::Birds`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BirdVision {
+    /// Proportionally to their energy, relative to the best-fed bird; when
+    /// nobody has any energy (e.g. without `SimulationConfig::energy`),
+    /// it's the same as `Presence`.
+    #[default]
+    Energy,
+
+    /// Just like foods, whatever their energy.
+    Presence,
+}
+
+/// Another bird, as seen through `ExtraInput::Birds`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Neighbor {
+    pub(crate) position: na::Point2<f32>,
+    pub(crate) intensity: f32,
+}
+
+impl Neighbor {
+    /// Returns how each of `animals` (by slot) looks to the others.
+    pub(crate) fn all(config: &SimulationConfig, animals: &[Animal]) -> Vec<Self> {
+        let max_energy = animals.iter().map(|animal| animal.energy).fold(0.0, f32::max);
+
+        animals
+            .iter()
+            .map(|animal| Self {
+                position: animal.position,
+                intensity: match config.bird_vision {
+                    BirdVision::Energy if max_energy > 0.0 => animal.energy / max_energy,
+                    _ => 1.0,
+                },
+            })
+            .collect()
+    }
+}
+
+impl Visible for Neighbor {
+    fn position(&self) -> na::Point2<f32> {
+        self.position
+    }
+
+    fn intensity(&self) -> f32 {
+        self.intensity
+    }
+}
+
+impl ExtraInput {
+    pub(crate) fn len(&self, config: &SimulationConfig) -> usize {
+        match self {
+            Self::HomeVector => 2,
+            Self::Scent => 1,
+            Self::Birds => config.eye_cells,
+        }
+    }
+
+    /// Reads this input for `animal`; `birds` are all the other birds.
+    pub(crate) fn read(
+        &self,
+        config: &SimulationConfig,
+        animal: &Animal,
+        foods: &[Food],
+        birds: &[Neighbor],
+    ) -> Vec<f32> {
+        match self {
+            Self::HomeVector => {
+                // Birds fly towards their local +y (see: `process_movements()`),
+                // which makes +x their right-hand side
+                let home = animal.rotation.inverse() * -animal.travelled;
+
+                vec![home.x, home.y]
+            }
+
+            Self::Scent => {
+                let scent = foods
+                    .iter()
+                    .map(|food| (-na::distance(&animal.position, &food.position) / config.scent_range).exp())
+                    .sum();
+
+                vec![scent]
+            }
+
+            Self::Birds => animal.eye.scan(animal.position, animal.rotation, birds),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::f32::consts::{FRAC_PI_2, PI};
+    use test_case::test_case;
+
+    fn config() -> SimulationConfig {
+        SimulationConfig {
+            n_foods: 0,
+            extra_inputs: vec![ExtraInput::HomeVector],
+            ..Default::default()
+        }
+    }
+
+    fn animal(config: &SimulationConfig) -> Animal {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Animal::random(config, &mut rng)
+    }
+
+    #[test]
+    fn dead_reckoning_matches_true_displacement() {
+        let config = config();
+        let mut animal = animal(&config);
+
+        animal.position = na::Point2::new(0.5, 0.5);
+
+        let spawn = animal.position;
+        let mut world = World::new(vec![animal], vec![]);
+
+        // A scripted zig-zag that stays far from the map's edges
+        for (step, angle) in [0.3, -1.2, 2.5, 0.0, PI, -0.7].into_iter().cycle().take(60).enumerate() {
+            world.animals[0].rotation = na::Rotation2::new(angle);
+            world.animals[0].speed = 0.001 + step as f32 * 0.00005;
+            world.process_movements(&config);
+        }
+
+        let animal = &world.animals[0];
+        let displacement = animal.position - spawn;
+
+        assert_relative_eq!(animal.travelled.x, displacement.x, epsilon = 1e-6);
+        assert_relative_eq!(animal.travelled.y, displacement.y, epsilon = 1e-6);
+    }
+
+    // Heading 0 flies towards +y, heading PI/2 towards -x
+    #[test_case(0.0, na::Vector2::new(0.0, 0.1), [0.0, 0.1] ; "home straight ahead")]
+    #[test_case(0.0, na::Vector2::new(0.1, 0.0), [0.1, 0.0] ; "home to the right")]
+    #[test_case(FRAC_PI_2, na::Vector2::new(-0.1, 0.0), [0.0, 0.1] ; "turned left, home straight ahead")]
+    #[test_case(FRAC_PI_2, na::Vector2::new(0.0, 0.1), [0.1, 0.0] ; "turned left, home to the right")]
+    #[test_case(PI, na::Vector2::new(0.0, 0.1), [0.0, -0.1] ; "turned around, home behind")]
+    fn home_vector_is_egocentric(heading: f32, home: na::Vector2<f32>, expected: [f32; 2]) {
+        let mut animal = animal(&config());
+
+        animal.rotation = na::Rotation2::new(heading);
+        animal.travelled = -home;
+
+        let actual = ExtraInput::HomeVector.read(&config(), &animal, &[], &[]);
+
+        assert_relative_eq!(actual[0], expected[0], epsilon = 1e-6);
+        assert_relative_eq!(actual[1], expected[1], epsilon = 1e-6);
+    }
+
+    fn food(x: f32, y: f32) -> Food {
+        Food {
+            position: na::Point2::new(x, y),
+        }
+    }
+
+    fn scent(config: &SimulationConfig, foods: &[Food]) -> f32 {
+        let mut animal = animal(config);
+        animal.position = na::Point2::new(0.5, 0.5);
+
+        ExtraInput::Scent.read(config, &animal, foods, &[])[0]
+    }
+
+    #[test]
+    fn scent_fades_with_distance() {
+        let config = SimulationConfig {
+            extra_inputs: vec![ExtraInput::Scent],
+            ..config()
+        };
+
+        let scents: Vec<_> = [0.0, 0.01, 0.05, 0.1, 0.2, 0.4]
+            .into_iter()
+            .map(|distance| scent(&config, &[food(0.5, 0.5 + distance), food(0.0, 0.0)]))
+            .collect();
+
+        assert!(scents.windows(2).all(|pair| pair[0] > pair[1]), "{:?}", scents);
+
+        // Nearest food contributes `exp(-distance / scent_range)`
+        assert_relative_eq!(scents[3] - scents[5], (-1.0f32).exp() - (-4.0f32).exp(), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn scent_of_a_pile_counts_its_foods() {
+        let config = SimulationConfig {
+            extra_inputs: vec![ExtraInput::Scent],
+            ..config()
+        };
+
+        let pile: Vec<_> = (0..7).map(|_| food(0.5, 0.5)).collect();
+
+        assert_relative_eq!(scent(&config, &pile), 7.0);
+    }
+
+    #[test]
+    fn disabled_scent_leaves_topology_alone() {
+        let without = SimulationConfig::default();
+
+        let with = SimulationConfig {
+            extra_inputs: vec![ExtraInput::Scent],
+            ..Default::default()
+        };
+
+        assert_eq!(without.extra_inputs_len(), 0);
+        assert_eq!(with.extra_inputs_len(), 1);
+        assert_eq!(Animal::chromosome_len(&without), Brain::chromosome_len(&without.eye()));
+
+        // One more input neuron, plus two more hidden ones
+        assert!(Animal::chromosome_len(&with) > Animal::chromosome_len(&without));
+        assert_eq!(animal(&with).brain.propagate(vec![0.0; CELLS + 1]).len(), 2);
+    }
+
+    /// Ratio of activations a well-fed bird & a hungry one (with half as
+    /// much energy) cause, when both are at the same distance.
+    fn bird_activations(bird_vision: BirdVision) -> f32 {
+        let config = SimulationConfig {
+            extra_inputs: vec![ExtraInput::Birds],
+            bird_vision,
+            ..config()
+        };
+
+        let mut animals: Vec<_> = (0..3).map(|_| animal(&config)).collect();
+
+        animals[0].position = na::Point2::new(0.5, 0.5);
+        animals[0].rotation = na::Rotation2::new(0.0);
+
+        // Symmetrically to the left & right of where the bird is facing
+        for (animal, (x, energy)) in animals[1..].iter_mut().zip([(0.45, 1.0), (0.55, 0.5)]) {
+            animal.position = na::Point2::new(x, 0.6);
+            animal.energy = energy;
+        }
+
+        let birds = Neighbor::all(&config, &animals);
+        let cells = ExtraInput::Birds.read(&config, &animals[0], &[], &birds[1..]);
+        let active: Vec<_> = cells.into_iter().filter(|&cell| cell > 0.0).collect();
+
+        assert_eq!(active.len(), 2);
+
+        // Cells go from the bird's right to its left
+        active[1] / active[0]
+    }
+
+    #[test]
+    fn birds_activate_cells_by_energy() {
+        assert_relative_eq!(bird_activations(BirdVision::Energy), 2.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn presence_ignores_energy() {
+        assert_relative_eq!(bird_activations(BirdVision::Presence), 1.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn birds_take_one_value_per_cell() {
+        let config = SimulationConfig {
+            eye_cells: 5,
+            extra_inputs: vec![ExtraInput::Birds, ExtraInput::Scent],
+            ..Default::default()
+        };
+
+        assert_eq!(config.extra_inputs_len(), 6);
+    }
+
+    #[test]
+    fn bird_does_not_see_itself() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 1,
+            extra_inputs: vec![ExtraInput::Birds],
+            transitions: Some(TransitionConfig {
+                sample_rate: 1.0,
+                ..Default::default()
+            }),
+            ..config()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        sim.step(&mut rng);
+        sim.step(&mut rng);
+
+        // Header, followed by the first transition's vision
+        let export = sim.export_transitions();
+        let input_dim = u32::from_le_bytes(export[8..12].try_into().unwrap()) as usize;
+
+        let birds: Vec<_> = export[20..]
+            .chunks(4)
+            .take(input_dim)
+            .skip(CELLS)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        assert_eq!(input_dim, 2 * CELLS);
+        assert_eq!(birds, vec![0.0; CELLS]);
+    }
+
+    #[test]
+    fn home_vector_resets_every_generation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 3,
+            generation_length: 10,
+            ..config()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        sim.train(&mut rng);
+
+        for animal in sim.world().animal() {
+            assert_eq!(animal.travelled, na::Vector2::zeros());
+        }
+    }
+}
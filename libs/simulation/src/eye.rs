@@ -1,4 +1,5 @@
 use crate::*;
+use crate::fast_math::{ActiveGeometry, Geometry};
 use std::f32::consts::*;
 
 /// How far our eye can see:
@@ -18,7 +19,7 @@ use std::f32::consts::*;
 /// - 0.1 = 10% of the map = bird sees no foods (at least in this case)
 /// - 0.5 = 50% of the map = bird sees one of the foods
 /// - 1.0 = 100% of the map = bird sees both foods
-const FOV_RANGE: f32 = 0.25;
+pub(crate) const FOV_RANGE: f32 = 0.25;
 
 /// How wide our eye can see.
 ///
@@ -81,7 +82,7 @@ const FOV_RANGE: f32 = 0.25;
 ///   |      ---      |
 ///   |               |
 ///   -----------------
-const FOV_ANGLE: f32 = PI + FRAC_PI_4;
+pub(crate) const FOV_ANGLE: f32 = PI + FRAC_PI_4;
 
 /// How much photoreceptors there are in a single eye.
 ///
@@ -92,7 +93,7 @@ const FOV_ANGLE: f32 = PI + FRAC_PI_4;
 ///
 /// I've found values between 3~11 sufficient, with eyes having more
 /// than ~20 photoreceptors yielding progressively worse results.
-const CELLS: usize = 9;
+pub(crate) const CELLS: usize = 9;
 
 #[derive(Debug)]
 pub struct Eye {
@@ -104,8 +105,9 @@ pub struct Eye {
 impl Eye {
     // FOV_RANGE, FOV_ANGLE & CELLS are the values we'll use during
     // simulation - but being able to create an arbitrary eye will
-    // come handy during the testing:
-    fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
+    // come handy during the testing (and when inspecting brains
+    // outside of the simulation):
+    pub fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
         assert!(fov_range > 0.0);
         assert!(fov_angle > 0.0);
         assert!(cells > 0);
@@ -117,27 +119,89 @@ impl Eye {
         self.cells
     }
 
+    pub fn fov_range(&self) -> f32 {
+        self.fov_range
+    }
+
     pub fn process_vision(
         &self,
         position: na::Point2<f32>,
         rotation: na::Rotation2<f32>,
         foods: &[Food],
+    ) -> Vec<f32> {
+        self.scan(position, rotation, foods)
+    }
+
+    /// Like `process_vision()`, but as if the eye could see only up to
+    /// `fov_range` (e.g. at night; see: `EnvironmentCycle`).
+    pub fn process_vision_within(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        foods: &[Food],
+        fov_range: f32,
+    ) -> Vec<f32> {
+        self.scan_within(position, rotation, foods, fov_range)
+    }
+
+    /// Maps what an eye has seen onto `cells` cells, as if the same objects
+    /// were looked at through an eye with more (or less) of them - e.g. to
+    /// check what a brain transplanted through `TransplantStrategy::Resample`
+    /// makes of familiar sights.
+    ///
+    /// Cells get linearly interpolated and then rescaled, so that the total
+    /// activation stays the same - just as an object seen through narrower
+    /// cells activates fewer of them.
+    pub fn resample_vision(vision: &[f32], cells: usize) -> Vec<f32> {
+        let scale = vision.len() as f32 / cells as f32;
+
+        narrow_all(nn::interpolate(&widen_all(vision.to_vec()), cells))
+            .into_iter()
+            .map(|cell| cell * scale)
+            .collect()
+    }
+
+    /// Like `process_vision()`, but for any kind of objects - each object
+    /// activates its cell proportionally to its `Visible::intensity()`.
+    pub fn scan<T: Visible>(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        objects: &[T],
+    ) -> Vec<f32> {
+        self.scan_within(position, rotation, objects, self.fov_range)
+    }
+
+    fn scan_within<T: Visible>(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        objects: &[T],
+        fov_range: f32,
+    ) -> Vec<f32> {
+        self.scan_with::<T, ActiveGeometry>(position, rotation, objects, fov_range)
+    }
+
+    /// Like `scan_within()`, but with explicitly given geometry - exact or
+    /// fast (see: `fast_math`).
+    fn scan_with<T: Visible, G: Geometry>(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        objects: &[T],
+        fov_range: f32,
     ) -> Vec<f32> {
         let mut cells = vec![0.0; self.cells];
 
-        for food in foods {
+        for object in objects {
             // Check if the food is in range
-            let vec = food.position - position;
-            let dist = vec.norm();
-            if dist >= self.fov_range {
+            let vec = object.position() - position;
+            let Some(dist) = G::distance_within(vec, fov_range) else {
                 continue;
-            }
+            };
 
             // Checks if the food is within POV
-            let angle = na::Rotation2::rotation_between(
-                &na::Vector2::y(),
-                &vec
-            ).angle();
+            let angle = G::angle(vec);
             let angle = angle - rotation.angle();
 
             /*    Rotation is wrapping (from -PI to PI), that is:
@@ -239,14 +303,80 @@ impl Eye {
             // energy, the further away the food" - but from what I've seen, it
             // makes the learning process a bit harder.
 
-            let energy = (self.fov_range - dist) / self.fov_range;
+            let energy = (fov_range - dist) / fov_range;
 
-            cells[cell] += energy;
+            cells[cell] += energy * object.intensity();
             
         }
 
         cells
     }
+
+    /// Returns the part of the world each cell looks at, as seen from
+    /// given position & rotation - e.g. to draw the field of view.
+    ///
+    /// Cells are ordered the same way `process_vision()` orders them.
+    pub fn cell_geometry(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+    ) -> Vec<CellGeometry> {
+        let cell_angle = self.fov_angle / self.cells as f32;
+
+        (0..self.cells)
+            .map(|cell| {
+                // Inverse of what `process_vision()` does to find a cell
+                let boundary = |cell: usize| rotation.angle() - self.fov_angle / 2.0 + cell as f32 * cell_angle;
+                let (start_angle, end_angle) = (boundary(cell), boundary(cell + 1));
+
+                let ray = |angle| position + na::Rotation2::new(angle) * na::Vector2::new(0.0, self.fov_range);
+
+                CellGeometry {
+                    start_angle,
+                    end_angle,
+                    range: self.fov_range,
+                    start_point: ray(start_angle),
+                    end_point: ray(end_angle),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Something that can be seen by an `Eye`.
+pub trait Visible {
+    fn position(&self) -> na::Point2<f32>;
+
+    /// How strongly this object activates the eye cell it falls into,
+    /// relative to a regular food; e.g. 2.0 = seen as if it was two foods
+    /// at the same spot, 0.0 = invisible.
+    fn intensity(&self) -> f32 {
+        1.0
+    }
+}
+
+impl Visible for Food {
+    fn position(&self) -> na::Point2<f32> {
+        self.position
+    }
+}
+
+/// Slice of the field of view seen by a single eye cell.
+///
+/// Angles follow the same convention as birds' rotations - an angle of 0
+/// points towards +y and angles grow counterclockwise; they're not
+/// wrapped, so `start_angle < end_angle` always holds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellGeometry {
+    pub start_angle: f32,
+    pub end_angle: f32,
+    pub range: f32,
+
+    /// Where the ray along `start_angle` ends, `range` away from the bird.
+    pub start_point: na::Point2<f32>,
+
+    /// Where the ray along `end_angle` ends, `range` away from the bird.
+    pub end_point: na::Point2<f32>,
 }
 
 impl Default for Eye {
@@ -433,5 +563,241 @@ mod tests {
             expected_vision,
         }.run()
     }
-    
-}
\ No newline at end of file
+
+    mod cell_geometry {
+        use super::*;
+        use approx::assert_relative_eq;
+        use test_case::test_case;
+
+        #[test_case(1)]
+        #[test_case(4)]
+        #[test_case(9)]
+        #[test_case(13)]
+        fn matches_process_vision(cells: usize) {
+            let eye = Eye::new(0.25, FOV_ANGLE, cells);
+            let position = na::Point2::new(0.5, 0.5);
+
+            for rotation in [0.0, 1.0, -2.5, PI] {
+                let rotation = na::Rotation2::new(rotation);
+
+                for (cell, geometry) in eye.cell_geometry(position, rotation).iter().enumerate() {
+                    let angle = (geometry.start_angle + geometry.end_angle) / 2.0;
+                    let food = Food {
+                        position: position + na::Rotation2::new(angle) * na::Vector2::new(0.0, geometry.range / 2.0),
+                    };
+
+                    let vision = eye.process_vision(position, rotation, &[food]);
+
+                    for (idx, &activation) in vision.iter().enumerate() {
+                        assert_eq!(activation > 0.0, idx == cell, "cells: {}, cell: {}, rotation: {}", cells, cell, rotation.angle());
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn cells_cover_the_field_of_view() {
+            let eye = Eye::new(0.25, FOV_ANGLE, 9);
+            let geometry = eye.cell_geometry(na::Point2::new(0.5, 0.5), na::Rotation2::new(0.0));
+
+            assert_relative_eq!(geometry[0].start_angle, -FOV_ANGLE / 2.0);
+            assert_relative_eq!(geometry[8].end_angle, FOV_ANGLE / 2.0, epsilon = 1e-6);
+
+            for pair in geometry.windows(2) {
+                assert_eq!(pair[0].end_angle, pair[1].start_angle);
+            }
+        }
+    }
+
+    mod scan {
+        use super::*;
+        #[cfg(not(feature = "fast-math"))]
+        use rand::{Rng, SeedableRng};
+        #[cfg(not(feature = "fast-math"))]
+        use rand_chacha::ChaCha8Rng;
+
+        struct Lamp {
+            position: na::Point2<f32>,
+            intensity: f32,
+        }
+
+        impl Visible for Lamp {
+            fn position(&self) -> na::Point2<f32> {
+                self.position
+            }
+
+            fn intensity(&self) -> f32 {
+                self.intensity
+            }
+        }
+
+        /// `process_vision()` as it used to be, before `scan()` came along.
+        #[cfg(not(feature = "fast-math"))]
+        fn food_only_vision(eye: &Eye, position: na::Point2<f32>, rotation: na::Rotation2<f32>, foods: &[Food]) -> Vec<f32> {
+            let mut cells = vec![0.0; eye.cells];
+
+            for food in foods {
+                let vec = food.position - position;
+                let dist = vec.norm();
+
+                if dist >= eye.fov_range {
+                    continue;
+                }
+
+                let angle = na::Rotation2::rotation_between(&na::Vector2::y(), &vec).angle();
+                let angle = na::wrap(angle - rotation.angle(), -PI, PI);
+
+                if angle < -eye.fov_angle / 2.0 || angle > eye.fov_angle / 2.0 {
+                    continue;
+                }
+
+                let cell = (angle + eye.fov_angle / 2.0) / eye.fov_angle * (eye.cells as f32);
+                let cell = (cell as usize).min(cells.len() - 1);
+
+                cells[cell] += (eye.fov_range - dist) / eye.fov_range;
+            }
+
+            cells
+        }
+
+        #[test]
+        #[cfg(not(feature = "fast-math"))]
+        fn food_vision_is_unchanged() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            for _ in 0..200 {
+                let eye = Eye::new(rng.gen_range(0.05..=1.0), rng.gen_range(0.1..=2.0 * PI), rng.gen_range(1..=15));
+                let foods: Vec<_> = (0..rng.gen_range(0..30)).map(|_| Food::random(&mut rng)).collect();
+                let position = rng.gen();
+                let rotation = na::Rotation2::new(rng.gen_range(-PI..=PI));
+
+                assert_eq!(
+                    eye.process_vision(position, rotation, &foods),
+                    food_only_vision(&eye, position, rotation, &foods),
+                );
+            }
+        }
+
+        #[test]
+        fn intensity_scales_activation() {
+            let eye = Eye::new(1.0, PI, 1);
+            let position = na::Point2::new(0.5, 0.5);
+            let rotation = na::Rotation2::new(0.0);
+
+            let lamp = |intensity| Lamp {
+                position: na::Point2::new(0.5, 0.75),
+                intensity,
+            };
+
+            let dim = eye.scan(position, rotation, &[lamp(1.0)]);
+            let bright = eye.scan(position, rotation, &[lamp(2.5)]);
+            let off = eye.scan(position, rotation, &[lamp(0.0)]);
+            let food = eye.process_vision(position, rotation, &[Food { position: lamp(1.0).position }]);
+
+            assert_eq!(dim, food);
+            assert_eq!(bright[0], 2.5 * dim[0]);
+            assert_eq!(off, vec![0.0]);
+        }
+    }
+
+    mod fast_math {
+        use super::*;
+        use crate::fast_math::{Exact, Fast, Ops, MAX_ATAN2_ERROR};
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha8Rng;
+
+        /// Fraction of objects `Fast` is allowed to put into a different
+        /// cell than `Exact` does, given the default eye.
+        const MAX_MISMATCH_RATE: f32 = 0.002;
+
+        /// Index of the cell that sees `food`, if any.
+        fn cell_of<G: Geometry>(eye: &Eye, position: na::Point2<f32>, rotation: na::Rotation2<f32>, food: &Food) -> Option<usize> {
+            eye.scan_with::<_, G>(position, rotation, std::slice::from_ref(food), eye.fov_range)
+                .into_iter()
+                .position(|cell| cell > 0.0)
+        }
+
+        #[test]
+        fn cell_assignment_differs_only_near_boundaries() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let eye = Eye::default();
+            let cell_angle = eye.fov_angle / eye.cells as f32;
+            let trials = 100_000;
+            let mut mismatches = 0;
+
+            for _ in 0..trials {
+                let position = na::Point2::new(0.5, 0.5);
+                let rotation = na::Rotation2::new(rng.gen_range(-PI..=PI));
+
+                let food = Food {
+                    position: position + na::Vector2::new(rng.gen_range(-0.3..=0.3), rng.gen_range(-0.3..=0.3)),
+                };
+
+                if cell_of::<Exact>(&eye, position, rotation, &food) == cell_of::<Fast>(&eye, position, rotation, &food) {
+                    continue;
+                }
+
+                mismatches += 1;
+
+                let vec = food.position - position;
+                let angle = na::wrap(Exact::angle(vec) - rotation.angle(), -PI, PI) + eye.fov_angle / 2.0;
+
+                let near_boundary = (0..=eye.cells)
+                    .any(|boundary| (angle - boundary as f32 * cell_angle).abs() <= MAX_ATAN2_ERROR + 1e-5);
+
+                let near_range = (vec.norm() - eye.fov_range).abs() <= 1e-5;
+
+                assert!(near_boundary || near_range, "food = {:?}, rotation = {}", food.position, rotation.angle());
+            }
+
+            let mismatch_rate = mismatches as f32 / trials as f32;
+
+            assert!(mismatch_rate <= MAX_MISMATCH_RATE, "mismatch_rate = {}", mismatch_rate);
+        }
+
+        #[test]
+        fn fast_path_avoids_sqrt_and_atan2() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let eye = Eye::default();
+            let foods: Vec<_> = (0..1000).map(|_| Food::random(&mut rng)).collect();
+            let position = na::Point2::new(0.5, 0.5);
+            let rotation = na::Rotation2::new(0.0);
+
+            let in_range = foods
+                .iter()
+                .filter(|food| na::distance(&food.position, &position) < eye.fov_range)
+                .count();
+
+            let exact = Ops::count(|| {
+                eye.scan_with::<_, Exact>(position, rotation, &foods, eye.fov_range);
+            });
+
+            let fast = Ops::count(|| {
+                eye.scan_with::<_, Fast>(position, rotation, &foods, eye.fov_range);
+            });
+
+            // Exact path takes a square root of every object, and atan2 of
+            // every object in range ...
+            assert_eq!(
+                exact,
+                Ops {
+                    sqrt: 1000,
+                    atan2: in_range,
+                    ..Default::default()
+                }
+            );
+
+            // ... while the fast one skips objects out of range entirely
+            assert_eq!(
+                fast,
+                Ops {
+                    inv_sqrt: in_range,
+                    fast_atan2: in_range,
+                    ..Default::default()
+                }
+            );
+
+            assert!(in_range < 1000 / 4);
+        }
+    }
+}
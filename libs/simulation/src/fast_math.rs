@@ -0,0 +1,217 @@
+//! Geometry behind `Eye`'s hot loop, which - in the wasm build -
+//! is dominated by square roots and `atan2()`.
+//!
+//! By default, everything is computed exactly (`Exact`); with the
+//! `fast-math` feature enabled, eyes switch to `Fast`, which:
+//!
+//! - rejects objects out of range by comparing squared distances,
+//! - computes distances of the remaining ones through `inv_sqrt()`,
+//!   off by at most [`MAX_INV_SQRT_ERROR`] (relative),
+//! - computes their angles through `atan2()`, off by at most
+//!   [`MAX_ATAN2_ERROR`] radians.
+//!
+//! So an object can land in a different cell than it would otherwise only
+//! when it's within `MAX_ATAN2_ERROR` (angularly) of a cell's boundary, or
+//! right at the edge of the eye's range.
+
+// Outside of tests, only one of the geometries is in use
+#![cfg_attr(not(test), allow(dead_code))]
+
+use crate::*;
+use std::f32::consts::*;
+
+/// Maximum absolute error of `atan2()`, in radians (~0.09°).
+pub(crate) const MAX_ATAN2_ERROR: f32 = 0.0016;
+
+/// Maximum relative error of `inv_sqrt()`.
+pub(crate) const MAX_INV_SQRT_ERROR: f32 = 0.00176;
+
+/// Bumps given counter of `Ops`; compiles away outside of tests.
+macro_rules! count {
+    ($op:ident) => {
+        #[cfg(test)]
+        OPS.with(|ops| {
+            let mut counts = ops.get();
+            counts.$op += 1;
+            ops.set(counts);
+        });
+    };
+}
+
+/// How `Eye` measures where an object is, relative to the bird.
+pub(crate) trait Geometry {
+    /// Length of `vec`, if it's shorter than `range`.
+    fn distance_within(vec: na::Vector2<f32>, range: f32) -> Option<f32>;
+
+    /// Angle between +y and `vec`, in <-PI, PI>; follows the same
+    /// convention as birds' rotations.
+    fn angle(vec: na::Vector2<f32>) -> f32;
+}
+
+pub(crate) struct Exact;
+
+impl Geometry for Exact {
+    fn distance_within(vec: na::Vector2<f32>, range: f32) -> Option<f32> {
+        count!(sqrt);
+
+        let dist = vec.norm();
+        (dist < range).then_some(dist)
+    }
+
+    fn angle(vec: na::Vector2<f32>) -> f32 {
+        count!(atan2);
+
+        na::Rotation2::rotation_between(&na::Vector2::y(), &vec).angle()
+    }
+}
+
+pub(crate) struct Fast;
+
+impl Geometry for Fast {
+    fn distance_within(vec: na::Vector2<f32>, range: f32) -> Option<f32> {
+        let dist_squared = vec.norm_squared();
+
+        if dist_squared >= range * range {
+            return None;
+        }
+
+        count!(inv_sqrt);
+
+        Some(dist_squared * inv_sqrt(dist_squared))
+    }
+
+    fn angle(vec: na::Vector2<f32>) -> f32 {
+        count!(fast_atan2);
+
+        // Same as `rotation_between(y, vec)`, which is `atan2(y ⨯ vec, y · vec)`
+        atan2(-vec.x, vec.y)
+    }
+}
+
+/// Geometry eyes use in this build.
+#[cfg(not(feature = "fast-math"))]
+pub(crate) type ActiveGeometry = Exact;
+
+#[cfg(feature = "fast-math")]
+pub(crate) type ActiveGeometry = Fast;
+
+/// Approximates `y.atan2(x)` with a polynomial; see: [`MAX_ATAN2_ERROR`].
+pub(crate) fn atan2(y: f32, x: f32) -> f32 {
+    // atan(z) for z in <-1, 1>
+    let atan = |z: f32| FRAC_PI_4 * z - z * (z.abs() - 1.0) * (0.2447 + 0.0663 * z.abs());
+
+    if x == 0.0 && y == 0.0 {
+        0.0
+    } else if x.abs() >= y.abs() {
+        let angle = atan(y / x);
+
+        if x >= 0.0 {
+            angle
+        } else if y >= 0.0 {
+            angle + PI
+        } else {
+            angle - PI
+        }
+    } else {
+        FRAC_PI_2.copysign(y) - atan(x / y)
+    }
+}
+
+/// Approximates `1.0 / x.sqrt()` for positive `x`, with a single
+/// Newton-Raphson refinement; see: [`MAX_INV_SQRT_ERROR`].
+pub(crate) fn inv_sqrt(x: f32) -> f32 {
+    let y = f32::from_bits(0x5f37_5a86 - (x.to_bits() >> 1));
+
+    y * (1.5 - 0.5 * x * y * y)
+}
+
+/// Number of times each of the hot-path operations got performed by the
+/// current thread; tests only.
+#[cfg(test)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Ops {
+    pub sqrt: usize,
+    pub atan2: usize,
+    pub inv_sqrt: usize,
+    pub fast_atan2: usize,
+}
+
+#[cfg(test)]
+thread_local! {
+    pub(crate) static OPS: std::cell::Cell<Ops> = Default::default();
+}
+
+#[cfg(test)]
+impl Ops {
+    /// Returns operations performed while running `f`.
+    pub(crate) fn count(f: impl FnOnce()) -> Self {
+        OPS.with(|ops| ops.set(Ops::default()));
+        f();
+        OPS.with(|ops| ops.get())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn atan2_stays_within_max_error() {
+        let mut max_error: f32 = 0.0;
+
+        for step in 0..=100_000 {
+            let (y, x) = (step as f32 / 100_000.0 * 2.0 * PI - PI).sin_cos();
+
+            for scale in [1e-3, 1.0, 1e3] {
+                let error = (atan2(y * scale, x * scale) - (y * scale).atan2(x * scale)).abs();
+                max_error = max_error.max(error);
+            }
+        }
+
+        assert!(max_error <= MAX_ATAN2_ERROR, "max_error = {}", max_error);
+
+        // ... which is as tight as it gets
+        assert!(max_error >= 0.9 * MAX_ATAN2_ERROR, "max_error = {}", max_error);
+    }
+
+    #[test]
+    fn atan2_handles_axes() {
+        assert_eq!(atan2(0.0, 0.0), 0.0);
+        assert_eq!(atan2(0.0, 1.0), 0.0);
+        assert_eq!(atan2(1.0, 0.0), FRAC_PI_2);
+        assert_eq!(atan2(-1.0, 0.0), -FRAC_PI_2);
+        assert_eq!(atan2(0.0, -1.0), PI);
+    }
+
+    #[test]
+    fn inv_sqrt_stays_within_max_error() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        for _ in 0..100_000 {
+            let x: f32 = rng.gen_range(1e-8..=4.0);
+            let error = (inv_sqrt(x) * x.sqrt() - 1.0).abs();
+
+            assert!(error <= MAX_INV_SQRT_ERROR, "x = {}, error = {}", x, error);
+        }
+    }
+
+    #[test]
+    fn fast_geometry_matches_exact_geometry() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        for _ in 0..10_000 {
+            let vec = na::Vector2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0));
+
+            let exact = Exact::angle(vec);
+            let fast = Fast::angle(vec);
+
+            assert!((exact - fast).abs() <= MAX_ATAN2_ERROR, "vec = {:?}", vec);
+
+            if let (Some(exact), Some(fast)) = (Exact::distance_within(vec, 2.0), Fast::distance_within(vec, 2.0)) {
+                assert!((exact - fast).abs() <= MAX_INV_SQRT_ERROR * exact, "vec = {:?}", vec);
+            }
+        }
+    }
+}
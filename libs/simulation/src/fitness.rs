@@ -0,0 +1,334 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Something a bird can be scored by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FitnessSignal {
+    /// Number of foods eaten (outside of the nursery, see: `Nursery`).
+    Foods,
+
+    /// Energy left at the end of the generation; always zero unless
+    /// `SimulationConfig::energy` is enabled.
+    Energy,
+
+    /// Total length of the path flown.
+    Distance,
+
+    /// 1.0 if the bird ended the generation flagged as a spinner, 0.0
+    /// otherwise - meant to be given a negative weight; requires
+    /// `SimulationConfig::behavior`.
+    Spinning,
+
+    /// Like `Spinning`, but for wall-huggers.
+    WallHugging,
+
+    /// Like `Spinning`, but for idlers.
+    Idling,
+}
+
+impl FitnessSignal {
+    fn measure(&self, animal: &Animal) -> f32 {
+        match self {
+            Self::Foods => animal.scored_satiation as f32,
+            Self::Energy => animal.energy,
+            Self::Distance => animal.distance,
+            Self::Spinning => animal.behavior().spinner as u8 as f32,
+            Self::WallHugging => animal.behavior().wall_hugger as u8 as f32,
+            Self::Idling => animal.behavior().idler as u8 as f32,
+        }
+    }
+
+    /// Whether this signal comes from `BehaviorConfig`'s classifiers.
+    pub(crate) fn is_behavior(&self) -> bool {
+        matches!(self, Self::Spinning | Self::WallHugging | Self::Idling)
+    }
+}
+
+/// How a signal gets rescaled (across the population) before it's
+/// weighted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Normalization {
+    /// Raw values are used as they are.
+    #[default]
+    None,
+
+    /// Values get shifted to mean 0 and scaled to standard deviation 1;
+    /// if all of them are equal, they all become 0.
+    ZScorePerGeneration,
+
+    /// Values get replaced with their rank, scaled into <0,1> - the worst
+    /// bird gets 0.0, the best one 1.0 and ties share their mean rank.
+    RankPerGeneration,
+}
+
+impl Normalization {
+    fn apply(&self, values: &mut [f32]) {
+        match self {
+            Self::None => {}
+
+            Self::ZScorePerGeneration => {
+                let (mean, std) = mean_std(values);
+
+                for value in values.iter_mut() {
+                    *value = if std > 0.0 { (*value - mean) / std } else { 0.0 };
+                }
+            }
+
+            Self::RankPerGeneration => {
+                let mut order: Vec<_> = (0..values.len()).collect();
+                order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+                let mut ranks = vec![0.0; values.len()];
+                let mut start = 0;
+
+                while start < order.len() {
+                    let mut end = start + 1;
+
+                    while end < order.len() && values[order[end]] == values[order[start]] {
+                        end += 1;
+                    }
+
+                    // Ties share the mean of the ranks they span
+                    let rank = (start + end - 1) as f32 / 2.0;
+
+                    for &idx in &order[start..end] {
+                        ranks[idx] = rank;
+                    }
+
+                    start = end;
+                }
+
+                let max_rank = (values.len() as f32 - 1.0).max(1.0);
+
+                for (value, rank) in values.iter_mut().zip(ranks) {
+                    *value = rank / max_rank;
+                }
+            }
+        }
+    }
+}
+
+fn mean_std(values: &[f32]) -> (f32, f32) {
+    let n = values.len() as f32;
+    let mean = values.iter().sum::<f32>() / n;
+    let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f32>() / n;
+
+    (mean, variance.sqrt())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FitnessTerm {
+    pub signal: FitnessSignal,
+    pub weight: f32,
+    pub normalization: Normalization,
+}
+
+/// Combines multiple signals into a single fitness:
+///
+/// ```text
+/// fitness = sum(term.weight * term.normalization(term.signal))
+/// ```
+///
+/// Since the genetic algorithm's selection requires fitness to be
+/// non-negative, the whole population's fitness gets shifted up if any
+/// of its members ended up below zero (which doesn't change the order).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FitnessComposer {
+    pub terms: Vec<FitnessTerm>,
+}
+
+impl FitnessComposer {
+    pub fn new(terms: Vec<FitnessTerm>) -> Self {
+        Self { terms }
+    }
+
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(!self.terms.is_empty(), "fitness composer requires at least one term");
+
+        for term in &self.terms {
+            ensure!(term.weight.is_finite());
+        }
+
+        Ok(())
+    }
+
+    /// Returns each bird's fitness, along with each term's population
+    /// mean of its raw (not normalized) signal.
+    pub(crate) fn compose(&self, animals: &[Animal]) -> (Vec<f32>, Vec<f32>) {
+        let mut fitnesses = vec![0.0; animals.len()];
+        let mut means = Vec::with_capacity(self.terms.len());
+
+        for term in &self.terms {
+            let mut values: Vec<_> = animals.iter().map(|animal| term.signal.measure(animal)).collect();
+
+            means.push(mean_std(&values).0);
+            term.normalization.apply(&mut values);
+
+            for (fitness, value) in fitnesses.iter_mut().zip(values) {
+                *fitness += term.weight * value;
+            }
+        }
+
+        let min = fitnesses.iter().copied().fold(0.0, f32::min);
+
+        for fitness in &mut fitnesses {
+            *fitness -= min;
+        }
+
+        (fitnesses, means)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const VALUES: [f32; 6] = [3.0, 0.0, 12.0, 5.0, 5.0, 1.0];
+
+    fn normalized(normalization: Normalization) -> Vec<f32> {
+        let mut values = VALUES.to_vec();
+        normalization.apply(&mut values);
+        values
+    }
+
+    #[test]
+    fn z_score_has_zero_mean_and_unit_std() {
+        let (mean, std) = mean_std(&normalized(Normalization::ZScorePerGeneration));
+
+        assert_relative_eq!(mean, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(std, 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn z_score_of_equal_values_is_zero() {
+        let mut values = vec![4.0; 5];
+        Normalization::ZScorePerGeneration.apply(&mut values);
+
+        assert_eq!(values, vec![0.0; 5]);
+    }
+
+    #[test]
+    fn rank_is_order_preserving() {
+        let ranks = normalized(Normalization::RankPerGeneration);
+
+        for a in 0..VALUES.len() {
+            for b in 0..VALUES.len() {
+                assert_eq!(
+                    VALUES[a].partial_cmp(&VALUES[b]),
+                    ranks[a].partial_cmp(&ranks[b]),
+                );
+            }
+        }
+
+        assert_eq!(ranks, vec![0.4, 0.0, 1.0, 0.7, 0.7, 0.2]);
+    }
+
+    #[test]
+    fn single_raw_signal_reproduces_raw_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 10,
+            generation_length: 200,
+            ..Default::default()
+        };
+
+        let mut world = World::from_config(&config, &mut rng);
+
+        for step in 0..config.generation_length {
+            world.step(&config, &mut rng, step);
+        }
+
+        let composer = FitnessComposer::new(vec![FitnessTerm {
+            signal: FitnessSignal::Foods,
+            weight: 1.0,
+            normalization: Normalization::None,
+        }]);
+
+        let (fitnesses, means) = composer.compose(&world.animals);
+        let raw: Vec<_> = world.animals.iter().map(|animal| animal.satiation as f32).collect();
+
+        assert_eq!(fitnesses, raw);
+        assert_relative_eq!(means[0], raw.iter().sum::<f32>() / raw.len() as f32);
+    }
+
+    #[test]
+    fn negative_fitness_gets_shifted() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut animals: Vec<_> = (0..3)
+            .map(|_| Animal::random(&SimulationConfig::default(), &mut rng))
+            .collect();
+
+        for (animal, distance) in animals.iter_mut().zip([1.0, 3.0, 2.0]) {
+            animal.distance = distance;
+        }
+
+        let composer = FitnessComposer::new(vec![FitnessTerm {
+            signal: FitnessSignal::Distance,
+            weight: -1.0,
+            normalization: Normalization::None,
+        }]);
+
+        assert_eq!(composer.compose(&animals).0, vec![2.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn behavior_flags_get_penalized() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            behavior: Some(BehaviorConfig::default()),
+            ..Default::default()
+        };
+
+        let mut animals: Vec<_> = (0..3).map(|_| Animal::random(&config, &mut rng)).collect();
+        let behavior = config.behavior.unwrap();
+
+        // Second bird idles in the corner for a whole window
+        for _ in 0..behavior.window {
+            animals[1].behavior.observe(
+                &behavior,
+                na::Point2::new(0.0, 0.0),
+                na::Rotation2::new(0.0),
+                na::Vector2::zeros(),
+            );
+        }
+
+        for animal in &mut animals {
+            animal.scored_satiation = 2;
+        }
+
+        let composer = FitnessComposer::new(vec![
+            FitnessTerm {
+                signal: FitnessSignal::Foods,
+                weight: 1.0,
+                normalization: Normalization::None,
+            },
+            FitnessTerm {
+                signal: FitnessSignal::Idling,
+                weight: -1.5,
+                normalization: Normalization::None,
+            },
+        ]);
+
+        assert_eq!(composer.compose(&animals).0, vec![2.0, 0.5, 2.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "behavior signals require behavior classifiers")]
+    fn behavior_signals_require_classifiers() {
+        SimulationConfig {
+            fitness: Some(FitnessComposer::new(vec![FitnessTerm {
+                signal: FitnessSignal::Spinning,
+                weight: -1.0,
+                normalization: Normalization::None,
+            }])),
+            ..Default::default()
+        }
+        .validate();
+    }
+}
@@ -0,0 +1,298 @@
+//! Stress-testing entry point for the whole pipeline.
+//!
+//! Each run builds a randomized (but valid) `SimulationConfig` out of a
+//! seed, simulates a couple of generations and checks the simulation's
+//! invariants after every single step - so whenever something breaks,
+//! the returned `FuzzFailure` is enough to replay it:
+//!
+//! ```ignore
+//! lib_simulation::fuzz::fuzz_run(failure.seed, failure.config_mutations)
+//! ```
+
+use crate::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::any::Any;
+use std::f32::consts::PI;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// How many generations a single fuzz run lasts.
+///
+/// Combined with the bounded `generation_length` (see `mutate_config()`),
+/// this keeps a single run in the milliseconds range.
+const GENERATIONS: usize = 3;
+
+#[derive(Clone, Debug)]
+pub struct FuzzFailure {
+    pub seed: u64,
+    pub config_mutations: usize,
+
+    /// Step after which the violation happened; 0 means "right after
+    /// the simulation got created".
+    pub step: usize,
+
+    pub violation: Violation,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    Invariant(Invariant),
+    Panic(String),
+}
+
+impl FuzzFailure {
+    /// Re-creates the config this run was using.
+    pub fn config(&self) -> SimulationConfig {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+
+        random_config(&mut rng, self.config_mutations)
+    }
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let violation = match &self.violation {
+            Violation::Invariant(invariant) => invariant.to_string(),
+            Violation::Panic(message) => format!("panic: {}", message),
+        };
+
+        write!(
+            f,
+            "{} after step {} (replay with `fuzz_run({}, {})`, config: {:?})",
+            violation, self.step, self.seed, self.config_mutations, self.config(),
+        )
+    }
+}
+
+pub fn fuzz_run(seed: u64, config_mutations: usize) -> Result<(), FuzzFailure> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let config = random_config(&mut rng, config_mutations);
+
+    let fail = |step, violation| FuzzFailure {
+        seed,
+        config_mutations,
+        step,
+        violation,
+    };
+
+    let mut sim = panic::catch_unwind(AssertUnwindSafe(|| {
+        Simulation::from_config(config.clone(), &mut rng)
+    }))
+    .map_err(|err| fail(0, Violation::Panic(panic_message(err))))?;
+
+    sim.check_invariants(None)
+        .map_err(|invariant| fail(0, Violation::Invariant(invariant)))?;
+
+    for step in 1..=GENERATIONS * (config.generation_length + 1) {
+        let summary = panic::catch_unwind(AssertUnwindSafe(|| sim.step(&mut rng)))
+            .map_err(|err| fail(step, Violation::Panic(panic_message(err))))?;
+
+        sim.check_invariants(summary.as_ref())
+            .map_err(|invariant| fail(step, Violation::Invariant(invariant)))?;
+    }
+
+    Ok(())
+}
+
+fn random_config(rng: &mut dyn RngCore, config_mutations: usize) -> SimulationConfig {
+    // Starting from a small world keeps each run cheap; mutations below
+    // never go much further than that either.
+    let mut config = SimulationConfig {
+        n_animals: 8,
+        n_foods: 10,
+        generation_length: 25,
+        ..Default::default()
+    };
+
+    for _ in 0..config_mutations {
+        mutate_config(rng, &mut config);
+    }
+
+    config
+}
+
+fn mutate_config(rng: &mut dyn RngCore, config: &mut SimulationConfig) {
+    match rng.gen_range(0..10) {
+        0 => {
+            config.n_animals = rng.gen_range(1..=12);
+
+            config.habitats = (config.n_animals >= 2 && rng.gen_bool(0.5)).then(|| Habitats {
+                migration_rate: rng.gen_range(0.0..=1.0),
+                ..Default::default()
+            });
+
+            config.ab_test = (config.n_animals >= 2 && config.habitats.is_none() && rng.gen_bool(0.5)).then(|| AbTest {
+                b: GaParams {
+                    mutation_chance: rng.gen_range(0.0..=1.0),
+                    brood_size: rng.gen_range(1..=3),
+                    ..Default::default()
+                },
+            });
+        }
+        1 => {
+            config.n_foods = rng.gen_range(0..=20);
+
+            config.auto_balance = rng.gen_bool(0.5).then(|| AutoBalance {
+                min_eaten: rng.gen_range(0.0..=1.0),
+                max_eaten: rng.gen_range(1.0..=3.0),
+                rate: rng.gen_range(0.01..=1.0),
+                min_foods: rng.gen_range(0..=5),
+                max_foods: rng.gen_range(5..=20),
+            });
+        }
+        2 => {
+            config.generation_length = rng.gen_range(1..=40);
+
+            config.food_layout = match rng.gen_range(0..4) {
+                0 => PersistMode::Carry,
+                1 => PersistMode::Rerandomize,
+                2 => PersistMode::KeepPositions,
+                _ => PersistMode::KeepForK(rng.gen_range(1..=3)),
+            };
+        }
+        3 => {
+            config.speed_min = rng.gen_range(0.0001..=0.01);
+            config.speed_max = config.speed_min + rng.gen_range(0.0..=0.01);
+            config.brain.init_forward_bias = rng.gen_range(-1.0..=2.0);
+        }
+        4 => {
+            config.speed_accel = rng.gen_range(0.0..=0.5);
+            config.rotation_accel = rng.gen_range(0.0..=2.0 * PI);
+
+            config.rotational_inertia = rng.gen_bool(0.5).then(|| RotationalInertia {
+                max_torque: rng.gen_range(0.0..=PI),
+                damping: rng.gen_range(0.01..=1.0),
+            });
+
+            config.behavior = rng.gen_bool(0.5).then(|| BehaviorConfig {
+                window: rng.gen_range(1..=50),
+                wall_margin: rng.gen_range(0.0..0.5),
+                ..Default::default()
+            });
+        }
+        5 => {
+            config.fov_range = rng.gen_range(0.01..=1.0);
+            config.fov_angle = rng.gen_range(0.01..=2.0 * PI);
+
+            config.environment_cycle = rng.gen_bool(0.5).then(|| EnvironmentCycle {
+                period: rng.gen_range(1..=50),
+                amplitude: rng.gen_range(0.0..=1.0),
+            });
+        }
+        6 => {
+            config.eye_cells = rng.gen_range(1..=15);
+
+            config.extra_inputs = [ExtraInput::HomeVector, ExtraInput::Scent, ExtraInput::Birds]
+                .into_iter()
+                .filter(|_| rng.gen_bool(0.5))
+                .collect();
+
+            config.scent_range = rng.gen_range(0.01..=1.0);
+
+            config.bird_vision = if rng.gen_bool(0.5) {
+                BirdVision::Energy
+            } else {
+                BirdVision::Presence
+            };
+        }
+        7 => {
+            config.evaluation = if rng.gen_bool(0.5) {
+                Evaluation::Shared
+            } else {
+                Evaluation::Isolated {
+                    steps: rng.gen_range(1..=40),
+                    seed: rng.gen(),
+                }
+            };
+        }
+        8 => {
+            config.energy = Some(EnergyConfig {
+                initial: rng.gen_range(0.0..=2.0),
+                per_step: rng.gen_range(0.0..=0.01),
+                per_food: rng.gen_range(0.0..=1.0),
+            });
+
+            config.sharing = rng.gen_bool(0.5).then(|| SharingConfig {
+                contact_radius: rng.gen_range(0.001..=0.5),
+                rate: rng.gen_range(0.0..=0.5),
+                min_gap: rng.gen_range(0.0..=1.0),
+            });
+
+            config.nursery = rng.gen_bool(0.5).then(|| Nursery {
+                grace_steps: rng.gen_range(0..=50),
+            });
+
+            config.hibernation = rng.gen_bool(0.5).then(|| Hibernation {
+                threshold: rng.gen_range(0.0..=1.0),
+                cost_factor: rng.gen_range(0.0..=1.0),
+            });
+        }
+        _ => {
+            config.ga.mutation_chance = rng.gen_range(0.0..=1.0);
+            config.ga.mutation_coeff = rng.gen_range(0.0..=2.0);
+            config.ga.weight_decay = rng.gen_range(0.0..=0.1);
+            config.ga.brood_size = rng.gen_range(1..=5);
+
+            if rng.gen_bool(0.5) {
+                config.ga.reproduction = ga::ReproductionMode::Asexual;
+            }
+
+            config.ga.self_mating = rng.gen_bool(0.5);
+
+            if rng.gen_bool(0.5) {
+                config.ga.layer_scales = vec![rng.gen_range(0.0..=2.0), rng.gen_range(0.0..=2.0)];
+            }
+
+            config.genealogy = rng.gen_bool(0.5).then(|| GenealogyConfig {
+                depth: rng.gen_range(0..=4),
+            });
+
+            config.gene_ages = rng.gen_bool(0.5);
+            config.event_log = rng.gen_bool(0.5).then(|| rng.gen_range(1..=10));
+
+            config.transitions = rng.gen_bool(0.5).then(|| TransitionConfig {
+                sample_rate: rng.gen_range(0.0..=1.0),
+                capacity: rng.gen_range(1..=100),
+            });
+
+            config.memory_budget = rng.gen_bool(0.5).then(|| MemoryBudget {
+                genealogy: rng.gen_range(0..=4096),
+                transitions: rng.gen_range(0..=4096),
+                events: rng.gen_range(0..=4096),
+            });
+        }
+    }
+}
+
+fn panic_message(err: Box<dyn Any + Send>) -> String {
+    if let Some(message) = err.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = err.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<unknown panic>".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_configs_uphold_invariants() {
+        for seed in 0..300 {
+            if let Err(failure) = fuzz_run(seed, (seed % 8) as usize) {
+                panic!("{}", failure);
+            }
+        }
+    }
+
+    #[test]
+    fn runs_are_replayable() {
+        let mut a = ChaCha8Rng::seed_from_u64(1234);
+        let mut b = ChaCha8Rng::seed_from_u64(1234);
+
+        assert_eq!(random_config(&mut a, 5), random_config(&mut b, 5));
+    }
+}
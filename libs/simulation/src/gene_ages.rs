@@ -0,0 +1,106 @@
+use crate::*;
+
+/// Ages of each bird's genes (see: `ga::Evolution::gene_ages`), all at
+/// zero; empty unless `SimulationConfig::gene_ages` is enabled.
+pub(crate) fn fresh_gene_ages(config: &SimulationConfig) -> Vec<Vec<u16>> {
+    if config.gene_ages {
+        vec![vec![0; Animal::chromosome_len(config)]; config.n_animals]
+    } else {
+        Vec::new()
+    }
+}
+
+impl Simulation {
+    /// Mean number of generations each gene has survived unchanged, across
+    /// the whole population, in order of the chromosome - so that it's
+    /// visible which weights evolution has "locked in"; present only when
+    /// `SimulationConfig::gene_ages` is enabled.
+    ///
+    /// Birds bred by hand (see: `Simulation::breed()`) and birds spawned
+    /// by a restart start with all genes at zero.
+    pub fn gene_age_profile(&self) -> Option<Vec<f32>> {
+        if !self.config.gene_ages {
+            return None;
+        }
+
+        let mut profile = vec![0.0; Animal::chromosome_len(&self.config)];
+
+        for ages in &self.gene_ages {
+            for (mean, &age) in profile.iter_mut().zip(ages) {
+                *mean += age as f32;
+            }
+        }
+
+        for mean in &mut profile {
+            *mean /= self.gene_ages.len() as f32;
+        }
+
+        Some(profile)
+    }
+
+    /// Evolves given individuals with `ga`, tracking their genes' ages if enabled;
+    /// `gene_ages` are the ages of `population`'s genes.
+    pub(crate) fn evolve_aged(
+        &self,
+        ga: &Ga,
+        rng: &mut dyn RngCore,
+        population: &[AnimalIndividual],
+        sample_rate: f32,
+        gene_ages: &[Vec<u16>],
+    ) -> ga::Evolution<AnimalIndividual> {
+        trace_span!(DEBUG, "ga", population = population.len(), sample_rate);
+
+        let evolution = if self.config.gene_ages {
+            ga.evolve_with_gene_ages(rng, population, sample_rate, gene_ages)
+        } else {
+            ga.evolve_traced(rng, population, sample_rate)
+        };
+
+        evolution.expect("birds' chromosomes have the same length")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn profile(mutation_chance: f32, gene_ages: bool) -> Option<Vec<f32>> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 10,
+            generation_length: 50,
+            ga: GaParams {
+                mutation_chance,
+                ..Default::default()
+            },
+            gene_ages,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        sim.train_many(&mut rng, 3);
+        sim.gene_age_profile()
+    }
+
+    fn chromosome_len() -> usize {
+        Animal::chromosome_len(&SimulationConfig::default())
+    }
+
+    #[test]
+    fn unmutated_genes_grow_older() {
+        assert_eq!(profile(0.0, true), Some(vec![3.0; chromosome_len()]));
+    }
+
+    #[test]
+    fn mutated_genes_start_over() {
+        assert_eq!(profile(1.0, true), Some(vec![0.0; chromosome_len()]));
+    }
+
+    #[test]
+    fn profile_is_disabled_by_default() {
+        assert_eq!(profile(0.0, false), None);
+    }
+}
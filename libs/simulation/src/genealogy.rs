@@ -0,0 +1,254 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Bird #`.1` of generation #`.0`.
+pub type Lineage = (usize, usize);
+
+/// Genealogy tracking - when enabled, the simulation remembers who each
+/// bird's parents were; see: `Simulation::genealogy()`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenealogyConfig {
+    /// How many generations back `Genealogy::relatedness()` looks; older
+    /// ancestors are treated as unrelated founders, which keeps the cost
+    /// bounded no matter how long the simulation runs.
+    pub depth: usize,
+}
+
+impl Default for GenealogyConfig {
+    fn default() -> Self {
+        Self { depth: 4 }
+    }
+}
+
+/// Family tree of the entire simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Genealogy {
+    depth: usize,
+
+    /// Generation #`generations[0]` is about, i.e. how many generations
+    /// have been evicted (see: `MemoryBudget`); birds whose parents have
+    /// been evicted are treated as founders.
+    first: usize,
+
+    /// `generations[g][idx]` = parents of bird #idx of generation
+    /// #(first + g), as indices into the previous generation; `None` for
+    /// birds without known parents (the initial population, restarts).
+    generations: VecDeque<Vec<Option<(usize, usize)>>>,
+
+    /// Number of birds across `generations`.
+    birds: usize,
+}
+
+impl Genealogy {
+    pub(crate) fn new(config: &GenealogyConfig, founders: usize) -> Self {
+        Self {
+            depth: config.depth,
+            first: 0,
+            generations: VecDeque::from([vec![None; founders]]),
+            birds: founders,
+        }
+    }
+
+    pub(crate) fn push_generation(&mut self, parents: Vec<Option<(usize, usize)>>) {
+        self.birds += parents.len();
+        self.generations.push_back(parents);
+    }
+
+    /// Returns given bird's parents, if they're known.
+    pub fn parents(&self, (generation, idx): Lineage) -> Option<(Lineage, Lineage)> {
+        let (a, b) = (*self.generations.get(generation.checked_sub(self.first)?)?.get(idx)?)?;
+
+        Some(((generation - 1, a), (generation - 1, b)))
+    }
+
+    /// How related two birds are - Wright's coefficient of relationship,
+    /// i.e. the expected fraction of genes they share through common
+    /// ancestors:
+    ///
+    /// - 1.0 = same bird (or clones),
+    /// - 0.5 = full siblings, parent & child,
+    /// - 0.25 = half siblings, grandparent & grandchild,
+    /// - 0.0 = no common ancestors within `GenealogyConfig::depth`
+    ///   generations.
+    ///
+    /// Since selection allows a bird to be crossed with itself, inbred
+    /// lineages could go beyond 1.0 - the result is capped there.
+    pub fn relatedness(&self, a: Lineage, b: Lineage) -> f32 {
+        let cutoff = a.0.max(b.0).saturating_sub(self.depth);
+
+        (2.0 * self.kinship(a, b, cutoff, &mut HashMap::new())).min(1.0)
+    }
+
+    /// Mean relatedness across all pairs of birds of given generation; 0.0
+    /// if there are less than two birds.
+    pub fn mean_relatedness(&self, generation: usize) -> f32 {
+        let n = self.generations[generation - self.first].len();
+        let cutoff = generation.saturating_sub(self.depth);
+        let mut memo = HashMap::new();
+        let mut sum = 0.0;
+
+        for a in 0..n {
+            for b in (a + 1)..n {
+                let kinship = self.kinship((generation, a), (generation, b), cutoff, &mut memo);
+                sum += (2.0 * kinship).min(1.0);
+            }
+        }
+
+        if n < 2 {
+            0.0
+        } else {
+            sum / (n * (n - 1) / 2) as f32
+        }
+    }
+
+    /// Kinship coefficient - the probability that genes picked at random
+    /// from both birds are identical by descent; birds of generations up
+    /// to `cutoff` are treated as founders.
+    fn kinship(
+        &self,
+        a: Lineage,
+        b: Lineage,
+        cutoff: usize,
+        memo: &mut HashMap<(Lineage, Lineage), f32>,
+    ) -> f32 {
+        let key = (a.min(b), a.max(b));
+
+        if let Some(&kinship) = memo.get(&key) {
+            return kinship;
+        }
+
+        let parents = |bird: Lineage| {
+            if bird.0 <= cutoff {
+                None
+            } else {
+                self.parents(bird)
+            }
+        };
+
+        let kinship = if a == b {
+            match parents(a) {
+                Some((pa, pb)) => (1.0 + self.kinship(pa, pb, cutoff, memo)) / 2.0,
+                None => 0.5,
+            }
+        } else {
+            // Always go up through the younger bird, so that we don't
+            // skip over the older one if it's the younger one's ancestor
+            let (younger, older) = if a.0 >= b.0 { (a, b) } else { (b, a) };
+
+            match (parents(younger), parents(older)) {
+                (Some((pa, pb)), _) => {
+                    (self.kinship(pa, older, cutoff, memo) + self.kinship(pb, older, cutoff, memo)) / 2.0
+                }
+                (None, Some((pa, pb))) => {
+                    (self.kinship(younger, pa, cutoff, memo) + self.kinship(younger, pb, cutoff, memo)) / 2.0
+                }
+                (None, None) => 0.0,
+            }
+        };
+
+        memo.insert(key, kinship);
+        kinship
+    }
+}
+
+/// Evicts whole generations, oldest first - except for the most recent
+/// one, which the next generation's parents refer to.
+impl MemoryComponent for Genealogy {
+    fn live_bytes(&self) -> usize {
+        self.birds * std::mem::size_of::<Option<(usize, usize)>>()
+    }
+
+    fn evict_oldest(&mut self, cap: usize) {
+        while self.live_bytes() > cap && self.generations.len() > 1 {
+            let evicted = self.generations.pop_front().expect("there's more than one generation");
+
+            self.birds -= evicted.len();
+            self.first += 1;
+        }
+    }
+}
+
+impl Simulation {
+    /// Returns `None` unless `SimulationConfig::genealogy` is enabled.
+    pub fn genealogy(&self) -> Option<&Genealogy> {
+        self.genealogy.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Three founders; then two full siblings (of #0 and #1) and their
+    /// half sibling (of #1 and #2).
+    fn genealogy(depth: usize) -> Genealogy {
+        let mut genealogy = Genealogy::new(&GenealogyConfig { depth }, 3);
+        genealogy.push_generation(vec![Some((0, 1)), Some((0, 1)), Some((1, 2))]);
+        genealogy
+    }
+
+    #[test]
+    fn full_siblings() {
+        assert_relative_eq!(genealogy(1).relatedness((1, 0), (1, 1)), 0.5);
+    }
+
+    #[test]
+    fn half_siblings() {
+        assert_relative_eq!(genealogy(1).relatedness((1, 0), (1, 2)), 0.25);
+    }
+
+    #[test]
+    fn parent_and_child() {
+        assert_relative_eq!(genealogy(1).relatedness((0, 0), (1, 0)), 0.5);
+    }
+
+    #[test]
+    fn unrelated_founders() {
+        let genealogy = genealogy(1);
+
+        assert_eq!(genealogy.relatedness((0, 0), (0, 1)), 0.0);
+        assert_eq!(genealogy.relatedness((0, 1), (0, 1)), 1.0);
+    }
+
+    #[test]
+    fn ancestors_beyond_depth_are_ignored() {
+        assert_eq!(genealogy(0).relatedness((1, 0), (1, 1)), 0.0);
+    }
+
+    #[test]
+    fn population_mean() {
+        // (siblings + 2 * half siblings) / 3 pairs
+        assert_relative_eq!(genealogy(2).mean_relatedness(1), (0.5 + 0.25 + 0.25) / 3.0);
+        assert_eq!(genealogy(2).mean_relatedness(0), 0.0);
+    }
+
+    #[test]
+    fn simulation_reports_mean_relatedness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 8,
+            generation_length: 50,
+            genealogy: Some(GenealogyConfig::default()),
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        let summaries = sim.train_many(&mut rng, 3);
+
+        // Everybody's a founder at first
+        assert_eq!(summaries[0].mean_relatedness, Some(0.0));
+        assert!(summaries[2].mean_relatedness.unwrap() > 0.0);
+
+        let genealogy = sim.genealogy().unwrap();
+
+        for idx in 0..8 {
+            assert!(genealogy.parents((3, idx)).is_some());
+        }
+    }
+}
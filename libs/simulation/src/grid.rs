@@ -0,0 +1,71 @@
+use crate::*;
+
+/// The finest grid we're willing to allocate, per axis; radiuses smaller
+/// than `1 / MAX_CELLS` simply get more points per cell.
+const MAX_CELLS: usize = 64;
+
+/// Finds all pairs of points that lie at most `radius` apart.
+///
+/// Instead of checking every pair, points get bucketed into a uniform
+/// grid with cells at least `radius` wide, so that each point has to be
+/// compared only with points from its own and the neighbouring cells.
+///
+/// Returned pairs are `(a, b)` with `a < b`, sorted.
+pub(crate) fn pairs_within(positions: &[na::Point2<f32>], radius: f32) -> Vec<(usize, usize)> {
+    let cells_per_axis = ((1.0 / radius).floor() as usize).clamp(1, MAX_CELLS);
+    let cell_of = |coord: f32| ((coord * cells_per_axis as f32) as usize).min(cells_per_axis - 1);
+
+    let mut cells = vec![Vec::new(); cells_per_axis * cells_per_axis];
+
+    for (idx, position) in positions.iter().enumerate() {
+        cells[cell_of(position.y) * cells_per_axis + cell_of(position.x)].push(idx);
+    }
+
+    let mut pairs = Vec::new();
+
+    for (a, position) in positions.iter().enumerate() {
+        let (cx, cy) = (cell_of(position.x), cell_of(position.y));
+
+        for y in cy.saturating_sub(1)..=(cy + 1).min(cells_per_axis - 1) {
+            for x in cx.saturating_sub(1)..=(cx + 1).min(cells_per_axis - 1) {
+                for &b in &cells[y * cells_per_axis + x] {
+                    if b > a && na::distance(position, &positions[b]) <= radius {
+                        pairs.push((a, b));
+                    }
+                }
+            }
+        }
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use test_case::test_case;
+
+    #[test_case(0.005)]
+    #[test_case(0.05)]
+    #[test_case(0.3)]
+    #[test_case(1.5)]
+    fn finds_same_pairs_as_brute_force(radius: f32) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let positions: Vec<na::Point2<f32>> = (0..300).map(|_| rng.gen()).collect();
+
+        let mut expected = Vec::new();
+
+        for a in 0..positions.len() {
+            for b in (a + 1)..positions.len() {
+                if na::distance(&positions[a], &positions[b]) <= radius {
+                    expected.push((a, b));
+                }
+            }
+        }
+
+        assert_eq!(pairs_within(&positions, radius), expected);
+    }
+}
@@ -0,0 +1,437 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Axis-aligned rectangle of the map, inclusive on both ends.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Region {
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+}
+
+impl Region {
+    pub fn contains(&self, point: na::Point2<f32>) -> bool {
+        (self.min[0]..=self.max[0]).contains(&point.x)
+            && (self.min[1]..=self.max[1]).contains(&point.y)
+    }
+
+    fn clamp(&self, point: na::Point2<f32>) -> na::Point2<f32> {
+        na::Point2::new(
+            point.x.clamp(self.min[0], self.max[0]),
+            point.y.clamp(self.min[1], self.max[1]),
+        )
+    }
+
+    fn random(&self, rng: &mut dyn RngCore) -> na::Point2<f32> {
+        na::Point2::new(
+            rng.gen_range(self.min[0]..=self.max[0]),
+            rng.gen_range(self.min[1]..=self.max[1]),
+        )
+    }
+
+    fn try_validate(&self) -> Result<(), ConfigError> {
+        for axis in 0..2 {
+            ensure!(0.0 <= self.min[axis]);
+            ensure!(self.min[axis] <= self.max[axis]);
+            ensure!(self.max[axis] <= 1.0);
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits the map into habitats, each home to a separate sub-population
+/// that evolves on its own - birds cannot leave their habitat, except
+/// through corridors.
+///
+/// A bird that flies through a corridor into another habitat counts as
+/// crossing into it and, with probability `migration_rate`, gets to breed
+/// there at the end of the generation, on top of breeding back home.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Habitats {
+    /// Sub-population #n lives in `regions[n]`; birds get split between
+    /// them evenly, by their slots (see: `Habitats::slots()`).
+    pub regions: Vec<Region>,
+
+    /// Areas birds of every habitat can fly through.
+    pub corridors: Vec<Region>,
+
+    pub migration_rate: f32,
+}
+
+impl Habitats {
+    pub(crate) fn try_validate(&self, n_animals: usize) -> Result<(), ConfigError> {
+        ensure!(!self.regions.is_empty());
+        ensure!(n_animals >= self.regions.len(), "each habitat requires at least one animal");
+        ensure!((0.0..=1.0).contains(&self.migration_rate));
+
+        for region in self.regions.iter().chain(&self.corridors) {
+            region.try_validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Slots (indices into `World::animal()`) of birds living in given
+    /// habitat; the first `n_animals % regions.len()` habitats get one
+    /// bird more than the rest.
+    pub fn slots(&self, habitat: usize, n_animals: usize) -> Range<usize> {
+        let base = n_animals / self.regions.len();
+        let extra = n_animals % self.regions.len();
+        let start = habitat * base + habitat.min(extra);
+
+        start..(start + base + (habitat < extra) as usize)
+    }
+
+    pub fn habitat_of(&self, slot: usize, n_animals: usize) -> usize {
+        (0..self.regions.len())
+            .find(|&habitat| self.slots(habitat, n_animals).contains(&slot))
+            .expect("slot is out of bounds")
+    }
+
+    /// Whether a bird of given habitat is allowed to be at `point`.
+    pub fn allows(&self, habitat: usize, point: na::Point2<f32>) -> bool {
+        self.regions[habitat].contains(point) || self.in_corridor(point)
+    }
+
+    fn in_corridor(&self, point: na::Point2<f32>) -> bool {
+        self.corridors.iter().any(|corridor| corridor.contains(point))
+    }
+}
+
+impl Default for Habitats {
+    /// Left & right half of the map, connected by a narrow corridor in the
+    /// middle.
+    fn default() -> Self {
+        Self {
+            regions: vec![
+                Region {
+                    min: [0.0, 0.0],
+                    max: [0.48, 1.0],
+                },
+                Region {
+                    min: [0.52, 0.0],
+                    max: [1.0, 1.0],
+                },
+            ],
+            corridors: vec![Region {
+                min: [0.45, 0.45],
+                max: [0.55, 0.55],
+            }],
+            migration_rate: 0.1,
+        }
+    }
+}
+
+/// What happened in a single habitat during a generation.
+#[derive(Clone, Debug)]
+pub struct HabitatSummary {
+    /// Fitness statistics of the birds living in this habitat.
+    pub stats: ga::Statistics,
+
+    /// Number of times birds of other habitats have crossed into this one.
+    pub crossings: usize,
+
+    /// Birds of other habitats that got to breed here.
+    pub immigrants: usize,
+}
+
+impl Animal {
+    /// Keeps the bird within its habitat (or a corridor), after it has
+    /// moved from `previous`; returns the habitat it has crossed into, if
+    /// any.
+    pub(crate) fn confine(
+        &mut self,
+        habitats: &Habitats,
+        home: usize,
+        previous: na::Point2<f32>,
+        movement: na::Vector2<f32>,
+    ) -> Option<usize> {
+        if !habitats.allows(home, self.position) {
+            let home_region = habitats.regions[home];
+
+            self.position = if home_region.contains(previous) {
+                // Clamping what hasn't been wrapped around the map's edges
+                // makes the bird slide along its habitat's border
+                home_region.clamp(previous + movement)
+            } else {
+                previous
+            };
+        }
+
+        let crossed_into = (0..habitats.regions.len()).find(|&habitat| {
+            let region = habitats.regions[habitat];
+
+            habitat != home && region.contains(self.position) && !region.contains(previous)
+        })?;
+
+        self.crossed_into = Some(crossed_into);
+        Some(crossed_into)
+    }
+}
+
+impl World {
+    /// Spawns each bird at a random position within its habitat; no-op
+    /// unless `SimulationConfig::habitats` is enabled.
+    pub(crate) fn settle_habitats(&mut self, config: &SimulationConfig, rng: &mut dyn RngCore) {
+        let Some(habitats) = &config.habitats else {
+            return;
+        };
+
+        for (slot, animal) in self.animals.iter_mut().enumerate() {
+            let habitat = habitats.habitat_of(slot, config.n_animals);
+
+            animal.position = habitats.regions[habitat].random(rng);
+            animal.crossed_into = None;
+        }
+
+        self.crossings = vec![0; habitats.regions.len()];
+    }
+}
+
+impl Simulation {
+    /// Like `ga::GeneticAlgorithm::evolve_traced()`, but evolves each
+    /// habitat's sub-population separately, along with immigrants (see:
+    /// `Habitats::migration_rate`).
+    ///
+    /// Immigrants make a habitat's breeding pool larger than its share of
+    /// the population, so some of the children bred there get discarded -
+    /// the returned diagnostics cover them, too.
+    pub(crate) fn evolve_habitats(
+        &self,
+        habitats: &Habitats,
+        rng: &mut dyn RngCore,
+        population: &[AnimalIndividual],
+        sample_rate: f32,
+    ) -> (ga::Evolution<AnimalIndividual>, Vec<HabitatSummary>) {
+        let n_animals = population.len();
+
+        let stats_of = |slots: &mut dyn Iterator<Item = usize>| {
+            let mut stats = ga::StatisticsBuilder::default();
+
+            for slot in slots {
+                stats.observe(population[slot].fitness());
+            }
+
+            stats.finish()
+        };
+
+        let mut evolution = ga::Evolution {
+            population: Vec::with_capacity(n_animals),
+            stats: stats_of(&mut (0..n_animals)),
+            samples: Vec::new(),
+            parents: Vec::with_capacity(n_animals),
+            diagnostics: Default::default(),
+            gene_ages: Vec::new(),
+        };
+
+        let mut summaries = Vec::new();
+
+        for habitat in 0..habitats.regions.len() {
+            let home = habitats.slots(habitat, n_animals);
+
+            let immigrants: Vec<_> = (0..n_animals)
+                .filter(|slot| !home.contains(slot))
+                .filter(|&slot| self.world.animals[slot].crossed_into == Some(habitat))
+                .filter(|_| rng.gen_bool(habitats.migration_rate as f64))
+                .collect();
+
+            let pool: Vec<_> = home.clone().chain(immigrants.iter().copied()).collect();
+
+            let individuals: Vec<_> = pool.iter().map(|&slot| population[slot].clone()).collect();
+
+            let gene_ages: Vec<_> = pool
+                .iter()
+                .filter_map(|&slot| self.gene_ages.get(slot).cloned())
+                .collect();
+
+            let mut bred = self.evolve_aged(&self.ga, rng, &individuals, sample_rate, &gene_ages);
+
+            bred.population.truncate(home.len());
+            bred.parents.truncate(home.len());
+            bred.diagnostics.inheritance.truncate(home.len());
+            bred.gene_ages.truncate(home.len());
+
+            evolution.population.extend(bred.population);
+            evolution.samples.extend(bred.samples);
+            evolution.gene_ages.extend(bred.gene_ages);
+
+            evolution
+                .parents
+                .extend(bred.parents.into_iter().map(|(a, b)| (pool[a], pool[b])));
+
+            let diagnostics = &mut evolution.diagnostics;
+            diagnostics.genes += bred.diagnostics.genes;
+            diagnostics.genes_touched += bred.diagnostics.genes_touched;
+            diagnostics.total_delta += bred.diagnostics.total_delta;
+            diagnostics.compatibility_retries += bred.diagnostics.compatibility_retries;
+            diagnostics.asexual += bred.diagnostics.asexual;
+            diagnostics.inheritance.extend(bred.diagnostics.inheritance);
+
+            summaries.push(HabitatSummary {
+                stats: stats_of(&mut home.clone()),
+                crossings: self.world.crossings.get(habitat).copied().unwrap_or(0),
+                immigrants: immigrants.len(),
+            });
+        }
+
+        (evolution, summaries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn config(migration_rate: f32) -> SimulationConfig {
+        SimulationConfig {
+            n_animals: 11,
+            generation_length: 100,
+            habitats: Some(Habitats {
+                migration_rate,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn slots_split_population_evenly() {
+        let habitats = Habitats::default();
+
+        assert_eq!(habitats.slots(0, 11), 0..6);
+        assert_eq!(habitats.slots(1, 11), 6..11);
+        assert_eq!(habitats.habitat_of(5, 11), 0);
+        assert_eq!(habitats.habitat_of(6, 11), 1);
+    }
+
+    #[test]
+    fn animals_never_leave_their_habitat_except_through_corridors() {
+        let config = config(0.1);
+        let habitats = config.habitats.clone().unwrap();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config, &mut rng);
+
+        for _ in 0..(3 * 101) {
+            sim.step(&mut rng);
+
+            for (slot, animal) in sim.world().animal().iter().enumerate() {
+                let home = habitats.habitat_of(slot, 11);
+
+                assert!(
+                    habitats.regions[home].contains(animal.position())
+                        || habitats.in_corridor(animal.position()),
+                    "animal #{} escaped to {}",
+                    slot,
+                    animal.position(),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn habitat_population_sizes_stay_constant() {
+        let config = config(1.0);
+        let habitats = config.habitats.clone().unwrap();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config, &mut rng);
+
+        for _ in 0..4 {
+            let summary = sim.train(&mut rng);
+
+            assert_eq!(summary.habitats.len(), 2);
+
+            // Right after evolution, everybody's been spawned back home
+            for (habitat, region) in habitats.regions.iter().enumerate() {
+                let inside = sim
+                    .world()
+                    .animal()
+                    .iter()
+                    .filter(|animal| region.contains(animal.position()))
+                    .count();
+
+                assert_eq!(inside, habitats.slots(habitat, 11).len());
+            }
+        }
+    }
+
+    /// A bird of the left habitat at `(x, y)`, flying right.
+    fn world(x: f32, y: f32) -> World {
+        let config = SimulationConfig::default();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(&config, &mut rng);
+
+        animal.position = na::Point2::new(x, y);
+        animal.rotation = na::Rotation2::new(-FRAC_PI_2);
+        animal.speed = 0.01;
+
+        let mut world = World::new(vec![animal], vec![]);
+        world.crossings = vec![0; 2];
+        world
+    }
+
+    fn movements_config() -> SimulationConfig {
+        SimulationConfig {
+            n_animals: 2,
+            habitats: Some(Habitats::default()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn corridor_crossing_is_detected_and_counted() {
+        let config = movements_config();
+        let mut world = world(0.47, 0.5);
+
+        for _ in 0..10 {
+            world.process_movements(&config);
+        }
+
+        assert_eq!(world.crossings, vec![0, 1]);
+        assert_eq!(world.animals[0].crossed_into, Some(1));
+
+        // ... and the bird got stuck right at the corridor's end
+        assert!(world.animals[0].position.x <= 0.55);
+    }
+
+    #[test]
+    fn habitat_borders_stop_birds_outside_corridors() {
+        let config = movements_config();
+        let mut world = world(0.47, 0.9);
+
+        for _ in 0..10 {
+            world.process_movements(&config);
+        }
+
+        assert_eq!(world.crossings, vec![0, 0]);
+        assert_eq!(world.animals[0].position.x, 0.48);
+    }
+
+    #[test]
+    fn crossed_birds_breed_abroad() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(1.0), &mut rng);
+
+        sim.world.animals[0].crossed_into = Some(1);
+
+        let summary = sim.train(&mut rng);
+
+        assert!(summary.habitats[1].immigrants >= 1);
+    }
+
+    #[test]
+    fn zero_migration_rate_keeps_habitats_apart() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(0.0), &mut rng);
+
+        sim.world.animals[0].crossed_into = Some(1);
+
+        let summary = sim.train(&mut rng);
+
+        assert_eq!(summary.habitats[1].immigrants, 0);
+    }
+}
@@ -0,0 +1,304 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Describes the shape of a chromosome - two chromosomes can be used
+/// interchangeably only if their genome specs are equal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenomeSpec {
+    pub eye_cells: usize,
+    pub chromosome_len: usize,
+}
+
+impl GenomeSpec {
+    pub fn of(config: &SimulationConfig) -> Self {
+        Self {
+            eye_cells: config.eye_cells,
+            chromosome_len: Animal::chromosome_len(config),
+        }
+    }
+}
+
+/// One of the best birds ever seen.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Champion {
+    /// Generation this bird has lived in.
+    pub generation: usize,
+    pub fitness: ga::Float,
+    pub genome: GenomeSpec,
+    pub genes: Vec<ga::Float>,
+}
+
+impl Champion {
+    pub fn chromosome(&self) -> ga::Chromosome {
+        self.genes.iter().copied().collect()
+    }
+
+    /// Identifies the champion by its genes (FNV-1a over their bits), so
+    /// that the same bird doesn't get into the hall of fame twice.
+    pub fn fingerprint(&self) -> u64 {
+        fnv1a(self.genes.iter().copied())
+    }
+}
+
+/// FNV-1a over the bits of given genes.
+pub(crate) fn fnv1a(genes: impl IntoIterator<Item = ga::Float>) -> u64 {
+    genes.into_iter().fold(0xcbf29ce484222325, |hash, gene| {
+        gene.to_bits()
+            .to_le_bytes()
+            .iter()
+            .fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+    })
+}
+
+/// The all-time best birds, ordered from the fittest one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HallOfFame {
+    capacity: usize,
+    champions: Vec<Champion>,
+}
+
+impl HallOfFame {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            champions: Vec::new(),
+        }
+    }
+
+    pub fn champions(&self) -> &[Champion] {
+        &self.champions
+    }
+
+    pub(crate) fn champions_mut(&mut self) -> &mut [Champion] {
+        &mut self.champions
+    }
+
+    /// Adds a champion, unless it's already here (by fingerprint) or it's
+    /// worse than everybody in an already full hall of fame; returns
+    /// whether the champion got added.
+    pub fn insert(&mut self, champion: Champion) -> bool {
+        let fingerprint = champion.fingerprint();
+
+        if self.champions.iter().any(|c| c.fingerprint() == fingerprint) {
+            return false;
+        }
+
+        // Ties keep older champions first
+        let position = self
+            .champions
+            .iter()
+            .position(|c| c.fitness < champion.fitness)
+            .unwrap_or(self.champions.len());
+
+        if position >= self.capacity {
+            return false;
+        }
+
+        self.champions.insert(position, champion);
+        self.champions.truncate(self.capacity);
+
+        true
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("hall of fame should always serialize")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HallOfFameError> {
+        serde_json::from_slice(bytes).map_err(|err| HallOfFameError::Malformed(err.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HallOfFameError {
+    /// Given bytes are not a hall of fame exported by
+    /// `Simulation::export_hall_of_fame()`.
+    Malformed(String),
+}
+
+impl fmt::Display for HallOfFameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(err) => write!(f, "malformed hall of fame: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for HallOfFameError {}
+
+/// Outcome of `Simulation::import_hall_of_fame()`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportReport {
+    /// Champions that made it into the hall of fame.
+    pub imported: usize,
+
+    /// Champions that were already there, or that weren't fit enough.
+    pub skipped: usize,
+
+    /// Champions whose genome doesn't fit this simulation, by their index
+    /// in the imported hall of fame.
+    pub rejected: Vec<(usize, GenomeSpec)>,
+}
+
+impl Simulation {
+    pub fn hall_of_fame(&self) -> &HallOfFame {
+        &self.hall_of_fame
+    }
+
+    pub fn export_hall_of_fame(&self) -> Vec<u8> {
+        self.hall_of_fame.to_bytes()
+    }
+
+    /// Merges champions exported from another session into this one's
+    /// hall of fame, keeping the top ones; champions of an incompatible
+    /// genome get rejected one by one, without failing the whole import.
+    pub fn import_hall_of_fame(&mut self, bytes: &[u8]) -> Result<ImportReport, HallOfFameError> {
+        let imported = HallOfFame::from_bytes(bytes)?;
+        let genome = GenomeSpec::of(&self.config);
+        let mut report = ImportReport::default();
+
+        for (idx, champion) in imported.champions.into_iter().enumerate() {
+            if champion.genome != genome || champion.genes.len() != genome.chromosome_len {
+                report.rejected.push((idx, champion.genome));
+            } else if self.hall_of_fame.insert(champion) {
+                report.imported += 1;
+            } else {
+                report.skipped += 1;
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn champion(fitness: ga::Float, gene: ga::Float) -> Champion {
+        Champion {
+            generation: 0,
+            fitness,
+            genome: GenomeSpec {
+                eye_cells: 1,
+                chromosome_len: 2,
+            },
+            genes: vec![gene, gene],
+        }
+    }
+
+    fn fitnesses(hall: &HallOfFame) -> Vec<ga::Float> {
+        hall.champions().iter().map(|c| c.fitness).collect()
+    }
+
+    #[test]
+    fn keeps_top_champions_in_order() {
+        let mut hall = HallOfFame::new(3);
+
+        for (fitness, gene) in [(1.0, 0.1), (5.0, 0.2), (3.0, 0.3), (4.0, 0.4), (2.0, 0.5)] {
+            hall.insert(champion(fitness, gene));
+        }
+
+        assert_eq!(fitnesses(&hall), vec![5.0, 4.0, 3.0]);
+    }
+
+    #[test]
+    fn deduplicates_by_fingerprint() {
+        let mut hall = HallOfFame::new(3);
+
+        assert!(hall.insert(champion(1.0, 0.1)));
+        assert!(!hall.insert(champion(2.0, 0.1)));
+        assert_eq!(hall.champions().len(), 1);
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let mut hall = HallOfFame::new(3);
+        hall.insert(champion(1.0, 0.1));
+        hall.insert(champion(2.0, -0.2));
+
+        assert_eq!(HallOfFame::from_bytes(&hall.to_bytes()), Ok(hall));
+    }
+
+    #[test]
+    fn rejects_malformed_bytes() {
+        assert!(matches!(
+            HallOfFame::from_bytes(b"definitely not a hall of fame"),
+            Err(HallOfFameError::Malformed(_)),
+        ));
+    }
+
+    mod simulation {
+        use super::*;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        fn sim(eye_cells: usize, seed: u8) -> Simulation {
+            let mut rng = ChaCha8Rng::from_seed([seed; 32]);
+
+            let config = SimulationConfig {
+                n_animals: 10,
+                generation_length: 200,
+                eye_cells,
+                hall_of_fame_size: 5,
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::from_config(config, &mut rng);
+
+            for _ in 0..3 {
+                sim.train(&mut rng);
+            }
+
+            sim
+        }
+
+        #[test]
+        fn export_import_round_trip() {
+            let a = sim(3, 0);
+            let mut b = sim(3, 1);
+            b.hall_of_fame = HallOfFame::new(5);
+
+            let report = b.import_hall_of_fame(&a.export_hall_of_fame()).unwrap();
+
+            assert_eq!(report.imported, a.hall_of_fame().champions().len());
+            assert_eq!(b.hall_of_fame(), a.hall_of_fame());
+        }
+
+        #[test]
+        fn import_merges_and_keeps_global_top() {
+            // Different seeds, so that both have their own champions
+            let a = sim(3, 0);
+            let mut b = sim(3, 1);
+
+            let mut expected: Vec<_> = a
+                .hall_of_fame()
+                .champions()
+                .iter()
+                .chain(b.hall_of_fame().champions())
+                .map(|c| c.fitness)
+                .collect();
+
+            expected.sort_by(|x, y| y.total_cmp(x));
+            expected.truncate(5);
+
+            b.import_hall_of_fame(&a.export_hall_of_fame()).unwrap();
+
+            assert_eq!(fitnesses(b.hall_of_fame()), expected);
+        }
+
+        #[test]
+        fn import_rejects_incompatible_genomes_per_entry() {
+            let a = sim(3, 0);
+            let mut b = sim(5, 1);
+            let before = b.hall_of_fame().clone();
+
+            let report = b.import_hall_of_fame(&a.export_hall_of_fame()).unwrap();
+
+            assert_eq!(report.imported, 0);
+            assert_eq!(report.rejected.len(), a.hall_of_fame().champions().len());
+            assert_eq!(b.hall_of_fame(), &before);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Hibernation gate - when enabled, every brain gets a third output and
+/// whenever it's strictly above `threshold`, the bird hibernates for that
+/// step: it doesn't accelerate, turn nor move, and pays just `cost_factor`
+/// of `EnergyConfig::per_step`.
+///
+/// This lets evolution discover sit-and-wait strategies, e.g. next to food
+/// clusters; hibernating birds still eat whatever they're touching.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Hibernation {
+    pub threshold: f32,
+    pub cost_factor: f32,
+}
+
+impl Hibernation {
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(self.threshold.is_finite());
+        ensure!((0.0..=1.0).contains(&self.cost_factor));
+
+        Ok(())
+    }
+}
+
+impl Default for Hibernation {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            cost_factor: 0.25,
+        }
+    }
+}
+
+impl Animal {
+    /// Decides whether the bird hibernates during this step, given brain's
+    /// `response`.
+    pub(crate) fn hibernate(&mut self, config: &SimulationConfig, response: &[f32]) -> bool {
+        self.hibernating = config
+            .hibernation
+            .is_some_and(|hibernation| response[2] > hibernation.threshold);
+
+        if self.hibernating {
+            self.hibernated_steps += 1;
+        }
+
+        self.hibernating
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn config() -> SimulationConfig {
+        SimulationConfig {
+            eye_cells: 3,
+            energy: Some(EnergyConfig {
+                initial: 1.0,
+                per_step: 0.01,
+                per_food: 0.0,
+            }),
+            hibernation: Some(Hibernation {
+                threshold: 0.5,
+                cost_factor: 0.25,
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// A bird whose brain ignores everything and always returns `gate` as
+    /// the hibernation output.
+    fn world(config: &SimulationConfig, gate: f32) -> World {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let len = Animal::chromosome_len(config);
+        let mut genes = vec![0.0; len];
+
+        // Bias of the last output neuron, which comes after the weights
+        // of the hidden layer's neurons
+        genes[len - (2 * config.eye_cells + 1)] = widen(gate);
+
+        let animal = Animal::from_chromosome(config, genes.into_iter().collect(), &mut rng);
+
+        World::new(vec![animal], vec![])
+    }
+
+    #[test]
+    fn adds_an_output() {
+        let config = config();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let animal = Animal::random(&config, &mut rng);
+
+        assert_eq!(animal.brain.propagate(vec![0.0; 3]).len(), 3);
+    }
+
+    #[test]
+    fn hibernating_bird_stays_put_and_saves_energy() {
+        let config = config();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut world = world(&config, 1.0);
+        let position = world.animals[0].position;
+        let mut expected_energy = 1.0;
+
+        for step in 0..10 {
+            world.step(&config, &mut rng, step);
+            expected_energy = (expected_energy - 0.01 * 0.25f32).max(0.0);
+
+            assert_eq!(world.animals[0].position, position);
+            assert_eq!(world.animals[0].energy, expected_energy);
+        }
+
+        assert_eq!(world.animals[0].hibernated_steps(), 10);
+    }
+
+    #[test]
+    fn gate_at_threshold_stays_awake() {
+        let config = config();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut world = world(&config, 0.5);
+        let position = world.animals[0].position;
+
+        world.step(&config, &mut rng, 0);
+
+        assert_ne!(world.animals[0].position, position);
+        assert_eq!(world.animals[0].energy, 1.0 - 0.01);
+        assert_eq!(world.animals[0].hibernated_steps(), 0);
+    }
+
+    #[test]
+    fn gate_above_threshold_hibernates() {
+        let config = config();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut world = world(&config, 0.5 + f32::EPSILON);
+
+        world.step(&config, &mut rng, 0);
+
+        assert_eq!(world.animals[0].hibernated_steps(), 1);
+    }
+}
@@ -0,0 +1,433 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Range;
+
+/// Champions of consecutive generations, stored compactly: every
+/// `keyframe_interval`-th champion gets stored in full, while the ones in
+/// between get stored as genes that differ from the previous champion -
+/// which usually are just a few, since consecutive champions tend to be
+/// nearly identical.
+///
+/// Reconstruction (see: `ChampionHistory::champion_at()`) is exact; when a
+/// delta wouldn't reproduce a champion bit-for-bit (e.g. when its genome
+/// has changed), the champion gets stored in full instead.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChampionHistory {
+    keyframe_interval: usize,
+    first_generation: usize,
+    frames: Vec<Frame>,
+
+    /// The most recent champion, which the next one gets diffed against.
+    #[serde(skip)]
+    last: Option<ga::Chromosome>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum Frame {
+    Full(Vec<ga::Float>),
+
+    /// Indices and values of genes that differ from the previous frame.
+    Delta(Vec<(u32, ga::Float)>),
+}
+
+impl Frame {
+    fn storage_size(&self) -> usize {
+        let gene = std::mem::size_of::<ga::Float>();
+
+        match self {
+            Frame::Full(genes) => genes.len() * gene,
+            Frame::Delta(genes) => genes.len() * (std::mem::size_of::<u32>() + gene),
+        }
+    }
+}
+
+impl ChampionHistory {
+    pub fn new(keyframe_interval: usize) -> Self {
+        assert!(keyframe_interval > 0);
+
+        Self {
+            keyframe_interval,
+            first_generation: 0,
+            frames: Vec::new(),
+            last: None,
+        }
+    }
+
+    /// Generations whose champions have been recorded.
+    pub fn generations(&self) -> Range<usize> {
+        self.first_generation..(self.first_generation + self.frames.len())
+    }
+
+    /// Records the champion of the generation that comes right after the
+    /// most recently recorded one (or of any generation, if it's the first
+    /// one being recorded).
+    pub fn push(&mut self, generation: usize, champion: &ga::Chromosome) {
+        if self.frames.is_empty() {
+            self.first_generation = generation;
+        }
+
+        assert_eq!(generation, self.generations().end, "generations must be recorded one after another");
+
+        let keyframe = self.frames.len().is_multiple_of(self.keyframe_interval);
+
+        let delta = self
+            .last
+            .as_ref()
+            .filter(|_| !keyframe)
+            .and_then(|last| Self::encode(last, champion));
+
+        self.frames.push(delta.unwrap_or_else(|| Frame::Full(champion.iter().copied().collect())));
+        self.last = Some(champion.clone());
+    }
+
+    /// Returns champion of given generation.
+    pub fn champion_at(&self, generation: usize) -> Result<ga::Chromosome, HistoryError> {
+        let recorded = self.generations();
+
+        if !recorded.contains(&generation) {
+            return Err(HistoryError::OutOfRange { generation, recorded });
+        }
+
+        let frame = generation - self.first_generation;
+        let base = frame - frame % self.keyframe_interval;
+
+        let Frame::Full(genes) = &self.frames[base] else {
+            return Err(HistoryError::MissingBase {
+                generation,
+                base: self.first_generation + base,
+            });
+        };
+
+        let mut champion: ga::Chromosome = genes.iter().copied().collect();
+
+        for (offset, frame) in self.frames[(base + 1)..=frame].iter().enumerate() {
+            match frame {
+                Frame::Full(genes) => {
+                    champion = genes.iter().copied().collect();
+                }
+
+                Frame::Delta(genes) => {
+                    champion
+                        .patch(&Self::decode(genes), ga::PatchSource::B)
+                        .map_err(|err| HistoryError::Corrupted {
+                            generation: self.first_generation + base + 1 + offset,
+                            err,
+                        })?;
+                }
+            }
+        }
+
+        Ok(champion)
+    }
+
+    /// Number of bytes the recorded genes take (ignoring the overhead of
+    /// containers).
+    pub fn storage_size(&self) -> usize {
+        self.frames.iter().map(Frame::storage_size).sum()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("champion history should always serialize")
+    }
+
+    /// Loads a history exported by `.to_bytes()`, making sure that its most
+    /// recent champion can be reconstructed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HistoryError> {
+        let mut history: Self =
+            serde_json::from_slice(bytes).map_err(|err| HistoryError::Malformed(err.to_string()))?;
+
+        if history.keyframe_interval == 0 {
+            return Err(HistoryError::Malformed("keyframe interval must be positive".into()));
+        }
+
+        if let Some(generation) = history.generations().last() {
+            history.last = Some(history.champion_at(generation)?);
+        }
+
+        Ok(history)
+    }
+
+    /// Encodes `champion` as a delta from `last`, if that reproduces it
+    /// exactly and takes less space than storing it in full.
+    fn encode(last: &ga::Chromosome, champion: &ga::Chromosome) -> Option<Frame> {
+        let diffs = last.diff(champion, 0.0).ok()?;
+
+        if diffs.len() * (std::mem::size_of::<u32>() + std::mem::size_of::<ga::Float>())
+            >= champion.len() * std::mem::size_of::<ga::Float>()
+        {
+            return None;
+        }
+
+        let genes: Vec<_> = diffs.iter().map(|diff| (diff.index as u32, diff.b)).collect();
+
+        // Genes such as `0.0` vs `-0.0` (or NaNs) don't show up in diffs,
+        // so the result has to be double-checked
+        let mut decoded = last.clone();
+        decoded.patch(&Self::decode(&genes), ga::PatchSource::B).ok()?;
+
+        let exact = decoded
+            .iter()
+            .zip(champion.iter())
+            .all(|(a, b)| a.to_bits() == b.to_bits());
+
+        exact.then_some(Frame::Delta(genes))
+    }
+
+    fn decode(genes: &[(u32, ga::Float)]) -> Vec<ga::GeneDiff> {
+        genes
+            .iter()
+            .map(|&(index, value)| ga::GeneDiff {
+                index: index as usize,
+                // Patching from `b` doesn't look at the other fields
+                a: value,
+                b: value,
+                delta: 0.0,
+            })
+            .collect()
+    }
+}
+
+/// Evicts whole runs of `keyframe_interval` champions (a keyframe and the
+/// deltas that build on it), oldest first - except for the most recent
+/// run, which the next champion gets diffed against.
+impl MemoryComponent for ChampionHistory {
+    fn live_bytes(&self) -> usize {
+        self.storage_size()
+    }
+
+    fn evict_oldest(&mut self, cap: usize) {
+        let mut bytes = self.storage_size();
+
+        while bytes > cap && self.frames.len() > self.keyframe_interval {
+            bytes -= self
+                .frames
+                .drain(..self.keyframe_interval)
+                .map(|frame| frame.storage_size())
+                .sum::<usize>();
+
+            self.first_generation += self.keyframe_interval;
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HistoryError {
+    /// There's no champion recorded for this generation.
+    OutOfRange { generation: usize, recorded: Range<usize> },
+
+    /// The full champion this generation's delta is based on is missing.
+    MissingBase { generation: usize, base: usize },
+
+    /// Delta of this generation doesn't fit the champion it's based on.
+    Corrupted { generation: usize, err: ga::ChromosomeError },
+
+    /// Given bytes are not a history exported by
+    /// `ChampionHistory::to_bytes()`.
+    Malformed(String),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange { generation, recorded } => write!(
+                f,
+                "generation {} hasn't been recorded (recorded: {}..{})",
+                generation, recorded.start, recorded.end,
+            ),
+            Self::MissingBase { generation, base } => write!(
+                f,
+                "champion of generation {} is missing its base frame (generation {})",
+                generation, base,
+            ),
+            Self::Corrupted { generation, err } => {
+                write!(f, "delta of generation {} is corrupted: {}", generation, err)
+            }
+            Self::Malformed(err) => write!(f, "malformed champion history: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    /// Champions that change by a couple of genes per generation.
+    fn champions(rng: &mut ChaCha8Rng, generations: usize, len: usize) -> Vec<ga::Chromosome> {
+        let mut champion: Vec<ga::Float> = (0..len).map(|_| rng.gen_range(-1.0..=1.0)).collect();
+
+        (0..generations)
+            .map(|_| {
+                for _ in 0..rng.gen_range(0..=3) {
+                    let gene = rng.gen_range(0..len);
+                    champion[gene] += rng.gen_range(-0.1..=0.1);
+                }
+
+                champion.iter().copied().collect()
+            })
+            .collect()
+    }
+
+    fn history(champions: &[ga::Chromosome], first_generation: usize) -> ChampionHistory {
+        let mut history = ChampionHistory::new(10);
+
+        for (offset, champion) in champions.iter().enumerate() {
+            history.push(first_generation + offset, champion);
+        }
+
+        history
+    }
+
+    #[test]
+    fn reconstruction_is_exact() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut champions = champions(&mut rng, 35, 50);
+
+        // A gene flipping its sign of zero doesn't show up in a diff
+        champions[13] = champions[12].iter().copied().map(|gene| gene * 0.0).collect();
+        champions[14] = champions[13].iter().copied().map(|gene| -gene).collect();
+
+        let history = history(&champions, 5);
+
+        assert_eq!(history.generations(), 5..40);
+
+        for (offset, champion) in champions.iter().enumerate() {
+            let reconstructed = history.champion_at(5 + offset).unwrap();
+
+            assert!(
+                reconstructed
+                    .iter()
+                    .zip(champion.iter())
+                    .all(|(a, b)| a.to_bits() == b.to_bits()),
+                "generation = {}",
+                5 + offset,
+            );
+        }
+    }
+
+    #[test]
+    fn slowly_changing_champions_take_less_space() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let champions = champions(&mut rng, 100, 200);
+        let history = history(&champions, 0);
+
+        let full = 100 * 200 * std::mem::size_of::<ga::Float>();
+
+        // Ten keyframes, plus a few genes per generation
+        assert!(history.storage_size() < full / 5, "storage_size = {}", history.storage_size());
+    }
+
+    #[test]
+    fn unrecorded_generations_fail() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let history = history(&champions(&mut rng, 5, 10), 3);
+
+        for generation in [0, 2, 8, 100] {
+            assert_eq!(
+                history.champion_at(generation).err(),
+                Some(HistoryError::OutOfRange {
+                    generation,
+                    recorded: 3..8,
+                })
+            );
+        }
+
+        assert!(matches!(
+            ChampionHistory::new(1).champion_at(0),
+            Err(HistoryError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn damaged_frames_fail() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut history = history(&champions(&mut rng, 25, 10), 0);
+
+        history.frames[10] = Frame::Delta(Vec::new());
+
+        assert_eq!(
+            history.champion_at(14).err(),
+            Some(HistoryError::MissingBase {
+                generation: 14,
+                base: 10,
+            })
+        );
+
+        assert!(history.champion_at(9).is_ok());
+
+        history.frames[21] = Frame::Delta(vec![(10, 1.0)]);
+
+        assert_eq!(
+            history.champion_at(23).err(),
+            Some(HistoryError::Corrupted {
+                generation: 21,
+                err: ga::ChromosomeError::IndexOutOfBounds { index: 10, len: 10 },
+            })
+        );
+
+        assert!(matches!(
+            ChampionHistory::from_bytes(&history.to_bytes()),
+            Err(HistoryError::Corrupted { generation: 21, .. })
+        ));
+    }
+
+    #[test]
+    fn survives_round_trip() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let champions = champions(&mut rng, 15, 10);
+        let history = history(&champions[..12], 0);
+
+        let mut restored = ChampionHistory::from_bytes(&history.to_bytes()).unwrap();
+
+        for (generation, champion) in champions.iter().enumerate().skip(12) {
+            restored.push(generation, champion);
+        }
+
+        for (generation, champion) in champions.iter().enumerate() {
+            assert!(restored.champion_at(generation).unwrap().iter().eq(champion.iter()));
+        }
+
+        assert_eq!(
+            ChampionHistory::from_bytes(b"nope").err(),
+            Some(HistoryError::Malformed("expected ident at line 1 column 2".into()))
+        );
+    }
+
+    #[test]
+    fn recorder_tracks_simulation_champions() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 5,
+            generation_length: 20,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        let mut recorder = StatsRecorder::new();
+
+        assert!(sim.last_champion().is_none());
+
+        let mut champions = Vec::new();
+
+        for _ in 0..4 {
+            let summary = sim.train(&mut rng);
+            let champion = sim.last_champion().unwrap();
+
+            assert_eq!(champion.generation, summary.generation);
+            assert_eq!(champion.fitness, summary.stats.max_fitness);
+
+            recorder.record(&summary);
+            recorder.record_champion(champion);
+            champions.push(champion.chromosome());
+        }
+
+        for (generation, champion) in champions.iter().enumerate() {
+            assert!(recorder.history().champion_at(generation).unwrap().iter().eq(champion.iter()));
+        }
+    }
+}
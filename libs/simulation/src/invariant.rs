@@ -0,0 +1,168 @@
+use crate::*;
+use std::fmt;
+
+/// A property of the simulation that should hold after every step; if
+/// one of these ever gets reported, there's a bug somewhere.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Invariant {
+    /// Bird's position is not finite or it escaped the <0,1> map.
+    AnimalOutOfBounds { animal: usize, x: f32, y: f32 },
+
+    /// Bird's rotation is not finite.
+    AnimalRotationNotFinite { animal: usize },
+
+    /// Bird's speed is not finite or it dropped to (or below) zero.
+    AnimalSpeedInvalid { animal: usize, speed: f32 },
+
+    /// Bird has left its habitat, other than through a corridor.
+    AnimalOutsideHabitat { animal: usize, x: f32, y: f32 },
+
+    /// Food's position is not finite or it escaped the <0,1> map.
+    FoodOutOfBounds { food: usize, x: f32, y: f32 },
+
+    /// Number of birds differs from what the config asks for.
+    PopulationSizeChanged { expected: usize, actual: usize },
+
+    /// Bird's chromosome doesn't match its brain's topology (and traits).
+    ChromosomeLengthMismatch {
+        animal: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    /// Statistics returned from the genetic algorithm are not finite.
+    StatisticsNotFinite,
+}
+
+impl fmt::Display for Invariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AnimalOutOfBounds { animal, x, y } => {
+                write!(f, "animal #{} is out of bounds at ({}, {})", animal, x, y)
+            }
+            Self::AnimalRotationNotFinite { animal } => {
+                write!(f, "animal #{} has a non-finite rotation", animal)
+            }
+            Self::AnimalSpeedInvalid { animal, speed } => {
+                write!(f, "animal #{} has an invalid speed of {}", animal, speed)
+            }
+            Self::AnimalOutsideHabitat { animal, x, y } => {
+                write!(f, "animal #{} is outside its habitat at ({}, {})", animal, x, y)
+            }
+            Self::FoodOutOfBounds { food, x, y } => {
+                write!(f, "food #{} is out of bounds at ({}, {})", food, x, y)
+            }
+            Self::PopulationSizeChanged { expected, actual } => {
+                write!(f, "expected {} animals, got {}", expected, actual)
+            }
+            Self::ChromosomeLengthMismatch { animal, expected, actual } => {
+                write!(
+                    f,
+                    "animal #{} has a chromosome of {} genes, expected {}",
+                    animal, actual, expected,
+                )
+            }
+            Self::StatisticsNotFinite => write!(f, "statistics are not finite"),
+        }
+    }
+}
+
+fn in_bounds(position: na::Point2<f32>) -> bool {
+    // na::wrap() maps positions into <0,1> inclusive on both ends
+    (0.0..=1.0).contains(&position.x) && (0.0..=1.0).contains(&position.y)
+}
+
+impl Animal {
+    /// Checks invariants of a single bird; `index` is only used to make
+    /// the reported invariant point at the culprit.
+    pub fn check_invariants(
+        &self,
+        config: &SimulationConfig,
+        index: usize,
+    ) -> Result<(), Invariant> {
+        if !in_bounds(self.position) {
+            return Err(Invariant::AnimalOutOfBounds {
+                animal: index,
+                x: self.position.x,
+                y: self.position.y,
+            });
+        }
+
+        if let Some(habitats) = &config.habitats {
+            if !habitats.allows(habitats.habitat_of(index, config.n_animals), self.position) {
+                return Err(Invariant::AnimalOutsideHabitat {
+                    animal: index,
+                    x: self.position.x,
+                    y: self.position.y,
+                });
+            }
+        }
+
+        if !self.rotation.angle().is_finite() {
+            return Err(Invariant::AnimalRotationNotFinite { animal: index });
+        }
+
+        if !self.speed.is_finite() || self.speed <= 0.0 {
+            return Err(Invariant::AnimalSpeedInvalid {
+                animal: index,
+                speed: self.speed,
+            });
+        }
+
+        let expected = Animal::chromosome_len(config);
+        let actual = self.as_chromosome().len();
+
+        if expected != actual {
+            return Err(Invariant::ChromosomeLengthMismatch {
+                animal: index,
+                expected,
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl World {
+    pub fn check_invariants(&self, config: &SimulationConfig) -> Result<(), Invariant> {
+        if self.animals.len() != config.n_animals {
+            return Err(Invariant::PopulationSizeChanged {
+                expected: config.n_animals,
+                actual: self.animals.len(),
+            });
+        }
+
+        for (index, animal) in self.animals.iter().enumerate() {
+            animal.check_invariants(config, index)?;
+        }
+
+        for (index, food) in self.foods.iter().enumerate() {
+            if !in_bounds(food.position) {
+                return Err(Invariant::FoodOutOfBounds {
+                    food: index,
+                    x: food.position.x,
+                    y: food.position.y,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Simulation {
+    /// Checks invariants of the entire simulation - that is: of its
+    /// world and, if provided, of the summary returned by `.step()`.
+    pub fn check_invariants(&self, summary: Option<&GenerationSummary>) -> Result<(), Invariant> {
+        self.world.check_invariants(&self.config)?;
+
+        if let Some(summary) = summary {
+            if !summary.stats.is_finite() {
+                return Err(Invariant::StatisticsNotFinite);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+use crate::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Scores a single chromosome by letting it fly alone, in a small world
+/// created out of `layout_seed`, for `steps` steps.
+///
+/// Everything in that world - the bird's starting position & rotation,
+/// the food layout and even where eaten foods respawn - comes out of
+/// `layout_seed`, so identical chromosomes always get identical scores
+/// and different chromosomes get evaluated under identical conditions.
+pub fn evaluate_isolated(
+    config: &SimulationConfig,
+    chromosome: ga::Chromosome,
+    layout_seed: u64,
+    steps: usize,
+) -> f32 {
+    run_isolated(config, chromosome, layout_seed, steps).scored_satiation as f32
+}
+
+/// Like `evaluate_isolated()`, but returns the bird itself, so that it can
+/// be scored by something else than food (see: `FitnessComposer`).
+pub(crate) fn run_isolated(
+    config: &SimulationConfig,
+    chromosome: ga::Chromosome,
+    layout_seed: u64,
+    steps: usize,
+) -> Animal {
+    let (mut world, mut rng) = isolated_world(config, chromosome, layout_seed);
+
+    for step in 0..steps {
+        world.step(config, &mut rng, step);
+    }
+
+    world.animals.remove(0)
+}
+
+fn isolated_world(
+    config: &SimulationConfig,
+    chromosome: ga::Chromosome,
+    layout_seed: u64,
+) -> (World, ChaCha8Rng) {
+    let mut rng = ChaCha8Rng::seed_from_u64(layout_seed);
+
+    // Order matters: the bird consumes the same number of draws no
+    // matter its chromosome, so foods always land at the same places
+    let animals = vec![Animal::from_chromosome(config, chromosome, &mut rng)];
+
+    let foods = (0..config.n_foods)
+        .map(|_| Food::random(&mut rng))
+        .collect();
+
+    let mut world = World::new(animals, foods);
+    world.settle_habitats(config, &mut rng);
+
+    (world, rng)
+}
+
+/// Seed of the isolated world used to score given generation.
+pub(crate) fn layout_seed(seed: u64, generation: usize) -> u64 {
+    seed.wrapping_add(generation as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SimulationConfig {
+        SimulationConfig {
+            n_animals: 4,
+            n_foods: 30,
+            generation_length: 50,
+            evaluation: Evaluation::Isolated { steps: 200, seed: 42 },
+            ..Default::default()
+        }
+    }
+
+    fn random_chromosome(config: &SimulationConfig, seed: u64) -> ga::Chromosome {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        Animal::random(config, &mut rng).as_chromosome()
+    }
+
+    #[test]
+    fn identical_chromosomes_get_identical_fitness() {
+        let config = config();
+        let chromosome = random_chromosome(&config, 1);
+
+        let a = evaluate_isolated(&config, chromosome.clone(), 123, 500);
+        let b = evaluate_isolated(&config, chromosome, 123, 500);
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn layout_is_identical_across_individuals() {
+        let config = config();
+
+        let (a, _) = isolated_world(&config, random_chromosome(&config, 1), 123);
+        let (b, _) = isolated_world(&config, random_chromosome(&config, 2), 123);
+
+        let positions = |world: &World| -> Vec<_> {
+            world.foods.iter().map(|food| food.position).collect()
+        };
+
+        assert_eq!(positions(&a), positions(&b));
+        assert_eq!(a.animals[0].position, b.animals[0].position);
+        assert_eq!(a.animals[0].rotation, b.animals[0].rotation);
+    }
+
+    #[test]
+    fn simulation_scores_birds_in_isolation() {
+        let config = config();
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut sim = Simulation::from_config(config.clone(), &mut rng);
+
+        // Skip the first generation so that the layout seed gets to
+        // depend on the generation number, too
+        sim.train(&mut rng);
+
+        let expected: Vec<_> = sim
+            .world()
+            .animals
+            .iter()
+            .map(|animal| {
+                evaluate_isolated(&config, animal.as_chromosome(), layout_seed(42, 1), 200)
+            })
+            .collect();
+
+        let stats = sim.train(&mut rng).stats;
+        let max = expected.iter().copied().fold(0.0, f32::max);
+        let avg = expected.iter().sum::<f32>() / expected.len() as f32;
+
+        assert_eq!(narrow(stats.max_fitness), max);
+        assert_eq!(narrow(stats.avg_fitness), avg);
+    }
+}
@@ -1,13 +1,62 @@
+#[macro_use]
+mod trace;
+#[macro_use]
+mod validation;
+mod ab_test;
 mod animal_individual;
 mod animal;
+mod balance;
+mod behavior;
+mod bootstrap;
+mod bottleneck;
 mod brain;
+mod breeding;
+mod cancel;
+mod config;
+pub mod determinism;
+mod energy;
+mod environment;
+mod events;
+mod fast_math;
 mod food;
+mod gene_ages;
+mod genealogy;
+mod habitat;
+mod hall_of_fame;
+mod hibernation;
+mod history;
 mod world;
 mod eye;
+mod fitness;
+mod extra_input;
+mod grid;
+mod invariant;
+mod isolated;
+mod lineage;
+mod memory;
+mod movement;
+mod nursery;
+mod overlay;
+mod pipeline;
+mod population;
+mod precision;
+mod recorder;
+mod restart;
+mod snapshot;
+mod summary;
+mod tournament;
+mod traits;
+mod transitions;
+mod transplant;
+mod tuning;
+mod weights;
+mod wrapping;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 
-pub use self::{animal::*, brain::*, eye::*, food::*, world::*};
+pub use self::{ab_test::*, animal::*, animal_individual::*, balance::*, behavior::*, bottleneck::*, brain::*, breeding::*, cancel::*, config::*, energy::*, environment::*, events::*, eye::*, extra_input::*, fitness::*, food::*, genealogy::*, habitat::*, hall_of_fame::*, hibernation::*, history::*, invariant::*, isolated::*, lineage::*, memory::*, movement::*, nursery::*, overlay::*, pipeline::*, population::*, recorder::*, snapshot::*, summary::*, tournament::*, traits::*, transitions::*, transplant::*, tuning::*, weights::*, world::*, wrapping::*};
 
-use self::animal_individual::*;
+use self::precision::*;
 use lib_neural_network as nn;
 use lib_genetic_algorithm as ga;
 use nalgebra as na;
@@ -25,10 +74,74 @@ use rand::{Rng, RngCore};
 /// to live"; 2500 was chosen with a fair dice roll.
 const GENERATION_LENGTH: usize = 2500;
 
+pub(crate) type Ga = ga::GeneticAlgorithm<
+    ga::RouletteWheelSelection,
+    ga::UniformCrossover,
+    ga::CompositeMutation<ga::LayerScaledMutation, ga::WeightDecayMutation>,
+>;
+
 pub struct Simulation{
+    config: SimulationConfig,
     world: World,
-    ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection,ga::UniformCrossover,ga::GaussianMutation>,
+    ga: Ga,
     age: usize,
+    generation: usize,
+    hall_of_fame: HallOfFame,
+
+    /// See: `Simulation::last_champion()`.
+    last_champion: Option<Champion>,
+
+    /// Food positions to restore at the beginning of each generation; see:
+    /// `PersistMode`.
+    food_layout: Vec<na::Point2<f32>>,
+
+    /// Children bred by hand, waiting for the next generation; see:
+    /// `Simulation::breed()`.
+    offspring: Vec<breeding::Offspring>,
+
+    /// Births paid for during the current generation; see: `BirthCost`.
+    births: BirthReport,
+
+    /// Number of consecutive generations with max fitness below
+    /// `AutoRestart::threshold`.
+    low_fitness_streak: usize,
+
+    genealogy: Option<Genealogy>,
+
+    /// See: `Simulation::set_pipeline()`.
+    pipeline: Box<dyn GenerationPipeline>,
+
+    /// See: `Simulation::publish_snapshot()`.
+    snapshots: SnapshotReader,
+
+    /// See: `Simulation::gene_age_profile()`.
+    gene_ages: Vec<Vec<u16>>,
+
+    /// See: `Simulation::drain_events()`.
+    events: Option<EventLog>,
+
+    /// See: `Simulation::set_clock()`.
+    clock: Box<dyn Clock>,
+
+    /// See: `PopulationControl`.
+    step_timings: population::StepTimings,
+
+    /// See: `Simulation::bottleneck()`.
+    bottleneck: Option<bottleneck::Bottleneck>,
+
+    /// See: `Simulation::export_transitions()`.
+    transitions: Option<transitions::TransitionRecorder>,
+
+    /// See: `AbTest`.
+    ab_test: Option<ab_test::AbState>,
+
+    /// See: `Simulation::reconfigure_eye()`.
+    eye_transplant: Option<transplant::EyeTransplant>,
+
+    /// Selection differential & mean fitness of the generation that has
+    /// just ended, waiting for the next one's response; see:
+    /// `GenerationSummary::response`.
+    last_selection: Option<(ga::Float, ga::Float)>,
 }
 // FRAC_PI_2 = PI / 2.0; a convenient shortcut
 use std::f32::consts::FRAC_PI_2;
@@ -51,7 +164,7 @@ const SPEED_MAX: f32 = 0.004;
 /// yells "stop flying!", a SPEED_ACCEL of:
 ///
 /// - 0.1 = makes it take 5 steps ("5 seconds") for the bird to actually
-///         slow down to SPEED_MIN,
+///   slow down to SPEED_MIN,
 ///
 /// - 0.5 = makes it take 1 step for the bird to slow down to SPEED_MIN.
 ///
@@ -68,16 +181,21 @@ const SPEED_ACCEL: f32 = 0.2;
 /// I've chosen PI/2, because - as our motto goes - this value seems
 /// to play nice.
 const ROTATION_ACCEL: f32 = FRAC_PI_2;
-impl Simulation{
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        let world = World::random(rng);
 
-        let ga = ga::GeneticAlgorithm::new(
-            ga::RouletteWheelSelection,
-            ga::UniformCrossover,
-            ga::GaussianMutation::new(0.01, 0.3),
-            // ---------------------- ^--^ -^-^
-            // | Chosen with a bit of experimentation.
+/// Builds the genetic algorithm described by `params`; see:
+/// `SimulationConfig::ga` and `AbTest::b`.
+pub(crate) fn genetic_algorithm(config: &SimulationConfig, params: &GaParams) -> Ga {
+    ga::GeneticAlgorithm::new(
+        ga::RouletteWheelSelection,
+        ga::UniformCrossover,
+        ga::CompositeMutation::new(
+            ga::LayerScaledMutation::from_lengths(
+                ga::GaussianMutation::new(params.mutation_chance, widen(params.mutation_coeff)),
+                &config.mutation_scales(params),
+            ),
+            // ------------------------------------- ^--^ -^-^
+            // | Defaults (0.01 and 0.3) chosen with a bit of
+            // | experimentation.
             // |
             // | Higher values can make the simulation more chaotic,
             // | which - a bit counterintuitively - might allow for
@@ -85,33 +203,100 @@ impl Simulation{
             // | is that higher values might also cause current, good
             // | enough solutions to be discarded.
             // ---
-        );
+            ga::WeightDecayMutation::new(widen(params.weight_decay)),
+        ),
+    )
+    .with_brood_size(params.brood_size)
+    .with_reproduction(params.reproduction)
+    .with_self_mating(params.self_mating)
+}
+
+impl Simulation{
+    pub fn random(rng: &mut dyn RngCore) -> Self {
+        Self::from_config(SimulationConfig::default(), rng)
+    }
+
+    pub fn from_config(config: SimulationConfig, rng: &mut dyn RngCore) -> Self {
+        config.validate();
+
+        let world = World::from_config(&config, rng);
+        let ga = genetic_algorithm(&config, &config.ga);
+
         Self {
+            hall_of_fame: HallOfFame::new(config.hall_of_fame_size),
+            last_champion: None,
+            food_layout: world.foods.iter().map(|food| food.position).collect(),
+            offspring: Vec::new(),
+            births: BirthReport::default(),
+            low_fitness_streak: 0,
+            genealogy: config
+                .genealogy
+                .map(|genealogy| Genealogy::new(&genealogy, config.n_animals)),
+            pipeline: Box::new(DefaultPipeline),
+            snapshots: SnapshotReader::default(),
+            gene_ages: gene_ages::fresh_gene_ages(&config),
+            events: config.event_log.map(EventLog::new),
+            clock: Box::new(SystemClock),
+            step_timings: Default::default(),
+            bottleneck: None,
+            transitions: config
+                .transitions
+                .map(|transitions| TransitionRecorder::new(&config, &transitions)),
+            ab_test: config.ab_test.as_ref().map(|ab_test| ab_test::AbState::new(&config, ab_test)),
+            eye_transplant: None,
+            last_selection: None,
+            config,
             world,
             ga,
             age: 0,
+            generation: 0,
         }
     }
-    // Getter function
+    // Getter functions
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
     pub fn world(&self) -> &World{
         &self.world
     }
+    /// Number of generations evolved so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
 
-    pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<ga::Statistics> {
-        self.process_collisions(rng);
-        self.process_brains();
-        self.process_movements();
+    /// Number of steps taken so far in the current generation.
+    pub fn age(&self) -> usize {
+        self.age
+    }
+
+    /// Fittest bird of the most recently evolved generation, whether it
+    /// made it into the hall of fame or not; see: `StatsRecorder::record_champion()`.
+    pub fn last_champion(&self) -> Option<&Champion> {
+        self.last_champion.as_ref()
+    }
+
+    pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<GenerationSummary> {
+        trace_span!(TRACE, "step", generation = self.generation, step = self.age);
+
+        self.timed(|sim| {
+            sim.world.step(&sim.config, rng, sim.age);
+            sim.record_eats(sim.age);
+            sim.record_transitions(sim.age == sim.config.generation_length);
+        });
 
         self.age += 1;
-        if self.age > GENERATION_LENGTH {
+        let summary = if self.age > self.config.generation_length {
             Some(self.evolve(rng))
         } else{
             None
-        }
+        };
+
+        self.enforce_memory_budget();
+        summary
     }
 
     // Fast forward till the end of the generation : 
-    pub fn train(&mut self, rng: &mut dyn RngCore) -> ga::Statistics {
+    pub fn train(&mut self, rng: &mut dyn RngCore) -> GenerationSummary {
         loop {
             if let Some(summary) = self.step(rng) {
                 return summary;
@@ -119,89 +304,602 @@ impl Simulation{
         }
     }
 
-    fn evolve(&mut self, rng: &mut dyn RngCore) -> ga::Statistics {
+    /// Trains for `generations` generations in a row; to be able to stop
+    /// midway, see: `Simulation::train_many_cancellable()`.
+    pub fn train_many(&mut self, rng: &mut dyn RngCore, generations: usize) -> Vec<GenerationSummary> {
+        (0..generations).map(|_| self.train(rng)).collect()
+    }
+
+    fn evolve(&mut self, rng: &mut dyn RngCore) -> GenerationSummary {
+        trace_span!(DEBUG, "evolve", generation = self.generation);
+
         self.age = 0;
     
-        // Transforms `Vec<Animal>` to `Vec<AnimalIndividual>`
-        let current_population: Vec<_> = self
-            .world
-            .animals
+        let (mut current_population, signal_means) = self.evaluate();
+        let mut eaten: Vec<_> = self.world.animals.iter().map(|animal| animal.satiation).collect();
+    
+        let genome = GenomeSpec::of(&self.config);
+
+        let champions: Vec<_> = current_population
             .iter()
-            .map(AnimalIndividual::from_animal)
+            .map(|individual| Champion {
+                generation: self.generation,
+                fitness: ga::Individual::fitness(individual),
+                genome,
+                genes: ga::Individual::chromosome(individual).iter().copied().collect(),
+            })
             .collect();
-    
+
+        // Ties go to the bird with the lowest slot
+        self.last_champion = champions
+            .iter()
+            .rev()
+            .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+            .cloned();
+
+        for champion in champions {
+            self.hall_of_fame.insert(champion);
+        }
+
         // Evolves this `Vec<AnimalIndividual>`
-        let (evolved_population, stats) = self.ga.evolve(
-            rng,
-            &current_population,
-        );
+        let sample_rate = self
+            .config
+            .mutation_impact
+            .map_or(0.0, |impact| impact.sample_rate);
+
+        let weights = WeightStats::of(&self.world.animals);
+
+        self.pipeline.before_evolution(&mut current_population);
+        self.assert_population_size(&current_population);
+
+        let fitnesses: Vec<_> = current_population.iter().map(ga::Individual::fitness).collect();
+        let lineages: Vec<_> = self.world.animals.iter().map(Animal::lineage).collect();
+
+        let population = self.next_population_size();
+        let resized = population.filter(|report| report.next_n_animals != report.n_animals);
+        let transition = self.bottleneck_transition();
+        let bottleneck = transition.as_ref().map(|transition| transition.event(self.config.n_animals));
+
+        let (evolution, habitats) = match (&self.config.habitats, resized, &transition) {
+            (_, _, Some(transition)) => (
+                self.evolve_bottleneck(transition, rng, &current_population),
+                Vec::new(),
+            ),
+            (_, Some(report), None) => (
+                self.ga
+                    .evolve_to(rng, &current_population, report.next_n_animals)
+                    .expect("birds' chromosomes have the same length"),
+                Vec::new(),
+            ),
+            (Some(habitats), None, None) => self.evolve_habitats(habitats, rng, &current_population, sample_rate),
+            (None, None, None) if self.ab_test.is_some() => (
+                self.evolve_ab(rng, &current_population, sample_rate),
+                Vec::new(),
+            ),
+            (None, None, None) => (
+                self.evolve_aged(&self.ga, rng, &current_population, sample_rate, &self.gene_ages),
+                Vec::new(),
+            ),
+        };
+
+        let ga::Evolution {
+            population: mut evolved_population,
+            stats,
+            samples,
+            parents,
+            mut diagnostics,
+            gene_ages,
+        } = evolution;
+
+        self.gene_ages = gene_ages;
+
+        // Habitats, A/B arms and bottlenecks evolve the population in parts
+        // (or not at all), so the differential has to span all of them
+        diagnostics.selection_differential = ga::selection_differential(&fitnesses, &parents);
+
+        let (selection_differential, response) = self
+            .last_selection
+            .take()
+            .map(|(differential, avg_fitness)| (differential, stats.avg_fitness - avg_fitness))
+            .unzip();
+
+        self.last_selection = Some((diagnostics.selection_differential, stats.avg_fitness));
+
+        let next_n_animals = bottleneck
+            .map(|event| event.to())
+            .or(resized.map(|report| report.next_n_animals));
+
+        if let Some(n_animals) = next_n_animals {
+            self.config.n_animals = n_animals;
+
+            // There's no telling which genes survived the resizing
+            self.gene_ages = gene_ages::fresh_gene_ages(&self.config);
+        }
+
+        self.pipeline.after_evolution(&mut evolved_population);
+        self.assert_population_size(&evolved_population);
+        self.pipeline.summarize(&stats);
+
+        let mutation_delta = self.mutation_delta(samples);
+        let sharing = self.config.sharing.map(|_| self.world.sharing_report());
+        let behaviors = self.config.behavior.map(|_| BehaviorReport::of(&self.world.animals));
+        let births = std::mem::take(&mut self.births);
+        let births = self.config.birth_cost.map(|_| births);
+        let ab_test = self.summarize_ab(&fitnesses);
+
+        self.transplant_eyes(rng, &mut evolved_population);
     
         // Transforms `Vec<AnimalIndividual>` back into `Vec<Animal>`
         self.world.animals = evolved_population
             .into_iter()
-            .map(|individual| individual.into_animal(rng))
+            .map(|individual| individual.into_animal(&self.config, rng))
             .collect();
-    
-        // for food in &mut self.world.foods {
-        //     food.position = rng.gen();
-        // }
 
-        stats
+        self.world.assign_ids();
+        let bred = self.place_offspring(&fitnesses, rng);
+        self.inherit_lineages(&lineages, &fitnesses, &parents, &bred);
+
+        for &(slot, _) in &bred {
+            if let Some(ages) = self.gene_ages.get_mut(slot) {
+                ages.fill(0);
+            }
+        }
+
+        self.world.settle_habitats(&self.config, rng);
+        self.world.assign_arms(&self.config);
+
+        // Degeneracies concern the generation that has just ended, so they
+        // have to be detected before its food count gets adjusted
+        let degeneracies = Degeneracy::detect(&self.config, &stats);
+        let food_balance = self.auto_balance(balance::median(&mut eaten), rng);
+        self.world.energy_transferred = 0.0;
+
+        self.reset_foods(rng);
+
+        let restarted = self.auto_restart(&stats, rng);
+        let lineages = LineageReport::of(&lineages, &self.world.animals);
+        let mean_relatedness = self.track_genealogy(parents, bred, restarted);
+
+        let summary = GenerationSummary {
+            generation: self.generation,
+            degeneracies,
+            stats,
+            mutation_delta,
+            signal_means,
+            sharing,
+            births,
+            behaviors,
+            bottleneck,
+            restarted,
+            mean_relatedness,
+            weights,
+            diagnostics,
+            habitats,
+            ab_test,
+            lineages,
+            food_balance,
+            population,
+            selection_differential,
+            response,
+        };
+
+        trace_event!(
+            INFO,
+            generation = summary.generation,
+            max_fitness = summary.stats.max_fitness,
+            avg_fitness = summary.stats.avg_fitness,
+            restarted,
+            "generation ended"
+        );
+
+        self.record(|| SimEvent::GenerationEnded {
+            generation: summary.generation,
+            max_fitness: summary.stats.max_fitness,
+            avg_fitness: summary.stats.avg_fitness,
+        });
+
+        if restarted {
+            self.record(|| SimEvent::Restarted {
+                generation: summary.generation,
+            });
+        }
+
+        self.generation += 1;
+
+        summary
+    }
+
+    fn assert_population_size(&self, population: &[AnimalIndividual]) {
+        assert_eq!(
+            population.len(),
+            self.config.n_animals,
+            "generation pipelines must not change the population's size",
+        );
+    }
+
+    /// Records parents of the next generation; returns the mean
+    /// relatedness of the generation that has just ended.
+    fn track_genealogy(
+        &mut self,
+        mut parents: Vec<(usize, usize)>,
+        bred: Vec<(usize, (usize, usize))>,
+        restarted: bool,
+    ) -> Option<f32> {
+        let genealogy = self.genealogy.as_mut()?;
+        let mean_relatedness = genealogy.mean_relatedness(self.generation);
+
+        for (slot, bred_parents) in bred {
+            parents[slot] = bred_parents;
+        }
+
+        genealogy.push_generation(if restarted {
+            vec![None; parents.len()]
+        } else {
+            parents.into_iter().map(Some).collect()
+        });
+
+        Some(mean_relatedness)
     }
 
-    fn process_collisions(&mut self, rng: &mut dyn RngCore) {
-        for animal in &mut self.world.animals{
-            for food in &mut self.world.foods{
-                let distance = na::distance(&animal.position, &food.position);
-                if distance <= 0.01{
-                    food.position = rng.gen();
-                    animal.satiation += 1; 
-                }
+    /// Lays foods out for the next generation, according to
+    /// `SimulationConfig::food_layout`.
+    fn reset_foods(&mut self, rng: &mut dyn RngCore) {
+        let next_generation = self.generation + 1;
+
+        let reroll = match self.config.food_layout {
+            PersistMode::Carry => return,
+            PersistMode::Rerandomize => true,
+            PersistMode::KeepPositions => false,
+            PersistMode::KeepForK(k) => next_generation.is_multiple_of(k),
+        };
+
+        if reroll {
+            for food in &mut self.world.foods {
+                food.position = rng.gen();
+            }
+
+            self.food_layout = self.world.foods.iter().map(|food| food.position).collect();
+        } else {
+            for (food, &position) in self.world.foods.iter_mut().zip(&self.food_layout) {
+                food.position = position;
             }
         }
     }
 
-    fn process_movements(&mut self) {
-        for animal in &mut self.world.animals {
-            animal.position += animal.rotation * na::Vector2::new(0.0, animal.speed);
-            animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
-            animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+    /// Transforms `Vec<Animal>` to `Vec<AnimalIndividual>`, scoring each
+    /// bird; returns `GenerationSummary::signal_means`, too.
+    fn evaluate(&self) -> (Vec<AnimalIndividual>, Vec<f32>) {
+        let isolated: Vec<Animal>;
+
+        // With isolated evaluation, each bird gets scored by its twin that
+        // has flown through an isolated world
+        let scored = match self.config.evaluation {
+            Evaluation::Shared => &self.world.animals,
+
+            Evaluation::Isolated { steps, seed } => {
+                let layout_seed = isolated::layout_seed(seed, self.generation);
+
+                isolated = self
+                    .world
+                    .animals
+                    .iter()
+                    .map(|animal| {
+                        run_isolated(&self.config, animal.as_chromosome(), layout_seed, steps)
+                    })
+                    .collect();
+
+                &isolated
+            }
+        };
+
+        let (fitnesses, signal_means) = match &self.config.fitness {
+            Some(composer) => composer.compose(scored),
+            None => (scored.iter().map(|animal| animal.scored_satiation as f32).collect(), Vec::new()),
+        };
+
+        let population = self
+            .world
+            .animals
+            .iter()
+            .zip(fitnesses)
+            .map(|(animal, fitness)| AnimalIndividual::from_animal_with_fitness(animal, fitness))
+            .collect();
+
+        (population, signal_means)
+    }
+
+    /// Computes `GenerationSummary::mutation_delta`; evaluations happen in
+    /// isolated worlds with their own RNG, so they don't affect the rest
+    /// of the simulation.
+    fn mutation_delta(&self, samples: Vec<ga::MutationSample>) -> Option<f32> {
+        let impact = self.config.mutation_impact?;
+
+        if samples.is_empty() {
+            return None;
+        }
+
+        let layout_seed = isolated::layout_seed(impact.seed, self.generation);
+        let evaluate = |chromosome| {
+            evaluate_isolated(&self.config, chromosome, layout_seed, impact.steps)
+        };
+
+        let total: f32 = samples
+            .iter()
+            .map(|sample| evaluate(sample.mutated.clone()) - evaluate(sample.unmutated.clone()))
+            .sum();
+
+        Some(total / samples.len() as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn config(ga: GaParams) -> SimulationConfig {
+        SimulationConfig {
+            n_animals: 20,
+            n_foods: 40,
+            generation_length: 500,
+            ga,
+            mutation_impact: Some(MutationImpact {
+                sample_rate: 0.5,
+                steps: 500,
+                seed: 7,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn train(config: SimulationConfig, generations: usize) -> Vec<GenerationSummary> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config, &mut rng);
+
+        (0..generations).map(|_| sim.train(&mut rng)).collect()
+    }
+
+    #[test]
+    fn single_animal_evolves() {
+        let config = SimulationConfig {
+            n_animals: 1,
+            generation_length: 200,
+            ..Default::default()
+        };
+
+        for summary in train(config, 5) {
+            assert_eq!(summary.stats.min_fitness, summary.stats.max_fitness);
+            assert_eq!(summary.stats.avg_fitness, summary.stats.max_fitness);
+            assert!(summary.degeneracies.contains(&Degeneracy::SingleAnimal));
         }
     }
 
-    fn process_brains(&mut self){
-        for animal in &mut self.world.animals{
-            let vision = animal.eye.process_vision(
-                animal.position,
-                animal.rotation,
-                &self.world.foods
+    #[test]
+    fn zero_food_evolves() {
+        let config = SimulationConfig {
+            n_foods: 0,
+            generation_length: 200,
+            ..Default::default()
+        };
+
+        for summary in train(config, 5) {
+            assert_eq!(summary.stats.max_fitness, 0.0);
+            assert_eq!(summary.stats.avg_fitness, 0.0);
+
+            assert_eq!(
+                summary.degeneracies,
+                vec![Degeneracy::NoFood, Degeneracy::ZeroFitness],
             );
-            let response = animal.brain.nn.propagate(vision);
-            // ---
-            // | Limits number to given range.
-            // -------------------- v---v
-            let speed = response[0].clamp(-SPEED_ACCEL, SPEED_ACCEL);
-            let rotation = response[1].clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
-
-            // Our speed & rotation here are *relative* - that is: when
-            // they are equal to zero, what the brain says is "keep
-            // flying as you are now", not "stop flying".
-            //
-            // Both values being relative is crucial, because our bird's
-            // brain doesn't know its own speed and rotation*, meaning
-            // that it fundamentally cannot return absolute values.
-            //
-            // * they'd have to be provided as separate inputs to the
-            //   neural network, which would make the evolution process
-            //   waaay longer, if even possible.
-
-            animal.speed = (animal.speed + speed).clamp(SPEED_MIN, SPEED_MAX);
-            animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
-
-            // (btw, there is no need for ROTATION_MIN or ROTATION_MAX,
-            // because rotation automatically wraps from 2*PI back to 0 -
-            // we've already witnessed that when we were testing eyes,
-            // inside `fn rotations { ... }`.)
-        }
-    }
-}
\ No newline at end of file
+        }
+    }
+
+    #[test]
+    fn healthy_simulation_is_not_degenerate() {
+        let config = SimulationConfig {
+            generation_length: 200,
+            ..Default::default()
+        };
+
+        for summary in train(config, 2) {
+            assert_eq!(summary.degeneracies, vec![]);
+        }
+    }
+
+    #[test]
+    fn response_gets_paired_with_previous_selection() {
+        let config = SimulationConfig {
+            generation_length: 200,
+            ..Default::default()
+        };
+
+        let summaries = train(config, 4);
+
+        assert_eq!((summaries[0].selection_differential, summaries[0].response), (None, None));
+
+        for pair in summaries.windows(2) {
+            let (previous, current) = (&pair[0], &pair[1]);
+
+            assert_eq!(current.selection_differential, Some(previous.diagnostics.selection_differential));
+            assert_eq!(current.response, Some(current.stats.avg_fitness - previous.stats.avg_fitness));
+        }
+
+        // Roulette wheel favours the fit ones
+        assert!(summaries.iter().all(|summary| summary.diagnostics.selection_differential > 0.0));
+    }
+
+    #[test]
+    fn restart_forgets_pending_selection() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = Simulation::from_config(
+            SimulationConfig {
+                generation_length: 200,
+                ..Default::default()
+            },
+            &mut rng,
+        );
+
+        sim.train(&mut rng);
+        sim.restart_from_best(&mut rng);
+
+        assert_eq!(sim.train(&mut rng).response, None);
+        assert!(sim.train(&mut rng).response.is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one animal")]
+    fn zero_animals_are_rejected() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 0,
+            ..Default::default()
+        };
+
+        Simulation::from_config(config, &mut rng);
+    }
+
+    #[test]
+    fn mutation_delta_is_zero_without_mutation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = Simulation::from_config(
+            config(GaParams {
+                mutation_chance: 0.0,
+                ..Default::default()
+            }),
+            &mut rng,
+        );
+
+        assert_eq!(sim.train(&mut rng).mutation_delta, Some(0.0));
+    }
+
+    #[test]
+    fn huge_mutation_hurts_converged_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(GaParams::default()), &mut rng);
+
+        // Twenty generations are enough for birds to learn to chase food
+        for _ in 0..20 {
+            sim.train(&mut rng);
+        }
+
+        // ... which a huge mutation should pretty much undo
+
+        sim.ga = ga::GeneticAlgorithm::new(
+            ga::RouletteWheelSelection,
+            ga::UniformCrossover,
+            ga::CompositeMutation::new(
+                ga::LayerScaledMutation::from_lengths(
+                    ga::GaussianMutation::new(0.5, 10.0),
+                    &sim.config.mutation_scales(&sim.config.ga),
+                ),
+                ga::WeightDecayMutation::new(0.0),
+            ),
+        );
+
+        let delta = sim.train(&mut rng).mutation_delta.unwrap();
+        assert!(delta < -1.0, "delta = {}", delta);
+    }
+
+    #[test]
+    fn zero_layer_scale_freezes_layer() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = Simulation::from_config(
+            config(GaParams {
+                mutation_chance: 1.0,
+                layer_scales: vec![0.0, 1.0],
+                reproduction: ga::ReproductionMode::Asexual,
+                ..Default::default()
+            }),
+            &mut rng,
+        );
+
+        let hidden_len = Brain::segment_lengths(&sim.config.eye())[0];
+        let hidden_layers = |sim: &Simulation| -> Vec<Vec<ga::Float>> {
+            sim.world()
+                .animal()
+                .iter()
+                .map(|animal| animal.as_chromosome().iter().take(hidden_len).copied().collect())
+                .collect()
+        };
+
+        let parents = hidden_layers(&sim);
+        let outputs_before = sim.world().animal()[0].as_chromosome().iter().skip(hidden_len).copied().collect::<Vec<_>>();
+
+        sim.train(&mut rng);
+
+        // Each child's hidden layer is a verbatim copy of its parent's ...
+        for child in hidden_layers(&sim) {
+            assert!(parents.contains(&child));
+        }
+
+        // ... while the output layer keeps on evolving
+        assert!(sim.world().animal().iter().all(|animal| !animal
+            .as_chromosome()
+            .iter()
+            .skip(hidden_len)
+            .copied()
+            .eq(outputs_before.iter().copied())));
+    }
+
+    mod food_layout {
+        use super::*;
+
+        /// Food layout at the beginning of each of the first `generations`
+        /// generations.
+        fn layouts(food_layout: PersistMode, generations: usize) -> Vec<Vec<na::Point2<f32>>> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = SimulationConfig {
+                n_animals: 5,
+                n_foods: 20,
+                generation_length: 100,
+                food_layout,
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::from_config(config, &mut rng);
+            let layout = |sim: &Simulation| sim.world().food().iter().map(|food| food.position()).collect();
+            let mut layouts = vec![layout(&sim)];
+
+            for _ in 1..generations {
+                sim.train(&mut rng);
+                layouts.push(layout(&sim));
+            }
+
+            layouts
+        }
+
+        #[test]
+        fn keep_positions_restores_initial_layout() {
+            let layouts = layouts(PersistMode::KeepPositions, 10);
+
+            for layout in &layouts[1..] {
+                assert_eq!(layout, &layouts[0]);
+            }
+        }
+
+        #[test]
+        fn keep_for_k_rerolls_every_k_generations() {
+            let layouts = layouts(PersistMode::KeepForK(3), 10);
+
+            let changed: Vec<_> = (1..layouts.len())
+                .filter(|&generation| layouts[generation] != layouts[generation - 1])
+                .collect();
+
+            assert_eq!(changed, vec![3, 6, 9]);
+        }
+
+        #[test]
+        fn rerandomize_changes_layout_every_generation() {
+            let layouts = layouts(PersistMode::Rerandomize, 4);
+
+            for generation in 1..layouts.len() {
+                assert_ne!(layouts[generation], layouts[generation - 1]);
+            }
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use crate::*;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// Family a bird descends from, e.g. to draw related birds in the same
+/// color.
+///
+/// Birds without traced parents (those of the initial population, those
+/// seeded by a restart) found their own lineages, named after their ids;
+/// everybody else inherits the lineage of their fitter parent - the first
+/// one on ties (which, when reproducing asexually, is the only one).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LineageId(pub(crate) u64);
+
+impl LineageId {
+    pub(crate) fn founded_by(id: AnimalId) -> Self {
+        Self(id.0)
+    }
+
+    /// Id of the bird that has founded this lineage.
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+
+    /// Color to draw this lineage's birds with - always the same for the
+    /// same lineage.
+    pub fn color(&self) -> Hsl {
+        // SplitMix64's finalizer, so that neighbouring ids (i.e. founders
+        // of the same generation) get unrelated colors
+        let mut x = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^= x >> 31;
+
+        let unit = |bits: u64| (bits & 0xffff) as f32 / 0xffff as f32;
+
+        Hsl {
+            hue: (x % 360) as f32,
+            saturation: 0.6 + 0.3 * unit(x >> 16),
+            lightness: 0.45 + 0.15 * unit(x >> 32),
+        }
+    }
+}
+
+/// Color as hue (in degrees), saturation and lightness (both in <0, 1>);
+/// displays as CSS' `hsl()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsl {
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+}
+
+impl fmt::Display for Hsl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hsl({:.0}, {:.0}%, {:.0}%)",
+            self.hue,
+            self.saturation * 100.0,
+            self.lightness * 100.0,
+        )
+    }
+}
+
+/// How lineages have fared through a generation boundary.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LineageReport {
+    /// Number of the next generation's birds in each of its lineages,
+    /// largest first (ties go to the older lineage); adds up to the size
+    /// of the population.
+    pub counts: Vec<(LineageId, usize)>,
+
+    /// Number of lineages of the generation that has just ended which have
+    /// no birds in the next one.
+    pub extinct: usize,
+}
+
+impl LineageReport {
+    pub(crate) fn of(ended: &[LineageId], next: &[Animal]) -> Self {
+        let mut counts = HashMap::new();
+
+        for animal in next {
+            *counts.entry(animal.lineage).or_insert(0) += 1;
+        }
+
+        let extinct = ended
+            .iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|lineage| !counts.contains_key(lineage))
+            .count();
+
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        Self { counts, extinct }
+    }
+
+    /// The largest lineage, along with its number of birds.
+    pub fn dominant(&self) -> Option<(LineageId, usize)> {
+        self.counts.first().copied()
+    }
+}
+
+impl Simulation {
+    /// Passes lineages of the generation that has just ended (by slot)
+    /// onto their children - both evolved (`parents`) and bred by hand
+    /// (`bred`).
+    pub(crate) fn inherit_lineages(
+        &mut self,
+        lineages: &[LineageId],
+        fitnesses: &[ga::Float],
+        parents: &[(usize, usize)],
+        bred: &[(usize, (usize, usize))],
+    ) {
+        let inherited = |(a, b): (usize, usize)| {
+            if fitnesses[b] > fitnesses[a] {
+                lineages[b]
+            } else {
+                lineages[a]
+            }
+        };
+
+        for (animal, &parents) in self.world.animals.iter_mut().zip(parents) {
+            animal.lineage = inherited(parents);
+        }
+
+        for &(slot, parents) in bred {
+            self.world.animals[slot].lineage = inherited(parents);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Gives each bird a distinct fitness.
+    struct Ranked;
+
+    impl GenerationPipeline for Ranked {
+        fn before_evolution(&mut self, individuals: &mut Vec<AnimalIndividual>) {
+            let n = individuals.len();
+
+            for (idx, individual) in individuals.iter_mut().enumerate() {
+                individual.set_fitness(fitness(idx, n));
+            }
+        }
+    }
+
+    fn fitness(idx: usize, n: usize) -> ga::Float {
+        ((idx * 7) % n) as ga::Float
+    }
+
+    fn sim(rng: &mut ChaCha8Rng) -> Simulation {
+        let config = SimulationConfig {
+            n_animals: 12,
+            generation_length: 10,
+            genealogy: Some(GenealogyConfig::default()),
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, rng);
+        sim.set_pipeline(Box::new(Ranked));
+        sim
+    }
+
+    fn lineages(sim: &Simulation) -> Vec<LineageId> {
+        sim.world().animal().iter().map(Animal::lineage).collect()
+    }
+
+    #[test]
+    fn founders_get_their_own_lineages() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let sim = sim(&mut rng);
+
+        for animal in sim.world().animal() {
+            assert_eq!(animal.lineage(), LineageId::founded_by(animal.id()));
+        }
+    }
+
+    #[test]
+    fn children_inherit_lineage_of_fitter_parent() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng);
+
+        for _ in 0..3 {
+            let ended = lineages(&sim);
+            sim.train(&mut rng);
+
+            let n = ended.len();
+            let genealogy = sim.genealogy().unwrap();
+
+            for (idx, &lineage) in lineages(&sim).iter().enumerate() {
+                let ((_, a), (_, b)) = genealogy.parents((sim.generation(), idx)).unwrap();
+                let fitter = if fitness(b, n) > fitness(a, n) { b } else { a };
+
+                assert_eq!(lineage, ended[fitter]);
+            }
+        }
+    }
+
+    #[test]
+    fn colors_are_deterministic() {
+        let color = LineageId(7).color();
+
+        assert_eq!(color, LineageId(7).color());
+        assert_eq!(color.to_string(), "hsl(327, 70%, 58%)");
+
+        for id in 0..1000 {
+            let color = LineageId(id).color();
+
+            assert!((0.0..360.0).contains(&color.hue));
+            assert!((0.6..=0.9).contains(&color.saturation));
+            assert!((0.45..=0.6).contains(&color.lightness));
+        }
+
+        // Same seed, same lineages - and so the same colors
+        let colors = || {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = sim(&mut rng);
+
+            sim.train_many(&mut rng, 2);
+            lineages(&sim).iter().map(|lineage| lineage.color().to_string()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(colors(), colors());
+    }
+
+    #[test]
+    fn counts_add_up_to_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng);
+        let mut alive = 12;
+
+        for _ in 0..10 {
+            let report = sim.train(&mut rng).lineages;
+
+            assert_eq!(report.counts.iter().map(|(_, count)| count).sum::<usize>(), 12);
+            assert_eq!(report.counts.len() + report.extinct, alive);
+            assert!(report.counts.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+            assert_eq!(report.dominant(), report.counts.first().copied());
+
+            alive = report.counts.len();
+        }
+
+        // Selection keeps only a handful of families around
+        assert!(alive < 12);
+    }
+
+    #[test]
+    fn restart_founds_new_lineages() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = sim(&mut rng);
+
+        sim.train(&mut rng);
+        sim.restart_from_best(&mut rng);
+
+        for animal in sim.world().animal() {
+            assert_eq!(animal.lineage(), LineageId::founded_by(animal.id()));
+        }
+    }
+}
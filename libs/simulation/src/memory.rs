@@ -0,0 +1,318 @@
+//! Accounting for the data that keeps piling up as the simulation runs,
+//! so that long sessions (e.g. hours in a browser tab) stay within a fixed
+//! amount of memory.
+
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Caps on how many bytes each component is allowed to take; once the
+/// component goes over its cap, its oldest data gets evicted (checked after
+/// every step). See: `Simulation::memory_report()`.
+///
+/// Sizes are approximate - they count the data itself, not the overhead
+/// of containers (nor of the allocator).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryBudget {
+    /// See: `SimulationConfig::genealogy`.
+    pub genealogy: usize,
+
+    /// See: `SimulationConfig::transitions`.
+    pub transitions: usize,
+
+    /// See: `SimulationConfig::event_log`.
+    pub events: usize,
+}
+
+impl MemoryBudget {
+    /// Cap of given component.
+    pub fn cap(&self, component: MemoryComponentKind) -> usize {
+        match component {
+            MemoryComponentKind::Genealogy => self.genealogy,
+            MemoryComponentKind::Transitions => self.transitions,
+            MemoryComponentKind::Events => self.events,
+        }
+    }
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self {
+            genealogy: 8 << 20,
+            transitions: 32 << 20,
+            events: 1 << 20,
+        }
+    }
+}
+
+/// Something whose data grows as the simulation runs.
+///
+/// Features that add such components have to give them a
+/// `MemoryComponentKind` (and a cap in `MemoryBudget`), so that they show
+/// up in `Simulation::memory_report()` and get evicted from.
+pub trait MemoryComponent {
+    /// Approximate number of bytes the component's data takes.
+    fn live_bytes(&self) -> usize;
+
+    /// Evicts the oldest data until the component takes no more than `cap`
+    /// bytes (or until there's nothing left that can be evicted).
+    fn evict_oldest(&mut self, cap: usize);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MemoryComponentKind {
+    Genealogy,
+    Transitions,
+    Events,
+}
+
+impl MemoryComponentKind {
+    pub const ALL: [Self; 3] = [Self::Genealogy, Self::Transitions, Self::Events];
+}
+
+/// How much memory each of the simulation's (enabled) components takes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryReport {
+    pub components: Vec<ComponentMemory>,
+}
+
+impl MemoryReport {
+    pub fn get(&self, kind: MemoryComponentKind) -> Option<&ComponentMemory> {
+        self.components.iter().find(|component| component.kind == kind)
+    }
+
+    /// Number of bytes across all the components.
+    pub fn total(&self) -> usize {
+        self.components.iter().map(|component| component.bytes).sum()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComponentMemory {
+    pub kind: MemoryComponentKind,
+
+    /// See: `MemoryComponent::live_bytes()`.
+    pub bytes: usize,
+
+    /// `None` unless `SimulationConfig::memory_budget` is enabled.
+    pub cap: Option<usize>,
+}
+
+impl Simulation {
+    /// Returns how much memory the simulation's growing components take -
+    /// e.g. for the frontend to display.
+    ///
+    /// The champion history (see: `StatsRecorder::with_history_cap()`)
+    /// lives outside of the simulation and doesn't count here.
+    pub fn memory_report(&self) -> MemoryReport {
+        let components = MemoryComponentKind::ALL
+            .into_iter()
+            .filter_map(|kind| {
+                Some(ComponentMemory {
+                    kind,
+                    bytes: self.memory_component(kind)?.live_bytes(),
+                    cap: self.config.memory_budget.map(|budget| budget.cap(kind)),
+                })
+            })
+            .collect();
+
+        MemoryReport { components }
+    }
+
+    /// Evicts from the components that have gone over their caps, if
+    /// there's a budget at all.
+    pub(crate) fn enforce_memory_budget(&mut self) {
+        let Some(budget) = self.config.memory_budget else {
+            return;
+        };
+
+        for kind in MemoryComponentKind::ALL {
+            if let Some(component) = self.memory_component_mut(kind) {
+                component.evict_oldest(budget.cap(kind));
+            }
+        }
+    }
+
+    /// Returns `None` if given component is disabled.
+    fn memory_component(&self, kind: MemoryComponentKind) -> Option<&dyn MemoryComponent> {
+        match kind {
+            MemoryComponentKind::Genealogy => Some(self.genealogy.as_ref()?),
+            MemoryComponentKind::Transitions => Some(self.transitions.as_ref()?),
+            MemoryComponentKind::Events => Some(self.events.as_ref()?),
+        }
+    }
+
+    fn memory_component_mut(&mut self, kind: MemoryComponentKind) -> Option<&mut dyn MemoryComponent> {
+        match kind {
+            MemoryComponentKind::Genealogy => Some(self.genealogy.as_mut()?),
+            MemoryComponentKind::Transitions => Some(self.transitions.as_mut()?),
+            MemoryComponentKind::Events => Some(self.events.as_mut()?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn sim(memory_budget: Option<MemoryBudget>) -> (Simulation, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 8,
+            generation_length: 30,
+            genealogy: Some(GenealogyConfig::default()),
+            event_log: Some(10_000),
+            transitions: Some(TransitionConfig {
+                sample_rate: 1.0,
+                capacity: 100_000,
+            }),
+            memory_budget,
+            ..Default::default()
+        };
+
+        (Simulation::from_config(config, &mut rng), rng)
+    }
+
+    fn budget() -> MemoryBudget {
+        MemoryBudget {
+            genealogy: 1000,
+            transitions: 20_000,
+            events: 500,
+        }
+    }
+
+    /// Asserts that the report says just what the components themselves do.
+    fn assert_report_matches_components(sim: &Simulation) {
+        let report = sim.memory_report();
+
+        let bytes = |kind| report.get(kind).unwrap().bytes;
+
+        assert_eq!(bytes(MemoryComponentKind::Genealogy), sim.genealogy.as_ref().unwrap().live_bytes());
+        assert_eq!(bytes(MemoryComponentKind::Transitions), sim.transitions.as_ref().unwrap().live_bytes());
+        assert_eq!(bytes(MemoryComponentKind::Events), sim.events.as_ref().unwrap().live_bytes());
+        assert_eq!(report.total(), report.components.iter().map(|component| component.bytes).sum::<usize>());
+    }
+
+    #[test]
+    fn components_get_evicted_past_their_caps() {
+        let (mut sim, mut rng) = sim(Some(budget()));
+
+        for _ in 0..20 {
+            sim.train(&mut rng);
+
+            let report = sim.memory_report();
+
+            for component in &report.components {
+                assert_eq!(component.cap, Some(budget().cap(component.kind)));
+                assert!(component.bytes <= budget().cap(component.kind), "{:?}", component);
+            }
+
+            assert!(report.total() <= 1000 + 20_000 + 500);
+            assert_report_matches_components(&sim);
+        }
+
+        // Each component has been filled past its cap (the uncapped run
+        // below shows by how much), and yet still holds its newest data
+        let (mut uncapped, mut rng) = self::sim(None);
+        uncapped.train_many(&mut rng, 20);
+
+        for component in uncapped.memory_report().components {
+            assert_eq!(component.cap, None);
+            assert!(component.bytes > 2 * budget().cap(component.kind), "{:?}", component);
+        }
+
+        assert_report_matches_components(&uncapped);
+
+        let genealogy = sim.genealogy().unwrap();
+
+        assert!(genealogy.parents((20, 0)).is_some());
+        assert!(genealogy.parents((1, 0)).is_none());
+        assert!(uncapped.genealogy().unwrap().parents((1, 0)).is_some());
+
+        assert!(!sim.export_transitions().is_empty());
+        assert!(!sim.drain_events().is_empty());
+    }
+
+    #[test]
+    fn evicted_genealogy_keeps_working() {
+        let mean_relatedness = |genealogy: GenealogyConfig, memory_budget: Option<MemoryBudget>| {
+            let (mut sim, mut rng) = sim(memory_budget);
+
+            sim.config.genealogy = Some(genealogy);
+            sim.genealogy = Some(Genealogy::new(&genealogy, 8));
+
+            let summaries = sim.train_many(&mut rng, 5);
+            let mean_relatedness: Vec<_> = summaries.iter().map(|summary| summary.mean_relatedness).collect();
+
+            (mean_relatedness, sim)
+        };
+
+        // Just the most recent generation is left, whose parents are gone
+        let (evicted, sim) = mean_relatedness(
+            GenealogyConfig { depth: 4 },
+            Some(MemoryBudget {
+                genealogy: 0,
+                ..budget()
+            }),
+        );
+
+        let genealogy = sim.genealogy().unwrap();
+
+        assert_eq!(genealogy.live_bytes(), 8 * std::mem::size_of::<Option<(usize, usize)>>());
+        assert!(genealogy.parents((5, 0)).is_some());
+        assert!(genealogy.parents((4, 0)).is_none());
+
+        // ... which makes relatedness look just one generation back
+        assert_eq!(evicted, mean_relatedness(GenealogyConfig { depth: 1 }, None).0);
+        assert_ne!(evicted, mean_relatedness(GenealogyConfig { depth: 4 }, None).0);
+    }
+
+    #[test]
+    fn report_lists_enabled_components_only() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let sim = Simulation::from_config(SimulationConfig::default(), &mut rng);
+
+        assert_eq!(sim.memory_report(), MemoryReport::default());
+    }
+
+    #[test]
+    fn history_evicts_whole_runs_of_champions() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(SimulationConfig::default(), &mut rng);
+
+        // Champions are mostly genes, so the cap follows their width
+        let cap = 2_500 * std::mem::size_of::<ga::Float>();
+
+        let mut recorder = StatsRecorder::new().with_history_cap(cap);
+        let mut uncapped = StatsRecorder::new();
+
+        for _ in 0..30 {
+            sim.train(&mut rng);
+
+            let champion = sim.last_champion().unwrap();
+
+            recorder.record_champion(champion);
+            uncapped.record_champion(champion);
+
+            assert!(recorder.history().live_bytes() <= cap);
+        }
+
+        let recorded = recorder.history().generations();
+
+        assert!(uncapped.history().live_bytes() > 2 * cap);
+        assert_eq!(recorded.end, 30);
+        assert!(recorded.start > 0 && recorded.start.is_multiple_of(10), "{:?}", recorded);
+
+        for generation in recorded {
+            assert!(recorder
+                .history()
+                .champion_at(generation)
+                .unwrap()
+                .iter()
+                .eq(uncapped.history().champion_at(generation).unwrap().iter()));
+        }
+    }
+}
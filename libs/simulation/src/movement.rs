@@ -0,0 +1,215 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Rotational inertia - when enabled, the brain's rotation output no
+/// longer turns the bird directly, but applies a torque to it:
+///
+/// ```text
+/// angular_velocity = (1 - damping) * angular_velocity + torque
+/// heading = heading + angular_velocity
+/// ```
+///
+/// ... where `torque` is the brain's output clamped to `max_torque`.
+///
+/// Under a constant torque, the angular velocity approaches
+/// `torque / damping`, so a damping of 1.0 means no inertia at all;
+/// smaller values make birds take a few steps to start (and stop)
+/// turning, which gets rid of the physically implausible zig-zags.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RotationalInertia {
+    pub max_torque: f32,
+    pub damping: f32,
+}
+
+impl RotationalInertia {
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(self.max_torque >= 0.0);
+        ensure!(self.damping > 0.0 && self.damping <= 1.0);
+
+        Ok(())
+    }
+}
+
+impl Default for RotationalInertia {
+    fn default() -> Self {
+        Self {
+            max_torque: ROTATION_ACCEL / 4.0,
+            damping: 0.25,
+        }
+    }
+}
+
+impl Animal {
+    /// Turns the bird according to its brain's rotation output.
+    ///
+    /// The output gets clamped symmetrically (positive turns the bird left,
+    /// negative - right) and only then added to the heading, which then
+    /// wraps back into `(-PI, PI]` - the range `na::Rotation2::angle()`
+    /// reports headings in.
+    pub(crate) fn turn(&mut self, config: &SimulationConfig, output: f32) {
+        let rotation = match config.rotational_inertia {
+            None => output.clamp(-config.rotation_accel, config.rotation_accel),
+
+            Some(inertia) => {
+                let torque = output.clamp(-inertia.max_torque, inertia.max_torque);
+
+                self.angular_velocity = (1.0 - inertia.damping) * self.angular_velocity + torque;
+                self.angular_velocity
+            }
+        };
+
+        self.rotation = na::Rotation2::new(self.rotation.angle() + rotation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    fn animal(config: &SimulationConfig) -> Animal {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Animal::random(config, &mut rng)
+    }
+
+    #[test]
+    fn constant_torque_approaches_terminal_velocity() {
+        let inertia = RotationalInertia {
+            max_torque: 0.1,
+            damping: 0.2,
+        };
+
+        let config = SimulationConfig {
+            rotational_inertia: Some(inertia),
+            ..Default::default()
+        };
+
+        let mut animal = animal(&config);
+
+        for _ in 0..200 {
+            // Gets clamped to `max_torque`
+            animal.turn(&config, 5.0);
+        }
+
+        assert_relative_eq!(animal.angular_velocity, 0.1 / 0.2, epsilon = 1e-5);
+    }
+
+    /// Turns `animal` by `output` for `steps` steps, returning how far it
+    /// has flown.
+    fn fly(config: &SimulationConfig, animal: &mut Animal, output: f32, steps: usize) -> na::Vector2<f32> {
+        (0..steps)
+            .map(|_| {
+                animal.turn(config, output);
+                animal.rotation * na::Vector2::new(0.0, 0.01)
+            })
+            .sum()
+    }
+
+    #[test]
+    fn left_and_right_turns_are_mirror_images() {
+        let configs = [
+            SimulationConfig::default(),
+            SimulationConfig {
+                rotational_inertia: Some(RotationalInertia::default()),
+                ..Default::default()
+            },
+        ];
+
+        for config in &configs {
+            for heading in [0.0, 0.3, FRAC_PI_2, 2.5, PI] {
+                let mut left = animal(config);
+                let mut right = animal(config);
+
+                left.rotation = na::Rotation2::new(heading);
+                right.rotation = na::Rotation2::new(-heading);
+
+                // Way beyond the limits, so that both get clamped
+                let left_path = fly(config, &mut left, 5.0, 200);
+                let right_path = fly(config, &mut right, -5.0, 200);
+
+                assert_relative_eq!(left_path.x, -right_path.x, epsilon = 1e-3);
+                assert_relative_eq!(left_path.y, right_path.y, epsilon = 1e-3);
+
+                assert_relative_eq!(left.rotation.angle().sin(), -right.rotation.angle().sin(), epsilon = 1e-3);
+                assert_relative_eq!(left.rotation.angle().cos(), right.rotation.angle().cos(), epsilon = 1e-3);
+                assert_relative_eq!(left.angular_velocity, -right.angular_velocity);
+            }
+        }
+    }
+
+    #[test]
+    fn turns_are_clamped_symmetrically() {
+        let config = SimulationConfig::default();
+
+        for output in [0.01, 0.1, config.rotation_accel, 1.0, 100.0] {
+            let mut left = animal(&config);
+            let mut right = animal(&config);
+
+            left.rotation = na::Rotation2::new(0.0);
+            right.rotation = na::Rotation2::new(0.0);
+
+            left.turn(&config, output);
+            right.turn(&config, -output);
+
+            assert!(left.rotation.angle() > 0.0);
+            assert!(left.rotation.angle() <= config.rotation_accel);
+            assert_eq!(left.rotation.angle(), -right.rotation.angle());
+        }
+    }
+
+    #[test]
+    fn headings_stay_within_range() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let configs = [
+            SimulationConfig::default(),
+            SimulationConfig {
+                rotational_inertia: Some(RotationalInertia {
+                    max_torque: 1.0,
+                    damping: 0.1,
+                }),
+                ..Default::default()
+            },
+        ];
+
+        for config in &configs {
+            let mut animal = animal(config);
+
+            // Mostly turning the same way, so that the heading goes around
+            // many times over
+            for _ in 0..10_000 {
+                animal.turn(config, rng.gen_range(-2.0..=5.0));
+
+                let heading = animal.rotation.angle();
+                assert!(-PI < heading && heading <= PI, "heading = {}", heading);
+            }
+        }
+    }
+
+    #[test]
+    fn without_inertia_turning_is_instantaneous() {
+        let config = SimulationConfig::default();
+        let mut animal = animal(&config);
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        for _ in 0..1000 {
+            let output = rng.gen_range(-5.0..=5.0);
+            let before = animal.rotation.angle();
+
+            animal.turn(&config, output);
+
+            // Bitwise the same as the original movement model
+            let expected = na::Rotation2::new(
+                before + output.clamp(-config.rotation_accel, config.rotation_accel),
+            );
+
+            assert_eq!(animal.rotation, expected);
+            assert_eq!(animal.angular_velocity, 0.0);
+        }
+    }
+}
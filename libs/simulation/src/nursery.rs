@@ -0,0 +1,114 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Grace period at the beginning of each bird's life - for its first
+/// `grace_steps` steps, foods it eats restore its energy as usual, but don't
+/// count towards its fitness (see: `Animal::scored_satiation()`).
+///
+/// Where a bird spawns is pure luck, and so is whatever food it happens to
+/// stumble upon right away; the grace period keeps that luck out of
+/// selection. A period that lasts the whole generation leaves everybody
+/// scored at zero.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Nursery {
+    pub grace_steps: usize,
+}
+
+impl Default for Nursery {
+    fn default() -> Self {
+        Self { grace_steps: 50 }
+    }
+}
+
+impl World {
+    /// Counts down the grace periods of all the birds that are still in
+    /// the nursery.
+    pub(crate) fn process_nursery(&mut self) {
+        for animal in &mut self.animals {
+            animal.nursery_steps = animal.nursery_steps.saturating_sub(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn sim(nursery: Option<Nursery>) -> (Simulation, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 1,
+            n_foods: 1,
+            generation_length: 20,
+            energy: Some(EnergyConfig {
+                initial: 0.0,
+                per_step: 0.0,
+                per_food: 0.5,
+            }),
+            nursery,
+            ..Default::default()
+        };
+
+        (Simulation::from_config(config, &mut rng), rng)
+    }
+
+    /// Steps the simulation with the food put right onto the bird, so that
+    /// it eats once per step.
+    fn step_feeding(sim: &mut Simulation, rng: &mut ChaCha8Rng) {
+        sim.world.foods[0].position = sim.world.animals[0].position;
+        sim.step(rng);
+    }
+
+    #[test]
+    fn foods_eaten_in_nursery_restore_energy_but_dont_score() {
+        let (mut sim, mut rng) = sim(Some(Nursery { grace_steps: 3 }));
+
+        for step in 1..=5 {
+            assert_eq!(sim.world().animal()[0].in_nursery(), step <= 3);
+
+            step_feeding(&mut sim, &mut rng);
+
+            let animal = &sim.world().animal()[0];
+
+            assert_eq!(animal.satiation, step);
+            assert_eq!(animal.scored_satiation(), step.saturating_sub(3));
+            assert_eq!(animal.energy(), 0.5 * step as f32);
+        }
+
+        assert!(!sim.world().animal()[0].in_nursery());
+    }
+
+    #[test]
+    fn fitness_counts_scored_foods_only() {
+        let fitness = |nursery| {
+            let (mut sim, mut rng) = sim(nursery);
+
+            loop {
+                sim.world.foods[0].position = sim.world.animals[0].position;
+
+                if let Some(summary) = sim.step(&mut rng) {
+                    return summary.stats.avg_fitness;
+                }
+            }
+        };
+
+        // A generation of length 20 lasts 21 steps
+        assert_eq!(fitness(None), 21.0);
+        assert_eq!(fitness(Some(Nursery { grace_steps: 5 })), 16.0);
+        assert_eq!(fitness(Some(Nursery { grace_steps: 21 })), 0.0);
+    }
+
+    #[test]
+    fn every_generation_starts_in_nursery() {
+        let (mut sim, mut rng) = sim(Some(Nursery { grace_steps: 3 }));
+
+        sim.train(&mut rng);
+
+        assert!(sim.world().animal()[0].in_nursery());
+        assert_eq!(sim.world().animal()[0].scored_satiation(), 0);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::*;
+
+/// What a bird sees, ready to be drawn on top of the world.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VisionOverlay {
+    /// One entry per eye cell; see: `Eye::cell_geometry()`.
+    pub cells: Vec<CellGeometry>,
+
+    /// Current activation of each cell, as fed into the brain; see:
+    /// `Eye::process_vision()`.
+    pub activations: Vec<f32>,
+}
+
+impl Simulation {
+    /// Returns `None` if there's no bird at given index.
+    pub fn vision_overlay(&self, animal_index: usize) -> Option<VisionOverlay> {
+        let animal = self.world.animals.get(animal_index)?;
+
+        Some(VisionOverlay {
+            cells: animal.eye.cell_geometry(animal.position, animal.rotation),
+            activations: animal.vision(&self.config, &self.world.foods, self.age),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn overlay_has_one_entry_per_cell() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let sim = Simulation::from_config(SimulationConfig::default(), &mut rng);
+        let overlay = sim.vision_overlay(0).unwrap();
+
+        assert_eq!(overlay.cells.len(), sim.config().eye_cells);
+        assert_eq!(overlay.activations.len(), sim.config().eye_cells);
+        assert_eq!(sim.vision_overlay(sim.config().n_animals), None);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::*;
+
+/// Hooks into the boundary between two generations, for custom logic
+/// the simulation doesn't provide on its own - filtering, logging
+/// chromosomes, injecting immigrants etc.
+///
+/// Both hooks have to keep the population's size intact - to filter a
+/// bird out, drop its fitness to zero instead of removing it.
+///
+/// Pipelines have to be `Send`, so that the simulation can be stepped on
+/// another thread.
+pub trait GenerationPipeline: Send {
+    /// Called with the scored birds of the generation that has just
+    /// ended, right before they get passed to the genetic algorithm.
+    fn before_evolution(&mut self, _individuals: &mut Vec<AnimalIndividual>) {
+        //
+    }
+
+    /// Called with the genetic algorithm's offspring, right before they
+    /// get turned back into birds.
+    fn after_evolution(&mut self, _offspring: &mut Vec<AnimalIndividual>) {
+        //
+    }
+
+    /// Called with statistics of each evolved generation.
+    fn summarize(&self, _stats: &ga::Statistics) {
+        //
+    }
+}
+
+/// Pipeline that doesn't interfere with the evolution at all.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultPipeline;
+
+impl GenerationPipeline for DefaultPipeline {}
+
+impl Simulation {
+    pub fn set_pipeline(&mut self, pipeline: Box<dyn GenerationPipeline>) {
+        self.pipeline = pipeline;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::{Arc, Mutex};
+
+    fn sim() -> (Simulation, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 10,
+            generation_length: 100,
+            genealogy: Some(GenealogyConfig::default()),
+            ..Default::default()
+        };
+
+        (Simulation::from_config(config, &mut rng), rng)
+    }
+
+    fn avg_fitness(individuals: &[AnimalIndividual]) -> ga::Float {
+        individuals.iter().map(AnimalIndividual::fitness).sum::<ga::Float>()
+            / individuals.len() as ga::Float
+    }
+
+    /// Scales every fitness, remembering the average fitness before that
+    /// and the one seen by the genetic algorithm.
+    struct Scale {
+        factor: ga::Float,
+        seen: Arc<Mutex<Vec<(ga::Float, ga::Float)>>>,
+    }
+
+    impl GenerationPipeline for Scale {
+        fn before_evolution(&mut self, individuals: &mut Vec<AnimalIndividual>) {
+            let before = avg_fitness(individuals);
+
+            for individual in individuals.iter_mut() {
+                individual.set_fitness(individual.fitness() * self.factor);
+            }
+
+            self.seen.lock().unwrap().push((before, 0.0));
+        }
+
+        fn summarize(&self, stats: &ga::Statistics) {
+            self.seen.lock().unwrap().last_mut().unwrap().1 = stats.avg_fitness;
+        }
+    }
+
+    #[test]
+    fn doubled_fitness_reaches_the_genetic_algorithm() {
+        let (mut sim, mut rng) = sim();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        sim.set_pipeline(Box::new(Scale {
+            factor: 2.0,
+            seen: seen.clone(),
+        }));
+
+        sim.train_many(&mut rng, 3);
+
+        let seen = seen.lock().unwrap();
+
+        assert_eq!(seen.len(), 3);
+
+        for &(before, after) in seen.iter() {
+            assert_eq!(after, 2.0 * before);
+        }
+    }
+
+    /// Zeroes fitness of everybody except the first bird.
+    struct OnlyFirst;
+
+    impl GenerationPipeline for OnlyFirst {
+        fn before_evolution(&mut self, individuals: &mut Vec<AnimalIndividual>) {
+            for (idx, individual) in individuals.iter_mut().enumerate() {
+                individual.set_fitness(if idx == 0 { 1.0 } else { 0.0 });
+            }
+        }
+    }
+
+    #[test]
+    fn changed_fitness_changes_selection() {
+        let (mut sim, mut rng) = sim();
+
+        sim.set_pipeline(Box::new(OnlyFirst));
+        sim.train(&mut rng);
+
+        let genealogy = sim.genealogy().unwrap();
+
+        // ... with the other parent picked uniformly, as nobody else has
+        // any fitness
+        for idx in 0..10 {
+            let (parent_a, parent_b) = genealogy.parents((1, idx)).unwrap();
+
+            assert_eq!(parent_a, (0, 0));
+            assert_ne!(parent_b, (0, 0));
+        }
+    }
+
+    /// Replaces the first offspring with a fixed immigrant.
+    struct Immigrant(ga::Chromosome);
+
+    impl GenerationPipeline for Immigrant {
+        fn after_evolution(&mut self, offspring: &mut Vec<AnimalIndividual>) {
+            offspring[0] = AnimalIndividual::new(self.0.clone(), 0.0);
+        }
+    }
+
+    #[test]
+    fn injected_chromosome_joins_next_population() {
+        let (mut sim, mut rng) = sim();
+        let len = Animal::chromosome_len(sim.config());
+        let immigrant: Vec<_> = (0..len).map(|n| n as ga::Float / 1000.0).collect();
+
+        sim.set_pipeline(Box::new(Immigrant(immigrant.iter().copied().collect())));
+        sim.train(&mut rng);
+
+        assert!(sim
+            .world()
+            .animals
+            .iter()
+            .any(|animal| animal.as_chromosome().iter().eq(&immigrant)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pipelines_cannot_resize_population() {
+        struct Shrink;
+
+        impl GenerationPipeline for Shrink {
+            fn after_evolution(&mut self, offspring: &mut Vec<AnimalIndividual>) {
+                offspring.pop();
+            }
+        }
+
+        let (mut sim, mut rng) = sim();
+
+        sim.set_pipeline(Box::new(Shrink));
+        sim.train(&mut rng);
+    }
+}
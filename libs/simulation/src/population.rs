@@ -0,0 +1,301 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+/// Source of time for `PopulationControl`; see: `Simulation::set_clock()`.
+pub trait Clock: Send {
+    /// Milliseconds elapsed since some arbitrary (but fixed) moment.
+    fn now_ms(&self) -> f64;
+}
+
+/// Clock backed by `std::time::Instant`, used unless another one gets
+/// set; note that `Instant` is not available on `wasm32-unknown-unknown`,
+/// where a clock backed by e.g. `performance.now()` has to be set instead.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> f64 {
+        static START: OnceLock<Instant> = OnceLock::new();
+
+        START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+/// Keeps the frame rate in check by adjusting the number of birds at each
+/// generation boundary, so that a single step takes about
+/// `target_step_ms`.
+///
+/// Step durations get measured (see: `Simulation::set_clock()`) over the
+/// last `window` steps; when their mean is `x`% above the target, the
+/// population shrinks by `gain * x`% - and vice versa - staying within
+/// `min_animals..=max_animals`.
+///
+/// Requires a population of variable size, so it can't be combined with
+/// `SimulationConfig::habitats` or `SimulationConfig::genealogy`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PopulationControl {
+    pub target_step_ms: f32,
+    pub window: usize,
+    pub gain: f32,
+    pub min_animals: usize,
+    pub max_animals: usize,
+}
+
+impl PopulationControl {
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!(self.target_step_ms > 0.0);
+        ensure!(self.window > 0);
+        ensure!(self.gain > 0.0);
+        ensure!(0 < self.min_animals && self.min_animals <= self.max_animals);
+
+        Ok(())
+    }
+
+    /// Number of birds the next generation gets, given the current one and
+    /// the mean step duration.
+    pub fn adjust(&self, n_animals: usize, step_ms: f32) -> usize {
+        let error = (self.target_step_ms - step_ms) / self.target_step_ms;
+        let n_animals = (n_animals as f32 * (1.0 + self.gain * error)).round().max(0.0) as usize;
+
+        n_animals.clamp(self.min_animals, self.max_animals)
+    }
+}
+
+impl Default for PopulationControl {
+    fn default() -> Self {
+        Self {
+            // ~60 frames per second, leaving some room for rendering
+            target_step_ms: 8.0,
+            window: 100,
+            gain: 0.5,
+            min_animals: 10,
+            max_animals: 1000,
+        }
+    }
+}
+
+/// Adjustment made by `PopulationControl`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PopulationReport {
+    /// Mean duration of the measured steps, in milliseconds.
+    pub step_ms: f32,
+
+    /// Number of birds during the generation that has just ended.
+    pub n_animals: usize,
+
+    /// Number of birds during the next generation.
+    pub next_n_animals: usize,
+}
+
+/// Durations of the most recent steps.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct StepTimings {
+    durations: VecDeque<f64>,
+}
+
+impl StepTimings {
+    fn push(&mut self, window: usize, duration: f64) {
+        if self.durations.len() == window {
+            self.durations.pop_front();
+        }
+
+        self.durations.push_back(duration);
+    }
+
+    fn mean(&self) -> Option<f64> {
+        if self.durations.is_empty() {
+            None
+        } else {
+            Some(self.durations.iter().sum::<f64>() / self.durations.len() as f64)
+        }
+    }
+}
+
+impl Simulation {
+    /// Changes the clock `PopulationControl` measures steps with; defaults
+    /// to `SystemClock`.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Runs `step` (a single world step), measuring how long it takes if
+    /// `SimulationConfig::population_control` is enabled.
+    pub(crate) fn timed<T>(&mut self, step: impl FnOnce(&mut Self) -> T) -> T {
+        let Some(control) = self.config.population_control else {
+            return step(self);
+        };
+
+        let started_at = self.clock.now_ms();
+        let result = step(self);
+        let duration = self.clock.now_ms() - started_at;
+
+        self.step_timings.push(control.window, duration);
+        result
+    }
+
+    /// Number of birds the next generation is going to have, according to
+    /// `SimulationConfig::population_control`; `None` until at least one
+    /// step has been measured.
+    pub(crate) fn next_population_size(&self) -> Option<PopulationReport> {
+        let control = self.config.population_control?;
+        let n_animals = self.config.n_animals;
+
+        let step_ms = self.step_timings.mean()? as f32;
+
+        // Hand-bred offspring are promised a slot
+        let next_n_animals = control.adjust(n_animals, step_ms).max(self.offspring.len());
+
+        Some(PopulationReport {
+            step_ms,
+            n_animals,
+            next_n_animals,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Clock that advances by `ms_per_call` each time it's read, so that
+    /// each step appears to take exactly `ms_per_call`.
+    struct FakeClock {
+        ms_per_call: f64,
+        calls: AtomicUsize,
+    }
+
+    impl Clock for FakeClock {
+        fn now_ms(&self) -> f64 {
+            self.calls.fetch_add(1, Ordering::SeqCst) as f64 * self.ms_per_call
+        }
+    }
+
+    fn control() -> PopulationControl {
+        PopulationControl {
+            target_step_ms: 10.0,
+            window: 5,
+            gain: 0.5,
+            min_animals: 4,
+            max_animals: 20,
+        }
+    }
+
+    fn sizes(control: PopulationControl, step_ms: f64) -> Vec<usize> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 10,
+            generation_length: 10,
+            population_control: Some(control),
+            gene_ages: true,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+
+        sim.set_clock(Box::new(FakeClock {
+            ms_per_call: step_ms,
+            calls: AtomicUsize::new(0),
+        }));
+
+        sim.train_many(&mut rng, 8)
+            .into_iter()
+            .map(|summary| {
+                let report = summary.population.unwrap();
+
+                assert_eq!(report.step_ms, step_ms as f32);
+                assert_eq!(sim.world().animal().len(), sim.config().n_animals);
+
+                report.next_n_animals
+            })
+            .collect()
+    }
+
+    #[test]
+    fn slow_steps_shrink_population() {
+        // 50% above the target, so 25% less birds each generation
+        assert_eq!(sizes(control(), 15.0), vec![8, 6, 5, 4, 4, 4, 4, 4]);
+    }
+
+    #[test]
+    fn fast_steps_grow_population() {
+        assert_eq!(sizes(control(), 5.0), vec![13, 16, 20, 20, 20, 20, 20, 20]);
+    }
+
+    #[test]
+    fn never_overshoots_bounds() {
+        let control = PopulationControl {
+            gain: 5.0,
+            ..control()
+        };
+
+        assert!(sizes(control, 15.0).iter().all(|&size| size == 4));
+        assert!(sizes(control, 5.0).iter().all(|&size| size == 20));
+        assert!(sizes(control, 1000.0).iter().all(|&size| size == 4));
+    }
+
+    #[test]
+    fn on_target_steps_keep_population() {
+        assert!(sizes(control(), 10.0).iter().all(|&size| size == 10));
+    }
+
+    #[test]
+    fn shrinking_keeps_hand_bred_offspring() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 10,
+            generation_length: 10,
+            population_control: Some(PopulationControl {
+                gain: 5.0,
+                ..control()
+            }),
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+
+        sim.set_clock(Box::new(FakeClock {
+            ms_per_call: 100.0,
+            calls: AtomicUsize::new(0),
+        }));
+
+        let parents: Vec<_> = sim.world().animal().iter().map(Animal::id).collect();
+        let offspring = sim.breed(&mut rng, parents[0], parents[1], 6).unwrap();
+
+        // Would've shrunk to 4, if not for the offspring
+        assert_eq!(sim.train(&mut rng).population.unwrap().next_n_animals, 6);
+
+        for id in offspring {
+            assert!(sim.world().animal().iter().any(|animal| animal.id() == id));
+        }
+    }
+
+    #[test]
+    fn disabled_control_measures_nothing() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 5,
+            generation_length: 10,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+
+        sim.set_clock(Box::new(FakeClock {
+            ms_per_call: 1.0,
+            calls: AtomicUsize::new(0),
+        }));
+
+        assert_eq!(sim.train(&mut rng).population, None);
+        assert_eq!(sim.world().animal().len(), 5);
+    }
+}
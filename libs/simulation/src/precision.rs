@@ -0,0 +1,45 @@
+//! Conversions between `f32` (positions, vision etc.) and `ga::Float`
+//! (genes, brain weights & fitness), which are the same type unless the
+//! `precision-f64` feature is enabled.
+
+use crate::*;
+
+#[cfg(not(feature = "precision-f64"))]
+pub(crate) fn widen(value: f32) -> ga::Float {
+    value
+}
+
+#[cfg(feature = "precision-f64")]
+pub(crate) fn widen(value: f32) -> ga::Float {
+    value.into()
+}
+
+#[cfg(not(feature = "precision-f64"))]
+pub(crate) fn narrow(value: ga::Float) -> f32 {
+    value
+}
+
+#[cfg(feature = "precision-f64")]
+pub(crate) fn narrow(value: ga::Float) -> f32 {
+    value as f32
+}
+
+#[cfg(not(feature = "precision-f64"))]
+pub(crate) fn widen_all(values: Vec<f32>) -> Vec<ga::Float> {
+    values
+}
+
+#[cfg(feature = "precision-f64")]
+pub(crate) fn widen_all(values: Vec<f32>) -> Vec<ga::Float> {
+    values.into_iter().map(widen).collect()
+}
+
+#[cfg(not(feature = "precision-f64"))]
+pub(crate) fn narrow_all(values: Vec<ga::Float>) -> Vec<f32> {
+    values
+}
+
+#[cfg(feature = "precision-f64")]
+pub(crate) fn narrow_all(values: Vec<ga::Float>) -> Vec<f32> {
+    values.into_iter().map(narrow).collect()
+}
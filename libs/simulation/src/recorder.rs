@@ -0,0 +1,129 @@
+use crate::*;
+use std::fmt::Write;
+
+/// How often `StatsRecorder` stores a champion in full; see:
+/// `ChampionHistory`.
+const KEYFRAME_INTERVAL: usize = 10;
+
+/// Collects summaries of consecutive generations, e.g. to plot how the
+/// fitness evolves over a training session.
+#[derive(Clone, Debug)]
+pub struct StatsRecorder {
+    summaries: Vec<GenerationSummary>,
+    history: ChampionHistory,
+
+    /// See: `StatsRecorder::with_history_cap()`.
+    history_cap: Option<usize>,
+}
+
+impl Default for StatsRecorder {
+    fn default() -> Self {
+        Self {
+            summaries: Vec::new(),
+            history: ChampionHistory::new(KEYFRAME_INTERVAL),
+            history_cap: None,
+        }
+    }
+}
+
+impl StatsRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caps the champion history at (approximately) `bytes` - the oldest
+    /// champions get evicted to make room for new ones; see:
+    /// `MemoryComponent`.
+    pub fn with_history_cap(mut self, bytes: usize) -> Self {
+        self.history_cap = Some(bytes);
+        self
+    }
+
+    pub fn record(&mut self, summary: &GenerationSummary) {
+        self.summaries.push(summary.clone());
+    }
+
+    /// Records the fittest bird of a generation (see:
+    /// `Simulation::last_champion()`), e.g. to replay it later; champions
+    /// have to be recorded one generation after another.
+    pub fn record_champion(&mut self, champion: &Champion) {
+        self.history.push(champion.generation, &champion.chromosome());
+
+        if let Some(cap) = self.history_cap {
+            self.history.evict_oldest(cap);
+        }
+    }
+
+    pub fn summaries(&self) -> &[GenerationSummary] {
+        &self.summaries
+    }
+
+    pub fn history(&self) -> &ChampionHistory {
+        &self.history
+    }
+
+    /// Returns recorded statistics as CSV, one row per generation; the
+    /// `mutation_delta` column is left empty when it wasn't measured.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("generation,min_fitness,max_fitness,avg_fitness,mutation_delta\n");
+
+        for summary in &self.summaries {
+            let mutation_delta = summary
+                .mutation_delta
+                .map(|delta| delta.to_string())
+                .unwrap_or_default();
+
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                summary.generation,
+                summary.stats.min_fitness,
+                summary.stats.max_fitness,
+                summary.stats.avg_fitness,
+                mutation_delta,
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn writes_one_row_per_generation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 5,
+            generation_length: 20,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        let mut recorder = StatsRecorder::new();
+
+        for summary in sim.train_many(&mut rng, 3) {
+            recorder.record(&summary);
+        }
+
+        let csv = recorder.to_csv();
+        let rows: Vec<_> = csv.lines().collect();
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], "generation,min_fitness,max_fitness,avg_fitness,mutation_delta");
+
+        for (generation, row) in rows[1..].iter().enumerate() {
+            let columns: Vec<_> = row.split(',').collect();
+
+            assert_eq!(columns.len(), 5);
+            assert_eq!(columns[0], generation.to_string());
+            assert_eq!(columns[4], "");
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::*;
+
+impl Simulation {
+    /// Starts over from the best birds seen so far: the world gets
+    /// recreated and the population gets seeded from the hall of fame
+    /// (see: `RestartPolicy`).
+    ///
+    /// The generation counter and the hall of fame itself stay intact.
+    pub fn restart_from_best(&mut self, rng: &mut dyn RngCore) {
+        let n_animals = self.config.n_animals;
+
+        let n_random = if self.hall_of_fame.champions().is_empty() {
+            n_animals
+        } else {
+            (n_animals as f32 * self.config.restart.random_fraction).round() as usize
+        };
+
+        let chromosomes: Vec<_> = self
+            .hall_of_fame
+            .champions()
+            .iter()
+            .map(Champion::chromosome)
+            .collect();
+
+        let mut world = World::from_config(&self.config, rng);
+
+        for idx in 0..(n_animals - n_random) {
+            let champion = &chromosomes[idx % chromosomes.len()];
+
+            let chromosome = if idx < chromosomes.len() {
+                champion.clone()
+            } else {
+                // Crossing a chromosome with itself leaves just the mutation
+                self.ga
+                    .breed(rng, champion, champion)
+                    .expect("a chromosome has the same length as itself")
+            };
+
+            world.animals[idx] = Animal::from_chromosome(&self.config, chromosome, rng);
+        }
+
+        // Ids must stay unique across the whole simulation
+        world.next_id = self.world.next_id;
+        world.assign_ids();
+        world.settle_habitats(&self.config, rng);
+        world.assign_arms(&self.config);
+
+        self.food_layout = world.foods.iter().map(|food| food.position).collect();
+        self.world = world;
+        self.age = 0;
+        self.low_fitness_streak = 0;
+        self.gene_ages = gene_ages::fresh_gene_ages(&self.config);
+
+        // The new population has nothing to respond with
+        self.last_selection = None;
+    }
+
+    /// Restarts the simulation if `AutoRestart`'s condition has been met;
+    /// returns whether it did.
+    pub(crate) fn auto_restart(&mut self, stats: &ga::Statistics, rng: &mut dyn RngCore) -> bool {
+        let Some(auto) = self.config.restart.auto else {
+            return false;
+        };
+
+        if stats.max_fitness < widen(auto.threshold) {
+            self.low_fitness_streak += 1;
+        } else {
+            self.low_fitness_streak = 0;
+        }
+
+        if self.low_fitness_streak < auto.generations {
+            return false;
+        }
+
+        self.restart_from_best(rng);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn config() -> SimulationConfig {
+        SimulationConfig {
+            n_animals: 10,
+            n_foods: 20,
+            generation_length: 100,
+            hall_of_fame_size: 4,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn restart_seeds_population_from_hall_of_fame() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(), &mut rng);
+
+        sim.train_many(&mut rng, 3);
+        sim.restart_from_best(&mut rng);
+
+        let chromosomes: Vec<Vec<ga::Float>> = sim
+            .world()
+            .animal()
+            .iter()
+            .map(|animal| animal.as_chromosome().iter().copied().collect())
+            .collect();
+
+        for champion in sim.hall_of_fame().champions() {
+            assert!(chromosomes.contains(&champion.genes));
+        }
+
+        assert_eq!(chromosomes.len(), 10);
+        assert_eq!(sim.generation(), 3);
+    }
+
+    #[test]
+    fn auto_restart_fires_exactly_after_streak() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            restart: RestartPolicy {
+                auto: Some(AutoRestart {
+                    // Unreachable, so every generation counts towards the streak
+                    threshold: 1000.0,
+                    generations: 3,
+                }),
+                ..Default::default()
+            },
+            ..config()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+
+        let restarts: Vec<_> = sim
+            .train_many(&mut rng, 7)
+            .iter()
+            .map(|summary| summary.restarted)
+            .collect();
+
+        assert_eq!(restarts, vec![false, false, true, false, false, true, false]);
+    }
+
+    #[test]
+    fn auto_restart_streak_resets_on_good_generation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            restart: RestartPolicy {
+                auto: Some(AutoRestart {
+                    threshold: 0.0,
+                    generations: 1,
+                }),
+                ..Default::default()
+            },
+            ..config()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+
+        // Max fitness can never be below zero
+        assert!(sim.train_many(&mut rng, 3).iter().all(|summary| !summary.restarted));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::*;
+use std::sync::{Arc, RwLock};
+
+/// Flat copy of the world's state, e.g. for rendering it on another
+/// thread than the one stepping the simulation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WorldBuffers {
+    pub generation: usize,
+
+    /// Step within the generation.
+    pub age: usize,
+
+    /// `x, y, rotation` of each bird, one after another (see:
+    /// `WorldBuffers::ANIMAL_STRIDE`).
+    pub animals: Vec<f32>,
+
+    /// `x, y` of each food, one after another (see:
+    /// `WorldBuffers::FOOD_STRIDE`).
+    pub foods: Vec<f32>,
+}
+
+impl WorldBuffers {
+    pub const ANIMAL_STRIDE: usize = 3;
+    pub const FOOD_STRIDE: usize = 2;
+
+    pub fn n_animals(&self) -> usize {
+        self.animals.len() / Self::ANIMAL_STRIDE
+    }
+
+    pub fn n_foods(&self) -> usize {
+        self.foods.len() / Self::FOOD_STRIDE
+    }
+
+    fn of(sim: &Simulation) -> Self {
+        Self {
+            generation: sim.generation,
+            age: sim.age,
+            animals: sim
+                .world
+                .animals
+                .iter()
+                .flat_map(|animal| [animal.position.x, animal.position.y, animal.rotation.angle()])
+                .collect(),
+            foods: sim
+                .world
+                .foods
+                .iter()
+                .flat_map(|food| [food.position.x, food.position.y])
+                .collect(),
+        }
+    }
+}
+
+/// Hands out the most recently published `WorldBuffers`; can be cloned and
+/// sent to other threads, independently of the simulation itself.
+///
+/// Each snapshot gets built in full before it's published, and publishing
+/// just swaps a pointer - so readers never see a half-updated frame and
+/// never wait for the simulation to finish stepping.
+#[derive(Clone, Debug, Default)]
+pub struct SnapshotReader {
+    slot: Arc<RwLock<Arc<WorldBuffers>>>,
+}
+
+impl SnapshotReader {
+    pub fn latest(&self) -> Arc<WorldBuffers> {
+        self.slot.read().expect("snapshot slot got poisoned").clone()
+    }
+
+    fn publish(&self, buffers: WorldBuffers) {
+        *self.slot.write().expect("snapshot slot got poisoned") = Arc::new(buffers);
+    }
+}
+
+impl Simulation {
+    /// Publishes current state of the world, so that it can be read
+    /// through `.latest_snapshot()` or `.snapshot_reader()`; meant to be
+    /// called after each `.step()`.
+    pub fn publish_snapshot(&self) {
+        self.snapshots.publish(WorldBuffers::of(self));
+    }
+
+    /// Returns the most recently published snapshot; it's empty until
+    /// `.publish_snapshot()` gets called for the first time.
+    pub fn latest_snapshot(&self) -> Arc<WorldBuffers> {
+        self.snapshots.latest()
+    }
+
+    /// Returns a handle for reading snapshots from another thread, while
+    /// this one keeps stepping the simulation.
+    pub fn snapshot_reader(&self) -> SnapshotReader {
+        self.snapshots.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    fn sim() -> (Simulation, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 12,
+            n_foods: 30,
+            generation_length: 20,
+            ..Default::default()
+        };
+
+        (Simulation::from_config(config, &mut rng), rng)
+    }
+
+    #[test]
+    fn snapshot_mirrors_world() {
+        let (mut sim, mut rng) = sim();
+
+        assert_eq!(*sim.latest_snapshot(), WorldBuffers::default());
+
+        sim.step(&mut rng);
+        sim.publish_snapshot();
+
+        let snapshot = sim.latest_snapshot();
+        let animal = &sim.world().animal()[3];
+
+        assert_eq!(snapshot.age, 1);
+        assert_eq!(snapshot.n_animals(), 12);
+        assert_eq!(snapshot.n_foods(), 30);
+        assert_eq!(snapshot.animals[9..12], [animal.position().x, animal.position().y, animal.rotation().angle()]);
+    }
+
+    #[test]
+    fn snapshots_stay_consistent_across_threads() {
+        let (mut sim, mut rng) = sim();
+        let reader = sim.snapshot_reader();
+        let done = AtomicBool::new(false);
+
+        sim.publish_snapshot();
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                // Goes through a few generation boundaries, too
+                for _ in 0..200 {
+                    sim.step(&mut rng);
+                    sim.publish_snapshot();
+                }
+
+                done.store(true, Ordering::SeqCst);
+            });
+
+            let mut last = (0, 0);
+            let mut reads = 0;
+
+            while !done.load(Ordering::SeqCst) || reads == 0 {
+                let snapshot = reader.latest();
+
+                assert_eq!(snapshot.animals.len(), 12 * WorldBuffers::ANIMAL_STRIDE);
+                assert_eq!(snapshot.foods.len(), 30 * WorldBuffers::FOOD_STRIDE);
+
+                // Snapshots never go back in time
+                let current = (snapshot.generation, snapshot.age);
+                assert!(current >= last);
+
+                last = current;
+                reads += 1;
+            }
+        });
+
+        assert_eq!(sim.latest_snapshot().generation, 9);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::*;
+
+/// What happened during a single generation, returned from `.step()`
+/// (and `.train()`) whenever a generation comes to an end.
+#[derive(Clone, Debug)]
+pub struct GenerationSummary {
+    /// Number of the generation that has just ended, counting from 0.
+    pub generation: usize,
+
+    /// Fitness statistics of the generation that has just ended.
+    pub stats: ga::Statistics,
+
+    /// Mean change in fitness caused by mutation alone; present only when
+    /// `SimulationConfig::mutation_impact` is enabled (and at least one
+    /// child got sampled).
+    ///
+    /// See: `MutationImpact`.
+    pub mutation_delta: Option<f32>,
+
+    /// Population mean of each `FitnessComposer`'s signal (before
+    /// normalization), in the order of its terms; empty when
+    /// `SimulationConfig::fitness` is not set.
+    pub signal_means: Vec<f32>,
+
+    /// Energy-sharing statistics; present only when
+    /// `SimulationConfig::sharing` is enabled.
+    pub sharing: Option<SharingReport>,
+
+    /// Births paid for with energy; present only when
+    /// `SimulationConfig::birth_cost` is enabled.
+    pub births: Option<BirthReport>,
+
+    /// Number of birds flagged with each of the degenerate strategies;
+    /// present only when `SimulationConfig::behavior` is enabled.
+    pub behaviors: Option<BehaviorReport>,
+
+    /// Change in the number of birds caused by `Simulation::bottleneck()`,
+    /// if it happened at the end of this generation.
+    pub bottleneck: Option<BottleneckEvent>,
+
+    /// Whether the simulation got restarted right after this generation
+    /// (see: `AutoRestart`).
+    pub restarted: bool,
+
+    /// Mean relatedness of this generation's birds (see:
+    /// `Genealogy::mean_relatedness()`); present only when
+    /// `SimulationConfig::genealogy` is enabled.
+    pub mean_relatedness: Option<f32>,
+
+    /// Magnitude of this generation's brain weights; for the full
+    /// picture, see: `Simulation::weight_histogram()`.
+    pub weights: WeightStats,
+
+    /// What the crossover & mutation did while producing the next
+    /// generation.
+    pub diagnostics: ga::EvolveDiagnostics,
+
+    /// Per-habitat breakdown, in order of `Habitats::regions`; empty when
+    /// `SimulationConfig::habitats` is not set.
+    pub habitats: Vec<HabitatSummary>,
+
+    /// Per-arm breakdown; present only when `SimulationConfig::ab_test`
+    /// is enabled.
+    pub ab_test: Option<AbTestSummary>,
+
+    /// Sizes of the next generation's lineages, and how many have died out.
+    pub lineages: LineageReport,
+
+    /// Change in the number of foods; present only when
+    /// `SimulationConfig::auto_balance` is enabled.
+    pub food_balance: Option<FoodBalance>,
+
+    /// Change in the number of birds; present only when
+    /// `SimulationConfig::population_control` is enabled.
+    pub population: Option<PopulationReport>,
+
+    /// Reasons why this generation couldn't evolve "normally"; empty for
+    /// a healthy simulation.
+    pub degeneracies: Vec<Degeneracy>,
+
+    /// Selection differential of the previous generation (see:
+    /// `ga::EvolveDiagnostics::selection_differential`), paired with
+    /// `response` - the breeder's equation's `S` to its `R`; missing for
+    /// the first generation and the one right after a restart.
+    pub selection_differential: Option<ga::Float>,
+
+    /// Mean fitness of this generation minus the previous one's, i.e. how
+    /// much of `selection_differential` has been passed on; missing
+    /// whenever `selection_differential` is.
+    pub response: Option<ga::Float>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Degeneracy {
+    /// There's no food in the world, so there's nothing to score birds
+    /// by - everybody ends up with zero fitness.
+    NoFood,
+
+    /// There's just one bird, so it can only get crossed with itself -
+    /// evolution is driven purely by mutation.
+    SingleAnimal,
+
+    /// No bird has eaten anything, so selection picked parents uniformly
+    /// at random.
+    ZeroFitness,
+}
+
+impl Degeneracy {
+    pub(crate) fn detect(config: &SimulationConfig, stats: &ga::Statistics) -> Vec<Self> {
+        let mut degeneracies = Vec::new();
+
+        if config.n_foods == 0 {
+            degeneracies.push(Self::NoFood);
+        }
+
+        if config.n_animals == 1 {
+            degeneracies.push(Self::SingleAnimal);
+        }
+
+        if stats.max_fitness == 0.0 {
+            degeneracies.push(Self::ZeroFitness);
+        }
+
+        degeneracies
+    }
+}
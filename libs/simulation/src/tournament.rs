@@ -0,0 +1,200 @@
+use crate::*;
+
+/// Outcome of `tournament()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TournamentResult {
+    /// Seeds each champion got evaluated with, in the order of
+    /// `Standing::scores`.
+    pub seeds: Vec<u64>,
+
+    /// Compatible champions, from the best one; champions with equal
+    /// mean (and median) fitness keep the order they were given in.
+    pub standings: Vec<Standing>,
+
+    /// Champions whose chromosome doesn't fit the arena.
+    pub rejected: Vec<Rejected>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub mean_fitness: f32,
+    pub median_fitness: f32,
+
+    /// Fitness scored in each of the worlds, one per seed.
+    pub scores: Vec<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rejected {
+    pub name: String,
+    pub expected_len: usize,
+    pub actual_len: usize,
+}
+
+/// Lets champions (e.g. loaded from different checkpoints) compete with
+/// each other in a fair bake-off.
+///
+/// Each champion flies alone in one isolated world per seed (see:
+/// `evaluate_isolated()`), for `arena_config.generation_length` steps -
+/// since the world's layout depends only on the seed, all champions face
+/// exactly the same conditions.
+pub fn tournament(
+    champions: Vec<(String, ga::Chromosome)>,
+    arena_config: &SimulationConfig,
+    seeds: &[u64],
+) -> TournamentResult {
+    assert!(!seeds.is_empty(), "tournament requires at least one seed");
+
+    arena_config.validate();
+
+    let expected_len = Animal::chromosome_len(arena_config);
+    let mut standings = Vec::new();
+    let mut rejected = Vec::new();
+
+    for (name, chromosome) in champions {
+        if chromosome.len() != expected_len {
+            rejected.push(Rejected {
+                name,
+                expected_len,
+                actual_len: chromosome.len(),
+            });
+
+            continue;
+        }
+
+        let scores: Vec<_> = seeds
+            .iter()
+            .map(|&seed| {
+                evaluate_isolated(
+                    arena_config,
+                    chromosome.clone(),
+                    seed,
+                    arena_config.generation_length,
+                )
+            })
+            .collect();
+
+        standings.push(Standing {
+            name,
+            mean_fitness: scores.iter().sum::<f32>() / scores.len() as f32,
+            median_fitness: median(&scores),
+            scores,
+        });
+    }
+
+    // Stable, so that ties keep their original order
+    standings.sort_by(|a, b| {
+        b.mean_fitness
+            .total_cmp(&a.mean_fitness)
+            .then(b.median_fitness.total_cmp(&a.median_fitness))
+    });
+
+    TournamentResult {
+        seeds: seeds.to_vec(),
+        standings,
+        rejected,
+    }
+}
+
+fn median(scores: &[f32]) -> f32 {
+    let mut scores = scores.to_vec();
+    scores.sort_by(f32::total_cmp);
+
+    let mid = scores.len() / 2;
+
+    if scores.len().is_multiple_of(2) {
+        (scores[mid - 1] + scores[mid]) / 2.0
+    } else {
+        scores[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const SEEDS: [u64; 4] = [1, 2, 3, 4];
+
+    fn config() -> SimulationConfig {
+        SimulationConfig {
+            n_foods: 30,
+            generation_length: 300,
+            ..Default::default()
+        }
+    }
+
+    fn champions(config: &SimulationConfig) -> Vec<(String, ga::Chromosome)> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        (0..4)
+            .map(|idx| (format!("bird #{}", idx), Animal::random(config, &mut rng).as_chromosome()))
+            .collect()
+    }
+
+    #[test]
+    fn duplicates_tie_exactly() {
+        let config = config();
+        let (name, chromosome) = champions(&config).remove(0);
+
+        let result = tournament(
+            vec![(name.clone(), chromosome.clone()), (format!("{} (again)", name), chromosome)],
+            &config,
+            &SEEDS,
+        );
+
+        assert_eq!(result.standings[0].scores, result.standings[1].scores);
+        assert_eq!(result.standings[0].mean_fitness, result.standings[1].mean_fitness);
+        assert_eq!(result.standings[0].name, name);
+    }
+
+    #[test]
+    fn rankings_are_deterministic() {
+        let config = config();
+        let champions = champions(&config);
+
+        let a = tournament(champions.clone(), &config, &SEEDS);
+        let b = tournament(champions, &config, &SEEDS);
+
+        assert_eq!(a, b);
+
+        for pair in a.standings.windows(2) {
+            assert!(pair[0].mean_fitness >= pair[1].mean_fitness);
+        }
+    }
+
+    #[test]
+    fn incompatible_genomes_are_rejected_by_name() {
+        let config = config();
+        let mut champions = champions(&config);
+
+        let narrow = SimulationConfig {
+            eye_cells: 3,
+            ..config.clone()
+        };
+
+        let (_, chromosome) = self::champions(&narrow).remove(0);
+        champions.insert(1, ("narrow-eyed".to_string(), chromosome));
+
+        let result = tournament(champions, &config, &SEEDS);
+
+        assert_eq!(result.standings.len(), 4);
+
+        assert_eq!(
+            result.rejected,
+            vec![Rejected {
+                name: "narrow-eyed".to_string(),
+                expected_len: Animal::chromosome_len(&config),
+                actual_len: Animal::chromosome_len(&narrow),
+            }]
+        );
+    }
+
+    #[test]
+    fn median_of_even_and_odd_number_of_scores() {
+        assert_eq!(median(&[3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(median(&[4.0, 1.0, 2.0, 3.0]), 2.5);
+    }
+}
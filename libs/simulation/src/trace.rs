@@ -0,0 +1,39 @@
+//! `tracing` instrumentation that compiles away entirely (arguments
+//! included) unless the `tracing` feature is enabled.
+
+/// Enters a span at given level for the rest of the enclosing scope.
+macro_rules! trace_span {
+    ($level:ident, $($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $($arg)*).entered();
+    };
+}
+
+/// Emits an event at given level.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::event!(tracing::Level::$level, $($arg)*);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(not(feature = "tracing"))]
+    fn calls_compile_away_without_the_feature() {
+        // Neither of these identifiers exists, so this compiles only as
+        // long as the macros drop their arguments
+        trace_span!(TRACE, "nothing", field = does_not_exist);
+        trace_event!(DEBUG, field = does_not_exist_either, "nothing");
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn calls_reach_tracing_with_the_feature() {
+        let value = 1;
+
+        trace_span!(TRACE, "something", field = value);
+        trace_event!(DEBUG, field = value, "something");
+    }
+}
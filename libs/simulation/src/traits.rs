@@ -0,0 +1,49 @@
+use crate::*;
+
+/// Bird's evolvable properties other than its brain.
+///
+/// They're encoded at the end of the chromosome, right after the brain's
+/// genes - and only when the corresponding feature is enabled, so that a
+/// default simulation evolves nothing but brains.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Traits {
+    /// Raw "sharing" gene; present only when `SimulationConfig::sharing`
+    /// is enabled.
+    pub(crate) sharing: Option<ga::Float>,
+}
+
+impl Traits {
+    /// Number of genes traits take in a chromosome.
+    pub(crate) fn genes_len(config: &SimulationConfig) -> usize {
+        config.sharing.is_some() as usize
+    }
+
+    pub(crate) fn random(config: &SimulationConfig, rng: &mut dyn RngCore) -> Self {
+        Self {
+            sharing: config.sharing.map(|_| rng.gen_range(0.0..=1.0)),
+        }
+    }
+
+    /// Decodes traits out of genes returned from `.genes()`.
+    pub(crate) fn from_genes(config: &SimulationConfig, genes: &[ga::Float]) -> Self {
+        assert_eq!(genes.len(), Self::genes_len(config));
+
+        let mut genes = genes.iter().copied();
+
+        Self {
+            sharing: config.sharing.and_then(|_| genes.next()),
+        }
+    }
+
+    pub(crate) fn genes(&self) -> impl Iterator<Item = ga::Float> + '_ {
+        self.sharing.iter().copied()
+    }
+
+    /// How eager the bird is to share its energy:
+    ///
+    /// - 0.0 = keeps everything to itself,
+    /// - 1.0 = shares as much as `SharingConfig::rate` allows.
+    pub fn sharing(&self) -> f32 {
+        narrow(self.sharing.unwrap_or(0.0)).clamp(0.0, 1.0)
+    }
+}
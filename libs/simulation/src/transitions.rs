@@ -0,0 +1,368 @@
+//! Recording of `(vision, action, reward, next_vision, done)` tuples, e.g.
+//! to train an offline reinforcement learning baseline to compare the
+//! evolved birds with.
+//!
+//! # Layout
+//!
+//! `Simulation::export_transitions()` returns a little-endian blob:
+//!
+//! ```text
+//! magic       [u8; 4] = b"EVTR"
+//! version     u32     = 1
+//! input_dim   u32     (eye cells + extra inputs)
+//! output_dim  u32     (speed, rotation + extra outputs)
+//! rows        u32
+//!
+//! ... followed by `rows` rows of packed f32s:
+//!
+//! vision      [f32; input_dim]
+//! action      [f32; output_dim]
+//! reward      f32     (number of foods eaten as a result of the action)
+//! next_vision [f32; input_dim]
+//! done        f32     (1.0 for the last transition of a generation)
+//! ```
+
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+pub const TRANSITIONS_MAGIC: [u8; 4] = *b"EVTR";
+pub const TRANSITIONS_VERSION: u32 = 1;
+
+/// Records transitions of a `sample_rate` fraction of birds, keeping the
+/// most recent `capacity` of them; see: `Simulation::export_transitions()`.
+///
+/// Birds get sampled by their ids (for their whole lives), so recording
+/// never draws any random numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransitionConfig {
+    pub sample_rate: f32,
+    pub capacity: usize,
+}
+
+impl TransitionConfig {
+    pub(crate) fn try_validate(&self) -> Result<(), ConfigError> {
+        ensure!((0.0..=1.0).contains(&self.sample_rate));
+        ensure!(self.capacity > 0);
+
+        Ok(())
+    }
+
+    /// Whether transitions of given bird get recorded.
+    pub(crate) fn samples(&self, id: AnimalId) -> bool {
+        // SplitMix64's finalizer spreads consecutive ids evenly
+        let mut x = id.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^= x >> 31;
+
+        ((x >> 40) as f32 / (1 << 24) as f32) < self.sample_rate
+    }
+}
+
+impl Default for TransitionConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: 0.1,
+            capacity: 100_000,
+        }
+    }
+}
+
+/// What a sampled bird's brain has seen and done during the most recent
+/// step.
+#[derive(Clone, Debug)]
+pub(crate) struct BrainIo {
+    pub(crate) animal: usize,
+    pub(crate) inputs: Vec<f32>,
+    pub(crate) outputs: Vec<f32>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct TransitionRecorder {
+    capacity: usize,
+    input_dim: usize,
+    output_dim: usize,
+
+    /// Brain input & output of each sampled bird (by slot) from the
+    /// previous step, waiting for their reward.
+    pending: HashMap<usize, BrainIo>,
+
+    rows: VecDeque<Vec<f32>>,
+}
+
+impl TransitionRecorder {
+    pub(crate) fn new(config: &SimulationConfig, transitions: &TransitionConfig) -> Self {
+        Self {
+            capacity: transitions.capacity,
+            input_dim: config.eye_cells + config.extra_inputs_len(),
+            output_dim: 2 + config.extra_outputs_len(),
+            pending: HashMap::new(),
+            rows: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, row: Vec<f32>) {
+        if self.rows.len() == self.capacity {
+            self.rows.pop_front();
+        }
+
+        self.rows.push_back(row);
+    }
+}
+
+impl MemoryComponent for TransitionRecorder {
+    fn live_bytes(&self) -> usize {
+        // input, output, reward, next input, done
+        let row = 2 * self.input_dim + self.output_dim + 2;
+
+        self.rows.len() * row * std::mem::size_of::<f32>()
+    }
+
+    fn evict_oldest(&mut self, cap: usize) {
+        while self.live_bytes() > cap && self.rows.pop_front().is_some() {}
+    }
+}
+
+impl Simulation {
+    /// Turns what sampled birds' brains did during the most recent step
+    /// into transitions; `done` marks the generation's last step.
+    pub(crate) fn record_transitions(&mut self, done: bool) {
+        let Some(recorder) = &mut self.transitions else {
+            return;
+        };
+
+        for io in self.world.brain_io.drain(..) {
+            if let Some(previous) = recorder.pending.remove(&io.animal) {
+                let reward = self.world.eats.iter().filter(|eat| eat.animal == io.animal).count();
+
+                let row = previous
+                    .inputs
+                    .into_iter()
+                    .chain(previous.outputs)
+                    .chain([reward as f32])
+                    .chain(io.inputs.iter().copied())
+                    .chain([done as u8 as f32])
+                    .collect();
+
+                recorder.push(row);
+            }
+
+            if !done {
+                recorder.pending.insert(io.animal, io);
+            }
+        }
+
+        if done {
+            recorder.pending.clear();
+        }
+    }
+
+    /// Returns recorded transitions in the layout described in
+    /// `transitions`' docs; empty unless `SimulationConfig::transitions`
+    /// is enabled.
+    pub fn export_transitions(&self) -> Vec<u8> {
+        let Some(recorder) = &self.transitions else {
+            return Vec::new();
+        };
+
+        let mut bytes = TRANSITIONS_MAGIC.to_vec();
+
+        for value in [
+            TRANSITIONS_VERSION,
+            recorder.input_dim as u32,
+            recorder.output_dim as u32,
+            recorder.rows.len() as u32,
+        ] {
+            bytes.extend(value.to_le_bytes());
+        }
+
+        for value in recorder.rows.iter().flatten() {
+            bytes.extend(value.to_le_bytes());
+        }
+
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    struct Export {
+        input_dim: usize,
+        output_dim: usize,
+        rows: Vec<Vec<f32>>,
+    }
+
+    fn parse(bytes: &[u8]) -> Export {
+        assert_eq!(bytes[0..4], TRANSITIONS_MAGIC);
+
+        let header: Vec<_> = bytes[4..20]
+            .chunks(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()) as usize)
+            .collect();
+
+        assert_eq!(header[0], TRANSITIONS_VERSION as usize);
+
+        let (input_dim, output_dim, n_rows) = (header[1], header[2], header[3]);
+        let row_len = 2 * input_dim + output_dim + 2;
+
+        let values: Vec<_> = bytes[20..]
+            .chunks(4)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        assert_eq!(values.len(), n_rows * row_len);
+
+        Export {
+            input_dim,
+            output_dim,
+            rows: values.chunks(row_len).map(<[f32]>::to_vec).collect(),
+        }
+    }
+
+    fn config(transitions: TransitionConfig) -> SimulationConfig {
+        SimulationConfig {
+            n_animals: 5,
+            generation_length: 10,
+            transitions: Some(transitions),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn dims_match_topology() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            eye_cells: 4,
+            extra_inputs: vec![ExtraInput::HomeVector, ExtraInput::Scent],
+            hibernation: Some(Hibernation::default()),
+            ..config(TransitionConfig {
+                sample_rate: 1.0,
+                ..Default::default()
+            })
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+
+        sim.step(&mut rng);
+        sim.step(&mut rng);
+
+        let export = parse(&sim.export_transitions());
+
+        assert_eq!(export.input_dim, 4 + 2 + 1);
+        assert_eq!(export.output_dim, 3);
+        assert_eq!(export.rows.len(), 5);
+    }
+
+    #[test]
+    fn rows_respect_sample_rate_and_capacity() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(
+            config(TransitionConfig {
+                sample_rate: 1.0,
+                ..Default::default()
+            }),
+            &mut rng,
+        );
+
+        sim.train(&mut rng);
+
+        let rows = parse(&sim.export_transitions()).rows;
+
+        // Each bird gets `generation_length` transitions, the last of which
+        // is done
+        assert_eq!(rows.len(), 5 * 10);
+        assert_eq!(rows.iter().filter(|row| *row.last().unwrap() == 1.0).count(), 5);
+
+        let mut sim = Simulation::from_config(
+            config(TransitionConfig {
+                sample_rate: 1.0,
+                capacity: 12,
+            }),
+            &mut rng,
+        );
+
+        sim.train(&mut rng);
+        assert_eq!(parse(&sim.export_transitions()).rows.len(), 12);
+
+        let mut sim = Simulation::from_config(
+            SimulationConfig {
+                n_animals: 200,
+                ..config(TransitionConfig {
+                    sample_rate: 0.25,
+                    ..Default::default()
+                })
+            },
+            &mut rng,
+        );
+
+        sim.train(&mut rng);
+
+        let sampled = parse(&sim.export_transitions()).rows.len() / 10;
+        assert!((35..=65).contains(&sampled), "sampled = {}", sampled);
+    }
+
+    #[test]
+    fn eat_shows_up_as_reward() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(
+            SimulationConfig {
+                n_animals: 1,
+                n_foods: 1,
+                ..config(TransitionConfig {
+                    sample_rate: 1.0,
+                    ..Default::default()
+                })
+            },
+            &mut rng,
+        );
+
+        sim.step(&mut rng);
+        sim.step(&mut rng);
+
+        // Gets eaten at the beginning of the next step, so it's the second
+        // action that gets rewarded
+        sim.world.foods[0].position = sim.world.animals[0].position;
+
+        for _ in 0..3 {
+            sim.step(&mut rng);
+        }
+
+        let export = parse(&sim.export_transitions());
+        let rewards: Vec<_> = export.rows.iter().map(|row| row[export.input_dim + export.output_dim]).collect();
+
+        assert_eq!(rewards, vec![0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn recording_draws_no_random_numbers() {
+        let run = |transitions| {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut sim = Simulation::from_config(
+                SimulationConfig {
+                    transitions,
+                    ..config(Default::default())
+                },
+                &mut rng,
+            );
+
+            sim.train_many(&mut rng, 3);
+            sim
+        };
+
+        let recorded = run(Some(TransitionConfig {
+            sample_rate: 1.0,
+            capacity: 10,
+        }));
+
+        assert_eq!(recorded.world.state_diff(&run(None).world), None);
+        assert!(run(None).export_transitions().is_empty());
+    }
+}
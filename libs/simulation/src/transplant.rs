@@ -0,0 +1,282 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Everything that determines how birds see the world; see:
+/// `Simulation::reconfigure_eye()`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EyeConfig {
+    pub fov_range: f32,
+    pub fov_angle: f32,
+    pub cells: usize,
+}
+
+impl EyeConfig {
+    pub fn of(config: &SimulationConfig) -> Self {
+        Self {
+            fov_range: config.fov_range,
+            fov_angle: config.fov_angle,
+            cells: config.eye_cells,
+        }
+    }
+
+    fn apply(&self, config: &mut SimulationConfig) {
+        config.fov_range = self.fov_range;
+        config.fov_angle = self.fov_angle;
+        config.eye_cells = self.cells;
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct EyeTransplant {
+    eye: EyeConfig,
+    strategy: TransplantStrategy,
+}
+
+impl Simulation {
+    /// Schedules a new eye: at the next generation boundary, the evolved
+    /// population (along with birds bred by hand and the hall of fame)
+    /// gets its brains transplanted onto it according to `strategy`, so
+    /// that e.g. the number of eye cells can be increased without starting
+    /// over.
+    ///
+    /// Requires the brain's input layer to stay the same throughout the
+    /// recording, so it can't be combined with `SimulationConfig::transitions`.
+    pub fn reconfigure_eye(&mut self, eye: EyeConfig, strategy: TransplantStrategy) {
+        assert!(
+            self.config.transitions.is_none(),
+            "eye can't be reconfigured while recording transitions",
+        );
+
+        let mut config = self.config.clone();
+        eye.apply(&mut config);
+        config.validate();
+
+        self.eye_transplant = Some(EyeTransplant { eye, strategy });
+    }
+
+    /// Carries `population` over to the scheduled eye (if any), which takes
+    /// effect from now on.
+    pub(crate) fn transplant_eyes(&mut self, rng: &mut dyn RngCore, population: &mut [AnimalIndividual]) {
+        let Some(EyeTransplant { eye, strategy }) = self.eye_transplant.take() else {
+            return;
+        };
+
+        let from = self.config.clone();
+        eye.apply(&mut self.config);
+
+        let to = &self.config;
+        let mut transplant = |chromosome: &ga::Chromosome| transplant_chromosome(rng, &from, to, strategy, chromosome);
+
+        for individual in population.iter_mut() {
+            *individual = AnimalIndividual::new(transplant(individual.chromosome()), individual.fitness());
+        }
+
+        for offspring in &mut self.offspring {
+            offspring.chromosome = transplant(&offspring.chromosome);
+        }
+
+        for champion in self.hall_of_fame.champions_mut() {
+            champion.genes = transplant(&champion.chromosome()).iter().copied().collect();
+            champion.genome = GenomeSpec::of(to);
+        }
+
+        // Mutation scales follow the lengths of the brain's layers
+        self.ga = genetic_algorithm(to, &to.ga);
+
+        if let (Some(state), Some(ab_test)) = (&mut self.ab_test, &to.ab_test) {
+            state.ga = genetic_algorithm(to, &ab_test.b);
+        }
+
+        self.gene_ages = gene_ages::fresh_gene_ages(to);
+    }
+}
+
+/// Transplants the brain encoded in `chromosome`, keeping its traits.
+fn transplant_chromosome(
+    rng: &mut dyn RngCore,
+    from: &SimulationConfig,
+    to: &SimulationConfig,
+    strategy: TransplantStrategy,
+    chromosome: &ga::Chromosome,
+) -> ga::Chromosome {
+    let mut genes: Vec<_> = chromosome.iter().copied().collect();
+    let traits = genes.split_off(genes.len() - Traits::genes_len(from));
+
+    let brain = Brain::from_chromosome_with_extras(
+        genes.into_iter().collect(),
+        &from.eye(),
+        from.extra_inputs_len(),
+        from.extra_outputs_len(),
+    )
+    .expect("chromosome should match the current eye");
+
+    brain
+        .transplant_with_extras(rng, from, to, strategy)
+        .as_chromosome()
+        .iter()
+        .copied()
+        .chain(traits)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::f32::consts::PI;
+
+    /// Something smooth enough to survive resampling, e.g. a flock of food
+    /// spread in front of the bird.
+    fn vision(cells: usize) -> Vec<f32> {
+        (0..cells)
+            .map(|cell| 0.5 + 0.5 * (PI * (cell as f32 + 0.5) / cells as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    fn resampled_brain_responds_to_resampled_vision() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (from, to) = (Eye::new(0.25, PI, 9), Eye::new(0.25, PI, 18));
+
+        for _ in 0..20 {
+            let brain = Brain::random(&mut rng, &from);
+            let transplanted = brain.transplant(&mut rng, &from, &to, TransplantStrategy::Resample);
+
+            let expected = brain.propagate(vision(9));
+            let actual = transplanted.propagate(Eye::resample_vision(&vision(9), 18));
+
+            for (actual, expected) in actual.iter().zip(&expected) {
+                assert!((actual - expected).abs() < 0.1 * expected.abs().max(1.0), "{} vs {}", actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn resample_vision_keeps_total_activation() {
+        let vision = vision(9);
+
+        for cells in [3, 9, 13, 27] {
+            let resampled = Eye::resample_vision(&vision, cells);
+
+            assert_eq!(resampled.len(), cells);
+            approx::assert_relative_eq!(resampled.iter().sum::<f32>(), vision.iter().sum::<f32>(), epsilon = 0.2);
+        }
+    }
+
+    #[test]
+    fn reinitialized_brain_keeps_deeper_layers() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (from, to) = (Eye::new(0.25, PI, 3), Eye::new(0.25, PI, 5));
+
+        let brain = Brain::random(&mut rng, &from);
+        let transplanted = brain.transplant(&mut rng, &from, &to, TransplantStrategy::Reinitialize);
+
+        let old = brain.as_chromosome();
+        let new = transplanted.as_chromosome();
+
+        assert_eq!(new.len(), Brain::chromosome_len(&to));
+
+        let [old_hidden, _] = Brain::segment_lengths(&from)[..] else { unreachable!() };
+        let [new_hidden, _] = Brain::segment_lengths(&to)[..] else { unreachable!() };
+
+        let old_outputs: Vec<_> = old.iter().skip(old_hidden).collect();
+        let new_outputs: Vec<_> = new.iter().skip(new_hidden).collect();
+
+        // Each output neuron: its bias, weights of the 6 old hidden neurons
+        // and then weights of the 4 new (dormant) ones
+        for (old, new) in old_outputs.chunks(7).zip(new_outputs.chunks(11)) {
+            assert!(old.iter().zip(&new[..7]).all(|(a, b)| a.to_bits() == b.to_bits()));
+            assert!(new[7..].iter().all(|&&weight| weight == 0.0));
+        }
+
+        // ... while the hidden layer is brand new
+        assert!(!old.iter().take(old_hidden).eq(new.iter().take(old_hidden)));
+    }
+
+    fn config() -> SimulationConfig {
+        SimulationConfig {
+            n_animals: 6,
+            generation_length: 10,
+            extra_inputs: vec![ExtraInput::Scent, ExtraInput::Birds],
+            energy: Some(Default::default()),
+            sharing: Some(Default::default()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reconfiguration_takes_effect_at_generation_boundary() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(), &mut rng);
+
+        sim.train(&mut rng);
+
+        let eye = EyeConfig {
+            cells: 13,
+            ..EyeConfig::of(sim.config())
+        };
+
+        sim.reconfigure_eye(eye, TransplantStrategy::Resample);
+        sim.step(&mut rng);
+
+        assert_eq!(sim.config().eye_cells, CELLS);
+
+        sim.train(&mut rng);
+
+        let expected = Animal::chromosome_len(sim.config());
+
+        assert_eq!(EyeConfig::of(sim.config()), eye);
+        assert_ne!(expected, Animal::chromosome_len(&config()));
+
+        for animal in sim.world().animal() {
+            assert_eq!(animal.as_chromosome().len(), expected);
+        }
+
+        for champion in sim.hall_of_fame.champions() {
+            assert_eq!(champion.genome, GenomeSpec::of(sim.config()));
+            assert_eq!(champion.genes.len(), expected);
+        }
+
+        // Everything keeps working with the new eye, including what's
+        // carried over from before the reconfiguration
+        sim.train(&mut rng);
+        sim.restart_from_best(&mut rng);
+        sim.train(&mut rng);
+    }
+
+    #[test]
+    fn eye_can_shrink() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::from_config(config(), &mut rng);
+
+        sim.reconfigure_eye(
+            EyeConfig {
+                cells: 4,
+                ..EyeConfig::of(sim.config())
+            },
+            TransplantStrategy::Reinitialize,
+        );
+
+        sim.train(&mut rng);
+        sim.train(&mut rng);
+
+        assert_eq!(sim.config().eye_cells, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "eye can't be reconfigured while recording transitions")]
+    fn rejects_recorded_transitions() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = Simulation::from_config(
+            SimulationConfig {
+                transitions: Some(TransitionConfig::default()),
+                ..config()
+            },
+            &mut rng,
+        );
+
+        sim.reconfigure_eye(EyeConfig::of(sim.config()), TransplantStrategy::Resample);
+    }
+}
@@ -0,0 +1,68 @@
+use crate::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Picks the best `GaParams` out of `candidates` through
+/// `ga::tuning::successive_halving()`, where a budget of `n` means
+/// training a fresh simulation for `n` generations.
+///
+/// Every run scores its birds in isolation (see: `Evaluation::Isolated`,
+/// with `generation_length` steps per bird) and starts from the same
+/// `seed`, so that candidates get compared under the same conditions;
+/// the score is the average fitness of the run's last generation.
+pub fn tune_ga_params(
+    config: &SimulationConfig,
+    candidates: Vec<GaParams>,
+    rounds: usize,
+    keep_fraction: f32,
+    seed: u64,
+) -> ga::tuning::HalvingResult<GaParams> {
+    let eval = |params: &GaParams, generations: usize| {
+        let config = SimulationConfig {
+            ga: params.clone(),
+            evaluation: Evaluation::Isolated {
+                steps: config.generation_length,
+                seed,
+            },
+            ..config.clone()
+        };
+
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut sim = Simulation::from_config(config, &mut rng);
+
+        sim.train_many(&mut rng, generations)
+            .last()
+            .map(|summary| narrow(summary.stats.avg_fitness))
+            .expect("budget should be at least one generation")
+    };
+
+    ga::tuning::successive_halving(candidates, eval, rounds, keep_fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tunes_ga_params() {
+        let config = SimulationConfig {
+            n_animals: 6,
+            n_foods: 20,
+            generation_length: 50,
+            ..Default::default()
+        };
+
+        let candidates: Vec<_> = [0.0, 0.01, 0.1, 0.5]
+            .into_iter()
+            .map(|mutation_chance| GaParams {
+                mutation_chance,
+                ..Default::default()
+            })
+            .collect();
+
+        let result = tune_ga_params(&config, candidates, 2, 0.5, 42);
+
+        assert_eq!(result.survivors.len(), 2);
+        assert_eq!(result.budget_spent, 4 + 2 * 2);
+    }
+}
@@ -0,0 +1,16 @@
+//! Fallible counterpart of `assert!()` for validating configs.
+
+/// Returns `ConfigError::Invalid` from the enclosing function unless the
+/// condition holds; the message defaults to the same one `assert!()` uses.
+macro_rules! ensure {
+    ($cond:expr $(,)?) => {
+        ensure!($cond, concat!("assertion failed: ", stringify!($cond)))
+    };
+    ($cond:expr, $msg:expr $(,)?) => {
+        let holds: bool = $cond;
+
+        if !holds {
+            return Err(ConfigError::Invalid($msg.into()));
+        }
+    };
+}
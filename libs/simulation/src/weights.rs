@@ -0,0 +1,145 @@
+use crate::*;
+use std::ops::Range;
+
+/// Magnitude of the brains' weights, across the entire population.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightStats {
+    pub mean_abs: f32,
+    pub max_abs: f32,
+}
+
+impl WeightStats {
+    pub(crate) fn of(animals: &[Animal]) -> Self {
+        let mut count = 0;
+        let mut sum_abs = 0.0;
+        let mut max_abs = 0.0f32;
+
+        for weight in animals.iter().flat_map(|animal| animal.brain.nn.weights()).map(narrow) {
+            count += 1;
+            sum_abs += weight.abs();
+            max_abs = max_abs.max(weight.abs());
+        }
+
+        Self {
+            mean_abs: if count == 0 { 0.0 } else { sum_abs / count as f32 },
+            max_abs,
+        }
+    }
+}
+
+impl Simulation {
+    /// Histogram of all brains' weights, split into `bins` equally wide
+    /// bins spanning `range`.
+    ///
+    /// The returned vector has `bins + 2` elements - the first and the
+    /// last one count weights below and above (or at the end of) `range`,
+    /// so that exploding weights don't go unnoticed; NaNs land in the
+    /// last one, too.
+    pub fn weight_histogram(&self, bins: usize, range: Range<f32>) -> Vec<usize> {
+        assert!(bins > 0);
+        assert!(range.start < range.end);
+
+        let width = (range.end - range.start) / bins as f32;
+        let mut histogram = vec![0; bins + 2];
+
+        for weight in self
+            .world
+            .animals
+            .iter()
+            .flat_map(|animal| animal.brain.nn.weights())
+            .map(narrow)
+        {
+            let bucket = if weight < range.start {
+                0
+            } else if weight < range.end {
+                // Rounding might push weights right next to the end into
+                // a non-existent bin
+                1 + (((weight - range.start) / width) as usize).min(bins - 1)
+            } else {
+                bins + 1
+            };
+
+            histogram[bucket] += 1;
+        }
+
+        histogram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn sim() -> Simulation {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 5,
+            generation_length: 50,
+            ..Default::default()
+        };
+
+        Simulation::from_config(config, &mut rng)
+    }
+
+    fn weights(sim: &Simulation) -> Vec<f32> {
+        sim.world()
+            .animals
+            .iter()
+            .flat_map(|animal| animal.brain.nn.weights())
+            .map(narrow)
+            .collect()
+    }
+
+    #[test]
+    fn counts_every_weight() {
+        let sim = sim();
+        let histogram = sim.weight_histogram(10, -1.0..1.0);
+
+        assert_eq!(histogram.len(), 12);
+        assert_eq!(histogram.iter().sum::<usize>(), weights(&sim).len());
+    }
+
+    #[test]
+    fn overflow_buckets_catch_extreme_weights() {
+        let mut sim = sim();
+        let len = Animal::chromosome_len(&sim.config);
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut genes = vec![0.0; len];
+        genes[0] = -100.0;
+        genes[1] = 100.0;
+        genes[2] = 0.5;
+
+        sim.world.animals = vec![Animal::from_chromosome(
+            &sim.config,
+            genes.into_iter().collect(),
+            &mut rng,
+        )];
+
+        let histogram = sim.weight_histogram(2, -1.0..1.0);
+        let weights = len - Traits::genes_len(&sim.config);
+
+        // Zeros go into the upper bin, along with 0.5
+        assert_eq!(histogram, vec![1, 0, weights - 2, 1]);
+    }
+
+    #[test]
+    fn summary_matches_brute_force() {
+        let mut sim = sim();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        sim.train(&mut rng);
+
+        let weights = weights(&sim);
+        let summary = sim.train(&mut rng);
+
+        let mean_abs = weights.iter().map(|w| w.abs()).sum::<f32>() / weights.len() as f32;
+        let max_abs = weights.iter().map(|w| w.abs()).fold(0.0, f32::max);
+
+        approx::assert_relative_eq!(summary.weights.mean_abs, mean_abs);
+        assert_eq!(summary.weights.max_abs, max_abs);
+    }
+}
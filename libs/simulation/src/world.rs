@@ -4,18 +4,83 @@ use crate::*;
 pub struct World{
     pub(crate) animals: Vec<Animal>,
     pub(crate) foods: Vec<Food>,
+
+    /// Foods eaten during the most recent step.
+    pub(crate) eats: Vec<EatEvent>,
+
+    /// Energy shared between birds during the current generation.
+    pub(crate) energy_transferred: f32,
+
+    /// Id the next bird born in this world is going to get.
+    pub(crate) next_id: u64,
+
+    /// Number of crossings into each habitat during the current
+    /// generation; empty unless `SimulationConfig::habitats` is enabled.
+    pub(crate) crossings: Vec<usize>,
+
+    /// What sampled brains did during the most recent step; empty unless
+    /// `SimulationConfig::transitions` is enabled.
+    pub(crate) brain_io: Vec<BrainIo>,
+}
+
+/// Bird #`animal` has eaten food #`food`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EatEvent {
+    pub animal: usize,
+    pub food: usize,
+
+    /// How deep the bird and the food overlapped, that is:
+    /// `bird_size + food_size - distance`.
+    pub overlap: f32,
 }
 impl World{
     pub fn random(rng: &mut dyn RngCore) -> Self{
-        let animals = (0..40)
-            .map(|_| Animal::random(rng))
+        Self::from_config(&SimulationConfig::default(), rng)
+    }
+
+    pub fn from_config(config: &SimulationConfig, rng: &mut dyn RngCore) -> Self{
+        let animals = (0..config.n_animals)
+            .map(|_| Animal::random(config, rng))
             .collect();
 
-        let foods = (0..60)
+        let foods = (0..config.n_foods)
             .map(|_| Food::random(rng))
             .collect();
 
-        Self { animals, foods }
+        let mut world = Self::new(animals, foods);
+        world.settle_habitats(config, rng);
+        world.assign_arms(config);
+        world
+    }
+
+    pub(crate) fn new(animals: Vec<Animal>, foods: Vec<Food>) -> Self {
+        let mut world = Self {
+            animals,
+            foods,
+            eats: Vec::new(),
+            energy_transferred: 0.0,
+            next_id: 0,
+            crossings: Vec::new(),
+            brain_io: Vec::new(),
+        };
+
+        world.assign_ids();
+        world
+    }
+
+    /// Gives fresh ids to all birds, e.g. after they've been evolved; each
+    /// bird founds its own lineage, unless it later inherits one.
+    pub(crate) fn assign_ids(&mut self) {
+        for idx in 0..self.animals.len() {
+            self.animals[idx].id = self.next_id();
+            self.animals[idx].lineage = LineageId::founded_by(self.animals[idx].id);
+        }
+    }
+
+    pub(crate) fn next_id(&mut self) -> AnimalId {
+        let id = AnimalId(self.next_id);
+        self.next_id += 1;
+        id
     }
 
     // Getter functions :
@@ -25,4 +90,205 @@ impl World{
     pub fn food(&self) -> &[Food]{
         &self.foods
     }
-}
\ No newline at end of file
+    pub fn eats(&self) -> &[EatEvent]{
+        &self.eats
+    }
+
+    /// Simulates a single step of the world - without evolving birds,
+    /// which is `Simulation`'s job; `step` counts from the beginning of
+    /// the generation.
+    pub(crate) fn step(&mut self, config: &SimulationConfig, rng: &mut dyn RngCore, step: usize) {
+        self.process_collisions(config, rng);
+        self.process_brains(config, step);
+        self.process_movements(config);
+        self.process_energy(config);
+        self.process_nursery();
+    }
+
+    fn process_collisions(&mut self, config: &SimulationConfig, rng: &mut dyn RngCore) {
+        trace_span!(TRACE, "collisions");
+
+        self.eats.clear();
+
+        let reach = config.bird_size + config.food_size;
+
+        for (animal_idx, animal) in self.animals.iter_mut().enumerate(){
+            for (food_idx, food) in self.foods.iter_mut().enumerate(){
+                let distance = na::distance(&animal.position, &food.position);
+                if distance <= reach{
+                    self.eats.push(EatEvent {
+                        animal: animal_idx,
+                        food: food_idx,
+                        overlap: reach - distance,
+                    });
+
+                    food.position = rng.gen();
+                    animal.satiation += 1; 
+
+                    if animal.nursery_steps == 0 {
+                        animal.scored_satiation += 1;
+                    }
+
+                    trace_event!(DEBUG, animal = animal.id.0, food = food_idx, "eat");
+
+                    if let Some(energy) = config.energy {
+                        animal.energy += energy.per_food;
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn process_movements(&mut self, config: &SimulationConfig) {
+        trace_span!(TRACE, "movements");
+
+        for (slot, animal) in self.animals.iter_mut().enumerate() {
+            if animal.hibernating {
+                continue;
+            }
+
+            let previous = animal.position;
+            let movement = animal.rotation * na::Vector2::new(0.0, animal.speed);
+
+            animal.position += movement;
+            animal.travelled += movement;
+            animal.distance += animal.speed;
+            animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
+            animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+
+            if let Some(habitats) = &config.habitats {
+                let home = habitats.habitat_of(slot, config.n_animals);
+
+                if let Some(habitat) = animal.confine(habitats, home, previous, movement) {
+                    if let Some(crossings) = self.crossings.get_mut(habitat) {
+                        *crossings += 1;
+                    }
+                }
+            }
+
+            if let Some(behavior) = &config.behavior {
+                animal.behavior.observe(behavior, animal.position, animal.rotation, movement);
+            }
+        }
+    }
+
+    fn process_brains(&mut self, config: &SimulationConfig, step: usize){
+        trace_span!(TRACE, "brains");
+
+        self.brain_io.clear();
+
+        // Everybody sees where the others were before anybody has moved
+        let neighbors = if config.extra_inputs.contains(&ExtraInput::Birds) {
+            Neighbor::all(config, &self.animals)
+        } else {
+            Vec::new()
+        };
+
+        for (slot, animal) in self.animals.iter_mut().enumerate() {
+            let others: Vec<_> = neighbors
+                .iter()
+                .enumerate()
+                .filter(|&(other, _)| other != slot)
+                .map(|(_, neighbor)| *neighbor)
+                .collect();
+
+            let vision = animal.vision(config, &self.foods, step);
+            let inputs: Vec<_> = vision
+                .into_iter()
+                .chain(config.extra_inputs.iter().flat_map(|input| input.read(config, animal, &self.foods, &others)))
+                .collect();
+
+            let sampled = config
+                .transitions
+                .is_some_and(|transitions| transitions.samples(animal.id));
+
+            let sampled_inputs = sampled.then(|| inputs.clone());
+            let response = animal.brain.propagate(inputs);
+
+            if let Some(inputs) = sampled_inputs {
+                self.brain_io.push(BrainIo {
+                    animal: slot,
+                    inputs,
+                    outputs: response.clone(),
+                });
+            }
+
+            if animal.hibernate(config, &response) {
+                continue;
+            }
+            // ---
+            // | Limits number to given range.
+            // -------------------- v---v
+            let speed = response[0].clamp(-config.speed_accel, config.speed_accel);
+
+            // Our speed & rotation here are *relative* - that is: when
+            // they are equal to zero, what the brain says is "keep
+            // flying as you are now", not "stop flying".
+            //
+            // Both values being relative is crucial, because our bird's
+            // brain doesn't know its own speed and rotation*, meaning
+            // that it fundamentally cannot return absolute values.
+            //
+            // * they'd have to be provided as separate inputs to the
+            //   neural network, which would make the evolution process
+            //   waaay longer, if even possible.
+
+            animal.speed = (animal.speed + speed).clamp(config.speed_min, config.speed_max);
+            animal.turn(config, response[1]);
+
+            // (btw, there is no need for ROTATION_MIN or ROTATION_MAX,
+            // because rotation automatically wraps from PI back to -PI -
+            // we've already witnessed that when we were testing eyes,
+            // inside `fn rotations { ... }`.)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// A bird at (0.5, 0.5) next to a food that's 0.012 to its right
+    fn near_miss(config: &SimulationConfig) -> World {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(config, &mut rng);
+        animal.position = na::Point2::new(0.5, 0.5);
+
+        let food = Food {
+            position: na::Point2::new(0.512, 0.5),
+        };
+
+        World::new(vec![animal], vec![food])
+    }
+
+    fn eats(food_size: f32) -> Vec<EatEvent> {
+        let config = SimulationConfig {
+            bird_size: 0.005,
+            food_size,
+            ..Default::default()
+        };
+
+        let mut world = near_miss(&config);
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        world.process_collisions(&config, &mut rng);
+
+        world.eats
+    }
+
+    #[test]
+    fn small_food_is_missed() {
+        assert_eq!(eats(0.005), vec![]);
+    }
+
+    #[test]
+    fn large_food_is_eaten() {
+        let eats = eats(0.01);
+
+        assert_eq!(eats.len(), 1);
+        assert_eq!((eats[0].animal, eats[0].food), (0, 0));
+        assert_relative_eq!(eats[0].overlap, 0.005 + 0.01 - 0.012, epsilon = 1e-6);
+    }
+}
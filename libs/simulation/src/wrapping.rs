@@ -0,0 +1,136 @@
+use crate::*;
+
+/// Ghost copies of entities that stick out over the map's edges - the map
+/// wraps around (see: `World::process_movements()`), so e.g. a bird that's
+/// flying out through the left edge should be partially visible on the
+/// right one, too.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WrappedEntities {
+    pub animals: Vec<Ghost>,
+    pub foods: Vec<Ghost>,
+}
+
+/// Where to draw a copy of `index`-th entity (of its kind), outside of the
+/// map.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ghost {
+    pub index: usize,
+    pub position: na::Point2<f32>,
+}
+
+impl Simulation {
+    /// Returns ghosts of birds within `bird_size` and of foods within
+    /// `food_size` of an edge - each of them gets copied over the opposite
+    /// edge (or both opposite edges and the opposite corner, when it's near
+    /// a corner), i.e. offset by ±1 on the axes it's near an edge on.
+    pub fn wrapped_render_entities(&self) -> WrappedEntities {
+        let animals = self.world.animals.iter().map(|animal| animal.position);
+        let foods = self.world.foods.iter().map(|food| food.position);
+
+        WrappedEntities {
+            animals: ghosts(animals, self.config.bird_size),
+            foods: ghosts(foods, self.config.food_size),
+        }
+    }
+}
+
+fn ghosts(positions: impl Iterator<Item = na::Point2<f32>>, size: f32) -> Vec<Ghost> {
+    // Which way to move a coordinate to get it over the opposite edge, if
+    // it's near an edge at all
+    let offset = |coord: f32| {
+        if coord <= size {
+            Some(1.0)
+        } else if coord >= 1.0 - size {
+            Some(-1.0)
+        } else {
+            None
+        }
+    };
+
+    positions
+        .enumerate()
+        .flat_map(|(index, position)| {
+            let (dx, dy) = (offset(position.x), offset(position.y));
+
+            let offsets = [
+                dx.map(|dx| (dx, 0.0)),
+                dy.map(|dy| (0.0, dy)),
+                dx.zip(dy),
+            ];
+
+            offsets.into_iter().flatten().map(move |(dx, dy)| Ghost {
+                index,
+                position: position + na::Vector2::new(dx, dy),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Simulation with a single bird at `position`, its food far from the
+    /// edges.
+    fn sim(position: na::Point2<f32>) -> Simulation {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = SimulationConfig {
+            n_animals: 1,
+            n_foods: 1,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::from_config(config, &mut rng);
+        sim.world.animals[0].position = position;
+        sim.world.foods[0].position = na::Point2::new(0.5, 0.5);
+        sim
+    }
+
+    fn assert_near(actual: na::Point2<f32>, expected: na::Point2<f32>) {
+        assert!(na::distance(&actual, &expected) < 1e-6, "{} vs {}", actual, expected);
+    }
+
+    #[test]
+    fn entity_near_edge_gets_one_ghost() {
+        let wrapped = sim(na::Point2::new(0.005, 0.5)).wrapped_render_entities();
+
+        assert_eq!(wrapped.animals.len(), 1);
+        assert_eq!(wrapped.animals[0].index, 0);
+        assert_near(wrapped.animals[0].position, na::Point2::new(1.005, 0.5));
+        assert!(wrapped.foods.is_empty());
+    }
+
+    #[test]
+    fn entity_in_corner_gets_three_ghosts() {
+        let wrapped = sim(na::Point2::new(0.002, 0.997)).wrapped_render_entities();
+        let positions: Vec<_> = wrapped.animals.iter().map(|ghost| ghost.position).collect();
+
+        assert_eq!(positions.len(), 3);
+        assert_near(positions[0], na::Point2::new(1.002, 0.997));
+        assert_near(positions[1], na::Point2::new(0.002, -0.003));
+        assert_near(positions[2], na::Point2::new(1.002, -0.003));
+    }
+
+    #[test]
+    fn entities_away_from_edges_get_no_ghosts() {
+        let wrapped = sim(na::Point2::new(0.3, 0.8)).wrapped_render_entities();
+
+        assert_eq!(wrapped, WrappedEntities::default());
+    }
+
+    #[test]
+    fn foods_get_ghosts_within_their_own_size() {
+        let mut sim = sim(na::Point2::new(0.5, 0.5));
+        sim.config.food_size = 0.02;
+        sim.world.foods[0].position = na::Point2::new(0.5, 0.985);
+
+        let wrapped = sim.wrapped_render_entities();
+
+        assert!(wrapped.animals.is_empty());
+        assert_eq!(wrapped.foods.len(), 1);
+        assert_near(wrapped.foods[0].position, na::Point2::new(0.5, -0.015));
+    }
+}
@@ -0,0 +1,57 @@
+//! Exercises the simulation the way an external tool (e.g. a brain
+//! inspector) would: without going through a full `Simulation`.
+
+use lib_genetic_algorithm::{Chromosome, Float};
+use lib_simulation::{Brain, BrainError, Eye};
+use std::f32::consts::PI;
+
+fn eye() -> Eye {
+    Eye::new(0.25, PI, 3)
+}
+
+#[test]
+fn brain_round_trips_through_chromosome() {
+    let eye = eye();
+    let genes: Vec<Float> = (0..Brain::chromosome_len(&eye))
+        .map(|n| n as Float / 100.0)
+        .collect();
+
+    let brain = Brain::from_chromosome(genes.iter().copied().collect(), &eye).unwrap();
+    let actual: Vec<Float> = brain.as_chromosome().into_iter().collect();
+
+    assert_eq!(actual, genes);
+}
+
+#[test]
+fn brain_responds_to_hand_made_vision() {
+    let eye = eye();
+
+    // With all weights & biases equal to 1.0 and ReLU activations, each
+    // hidden neuron outputs `1 + sum(vision)` and each output neuron
+    // outputs `1 + hidden_neurons * (1 + sum(vision))`.
+    let chromosome: Chromosome = vec![1.0; Brain::chromosome_len(&eye)]
+        .into_iter()
+        .collect();
+
+    let brain = Brain::from_chromosome(chromosome, &eye).unwrap();
+    let response = brain.propagate(vec![0.0, 0.5, 0.0]);
+
+    assert_eq!(response, vec![1.0 + 6.0 * 1.5, 1.0 + 6.0 * 1.5]);
+}
+
+#[test]
+fn brain_rejects_chromosome_of_wrong_length() {
+    let eye = eye();
+    let expected = Brain::chromosome_len(&eye);
+    let chromosome: Chromosome = vec![0.0; expected - 1].into_iter().collect();
+
+    let err = Brain::from_chromosome(chromosome, &eye).unwrap_err();
+
+    assert_eq!(
+        err,
+        BrainError::ChromosomeLengthMismatch {
+            expected,
+            actual: expected - 1,
+        }
+    );
+}
@@ -0,0 +1,66 @@
+//! Runs `determinism::verify()` over a couple of configs; whenever a
+//! feature starts drawing random numbers inconsistently, one of these is
+//! going to point at the first step (and field) that differs.
+
+use lib_simulation::determinism;
+use lib_simulation::*;
+
+const SEED: u64 = 0xB1AD;
+
+/// Spans a few generation boundaries.
+const STEPS: usize = 120;
+
+fn config() -> SimulationConfig {
+    SimulationConfig {
+        n_animals: 12,
+        n_foods: 30,
+        generation_length: 40,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn default_config() {
+    determinism::verify(&config(), SEED, STEPS).unwrap();
+}
+
+#[test]
+fn energy_config() {
+    let config = SimulationConfig {
+        energy: Some(EnergyConfig::default()),
+        sharing: Some(SharingConfig::default()),
+        hibernation: Some(Hibernation::default()),
+        birth_cost: Some(BirthCost::default()),
+        ..config()
+    };
+
+    determinism::verify(&config, SEED, STEPS).unwrap();
+}
+
+#[test]
+fn environment_config() {
+    let config = SimulationConfig {
+        food_layout: PersistMode::KeepPositions,
+        auto_balance: Some(AutoBalance::default()),
+        environment_cycle: Some(EnvironmentCycle::default()),
+        rotational_inertia: Some(RotationalInertia::default()),
+        habitats: Some(Habitats::default()),
+        brain: BrainConfig {
+            init_forward_bias: 0.5,
+        },
+        gene_ages: true,
+        mutation_impact: Some(MutationImpact {
+            sample_rate: 0.5,
+            steps: 10,
+            seed: 7,
+        }),
+        ..config()
+    };
+
+    determinism::verify(&config, SEED, STEPS).unwrap();
+}
+
+#[test]
+fn sparse_checks_catch_up_at_the_end() {
+    determinism::verify_every(&config(), SEED, 95, 10).unwrap();
+}
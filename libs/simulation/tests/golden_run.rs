@@ -0,0 +1,91 @@
+//! Trains a fixed config from a fixed seed, end to end - through the
+//! genetic algorithm, the neural network and the simulation - and checks
+//! that it still ends up with exactly the same population (and that it
+//! has actually learned something on the way).
+//!
+//! Changes that alter the outcome on purpose (e.g. a different mutation)
+//! have to re-bless the fingerprint:
+//!
+//! ```text
+//! BLESS=1 cargo test -p lib-simulation --test golden_run
+//! ```
+
+use lib_genetic_algorithm::Float;
+use lib_simulation::determinism;
+use lib_simulation::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::path::PathBuf;
+
+const SEED: u64 = 0xB1AD;
+const GENERATIONS: usize = 40;
+
+/// How many more foods (on average) the birds of the last five generations
+/// must eat than the birds of the first five; the blessed run makes it
+/// past 4.5.
+const MIN_IMPROVEMENT: Float = 3.0;
+
+fn config() -> SimulationConfig {
+    SimulationConfig {
+        n_animals: 30,
+        generation_length: 400,
+        ..Default::default()
+    }
+}
+
+fn run() -> (Simulation, Vec<GenerationSummary>) {
+    let mut rng = ChaCha8Rng::seed_from_u64(SEED);
+    let mut sim = Simulation::from_config(config(), &mut rng);
+    let summaries = sim.train_many(&mut rng, GENERATIONS);
+
+    (sim, summaries)
+}
+
+fn fingerprint_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden_run.fingerprint")
+}
+
+fn mean_fitness(summaries: &[GenerationSummary]) -> Float {
+    summaries.iter().map(|summary| summary.stats.avg_fitness).sum::<Float>() / summaries.len() as Float
+}
+
+#[test]
+fn reproduces_blessed_population() {
+    // Both features change the numbers (on purpose), so the fingerprint is
+    // blessed only for the default build
+    if cfg!(any(feature = "precision-f64", feature = "fast-math")) {
+        return;
+    }
+
+    let (sim, _) = run();
+    let actual = format!("{:016x}", determinism::population_fingerprint(&sim));
+
+    if std::env::var_os("BLESS").is_some() {
+        std::fs::write(fingerprint_path(), format!("{}\n", actual)).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(fingerprint_path()).unwrap();
+
+    assert_eq!(
+        actual,
+        expected.trim(),
+        "the golden run has ended up with a different population; if that's \
+         intended, re-bless it with `BLESS=1`",
+    );
+}
+
+#[test]
+fn learns_to_find_food() {
+    let (_, summaries) = run();
+
+    let first = mean_fitness(&summaries[..5]);
+    let last = mean_fitness(&summaries[GENERATIONS - 5..]);
+
+    assert!(
+        last - first >= MIN_IMPROVEMENT,
+        "mean fitness went from {} to {} only",
+        first,
+        last,
+    );
+}
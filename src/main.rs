@@ -3,7 +3,7 @@ use lib_neural_network::Network;
 
 fn main(){
     let layers: Vec<LayerTopology> = vec![LayerTopology::new(3), LayerTopology::new(2), LayerTopology::new(2)];
-    let ffnn = Network::random(&layers);
+    let ffnn = Network::random(&mut rand::thread_rng(), &layers);
     ffnn.propagate(vec![3.0,4.0,5.0]);
     println!("{:?}", ffnn);
 }
\ No newline at end of file
@@ -39,6 +39,7 @@ CanvasRenderingContext2D.prototype.drawTriangle = function (
     y,
     size,
     rotation,
+    color,
 ) {
     this.beginPath();
 
@@ -63,7 +64,7 @@ CanvasRenderingContext2D.prototype.drawTriangle = function (
     );
     // this.fillStyle = "#FF596B";
     // this.fill();
-    this.strokeStyle = '#FF597B';
+    this.strokeStyle = color;
     this.stroke();
 };
 
@@ -92,6 +93,7 @@ for (const animal of simulation.world().animals) {
         animal.y * viewportHeight,
         0.01 * viewportWidth,
         animal.rotation,
+        animal.color,
     );
 }
 
@@ -119,6 +121,7 @@ function redraw() {
             animal.y * viewportHeight,
             0.01 * viewportWidth,
             animal.rotation,
+            animal.color,
         );
     }
 